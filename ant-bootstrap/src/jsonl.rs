@@ -0,0 +1,198 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Append-only files of JSON lines, used for the local histories kept by nodes and clients, e.g.
+//! the payments received by a node or the quotes received by a client.
+
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// A file of JSON lines, each holding one value, appended to by any number of threads.
+#[derive(Debug)]
+pub struct JsonLinesFile {
+    file_path: PathBuf,
+    /// Once the file reaches this size, it is moved aside before the next append, replacing the
+    /// file moved aside before it. At most twice this size is kept.
+    max_size: Option<u64>,
+    /// Serialises the appends, so concurrent writers never interleave their lines.
+    append_lock: Mutex<()>,
+}
+
+impl JsonLinesFile {
+    /// The file is created, along with its parent dirs, on the first append.
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            max_size: None,
+            append_lock: Mutex::new(()),
+        }
+    }
+
+    /// Rotates the file once it reaches the given size in bytes, rather than letting it grow.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.file_path
+    }
+
+    /// The file the values are moved to on rotation.
+    fn rotated_path(&self) -> PathBuf {
+        let mut file_name = self.file_path.file_name().unwrap_or_default().to_owned();
+        file_name.push(".1");
+        self.file_path.with_file_name(file_name)
+    }
+
+    /// Appends the values to the file, one line each.
+    pub fn append<T: Serialize>(&self, values: &[T]) -> io::Result<()> {
+        let mut lines = String::new();
+        for value in values {
+            lines.push_str(&serde_json::to_string(value).map_err(io::Error::other)?);
+            lines.push('\n');
+        }
+
+        let _guard = self
+            .append_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if let Some(max_size) = self.max_size {
+            match fs::metadata(&self.file_path) {
+                Ok(metadata) if metadata.len() >= max_size => {
+                    debug!("Rotating {:?}, of {} bytes", self.file_path, metadata.len());
+                    fs::rename(&self.file_path, self.rotated_path())?;
+                }
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)?;
+        file.write_all(lines.as_bytes())
+    }
+
+    /// Replaces the content of the file with the values, one line each, e.g. to compact it. This
+    /// goes through a temporary file, so a crash never leaves the file truncated. The file moved
+    /// aside on rotation, if any, is removed.
+    pub fn rewrite<T: Serialize>(&self, values: &[T]) -> io::Result<()> {
+        let mut lines = String::new();
+        for value in values {
+            lines.push_str(&serde_json::to_string(value).map_err(io::Error::other)?);
+            lines.push('\n');
+        }
+
+        let _guard = self
+            .append_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(parent) = self.file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = self.file_path.with_extension("tmp");
+        fs::write(&temp_path, lines)?;
+        fs::rename(temp_path, &self.file_path)?;
+        match fs::remove_file(self.rotated_path()) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads the values back, oldest first, keeping those accepted by the filter. The file is
+    /// read a line at a time, so only the values kept are held in memory.
+    ///
+    /// Lines that cannot be parsed, e.g. one left half-written by a crash, are skipped.
+    pub fn read<T: DeserializeOwned>(
+        &self,
+        mut filter: impl FnMut(&T) -> bool,
+    ) -> io::Result<Vec<T>> {
+        let mut values = vec![];
+        for path in [self.rotated_path(), self.file_path.clone()] {
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            for line in BufReader::new(file).lines() {
+                match serde_json::from_str::<T>(&line?) {
+                    Ok(value) if filter(&value) => values.push(value),
+                    Ok(_) => {}
+                    Err(err) => warn!("Skipping unreadable line of {path:?}: {err}"),
+                }
+            }
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_survive_reopening_and_corrupt_lines_are_skipped() -> io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let file_path = temp_dir.path().join("history").join("values");
+        let file = JsonLinesFile::new(file_path.clone());
+        assert!(file.read::<u64>(|_| true)?.is_empty());
+
+        file.append(&[1u64, 2])?;
+        // Simulate a line left half-written by a crash.
+        OpenOptions::new()
+            .append(true)
+            .open(&file_path)?
+            .write_all(b"{\"half\":\n")?;
+        file.append(&[3u64])?;
+
+        let reopened = JsonLinesFile::new(file_path);
+        assert_eq!(reopened.read::<u64>(|_| true)?, vec![1, 2, 3]);
+        assert_eq!(reopened.read::<u64>(|value| *value >= 2)?, vec![2, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn file_is_rotated_once_it_reaches_the_max_size() -> io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        // Each line is two bytes long.
+        let file = JsonLinesFile::new(temp_dir.path().join("values")).with_max_size(4);
+
+        for value in 0u64..7 {
+            file.append(&[value])?;
+        }
+
+        // The oldest values were dropped along with the rotated files they were moved to.
+        assert_eq!(file.read::<u64>(|_| true)?, vec![4, 5, 6]);
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_replaces_the_values_along_with_the_rotated_ones() -> io::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let file = JsonLinesFile::new(temp_dir.path().join("values")).with_max_size(4);
+        for value in 0u64..5 {
+            file.append(&[value])?;
+        }
+
+        file.rewrite(&[7u64, 8])?;
+        assert_eq!(file.read::<u64>(|_| true)?, vec![7, 8]);
+        file.append(&[9u64])?;
+        assert_eq!(file.read::<u64>(|_| true)?, vec![7, 8, 9]);
+        Ok(())
+    }
+}
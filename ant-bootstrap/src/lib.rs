@@ -30,6 +30,7 @@ pub mod cache_store;
 pub mod config;
 pub mod contacts_fetcher;
 pub mod error;
+pub mod jsonl;
 
 use ant_protocol::version::{get_network_id_str, get_truncate_version_str};
 use libp2p::{Multiaddr, PeerId, multiaddr::Protocol};
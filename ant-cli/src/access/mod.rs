@@ -9,4 +9,5 @@
 pub mod cached_payments;
 pub mod data_dir;
 pub mod keys;
+pub mod quote_history;
 pub mod user_data;
@@ -0,0 +1,19 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::opt::NetworkId;
+use autonomi::client::quote::QuoteRecorder;
+use color_eyre::eyre::Result;
+
+/// The recorder of the quotes received by the client on the given network, kept in the client
+/// data dir. Each network has its own history, so test networks do not skew mainnet prices.
+pub fn quote_recorder(network_id: &NetworkId) -> Result<QuoteRecorder> {
+    let dir = super::data_dir::get_client_data_dir_path()?;
+    let file_name = format!("quote_history_{}.jsonl", network_id.as_u8());
+    Ok(QuoteRecorder::new(dir.join(file_name)))
+}
@@ -6,6 +6,7 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use crate::access::quote_history;
 use crate::exit_code::{
     ExitCodeError, bootstrap_error_exit_code, connect_error_exit_code, evm_util_error_exit_code,
 };
@@ -22,12 +23,24 @@ pub struct NetworkContext {
     pub peers: InitialPeersConfig,
     /// The network ID
     pub network_id: NetworkId,
+    /// Whether the quotes received are recorded to the quote history of the network
+    pub record_quotes: bool,
 }
 
 impl NetworkContext {
     /// Creates a new NetworkContext with the specified peer configuration and network ID
     pub fn new(peers: InitialPeersConfig, network_id: NetworkId) -> Self {
-        Self { peers, network_id }
+        Self {
+            peers,
+            network_id,
+            record_quotes: false,
+        }
+    }
+
+    /// Records the quotes received by the client to the quote history of the network
+    pub fn with_quote_recording(mut self, record_quotes: bool) -> Self {
+        self.record_quotes = record_quotes;
+        self
     }
 }
 
@@ -98,7 +111,16 @@ pub async fn connect_to_network_with_config(
             info!("Connected to the network");
             progress_bar.finish_with_message("Connected to the network".to_string());
             let client = client.with_strategy(operating_strategy);
-            Ok(client)
+            if !network_context.record_quotes {
+                return Ok(client);
+            }
+            match quote_history::quote_recorder(&network_context.network_id) {
+                Ok(recorder) => Ok(client.with_quote_recorder(recorder)),
+                Err(err) => {
+                    warn!("Quotes will not be recorded: {err}");
+                    Ok(client)
+                }
+            }
         }
         Err(e) => {
             println!("Failed to connect to the network: {e}");
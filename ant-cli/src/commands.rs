@@ -64,10 +64,17 @@ pub enum SubCmd {
     },
 
     /// Operations related to data analysis.
-    #[command(alias = "analyse")]
+    #[command(
+        alias = "analyse",
+        args_conflicts_with_subcommands = true,
+        subcommand_negates_reqs = true
+    )]
     Analyze {
+        #[command(subcommand)]
+        command: Option<AnalyzeCmd>,
         /// The address of the data to analyse.
-        addr: String,
+        #[arg(required = true)]
+        addr: Option<String>,
         /// Show closest nodes to this address instead of analyzing it.
         #[arg(long)]
         closest_nodes: bool,
@@ -100,6 +107,23 @@ pub enum SubCmd {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum AnalyzeCmd {
+    /// Report the trend of the prices quoted to this client, from the locally recorded quotes.
+    Prices {
+        /// Only consider the quotes received in this many last days.
+        #[arg(long, default_value_t = 30)]
+        days: u64,
+        /// Length of the periods the median price is reported over.
+        #[arg(long, value_enum, default_value_t = analyze::PricePeriod::Day)]
+        period: analyze::PricePeriod,
+        /// Report the nodes whose median price is at least this many times above or below the
+        /// median of all nodes.
+        #[arg(long, default_value_t = 3)]
+        outlier_factor: u64,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum FileCmd {
     /// Estimate cost to upload a file.
@@ -476,7 +500,8 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
         NetworkContext::new(opt.peers, NetworkId::alpha())
     } else {
         NetworkContext::new(opt.peers, opt.network_id)
-    };
+    }
+    .with_quote_recording(opt.record_quotes);
 
     match cmd {
         Some(SubCmd::File { command }) => match command {
@@ -670,7 +695,17 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
             WalletCmd::Balance => wallet::balance(network_context).await,
         },
         Some(SubCmd::Analyze {
-            addr,
+            command:
+                Some(AnalyzeCmd::Prices {
+                    days,
+                    period,
+                    outlier_factor,
+                }),
+            ..
+        }) => analyze::prices(&network_context.network_id, days, period, outlier_factor),
+        Some(SubCmd::Analyze {
+            command: None,
+            addr: Some(addr),
             closest_nodes,
             holders,
             nodes_health,
@@ -694,7 +729,12 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
             )
             .await
         }
-        None => {
+        Some(SubCmd::Analyze {
+            command: None,
+            addr: None,
+            ..
+        })
+        | None => {
             // If no subcommand is given, default to clap's error behaviour.
             Opt::command()
                 .error(ErrorKind::MissingSubcommand, "Please provide a subcommand")
@@ -71,6 +71,7 @@ pub enum NetworkErrorDisplay {
     OutdatedRecordRejected,
     GetQuoteError,
     InvalidQuote,
+    QuoteDeclined,
     InsufficientQuotes,
     SplitRecord,
     GetRecordTimeout,
@@ -100,6 +101,7 @@ impl NetworkErrorDisplay {
             NetworkError::OutdatedRecordRejected { .. } => Self::OutdatedRecordRejected,
             NetworkError::GetQuoteError(_) => Self::GetQuoteError,
             NetworkError::InvalidQuote(_) => Self::InvalidQuote,
+            NetworkError::QuoteDeclined(_) => Self::QuoteDeclined,
             NetworkError::InsufficientQuotes { .. } => Self::InsufficientQuotes,
             NetworkError::SplitRecord(_) => Self::SplitRecord,
             NetworkError::GetRecordTimeout(_) => Self::GetRecordTimeout,
@@ -8,8 +8,10 @@
 
 mod error;
 mod json;
+mod prices;
 
 pub use error::{AnalysisErrorDisplay, NetworkErrorDisplay};
+pub use prices::{PricePeriod, prices};
 
 use crate::actions::NetworkContext;
 use crate::wallet::load_wallet;
@@ -0,0 +1,114 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::access::quote_history::quote_recorder;
+use crate::opt::NetworkId;
+use autonomi::AttoTokens;
+use autonomi::client::quote::recorder::{median_price_per_mb_over_time, peer_outliers};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use comfy_table::{Cell, CellAlignment, Table};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Length of the periods the price trend is reported over.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum PricePeriod {
+    Day,
+    Week,
+    /// 30 days.
+    Month,
+}
+
+impl PricePeriod {
+    fn duration(self) -> Duration {
+        let days = match self {
+            PricePeriod::Day => 1,
+            PricePeriod::Week => 7,
+            PricePeriod::Month => 30,
+        };
+        Duration::from_secs(days * SECS_PER_DAY)
+    }
+}
+
+/// Report the trend of the prices quoted to this client over the last `days`, along with the
+/// nodes quoting far from the rest of the network.
+pub fn prices(
+    network_id: &NetworkId,
+    days: u64,
+    period: PricePeriod,
+    outlier_factor: u64,
+) -> Result<()> {
+    let recorder = quote_recorder(network_id)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let since = now.saturating_sub(days.saturating_mul(SECS_PER_DAY));
+    let records = recorder.records_since(since)?;
+
+    if records.is_empty() {
+        println!(
+            "No quotes were recorded in the last {days} days. Quotes are recorded to {} on every upload or cost estimate run with --record-quotes.",
+            recorder.path().display()
+        );
+        return Ok(());
+    }
+    println!(
+        "Analysed {} quotes received in the last {days} days.",
+        records.len()
+    );
+
+    let mut trend = Table::new();
+    trend.set_header(vec![
+        Cell::new("Period start (UTC)"),
+        Cell::new("Quotes"),
+        Cell::new("Median price per MB (ANT)"),
+    ]);
+    for point in median_price_per_mb_over_time(&records, period.duration()) {
+        trend.add_row(vec![
+            Cell::new(format_timestamp(point.period_start)),
+            Cell::new(point.quote_count).set_alignment(CellAlignment::Right),
+            Cell::new(AttoTokens::from_atto(point.median_price_per_mb))
+                .set_alignment(CellAlignment::Right),
+        ]);
+    }
+    println!("\nPrice trend:\n{trend}");
+
+    let outliers = peer_outliers(&records, outlier_factor);
+    if outliers.is_empty() {
+        println!("\nNo node quoted at least {outlier_factor}x above or below the network median.");
+        return Ok(());
+    }
+    let mut outliers_table = Table::new();
+    outliers_table.set_header(vec![
+        Cell::new("Peer ID"),
+        Cell::new("Quotes"),
+        Cell::new("Median price per MB (ANT)"),
+    ]);
+    for outlier in &outliers {
+        outliers_table.add_row(vec![
+            Cell::new(&outlier.peer_id),
+            Cell::new(outlier.quote_count).set_alignment(CellAlignment::Right),
+            Cell::new(AttoTokens::from_atto(outlier.median_price_per_mb))
+                .set_alignment(CellAlignment::Right),
+        ]);
+    }
+    println!(
+        "\nNodes quoting at least {outlier_factor}x above or below the network median of {} ANT per MB:\n{outliers_table}",
+        AttoTokens::from_atto(outliers[0].network_median_price_per_mb)
+    );
+    Ok(())
+}
+
+fn format_timestamp(secs: u64) -> String {
+    i64::try_from(secs)
+        .ok()
+        .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0))
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| secs.to_string())
+}
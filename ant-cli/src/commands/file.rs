@@ -11,13 +11,13 @@ use crate::actions::NetworkContext;
 use crate::args::max_fee_per_gas::{MaxFeePerGasParam, get_max_fee_per_gas_from_opt_param};
 use crate::exit_code::{ExitCodeError, FEES_ERROR, IO_ERROR, upload_exit_code};
 use crate::utils::collect_upload_summary;
-use crate::wallet::load_wallet;
+use crate::wallet::{load_wallet, load_wallet_address_without_prompt};
 use autonomi::client::PutError;
 use autonomi::client::analyze::Analysis;
 use autonomi::client::payment::PaymentOption;
 use autonomi::files::UploadError;
 use autonomi::networking::{Quorum, RetryStrategy};
-use autonomi::{Client, ClientOperatingStrategy, PaymentMode, TransactionConfig};
+use autonomi::{Client, ClientOperatingStrategy, EvmAddress, PaymentMode, TransactionConfig};
 use color_eyre::Section;
 use color_eyre::eyre::{Context, Result, eyre};
 use std::path::PathBuf;
@@ -41,16 +41,48 @@ pub async fn cost(
         println!("🎯 Using single node payment mode (default - saves gas fees)");
     }
 
+    // The gas depends on the allowance of the paying wallet, if there is one.
+    let payer = load_wallet_address_without_prompt(client.evm_network()).unwrap_or_else(|| {
+        info!("No wallet to estimate the gas with, assuming a fresh one");
+        EvmAddress::ZERO
+    });
+
     println!("Getting upload cost...");
     info!("Calculating cost for file: {file}");
-    let cost = client
-        .file_cost(&PathBuf::from(file))
+    let (cost, fees) = client
+        .file_cost_with_fees(&PathBuf::from(file), payer)
         .await
         .wrap_err("Failed to calculate cost for file")?;
 
     println!("Estimate cost to upload file: {file}");
     println!("Total cost: {cost}");
-    info!("Total cost: {cost} for file: {file}");
+    match fees {
+        Ok(fees) => {
+            println!(
+                "Gas cost: {} wei ({} gas over {} transaction(s) at {} wei per gas{})",
+                fees.fee(),
+                fees.gas_units,
+                fees.transaction_count,
+                fees.fee_per_gas,
+                if fees.needs_approval {
+                    ", including the token spend approval"
+                } else {
+                    ""
+                }
+            );
+            if !fees.simulated {
+                println!(
+                    "Some transactions could not be simulated, their gas is a typical estimate"
+                );
+            }
+            info!("Total cost: {cost} for file: {file}, fees: {fees:?}");
+        }
+        Err(err) => {
+            warn!("Failed to estimate the gas cost for file: {file}: {err}");
+            println!("Gas cost: unknown, the gas could not be estimated: {err}");
+            info!("Total cost: {cost} for file: {file}");
+        }
+    }
     Ok(())
 }
 
@@ -161,6 +193,12 @@ pub async fn upload(
         );
         println!("Total cost: {} AttoTokens", summary.tokens_spent);
     }
+    if summary.quotes_excluded > 0 {
+        println!(
+            "Number of suspicious quotes excluded before payment: {}",
+            summary.quotes_excluded
+        );
+    }
     info!("Summary for upload of file {file} at {local_addr:?}: {summary:?}");
 
     // save archive to local user data
@@ -27,7 +27,7 @@ pub use access::user_data;
 #[cfg(feature = "metrics")]
 use ant_logging::metrics::init_metrics;
 use ant_logging::{LogBuilder, LogFormat, ReloadHandle, WorkerGuard};
-use autonomi::version;
+use autonomi::{EvmConfig, version};
 use clap::Parser;
 use color_eyre::Result;
 use opt::{NetworkId, Opt};
@@ -86,6 +86,15 @@ async fn main() -> Result<()> {
 
     ant_build_info::log_version_info(env!("CARGO_PKG_VERSION"), &identify_protocol_str);
 
+    if let Some(evm_config) = &opt.evm_config {
+        let network_id = if opt.alpha {
+            NetworkId::alpha()
+        } else {
+            opt.network_id
+        };
+        let _ = EvmConfig::from_file(evm_config)?.install(network_id.as_u8())?;
+    }
+
     commands::handle_subcommand(opt).await?;
 
     Ok(())
@@ -13,6 +13,7 @@ use autonomi::Network as EvmNetwork;
 use autonomi::get_evm_network;
 use clap::Parser;
 use color_eyre::Result;
+use std::path::PathBuf;
 use std::time::Duration;
 
 pub(crate) const LOCAL_NETWORK_ID: u8 = 0;
@@ -108,6 +109,13 @@ pub(crate) struct Opt {
     #[clap(long)]
     pub crate_version: bool,
 
+    /// Path to an EVM network config file, listing several RPC endpoints to fail over between.
+    ///
+    /// The network of the file must be the one the network ID connects to. For custom networks,
+    /// it replaces the EVM network environment variables.
+    #[clap(long, global = true)]
+    pub evm_config: Option<PathBuf>,
+
     /// Specify the logging format.
     ///
     /// Valid values are "default" or "json".
@@ -160,6 +168,11 @@ pub(crate) struct Opt {
     #[clap(long)]
     pub protocol_version: bool,
 
+    /// Record the quotes received while uploading or estimating a cost to the quote history of
+    /// the network, which `analyze prices` reports on.
+    #[clap(long, global = true)]
+    pub record_quotes: bool,
+
     /// Print version information.
     #[clap(long)]
     pub version: bool,
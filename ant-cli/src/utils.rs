@@ -6,7 +6,7 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use autonomi::client::{Amount, ClientEvent, UploadSummary};
+use autonomi::client::{ClientEvent, UploadSummary};
 
 /// Collects upload summary from the event receiver.
 /// Send a signal to the returned sender to stop collecting and to return the result via the join handle.
@@ -18,18 +18,17 @@ pub fn collect_upload_summary(
 ) {
     let (upload_completed_tx, mut upload_completed_rx) = tokio::sync::oneshot::channel::<()>();
     let stats_thread = tokio::spawn(async move {
-        let mut tokens_spent: Amount = Amount::from(0);
-        let mut record_count = 0;
-        let mut records_already_paid = 0;
+        let mut summary = UploadSummary::default();
 
         loop {
             tokio::select! {
                 event = event_receiver.recv() => {
                     match event {
                         Some(ClientEvent::UploadComplete(upload_summary)) => {
-                            tokens_spent += upload_summary.tokens_spent;
-                            record_count += upload_summary.records_paid;
-                            records_already_paid += upload_summary.records_already_paid;
+                            summary.tokens_spent += upload_summary.tokens_spent;
+                            summary.records_paid += upload_summary.records_paid;
+                            summary.records_already_paid += upload_summary.records_already_paid;
+                            summary.quotes_excluded += upload_summary.quotes_excluded;
                         }
                         None => break,
                     }
@@ -42,18 +41,15 @@ pub fn collect_upload_summary(
         while let Ok(event) = event_receiver.try_recv() {
             match event {
                 ClientEvent::UploadComplete(upload_summary) => {
-                    tokens_spent += upload_summary.tokens_spent;
-                    record_count += upload_summary.records_paid;
-                    records_already_paid += upload_summary.records_already_paid;
+                    summary.tokens_spent += upload_summary.tokens_spent;
+                    summary.records_paid += upload_summary.records_paid;
+                    summary.records_already_paid += upload_summary.records_already_paid;
+                    summary.quotes_excluded += upload_summary.quotes_excluded;
                 }
             }
         }
 
-        UploadSummary {
-            tokens_spent,
-            records_paid: record_count,
-            records_already_paid,
-        }
+        summary
     });
 
     (stats_thread, upload_completed_tx)
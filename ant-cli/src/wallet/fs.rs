@@ -149,6 +149,20 @@ pub(crate) fn select_local_wallet_address() -> Result<String> {
         .to_string())
 }
 
+/// The address of the local wallet that would be used, if it can be known without asking the user
+/// to select one.
+pub(crate) fn local_wallet_address_without_prompt() -> Option<String> {
+    if let Some(wallet_address) = SELECTED_WALLET_ADDRESS.get() {
+        return Some(wallet_address.clone());
+    }
+
+    let wallets_folder = get_client_wallet_dir_path().ok()?;
+    match get_wallet_files(&wallets_folder).ok()?.as_slice() {
+        [wallet_file] => Some(filter_wallet_file_extension(wallet_file)),
+        _ => None,
+    }
+}
+
 fn get_wallet_selection(wallet_files: Vec<String>) -> Result<String> {
     list_wallets(&wallet_files);
 
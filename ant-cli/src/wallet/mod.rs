@@ -7,8 +7,11 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::keys::{get_secret_key_from_env, load_evm_wallet_from_env};
-use crate::wallet::fs::{select_wallet_from_disk, select_wallet_private_key};
-use autonomi::{Network, Wallet};
+use crate::wallet::fs::{
+    local_wallet_address_without_prompt, select_wallet_from_disk, select_wallet_private_key,
+};
+use autonomi::{EvmAddress, Network, Wallet};
+use std::str::FromStr;
 
 pub(crate) mod encryption;
 pub(crate) mod fs;
@@ -28,6 +31,16 @@ pub(crate) fn load_wallet(evm_network: &Network) -> color_eyre::Result<Wallet> {
     Ok(wallet)
 }
 
+/// The address of the wallet from ENV or disk, if it can be found without prompting for a wallet
+/// selection or a password.
+pub(crate) fn load_wallet_address_without_prompt(evm_network: &Network) -> Option<EvmAddress> {
+    if let Ok(wallet) = load_evm_wallet_from_env(evm_network) {
+        return Some(wallet.address());
+    }
+
+    local_wallet_address_without_prompt().and_then(|address| EvmAddress::from_str(&address).ok())
+}
+
 /// Load wallet private key from ENV or disk
 pub(crate) fn load_wallet_private_key() -> color_eyre::Result<String> {
     // First try wallet private key from ENV
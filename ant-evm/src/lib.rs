@@ -24,6 +24,7 @@ pub use evmlib::common::U256;
 pub use evmlib::common::{QuoteHash, TxHash};
 pub use evmlib::contract::payment_vault;
 pub use evmlib::cryptography;
+pub use evmlib::evm_config;
 #[cfg(feature = "external-signer")]
 pub use evmlib::external_signer;
 pub use evmlib::transaction_config::{MaxFeePerGas, TransactionConfig};
@@ -31,6 +32,7 @@ pub use evmlib::utils;
 pub use evmlib::utils::get_evm_network;
 pub use evmlib::utils::{DATA_PAYMENTS_ADDRESS, PAYMENT_TOKEN_ADDRESS, RPC_URL};
 pub use evmlib::wallet::Error as EvmWalletError;
+pub use evmlib::wallet::{PaymentFeeEstimate, estimate_pay_for_quotes};
 pub use evmlib::wallet::Wallet as EvmWallet;
 
 mod amount;
@@ -9,17 +9,19 @@
 use ant_bootstrap::InitialPeersConfig;
 use ant_evm::{EvmNetwork, RewardsAddress};
 use ant_logging::LogFormat;
-use ant_service_management::node::push_arguments_from_initial_peers_config;
+use ant_service_management::{ResourceLimits, node::push_arguments_from_initial_peers_config};
 use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use service_manager::{ServiceInstallCtx, ServiceLabel};
 use std::{
     ffi::OsString,
+    fmt,
     net::{Ipv4Addr, SocketAddr},
     path::PathBuf,
     str::FromStr,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum PortRange {
     Single(u16),
     Range(u16, u16),
@@ -68,6 +70,62 @@ impl PortRange {
     }
 }
 
+impl fmt::Display for PortRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Single(port) => write!(f, "{port}"),
+            Self::Range(start, end) => write!(f, "{start}-{end}"),
+        }
+    }
+}
+
+/// Serialized in the form the command line arguments take, e.g. '12000-12004'.
+impl Serialize for PortRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for PortRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // A single port can also be given as a number.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum PortRangeValue {
+            Port(u16),
+            Range(String),
+        }
+
+        match PortRangeValue::deserialize(deserializer)? {
+            PortRangeValue::Port(port) => Ok(Self::Single(port)),
+            PortRangeValue::Range(range) => {
+                Self::parse(&range).map_err(|err| serde::de::Error::custom(err.to_string()))
+            }
+        }
+    }
+}
+
+/// Validate a comma separated list of daily quiet windows, such as '22:00-07:00,12:00-13:00',
+/// in the form the antnode `--quiet-hours` argument accepts.
+pub fn parse_quiet_hours(s: &str) -> Result<String> {
+    let mut windows = Vec::new();
+    for window in s.split(',') {
+        let (start, end) = window
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| eyre!("Quiet window '{window}' must be in the format 'HH:MM-HH:MM'"))?;
+        let start = chrono::NaiveTime::parse_from_str(start.trim(), "%H:%M")
+            .map_err(|err| eyre!("Invalid start time in quiet window '{window}': {err}"))?;
+        let end = chrono::NaiveTime::parse_from_str(end.trim(), "%H:%M")
+            .map_err(|err| eyre!("Invalid end time in quiet window '{window}': {err}"))?;
+        if start == end {
+            return Err(eyre!("Quiet window '{window}' is empty"));
+        }
+        windows.push(format!("{}-{}", start.format("%H:%M"), end.format("%H:%M")));
+    }
+    Ok(windows.join(","))
+}
+
 #[derive(Debug, PartialEq)]
 pub struct InstallNodeServiceCtxBuilder {
     pub alpha: bool,
@@ -75,6 +133,7 @@ pub struct InstallNodeServiceCtxBuilder {
     pub autostart: bool,
     pub data_dir_path: PathBuf,
     pub env_variables: Option<Vec<(String, String)>>,
+    pub evm_config: Option<PathBuf>,
     pub evm_network: EvmNetwork,
     pub log_dir_path: PathBuf,
     pub log_format: Option<LogFormat>,
@@ -82,16 +141,27 @@ pub struct InstallNodeServiceCtxBuilder {
     pub network_id: Option<u8>,
     pub no_upnp: bool,
     pub max_archived_log_files: Option<usize>,
+    pub max_download_rate: Option<u64>,
     pub max_log_files: Option<usize>,
+    pub max_peer_download_rate: Option<u64>,
+    pub max_peer_upload_rate: Option<u64>,
+    pub max_upload_rate: Option<u64>,
     pub metrics_port: Option<u16>,
     pub node_ip: Option<Ipv4Addr>,
     pub node_port: Option<u16>,
     pub init_peers_config: InitialPeersConfig,
+    pub quiet_hours: Option<String>,
     pub rewards_address: RewardsAddress,
     pub relay: bool,
     pub rpc_socket_addr: SocketAddr,
     pub service_user: Option<String>,
+    pub tcp_port: Option<u16>,
+    pub throttle_cpu_usage: Option<u8>,
+    pub throttle_memory_usage: Option<u8>,
+    pub throttled_download_rate: Option<u64>,
+    pub throttled_upload_rate: Option<u64>,
     pub write_older_cache_files: bool,
+    pub ws_port: Option<u16>,
 }
 
 impl InstallNodeServiceCtxBuilder {
@@ -132,6 +202,14 @@ impl InstallNodeServiceCtxBuilder {
             args.push(OsString::from("--port"));
             args.push(OsString::from(node_port.to_string()));
         }
+        if let Some(tcp_port) = self.tcp_port {
+            args.push(OsString::from("--tcp-port"));
+            args.push(OsString::from(tcp_port.to_string()));
+        }
+        if let Some(ws_port) = self.ws_port {
+            args.push(OsString::from("--ws-port"));
+            args.push(OsString::from(ws_port.to_string()));
+        }
         if let Some(metrics_port) = self.metrics_port {
             args.push(OsString::from("--metrics-server-port"));
             args.push(OsString::from(metrics_port.to_string()));
@@ -144,6 +222,46 @@ impl InstallNodeServiceCtxBuilder {
             args.push(OsString::from("--max-log-files"));
             args.push(OsString::from(log_files.to_string()));
         }
+        if let Some(rate) = self.max_upload_rate {
+            args.push(OsString::from("--max-upload-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(rate) = self.max_download_rate {
+            args.push(OsString::from("--max-download-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(rate) = self.max_peer_upload_rate {
+            args.push(OsString::from("--max-peer-upload-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(rate) = self.max_peer_download_rate {
+            args.push(OsString::from("--max-peer-download-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(quiet_hours) = &self.quiet_hours {
+            args.push(OsString::from("--quiet-hours"));
+            args.push(OsString::from(quiet_hours));
+        }
+        if let Some(rate) = self.throttled_upload_rate {
+            args.push(OsString::from("--throttled-upload-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(rate) = self.throttled_download_rate {
+            args.push(OsString::from("--throttled-download-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(percent) = self.throttle_cpu_usage {
+            args.push(OsString::from("--throttle-cpu-usage"));
+            args.push(OsString::from(percent.to_string()));
+        }
+        if let Some(percent) = self.throttle_memory_usage {
+            args.push(OsString::from("--throttle-memory-usage"));
+            args.push(OsString::from(percent.to_string()));
+        }
+        if let Some(evm_config) = &self.evm_config {
+            args.push(OsString::from("--evm-config"));
+            args.push(OsString::from(evm_config));
+        }
 
         args.push(OsString::from("--rewards-address"));
         args.push(OsString::from(self.rewards_address.to_string()));
@@ -189,26 +307,39 @@ pub struct AddNodeServiceOptions {
     pub delete_antnode_src: bool,
     pub enable_metrics_server: bool,
     pub env_variables: Option<Vec<(String, String)>>,
+    pub evm_config: Option<PathBuf>,
     pub evm_network: EvmNetwork,
     pub init_peers_config: InitialPeersConfig,
     pub log_format: Option<LogFormat>,
     pub max_archived_log_files: Option<usize>,
+    pub max_download_rate: Option<u64>,
     pub max_log_files: Option<usize>,
+    pub max_peer_download_rate: Option<u64>,
+    pub max_peer_upload_rate: Option<u64>,
+    pub max_upload_rate: Option<u64>,
     pub metrics_port: Option<PortRange>,
     pub network_id: Option<u8>,
     pub node_ip: Option<Ipv4Addr>,
     pub node_port: Option<PortRange>,
     pub no_upnp: bool,
+    pub quiet_hours: Option<String>,
     pub relay: bool,
+    pub resource_limits: ResourceLimits,
     pub rewards_address: RewardsAddress,
     pub rpc_address: Option<Ipv4Addr>,
     pub rpc_port: Option<PortRange>,
     pub service_data_dir_path: PathBuf,
     pub service_log_dir_path: PathBuf,
+    pub tcp_port: Option<PortRange>,
+    pub throttle_cpu_usage: Option<u8>,
+    pub throttle_memory_usage: Option<u8>,
+    pub throttled_download_rate: Option<u64>,
+    pub throttled_upload_rate: Option<u64>,
     pub user: Option<String>,
     pub user_mode: bool,
     pub version: String,
     pub write_older_cache_files: bool,
+    pub ws_port: Option<PortRange>,
 }
 
 pub struct AddDaemonServiceOptions {
@@ -252,6 +383,18 @@ mod tests {
             rpc_socket_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
             service_user: None,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         }
     }
 
@@ -290,6 +433,18 @@ mod tests {
             service_user: None,
             no_upnp: false,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         }
     }
 
@@ -328,6 +483,18 @@ mod tests {
             service_user: None,
             no_upnp: false,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         }
     }
 
@@ -401,6 +568,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_quiet_hours_should_normalise_valid_windows() {
+        assert_eq!(
+            parse_quiet_hours("22:00-7:00, 12:00-13:30").unwrap(),
+            "22:00-07:00,12:00-13:30"
+        );
+        assert!(parse_quiet_hours("22:00").is_err());
+        assert!(parse_quiet_hours("22:00-25:00").is_err());
+        assert!(parse_quiet_hours("09:00-09:00").is_err());
+    }
+
     #[test]
     fn build_should_assign_expected_values_when_all_options_are_enabled() {
         let mut builder = create_builder_with_all_options_enabled();
@@ -410,7 +588,19 @@ mod tests {
         builder.no_upnp = true;
         builder.node_ip = Some(Ipv4Addr::new(192, 168, 1, 1));
         builder.node_port = Some(12345);
+        builder.tcp_port = Some(12346);
+        builder.ws_port = Some(12347);
         builder.metrics_port = Some(9090);
+        builder.max_upload_rate = Some(1024);
+        builder.max_download_rate = Some(2048);
+        builder.max_peer_upload_rate = Some(256);
+        builder.max_peer_download_rate = Some(512);
+        builder.quiet_hours = Some("22:00-07:00".to_string());
+        builder.throttled_upload_rate = Some(128);
+        builder.throttled_download_rate = Some(64);
+        builder.throttle_cpu_usage = Some(80);
+        builder.throttle_memory_usage = Some(90);
+        builder.evm_config = Some(PathBuf::from("/etc/antnode/evm.json"));
         builder.init_peers_config.addrs = vec![
             "/ip4/127.0.0.1/tcp/8080".parse().unwrap(),
             "/ip4/192.168.1.1/tcp/8081".parse().unwrap(),
@@ -450,12 +640,36 @@ mod tests {
             "192.168.1.1",
             "--port",
             "12345",
+            "--tcp-port",
+            "12346",
+            "--ws-port",
+            "12347",
             "--metrics-server-port",
             "9090",
             "--max-archived-log-files",
             "10",
             "--max-log-files",
             "10",
+            "--max-upload-rate",
+            "1024",
+            "--max-download-rate",
+            "2048",
+            "--max-peer-upload-rate",
+            "256",
+            "--max-peer-download-rate",
+            "512",
+            "--quiet-hours",
+            "22:00-07:00",
+            "--throttled-upload-rate",
+            "128",
+            "--throttled-download-rate",
+            "64",
+            "--throttle-cpu-usage",
+            "80",
+            "--throttle-memory-usage",
+            "90",
+            "--evm-config",
+            "/etc/antnode/evm.json",
             "--rewards-address",
             "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             "--write-older-cache-files",
@@ -16,8 +16,8 @@ use crate::{
     helpers::{check_port_availability, get_start_port_if_applicable, increment_port_option},
 };
 use ant_service_management::{
-    DaemonServiceData, NatDetectionStatus, NodeRegistryManager, NodeServiceData, ServiceStatus,
-    control::ServiceControl, node::NODE_SERVICE_DATA_SCHEMA_LATEST,
+    DaemonServiceData, Error as ServiceError, NatDetectionStatus, NodeRegistryManager,
+    NodeServiceData, ServiceStatus, control::ServiceControl, node::NODE_SERVICE_DATA_SCHEMA_LATEST,
 };
 use color_eyre::{Help, Result, eyre::eyre};
 use colored::Colorize;
@@ -25,6 +25,7 @@ use service_manager::ServiceInstallCtx;
 use std::{
     ffi::OsString,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::Path,
 };
 
 /// Install antnode as a service.
@@ -68,6 +69,16 @@ pub async fn add_node(
         check_port_availability(port_option, &node_registry.nodes).await?;
     }
 
+    if let Some(port_option) = &options.tcp_port {
+        port_option.validate(options.count.unwrap_or(1))?;
+        check_port_availability(port_option, &node_registry.nodes).await?;
+    }
+
+    if let Some(port_option) = &options.ws_port {
+        port_option.validate(options.count.unwrap_or(1))?;
+        check_port_availability(port_option, &node_registry.nodes).await?;
+    }
+
     if let Some(port_option) = &options.metrics_port {
         port_option.validate(options.count.unwrap_or(1))?;
         check_port_availability(port_option, &node_registry.nodes).await?;
@@ -97,10 +108,12 @@ pub async fn add_node(
     let mut failed_service_data = vec![];
 
     let current_node_count = node_registry.nodes.read().await.len() as u16;
-    let target_node_count = current_node_count + options.count.unwrap_or(1);
+    let mut target_node_count = current_node_count + options.count.unwrap_or(1);
 
     let mut node_number = current_node_count + 1;
     let mut node_port = get_start_port_if_applicable(options.node_port);
+    let mut tcp_port = get_start_port_if_applicable(options.tcp_port);
+    let mut ws_port = get_start_port_if_applicable(options.ws_port);
     let mut metrics_port = get_start_port_if_applicable(options.metrics_port);
     let mut rpc_port = get_start_port_if_applicable(options.rpc_port);
 
@@ -144,22 +157,6 @@ pub async fn add_node(
                 options.service_log_dir_path.join(service_name.clone())
             };
 
-        if let Some(user) = &options.user {
-            debug!("Creating data_dir and log_dirs with user {user}");
-            create_owned_dir(service_data_dir_path.clone(), user)?;
-            create_owned_dir(service_log_dir_path.clone(), user)?;
-        } else {
-            debug!("Creating data_dir and log_dirs without user");
-            std::fs::create_dir_all(service_data_dir_path.clone())?;
-            std::fs::create_dir_all(service_log_dir_path.clone())?;
-        }
-
-        debug!("Copying antnode binary to {service_antnode_path:?}");
-        std::fs::copy(
-            options.antnode_src_path.clone(),
-            service_antnode_path.clone(),
-        )?;
-
         if options.auto_set_nat_flags {
             let nat_status = node_registry.nat_status.read().await;
 
@@ -200,23 +197,133 @@ pub async fn add_node(
             log_dir_path: service_log_dir_path.clone(),
             log_format: options.log_format,
             max_archived_log_files: options.max_archived_log_files,
+            max_download_rate: options.max_download_rate,
             max_log_files: options.max_log_files,
+            max_peer_download_rate: options.max_peer_download_rate,
+            max_peer_upload_rate: options.max_peer_upload_rate,
+            max_upload_rate: options.max_upload_rate,
             metrics_port: metrics_free_port,
             name: service_name.clone(),
             network_id: options.network_id,
             node_ip: options.node_ip,
             node_port,
             init_peers_config: options.init_peers_config.clone(),
+            quiet_hours: options.quiet_hours.clone(),
+            evm_config: options.evm_config.clone(),
             rewards_address: options.rewards_address,
             rpc_socket_addr,
             antnode_path: service_antnode_path.clone(),
             service_user: options.user.clone(),
             no_upnp: options.no_upnp,
+            tcp_port,
+            throttle_cpu_usage: options.throttle_cpu_usage,
+            throttle_memory_usage: options.throttle_memory_usage,
+            throttled_download_rate: options.throttled_download_rate,
+            throttled_upload_rate: options.throttled_upload_rate,
             write_older_cache_files: options.write_older_cache_files,
+            ws_port,
         }
         .build()?;
 
-        match service_control.install(install_ctx, options.user_mode) {
+        // The name is reserved in the registry before anything is written for the service, so a
+        // service added by another writer under the same name is never overwritten.
+        node_registry
+            .push_node(NodeServiceData {
+                alpha: options.alpha,
+                antnode_path: service_antnode_path.clone(),
+                auto_restart: options.auto_restart,
+                connected_peers: None,
+                data_dir_path: service_data_dir_path.clone(),
+                evm_network: options.evm_network.clone(),
+                relay: options.relay,
+                resource_limits: options.resource_limits.clone(),
+                initial_peers_config: options.init_peers_config.clone(),
+                listen_addr: None,
+                log_dir_path: service_log_dir_path.clone(),
+                log_format: options.log_format,
+                max_archived_log_files: options.max_archived_log_files,
+                max_download_rate: options.max_download_rate,
+                max_log_files: options.max_log_files,
+                max_peer_download_rate: options.max_peer_download_rate,
+                max_peer_upload_rate: options.max_peer_upload_rate,
+                max_upload_rate: options.max_upload_rate,
+                metrics_port: metrics_free_port,
+                network_id: options.network_id,
+                node_ip: options.node_ip,
+                node_port,
+                number: node_number,
+                rewards_address: options.rewards_address,
+                reward_balance: None,
+                rpc_socket_addr,
+                peer_id: None,
+                pid: None,
+                quiet_hours: options.quiet_hours.clone(),
+                evm_config: options.evm_config.clone(),
+                schema_version: NODE_SERVICE_DATA_SCHEMA_LATEST,
+                service_name: service_name.clone(),
+                status: ServiceStatus::Added,
+                no_upnp: options.no_upnp,
+                user: options.user.clone(),
+                user_mode: options.user_mode,
+                version: options.version.clone(),
+                tcp_port,
+                throttle_cpu_usage: options.throttle_cpu_usage,
+                throttle_memory_usage: options.throttle_memory_usage,
+                throttled_download_rate: options.throttled_download_rate,
+                throttled_upload_rate: options.throttled_upload_rate,
+                write_older_cache_files: options.write_older_cache_files,
+                ws_port,
+            })
+            .await;
+        match node_registry.save().await {
+            Ok(()) => {}
+            Err(ServiceError::NodeRegistryConflict(_)) => {
+                // Another writer added a service with the same name, and the registry now has
+                // theirs, so this service is added under the next number.
+                warn!(
+                    "{service_name} was added by another writer; adding it under the next number"
+                );
+                let node_count = node_registry.nodes.read().await.len() as u16;
+                target_node_count = target_node_count - node_number + node_count + 1;
+                node_number = node_count + 1;
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        if let Err(err) = create_service_files(
+            options.user.as_deref(),
+            &service_data_dir_path,
+            &service_log_dir_path,
+            &options.antnode_src_path,
+            &service_antnode_path,
+        ) {
+            release_service_name(&node_registry, &service_name).await?;
+            return Err(err);
+        }
+
+        let result = service_control
+            .install(install_ctx, options.user_mode)
+            .and_then(|()| {
+                if options.resource_limits.is_empty() {
+                    return Ok(());
+                }
+                debug!(
+                    "Applying resource limits to {service_name}: {}",
+                    options.resource_limits
+                );
+                service_control
+                    .set_resource_limits(&service_name, &options.resource_limits, options.user_mode)
+                    .inspect_err(|_| {
+                        // The service is removed from the registry, so it must not stay installed.
+                        if let Err(err) =
+                            service_control.uninstall(&service_name, options.user_mode)
+                        {
+                            error!("Failed to uninstall {service_name}: {err}");
+                        }
+                    })
+            });
+        match result {
             Ok(()) => {
                 info!("Successfully added service {service_name}");
                 added_service_data.push((
@@ -226,54 +333,18 @@ pub async fn add_node(
                     service_log_dir_path.to_string_lossy().into_owned(),
                     rpc_socket_addr,
                 ));
-
-                node_registry
-                    .push_node(NodeServiceData {
-                        alpha: options.alpha,
-                        antnode_path: service_antnode_path,
-                        auto_restart: options.auto_restart,
-                        connected_peers: None,
-                        data_dir_path: service_data_dir_path.clone(),
-                        evm_network: options.evm_network.clone(),
-                        relay: options.relay,
-                        initial_peers_config: options.init_peers_config.clone(),
-                        listen_addr: None,
-                        log_dir_path: service_log_dir_path.clone(),
-                        log_format: options.log_format,
-                        max_archived_log_files: options.max_archived_log_files,
-                        max_log_files: options.max_log_files,
-                        metrics_port: metrics_free_port,
-                        network_id: options.network_id,
-                        node_ip: options.node_ip,
-                        node_port,
-                        number: node_number,
-                        rewards_address: options.rewards_address,
-                        reward_balance: None,
-                        rpc_socket_addr,
-                        peer_id: None,
-                        pid: None,
-                        schema_version: NODE_SERVICE_DATA_SCHEMA_LATEST,
-                        service_name,
-                        status: ServiceStatus::Added,
-                        no_upnp: options.no_upnp,
-                        user: options.user.clone(),
-                        user_mode: options.user_mode,
-                        version: options.version.clone(),
-                        write_older_cache_files: options.write_older_cache_files,
-                    })
-                    .await;
-                // We save the node registry for each service because it's possible any number of
-                // services could fail to be added.
-                node_registry.save().await?;
             }
             Err(e) => {
                 error!("Failed to add service {service_name}: {e}");
                 failed_service_data.push((service_name.clone(), e.to_string()));
+                release_service_name(&node_registry, &service_name).await?;
             }
         }
 
         node_number += 1;
         node_port = increment_port_option(node_port);
+        tcp_port = increment_port_option(tcp_port);
+        ws_port = increment_port_option(ws_port);
         metrics_port = increment_port_option(metrics_port);
         rpc_port = increment_port_option(rpc_port);
     }
@@ -320,6 +391,51 @@ pub async fn add_node(
     Ok(added_services_names)
 }
 
+/// Create the data and log directories of a service, and copy the antnode binary into place.
+fn create_service_files(
+    user: Option<&str>,
+    data_dir_path: &Path,
+    log_dir_path: &Path,
+    antnode_src_path: &Path,
+    antnode_path: &Path,
+) -> Result<()> {
+    if let Some(user) = user {
+        debug!("Creating data_dir and log_dirs with user {user}");
+        create_owned_dir(data_dir_path.to_path_buf(), user)?;
+        create_owned_dir(log_dir_path.to_path_buf(), user)?;
+    } else {
+        debug!("Creating data_dir and log_dirs without user");
+        std::fs::create_dir_all(data_dir_path)?;
+        std::fs::create_dir_all(log_dir_path)?;
+    }
+
+    debug!("Copying antnode binary to {antnode_path:?}");
+    std::fs::copy(antnode_src_path, antnode_path)?;
+    Ok(())
+}
+
+/// Remove a service reserved in the registry, once it could not be added.
+async fn release_service_name(
+    node_registry: &NodeRegistryManager,
+    service_name: &str,
+) -> Result<()> {
+    {
+        let mut nodes = node_registry.nodes.write().await;
+        let mut reserved = None;
+        for (index, node) in nodes.iter().enumerate() {
+            if node.read().await.service_name == service_name {
+                reserved = Some(index);
+                break;
+            }
+        }
+        if let Some(index) = reserved {
+            let _ = nodes.remove(index);
+        }
+    }
+    node_registry.save().await?;
+    Ok(())
+}
+
 /// Install the daemon as a service.
 ///
 /// This only defines the service; it does not start it.
@@ -18,9 +18,11 @@ use crate::{
 use ant_bootstrap::InitialPeersConfig;
 use ant_evm::{AttoTokens, CustomNetwork, EvmNetwork, RewardsAddress};
 use ant_service_management::{
-    DaemonServiceData, NodeRegistryManager, NodeServiceData, ServiceStatus,
+    DaemonServiceData, NodeRegistryManager, NodeServiceData, ResourceLimits, ServiceStatus,
+};
+use ant_service_management::{
+    NatDetectionStatus, error::Error as ServiceError, error::Result as ServiceControlResult,
 };
-use ant_service_management::{NatDetectionStatus, error::Result as ServiceControlResult};
 use ant_service_management::{control::ServiceControl, node::NODE_SERVICE_DATA_SCHEMA_LATEST};
 use assert_fs::prelude::*;
 use assert_matches::assert_matches;
@@ -50,6 +52,7 @@ mock! {
         fn create_service_user(&self, username: &str) -> ServiceControlResult<()>;
         fn get_available_port(&self) -> ServiceControlResult<u16>;
         fn install(&self, install_ctx: ServiceInstallCtx, user_mode: bool) -> ServiceControlResult<()>;
+        fn set_resource_limits(&self, service_name: &str, limits: &ResourceLimits, user_mode: bool) -> ServiceControlResult<()>;
         fn get_process_pid(&self, bin_path: &Path) -> ServiceControlResult<u32>;
         fn start(&self, service_name: &str, user_mode: bool) -> ServiceControlResult<()>;
         fn stop(&self, service_name: &str, user_mode: bool) -> ServiceControlResult<()>;
@@ -135,6 +138,18 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
     mock_service_control
@@ -154,6 +169,7 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -185,6 +201,18 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -273,6 +301,7 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: init_peers_config.clone(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -300,6 +329,18 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
             user_mode: false,
             version: latest_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         })
         .await;
 
@@ -323,6 +364,7 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -354,6 +396,18 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -405,6 +459,7 @@ async fn add_genesis_node_should_return_an_error_if_count_is_greater_than_1() ->
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -436,6 +491,18 @@ async fn add_genesis_node_should_return_an_error_if_count_is_greater_than_1() ->
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -511,6 +578,18 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -561,6 +640,18 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -611,6 +702,18 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -631,6 +734,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -662,6 +766,18 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -794,6 +910,18 @@ async fn add_node_should_update_the_environment_variables_inside_node_registry()
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
     mock_service_control
@@ -813,6 +941,7 @@ async fn add_node_should_update_the_environment_variables_inside_node_registry()
             enable_metrics_server: false,
             env_variables: env_variables.clone(),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -844,6 +973,18 @@ async fn add_node_should_update_the_environment_variables_inside_node_registry()
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -908,6 +1049,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -935,6 +1077,18 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
             user_mode: false,
             version: latest_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         })
         .await;
     let temp_dir = assert_fs::TempDir::new()?;
@@ -985,6 +1139,18 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -1005,6 +1171,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -1036,6 +1203,18 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -1164,6 +1343,7 @@ async fn add_node_should_create_service_file_with_first_arg() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -1195,6 +1375,18 @@ async fn add_node_should_create_service_file_with_first_arg() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -1315,6 +1507,7 @@ async fn add_node_should_create_service_file_with_peers_args() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -1346,6 +1539,18 @@ async fn add_node_should_create_service_file_with_peers_args() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -1461,6 +1666,7 @@ async fn add_node_should_create_service_file_with_local_arg() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -1492,6 +1698,18 @@ async fn add_node_should_create_service_file_with_local_arg() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -1611,6 +1829,7 @@ async fn add_node_should_create_service_file_with_network_contacts_url_arg() ->
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -1642,6 +1861,18 @@ async fn add_node_should_create_service_file_with_network_contacts_url_arg() ->
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -1757,6 +1988,7 @@ async fn add_node_should_create_service_file_with_ignore_cache_arg() -> Result<(
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -1788,6 +2020,18 @@ async fn add_node_should_create_service_file_with_ignore_cache_arg() -> Result<(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -1904,6 +2148,7 @@ async fn add_node_should_create_service_file_with_custom_bootstrap_cache_path()
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             init_peers_config: initial_peers_config.clone(),
             log_format: None,
             max_archived_log_files: None,
@@ -1935,6 +2180,18 @@ async fn add_node_should_create_service_file_with_custom_bootstrap_cache_path()
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -2045,6 +2302,7 @@ async fn add_node_should_create_service_file_with_network_id() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -2076,6 +2334,18 @@ async fn add_node_should_create_service_file_with_network_id() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -2184,6 +2454,7 @@ async fn add_node_should_use_custom_ip() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -2215,6 +2486,18 @@ async fn add_node_should_use_custom_ip() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -2293,6 +2576,18 @@ async fn add_node_should_use_custom_ports_for_one_service() -> Result<()> {
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -2313,6 +2608,7 @@ async fn add_node_should_use_custom_ports_for_one_service() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -2344,6 +2640,18 @@ async fn add_node_should_use_custom_ports_for_one_service() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -2568,6 +2876,7 @@ async fn add_node_should_use_a_custom_port_range() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -2599,6 +2908,18 @@ async fn add_node_should_use_a_custom_port_range() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -2642,6 +2963,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_format: None,
@@ -2669,6 +2991,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         })
         .await;
     let latest_version = "0.96.4";
@@ -2690,6 +3024,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -2721,6 +3056,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &MockServiceControl::new(),
@@ -2759,6 +3106,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_format: None,
@@ -2786,6 +3134,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         })
         .await;
     let latest_version = "0.96.4";
@@ -2807,6 +3167,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -2838,6 +3199,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry,
         &MockServiceControl::new(),
@@ -2879,6 +3252,7 @@ async fn add_node_should_return_an_error_if_port_and_node_count_do_not_match() -
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -2910,6 +3284,18 @@ async fn add_node_should_return_an_error_if_port_and_node_count_do_not_match() -
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &MockServiceControl::new(),
@@ -2956,6 +3342,7 @@ async fn add_node_should_return_an_error_if_multiple_services_are_specified_with
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -2987,6 +3374,18 @@ async fn add_node_should_return_an_error_if_multiple_services_are_specified_with
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry,
         &MockServiceControl::new(),
@@ -3096,6 +3495,7 @@ async fn add_node_should_set_random_ports_if_enable_metrics_server_is_true() ->
             enable_metrics_server: true,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -3127,6 +3527,18 @@ async fn add_node_should_set_random_ports_if_enable_metrics_server_is_true() ->
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -3228,6 +3640,7 @@ async fn add_node_should_set_max_archived_log_files() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: Some(20),
             max_log_files: None,
@@ -3259,6 +3672,18 @@ async fn add_node_should_set_max_archived_log_files() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -3361,6 +3786,7 @@ async fn add_node_should_set_max_log_files() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: Some(20),
@@ -3392,6 +3818,18 @@ async fn add_node_should_set_max_log_files() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -3611,6 +4049,7 @@ async fn add_node_should_use_a_custom_port_range_for_metrics_server() -> Result<
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -3642,6 +4081,18 @@ async fn add_node_should_use_a_custom_port_range_for_metrics_server() -> Result<
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -3683,6 +4134,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -3710,6 +4162,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         })
         .await;
     let latest_version = "0.96.4";
@@ -3731,6 +4195,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -3762,6 +4227,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry,
         &MockServiceControl::new(),
@@ -3801,6 +4278,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -3828,6 +4306,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         })
         .await;
     let latest_version = "0.96.4";
@@ -3849,6 +4339,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -3880,6 +4371,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry,
         &MockServiceControl::new(),
@@ -4082,6 +4585,7 @@ async fn add_node_should_use_a_custom_port_range_for_the_rpc_server() -> Result<
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -4113,6 +4617,18 @@ async fn add_node_should_use_a_custom_port_range_for_the_rpc_server() -> Result<
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -4164,6 +4680,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -4191,6 +4708,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         })
         .await;
     let latest_version = "0.96.4";
@@ -4212,6 +4741,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -4243,6 +4773,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry,
         &MockServiceControl::new(),
@@ -4282,6 +4824,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -4309,6 +4852,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         })
         .await;
     let latest_version = "0.96.4";
@@ -4330,6 +4885,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -4361,6 +4917,18 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &MockServiceControl::new(),
@@ -4437,6 +5005,18 @@ async fn add_node_should_disable_upnp_and_relay_if_nat_status_is_public() -> Res
         service_user: Some(get_username()),
         no_upnp: true,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
     mock_service_control
@@ -4456,6 +5036,7 @@ async fn add_node_should_disable_upnp_and_relay_if_nat_status_is_public() -> Res
             enable_metrics_server: false,
             env_variables: None,
             relay: true,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -4487,6 +5068,18 @@ async fn add_node_should_disable_upnp_and_relay_if_nat_status_is_public() -> Res
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -4561,6 +5154,18 @@ async fn add_node_should_not_set_no_upnp_if_nat_status_is_upnp() -> Result<()> {
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
     mock_service_control
@@ -4580,6 +5185,7 @@ async fn add_node_should_not_set_no_upnp_if_nat_status_is_upnp() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: true,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -4611,6 +5217,18 @@ async fn add_node_should_not_set_no_upnp_if_nat_status_is_upnp() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -4684,6 +5302,18 @@ async fn add_node_should_enable_relay_if_nat_status_is_private() -> Result<()> {
         service_user: Some(get_username()),
         no_upnp: true,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
     mock_service_control
@@ -4703,6 +5333,7 @@ async fn add_node_should_enable_relay_if_nat_status_is_private() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -4734,6 +5365,18 @@ async fn add_node_should_enable_relay_if_nat_status_is_private() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -4808,6 +5451,18 @@ async fn add_node_should_set_relay_and_no_upnp_if_nat_status_is_none_but_auto_se
         service_user: Some(get_username()),
         no_upnp: true,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
     mock_service_control
@@ -4827,6 +5482,7 @@ async fn add_node_should_set_relay_and_no_upnp_if_nat_status_is_none_but_auto_se
             enable_metrics_server: false,
             env_variables: None,
             relay: true,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -4858,6 +5514,18 @@ async fn add_node_should_set_relay_and_no_upnp_if_nat_status_is_none_but_auto_se
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -5068,6 +5736,18 @@ async fn add_node_should_not_delete_the_source_binary_if_path_arg_is_used() -> R
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -5088,6 +5768,7 @@ async fn add_node_should_not_delete_the_source_binary_if_path_arg_is_used() -> R
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -5119,6 +5800,18 @@ async fn add_node_should_not_delete_the_source_binary_if_path_arg_is_used() -> R
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -5192,6 +5885,18 @@ async fn add_node_should_apply_the_relay_flag_if_it_is_used() -> Result<()> {
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -5212,6 +5917,7 @@ async fn add_node_should_apply_the_relay_flag_if_it_is_used() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: true,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -5243,6 +5949,18 @@ async fn add_node_should_apply_the_relay_flag_if_it_is_used() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -5317,6 +6035,18 @@ async fn add_node_should_add_the_node_in_user_mode() -> Result<()> {
         service_user: Some(get_username()),
         no_upnp: false,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -5337,6 +6067,7 @@ async fn add_node_should_add_the_node_in_user_mode() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: true,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -5368,6 +6099,18 @@ async fn add_node_should_add_the_node_in_user_mode() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -5438,6 +6181,18 @@ async fn add_node_should_add_the_node_with_no_upnp_flag() -> Result<()> {
         service_user: Some(get_username()),
         no_upnp: true,
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -5458,6 +6213,7 @@ async fn add_node_should_add_the_node_with_no_upnp_flag() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: true,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -5489,6 +6245,18 @@ async fn add_node_should_add_the_node_with_no_upnp_flag() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -5588,6 +6356,7 @@ async fn add_node_should_auto_restart() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -5619,6 +6388,18 @@ async fn add_node_should_auto_restart() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -5692,6 +6473,18 @@ async fn add_node_should_add_the_node_with_write_older_cache_files() -> Result<(
         service_user: Some(get_username()),
         no_upnp: true,
         write_older_cache_files: true,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     }
     .build()?;
 
@@ -5712,6 +6505,7 @@ async fn add_node_should_add_the_node_with_write_older_cache_files() -> Result<(
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -5743,6 +6537,18 @@ async fn add_node_should_add_the_node_with_write_older_cache_files() -> Result<(
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: true,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -5853,6 +6659,7 @@ async fn add_node_should_create_service_file_with_alpha_arg() -> Result<()> {
             enable_metrics_server: false,
             env_variables: None,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
@@ -5884,6 +6691,18 @@ async fn add_node_should_create_service_file_with_alpha_arg() -> Result<()> {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         },
         node_registry.clone(),
         &mock_service_control,
@@ -5902,3 +6721,438 @@ async fn add_node_should_create_service_file_with_alpha_arg() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn add_node_should_apply_the_resource_limits() -> Result<()> {
+    let tmp_data_dir = assert_fs::TempDir::new()?;
+    let node_reg_path = tmp_data_dir.child("node_reg.json");
+
+    let mut mock_service_control = MockServiceControl::new();
+    let node_registry = NodeRegistryManager::empty(node_reg_path.to_path_buf());
+
+    let latest_version = "0.96.4";
+    let temp_dir = assert_fs::TempDir::new()?;
+    let node_data_dir = temp_dir.child("data");
+    node_data_dir.create_dir_all()?;
+    let node_logs_dir = temp_dir.child("logs");
+    node_logs_dir.create_dir_all()?;
+    let antnode_download_path = temp_dir.child(ANTNODE_FILE_NAME);
+    antnode_download_path.write_binary(b"fake antnode bin")?;
+
+    let resource_limits = ResourceLimits {
+        cpu_quota: Some(150),
+        max_memory: Some(512 * 1024 * 1024),
+        nofile: Some(4096),
+    };
+
+    let mut seq = Sequence::new();
+
+    // Expected calls for first installation
+    mock_service_control
+        .expect_get_available_port()
+        .times(1)
+        .returning(|| Ok(8081))
+        .in_sequence(&mut seq);
+
+    mock_service_control
+        .expect_install()
+        .times(1)
+        .with(
+            eq(ServiceInstallCtx {
+                args: vec![
+                    OsString::from("--rpc"),
+                    OsString::from("127.0.0.1:8081"),
+                    OsString::from("--root-dir"),
+                    OsString::from(
+                        node_data_dir
+                            .to_path_buf()
+                            .join("antnode1")
+                            .to_string_lossy()
+                            .to_string(),
+                    ),
+                    OsString::from("--log-output-dest"),
+                    OsString::from(
+                        node_logs_dir
+                            .to_path_buf()
+                            .join("antnode1")
+                            .to_string_lossy()
+                            .to_string(),
+                    ),
+                    OsString::from("--rewards-address"),
+                    OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
+                    OsString::from("evm-custom"),
+                    OsString::from("--rpc-url"),
+                    OsString::from("http://localhost:8545/"),
+                    OsString::from("--payment-token-address"),
+                    OsString::from("0x5FbDB2315678afecb367f032d93F642f64180aa3"),
+                    OsString::from("--data-payments-address"),
+                    OsString::from("0x8464135c8F25Da09e49BC8782676a84730C318bC"),
+                ],
+                autostart: false,
+                contents: None,
+                environment: None,
+                label: "antnode1".parse()?,
+                program: node_data_dir
+                    .to_path_buf()
+                    .join("antnode1")
+                    .join(ANTNODE_FILE_NAME),
+                username: Some(get_username()),
+                working_directory: None,
+                disable_restart_on_failure: true,
+            }),
+            eq(false),
+        )
+        .returning(|_, _| Ok(()))
+        .in_sequence(&mut seq);
+
+    mock_service_control
+        .expect_set_resource_limits()
+        .times(1)
+        .with(eq("antnode1"), eq(resource_limits.clone()), eq(false))
+        .returning(|_, _, _| Ok(()))
+        .in_sequence(&mut seq);
+
+    add_node(
+        AddNodeServiceOptions {
+            alpha: false,
+            auto_restart: false,
+            auto_set_nat_flags: false,
+            count: Some(1),
+            delete_antnode_src: false,
+            enable_metrics_server: false,
+            env_variables: None,
+            relay: false,
+            resource_limits: resource_limits.clone(),
+            log_format: None,
+            max_archived_log_files: None,
+            max_log_files: None,
+            metrics_port: None,
+            network_id: None,
+            node_ip: None,
+            node_port: None,
+            init_peers_config: InitialPeersConfig::default(),
+            rpc_address: None,
+            rpc_port: None,
+            antnode_dir_path: temp_dir.to_path_buf(),
+            antnode_src_path: antnode_download_path.to_path_buf(),
+            service_data_dir_path: node_data_dir.to_path_buf(),
+            service_log_dir_path: node_logs_dir.to_path_buf(),
+            no_upnp: false,
+            user: Some(get_username()),
+            user_mode: false,
+            version: latest_version.to_string(),
+            evm_network: EvmNetwork::Custom(CustomNetwork {
+                rpc_url_http: "http://localhost:8545".parse()?,
+                payment_token_address: RewardsAddress::from_str(
+                    "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+                )?,
+                data_payments_address: RewardsAddress::from_str(
+                    "0x8464135c8F25Da09e49BC8782676a84730C318bC",
+                )?,
+            }),
+            rewards_address: RewardsAddress::from_str(
+                "0x03B770D9cD32077cC0bF330c13C114a87643B124",
+            )?,
+            write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
+        },
+        node_registry.clone(),
+        &mock_service_control,
+        VerbosityLevel::Normal,
+    )
+    .await?;
+
+    let node0 = node_registry.nodes.read().await[0].read().await.clone();
+    assert_eq!(node0.resource_limits, resource_limits);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn add_node_should_uninstall_the_service_if_its_resource_limits_cannot_be_applied()
+-> Result<()> {
+    let tmp_data_dir = assert_fs::TempDir::new()?;
+    let node_reg_path = tmp_data_dir.child("node_reg.json");
+
+    let mut mock_service_control = MockServiceControl::new();
+    let node_registry = NodeRegistryManager::empty(node_reg_path.to_path_buf());
+
+    let latest_version = "0.96.4";
+    let temp_dir = assert_fs::TempDir::new()?;
+    let node_data_dir = temp_dir.child("data");
+    node_data_dir.create_dir_all()?;
+    let node_logs_dir = temp_dir.child("logs");
+    node_logs_dir.create_dir_all()?;
+    let antnode_download_path = temp_dir.child(ANTNODE_FILE_NAME);
+    antnode_download_path.write_binary(b"fake antnode bin")?;
+
+    let resource_limits = ResourceLimits {
+        cpu_quota: Some(150),
+        max_memory: Some(512 * 1024 * 1024),
+        nofile: Some(4096),
+    };
+
+    let mut seq = Sequence::new();
+
+    // Expected calls for first installation
+    mock_service_control
+        .expect_get_available_port()
+        .times(1)
+        .returning(|| Ok(8081))
+        .in_sequence(&mut seq);
+
+    mock_service_control
+        .expect_install()
+        .times(1)
+        .with(
+            eq(ServiceInstallCtx {
+                args: vec![
+                    OsString::from("--rpc"),
+                    OsString::from("127.0.0.1:8081"),
+                    OsString::from("--root-dir"),
+                    OsString::from(
+                        node_data_dir
+                            .to_path_buf()
+                            .join("antnode1")
+                            .to_string_lossy()
+                            .to_string(),
+                    ),
+                    OsString::from("--log-output-dest"),
+                    OsString::from(
+                        node_logs_dir
+                            .to_path_buf()
+                            .join("antnode1")
+                            .to_string_lossy()
+                            .to_string(),
+                    ),
+                    OsString::from("--rewards-address"),
+                    OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
+                    OsString::from("evm-custom"),
+                    OsString::from("--rpc-url"),
+                    OsString::from("http://localhost:8545/"),
+                    OsString::from("--payment-token-address"),
+                    OsString::from("0x5FbDB2315678afecb367f032d93F642f64180aa3"),
+                    OsString::from("--data-payments-address"),
+                    OsString::from("0x8464135c8F25Da09e49BC8782676a84730C318bC"),
+                ],
+                autostart: false,
+                contents: None,
+                environment: None,
+                label: "antnode1".parse()?,
+                program: node_data_dir
+                    .to_path_buf()
+                    .join("antnode1")
+                    .join(ANTNODE_FILE_NAME),
+                username: Some(get_username()),
+                working_directory: None,
+                disable_restart_on_failure: true,
+            }),
+            eq(false),
+        )
+        .returning(|_, _| Ok(()))
+        .in_sequence(&mut seq);
+
+    mock_service_control
+        .expect_set_resource_limits()
+        .times(1)
+        .with(eq("antnode1"), eq(resource_limits.clone()), eq(false))
+        .returning(|_, _, _| {
+            Err(ServiceError::ResourceLimitsNotSupported(
+                "Launchd".to_string(),
+            ))
+        })
+        .in_sequence(&mut seq);
+
+    mock_service_control
+        .expect_uninstall()
+        .times(1)
+        .with(eq("antnode1"), eq(false))
+        .returning(|_, _| Ok(()))
+        .in_sequence(&mut seq);
+
+    let result = add_node(
+        AddNodeServiceOptions {
+            alpha: false,
+            auto_restart: false,
+            auto_set_nat_flags: false,
+            count: Some(1),
+            delete_antnode_src: false,
+            enable_metrics_server: false,
+            env_variables: None,
+            relay: false,
+            resource_limits: resource_limits.clone(),
+            log_format: None,
+            max_archived_log_files: None,
+            max_log_files: None,
+            metrics_port: None,
+            network_id: None,
+            node_ip: None,
+            node_port: None,
+            init_peers_config: InitialPeersConfig::default(),
+            rpc_address: None,
+            rpc_port: None,
+            antnode_dir_path: temp_dir.to_path_buf(),
+            antnode_src_path: antnode_download_path.to_path_buf(),
+            service_data_dir_path: node_data_dir.to_path_buf(),
+            service_log_dir_path: node_logs_dir.to_path_buf(),
+            no_upnp: false,
+            user: Some(get_username()),
+            user_mode: false,
+            version: latest_version.to_string(),
+            evm_network: EvmNetwork::Custom(CustomNetwork {
+                rpc_url_http: "http://localhost:8545".parse()?,
+                payment_token_address: RewardsAddress::from_str(
+                    "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+                )?,
+                data_payments_address: RewardsAddress::from_str(
+                    "0x8464135c8F25Da09e49BC8782676a84730C318bC",
+                )?,
+            }),
+            rewards_address: RewardsAddress::from_str(
+                "0x03B770D9cD32077cC0bF330c13C114a87643B124",
+            )?,
+            write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
+        },
+        node_registry.clone(),
+        &mock_service_control,
+        VerbosityLevel::Normal,
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(node_registry.nodes.read().await.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn add_node_should_add_the_node_again_if_another_writer_added_one_with_the_same_name()
+-> Result<()> {
+    let tmp_data_dir = assert_fs::TempDir::new()?;
+    let node_reg_path = tmp_data_dir.child("node_reg.json");
+
+    let temp_dir = assert_fs::TempDir::new()?;
+    let node_data_dir = temp_dir.child("data");
+    node_data_dir.create_dir_all()?;
+    let node_logs_dir = temp_dir.child("logs");
+    node_logs_dir.create_dir_all()?;
+    let antnode_download_path = temp_dir.child(ANTNODE_FILE_NAME);
+    antnode_download_path.write_binary(b"fake antnode bin")?;
+
+    let options = |rpc_port: u16| -> Result<AddNodeServiceOptions> {
+        Ok(AddNodeServiceOptions {
+            alpha: false,
+            auto_restart: false,
+            auto_set_nat_flags: false,
+            count: Some(1),
+            delete_antnode_src: false,
+            enable_metrics_server: false,
+            env_variables: None,
+            relay: false,
+            resource_limits: ResourceLimits::default(),
+            log_format: None,
+            max_archived_log_files: None,
+            max_log_files: None,
+            metrics_port: None,
+            network_id: None,
+            node_ip: None,
+            node_port: None,
+            init_peers_config: InitialPeersConfig::default(),
+            rpc_address: None,
+            rpc_port: Some(PortRange::Single(rpc_port)),
+            antnode_dir_path: temp_dir.to_path_buf(),
+            antnode_src_path: antnode_download_path.to_path_buf(),
+            service_data_dir_path: node_data_dir.to_path_buf(),
+            service_log_dir_path: node_logs_dir.to_path_buf(),
+            no_upnp: false,
+            user: Some(get_username()),
+            user_mode: false,
+            version: "0.96.4".to_string(),
+            evm_network: EvmNetwork::ArbitrumOne,
+            rewards_address: RewardsAddress::from_str(
+                "0x03B770D9cD32077cC0bF330c13C114a87643B124",
+            )?,
+            write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
+        })
+    };
+
+    // Both writers load the registry before either of them adds a node.
+    let first_registry = NodeRegistryManager::load(&node_reg_path).await?;
+    let second_registry = NodeRegistryManager::load(&node_reg_path).await?;
+
+    let mut first_service_control = MockServiceControl::new();
+    first_service_control
+        .expect_install()
+        .times(1)
+        .withf(|ctx, _| ctx.label.to_string() == "antnode1")
+        .returning(|_, _| Ok(()));
+    add_node(
+        options(8081)?,
+        first_registry,
+        &first_service_control,
+        VerbosityLevel::Normal,
+    )
+    .await?;
+
+    // The name is taken before anything is installed, so the service of the first writer is
+    // never overwritten.
+    let mut second_service_control = MockServiceControl::new();
+    second_service_control
+        .expect_install()
+        .times(1)
+        .withf(|ctx, _| ctx.label.to_string() == "antnode2")
+        .returning(|_, _| Ok(()));
+    let added = add_node(
+        options(8082)?,
+        second_registry.clone(),
+        &second_service_control,
+        VerbosityLevel::Normal,
+    )
+    .await?;
+
+    assert_eq!(added, vec!["antnode2".to_string()]);
+    let saved = NodeRegistryManager::load(&node_reg_path).await?;
+    let nodes = saved.get_node_service_data().await;
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0].service_name, "antnode1");
+    assert_eq!(nodes[0].rpc_socket_addr.port(), 8081);
+    assert_eq!(nodes[1].service_name, "antnode2");
+    assert_eq!(nodes[1].rpc_socket_addr.port(), 8082);
+
+    Ok(())
+}
@@ -13,16 +13,24 @@ use ant_bootstrap::InitialPeersConfig;
 use ant_evm::RewardsAddress;
 use ant_logging::{LogBuilder, LogFormat};
 use ant_node_manager::{
-    DEFAULT_NODE_STARTUP_CONNECTION_TIMEOUT_S, VerbosityLevel,
-    add_services::config::PortRange,
+    DEFAULT_NODE_STARTUP_CONNECTION_TIMEOUT_S, ServiceBackend, VerbosityLevel,
+    add_services::config::{PortRange, parse_quiet_hours},
     cmd::{self},
     config,
+    health::{DEFAULT_HEALTH_CHECK_WINDOW_S, DEFAULT_MIN_CONNECTED_PEERS, HealthCheckOptions},
+    logs::{LogFilter, parse_since},
 };
-use ant_service_management::NodeRegistryManager;
+use ant_service_management::{
+    NodeRegistryManager, ResourceLimits,
+    control::{ServiceController, SharedServiceControl},
+    limits::parse_memory_size,
+    supervisor::SupervisorController,
+};
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 use color_eyre::{Result, eyre::eyre};
 use libp2p::Multiaddr;
-use std::{net::Ipv4Addr, path::PathBuf};
+use std::{net::Ipv4Addr, path::PathBuf, sync::Arc, time::Duration};
 use tracing::Level;
 
 const DEFAULT_NODE_COUNT: u16 = 25;
@@ -34,6 +42,15 @@ pub(crate) struct Cmd {
     #[clap(subcommand)]
     pub cmd: Option<SubCmd>,
 
+    /// Choose how the antnode services are managed.
+    ///
+    /// The supervisor runs the services as processes of the current user, restarting them if
+    /// they crash, for machines without a service manager, e.g., containers, or users without
+    /// sudo. Supervised services are not started when the machine boots. Use the same backend for
+    /// every command, e.g. by setting the ANTCTL_BACKEND environment variable.
+    #[clap(long, env = "ANTCTL_BACKEND", value_enum, default_value_t = ServiceBackend::ServiceManager)]
+    backend: ServiceBackend,
+
     /// Print the crate version.
     #[clap(long)]
     pub crate_version: bool,
@@ -60,6 +77,7 @@ pub(crate) struct Cmd {
 }
 
 #[derive(Subcommand, Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum SubCmd {
     /// Add one or more antnode services.
     ///
@@ -103,6 +121,11 @@ pub enum SubCmd {
         /// mutually exclusive.
         #[clap(long, conflicts_with = "first")]
         count: Option<u16>,
+        /// Limit the share of a CPU each node can use, in percent.
+        ///
+        /// Above 100 allows more than one CPU. Only applies to services managed by systemd.
+        #[clap(long, value_parser = clap::value_parser!(u16).range(1..))]
+        cpu_quota: Option<u16>,
         /// Provide the path for the data directory for the installed node.
         ///
         /// This path is a prefix. Each installed node will have its own directory underneath it.
@@ -128,6 +151,12 @@ pub enum SubCmd {
         /// Example: --env ANT_LOG=all,RUST_LOG=libp2p=debug
         #[clap(name = "env", long, use_value_delimiter = false, value_parser = parse_environment_variables)]
         env_variables: Option<Vec<(String, String)>>,
+        /// Provide the path of an EVM network config file for the antnode service(s) to use.
+        ///
+        /// The file lists several RPC endpoints for the nodes to fail over between. It takes
+        /// precedence over the EVM network subcommand.
+        #[clap(long)]
+        evm_config: Option<PathBuf>,
         /// Specify what EVM network to use for payments.
         #[command(subcommand)]
         evm_network: EvmNetworkCommand,
@@ -162,6 +191,27 @@ pub enum SubCmd {
         /// After reaching this limit, the older archived files are deleted.
         #[clap(long, verbatim_doc_comment)]
         max_archived_log_files: Option<usize>,
+        /// Limit the memory each node can use, e.g. '512M' or '2G'.
+        ///
+        /// With the process supervisor, this limits the size of the data segment of the node.
+        #[clap(long, value_parser = parse_memory_size)]
+        max_memory: Option<u64>,
+        /// Limit the download rate of each node, in KiB/s, across all of its connections.
+        ///
+        /// Replication slows down while a node is at the limit, to leave room for client traffic.
+        #[clap(long)]
+        max_download_rate: Option<u64>,
+        /// Limit the download rate of each node from any single peer, in KiB/s.
+        #[clap(long)]
+        max_peer_download_rate: Option<u64>,
+        /// Limit the upload rate of each node towards any single peer, in KiB/s.
+        #[clap(long)]
+        max_peer_upload_rate: Option<u64>,
+        /// Limit the upload rate of each node, in KiB/s, across all of its connections.
+        ///
+        /// Replication slows down while a node is at the limit, to leave room for client traffic.
+        #[clap(long)]
+        max_upload_rate: Option<u64>,
         /// Specify a port for the open metrics server.
         ///
         /// If you're passing the compiled antnode via --node-path, make sure to enable the open-metrics feature
@@ -194,6 +244,9 @@ pub enum SubCmd {
         /// services, which in this case would be 5. The range must also go from lower to higher.
         #[clap(long, value_parser = PortRange::parse)]
         node_port: Option<PortRange>,
+        /// Limit the number of files each node can have open.
+        #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+        nofile: Option<u64>,
         /// Provide a path for the antnode binary to be used by the service.
         ///
         /// Useful for creating the service using a custom built binary.
@@ -201,6 +254,15 @@ pub enum SubCmd {
         path: Option<PathBuf>,
         #[command(flatten)]
         peers: InitialPeersConfig,
+        /// Daily windows of local time during which the node(s) are throttled, e.g. '09:00-17:00'.
+        ///
+        /// Multiple windows can be given, separated by commas. A window can wrap around midnight,
+        /// e.g. '22:00-07:00'.
+        ///
+        /// While throttled, a node applies its throttled rate limits, slows down replication,
+        /// pauses storage challenges and asks its close peers not to dial it.
+        #[clap(long, value_parser = parse_quiet_hours, verbatim_doc_comment)]
+        quiet_hours: Option<String>,
         /// Specify the wallet address that will receive the node's earnings.
         #[clap(long)]
         rewards_address: RewardsAddress,
@@ -225,6 +287,30 @@ pub enum SubCmd {
         /// By default, antnode will try to use UPnP if available. Use this flag to disable UPnP.
         #[clap(long, default_value_t = false)]
         no_upnp: bool,
+        /// Specify a port for the antnode service(s) to listen on with TCP, in addition to QUIC.
+        ///
+        /// TCP is disabled unless this argument is used. It helps nodes behind firewalls that
+        /// block UDP traffic.
+        ///
+        /// If multiple services are being added and this argument is used, you must specify a
+        /// range. For example, '12000-12004'. The length of the range must match the number of
+        /// services, which in this case would be 5. The range must also go from lower to higher.
+        #[clap(long, value_parser = PortRange::parse)]
+        tcp_port: Option<PortRange>,
+        /// Throttle the node(s) while the system-wide CPU usage is above this percentage.
+        #[clap(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+        throttle_cpu_usage: Option<u8>,
+        /// Throttle the node(s) while the system-wide memory usage is above this percentage.
+        #[clap(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+        throttle_memory_usage: Option<u8>,
+        /// Limit the download rate of each node while throttled, in KiB/s, across all of its
+        /// connections.
+        #[clap(long)]
+        throttled_download_rate: Option<u64>,
+        /// Limit the upload rate of each node while throttled, in KiB/s, across all of its
+        /// connections.
+        #[clap(long)]
+        throttled_upload_rate: Option<u64>,
         /// Provide a antnode binary using a URL.
         ///
         /// The binary must be inside a zip or gzipped tar archive.
@@ -251,6 +337,39 @@ pub enum SubCmd {
         /// Set this to true if you want the node to write the cache files in the older formats.
         #[clap(long, default_value_t = false)]
         write_older_cache_files: bool,
+        /// Specify a port for the antnode service(s) to listen on with WebSocket, in addition to QUIC.
+        ///
+        /// WebSocket is disabled unless this argument is used.
+        ///
+        /// If multiple services are being added and this argument is used, you must specify a
+        /// range. For example, '12000-12004'. The length of the range must match the number of
+        /// services, which in this case would be 5. The range must also go from lower to higher.
+        #[clap(long, value_parser = PortRange::parse)]
+        ws_port: Option<PortRange>,
+    },
+    /// Bring the antnode services in line with a fleet file.
+    ///
+    /// The fleet file is a TOML document describing the desired nodes: their count, port ranges,
+    /// directories, environment variables, EVM network, version and other settings. The file can
+    /// be produced from the current services with the 'export' command.
+    ///
+    /// The services are compared against the file to make a plan: nodes beyond the count are
+    /// removed, nodes whose settings or version differ are reconfigured or upgraded, and missing
+    /// nodes are added. The plan is printed and confirmed before it is executed.
+    ///
+    /// On Windows, this command must run as the administrative user. On Linux/macOS, run using
+    /// sudo if you defined system-wide services; otherwise, do not run the command elevated.
+    #[clap(name = "apply")]
+    Apply {
+        /// Set to print the plan without executing it.
+        #[clap(long)]
+        dry_run: bool,
+        /// The path of the fleet file.
+        #[clap(long, short)]
+        file: PathBuf,
+        /// Set to suppress the confirmation prompt.
+        #[clap(long, short)]
+        yes: bool,
     },
     /// Get node reward balances.
     #[clap(name = "balance")]
@@ -268,8 +387,135 @@ pub enum SubCmd {
     },
     #[clap(subcommand)]
     Daemon(DaemonSubCmd),
+    /// Describe the antnode services as a fleet file, for use with the 'apply' command.
+    ///
+    /// The settings of the first node are used. Any node whose settings differ is reported, since
+    /// applying the file would reconfigure it.
+    #[clap(name = "export")]
+    Export {
+        /// The path to write the fleet file to. The file is printed if not given.
+        #[clap(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Export a node to an archive, to move it to another machine with its identity intact.
+    ///
+    /// The node is stopped, then its settings and data directory, which holds its keypair, record
+    /// store and quoting metrics, are written to a gzipped tarball. The node is left stopped, and
+    /// should be removed once it has been imported elsewhere, since two nodes cannot share an
+    /// identity.
+    ///
+    /// On Windows, this command must run as the administrative user. On Linux/macOS, run using
+    /// sudo if you defined system-wide services; otherwise, do not run the command elevated.
+    #[clap(name = "export-node")]
+    ExportNode {
+        /// The path to write the node archive to.
+        #[clap(long, short)]
+        output: PathBuf,
+        /// The name of the service of the node to export.
+        #[clap(long)]
+        service_name: String,
+    },
+    /// Add a node from an archive written by the 'export-node' command.
+    ///
+    /// The node is added with the settings it had, apart from its RPC and metrics ports, and its
+    /// data directory is restored, so it keeps its identity. By default, the version of antnode it
+    /// ran is installed; however, it is possible to provide a binary either by specifying a URL, a
+    /// local path, or a specific version number.
+    ///
+    /// On Windows, this command must run as the administrative user. On Linux/macOS, run using
+    /// sudo to define a system-wide service; otherwise, do not run the command elevated.
+    #[clap(name = "import-node")]
+    ImportNode {
+        /// The path of the node archive.
+        #[clap(long, short)]
+        file: PathBuf,
+        /// Provide the path for the data directory for the node, rather than the one it had.
+        ///
+        /// This path is a prefix. The node will have its own directory underneath it.
+        #[clap(long)]
+        data_dir_path: Option<PathBuf>,
+        /// Provide the path for the log directory for the node, rather than the one it had.
+        ///
+        /// This path is a prefix. The node will have its own directory underneath it.
+        #[clap(long)]
+        log_dir_path: Option<PathBuf>,
+        /// Provide a path for the antnode binary to be used by the service.
+        #[clap(long, conflicts_with_all = ["url", "version"])]
+        path: Option<PathBuf>,
+        /// Provide a binary to use for the service from a URL.
+        ///
+        /// The binary must be inside a zip or gzipped tar archive.
+        #[clap(long, conflicts_with = "version")]
+        url: Option<String>,
+        /// Provide a specific version of antnode to be installed.
+        ///
+        /// The version number should be in the form X.Y.Z, with no 'v' prefix.
+        #[clap(long)]
+        version: Option<String>,
+    },
     #[clap(subcommand)]
     Local(LocalSubCmd),
+    /// Show the logs of antnode service(s).
+    ///
+    /// If no peer ID(s) or service name(s) are supplied, the logs of all services are shown. The
+    /// logs of several services are merged in chronological order, with each line prefixed by the
+    /// name of its service. Archived log files are included.
+    #[clap(name = "logs")]
+    Logs {
+        /// Keep showing new log entries as they are written.
+        #[clap(long, short)]
+        follow: bool,
+        /// Only show entries containing this text.
+        #[clap(long)]
+        grep: Option<String>,
+        /// Output each entry as a JSON object on its own line.
+        #[clap(long)]
+        json: bool,
+        /// Only show entries at this level or a more severe one, e.g. 'warn'.
+        #[clap(long)]
+        level: Option<Level>,
+        /// Only show this many of the most recent entries.
+        #[clap(long, short = 'n')]
+        lines: Option<usize>,
+        /// The peer ID of the service to show the logs of.
+        ///
+        /// The argument can be used multiple times.
+        #[clap(long)]
+        peer_id: Vec<String>,
+        /// The name of the service to show the logs of.
+        ///
+        /// The argument can be used multiple times.
+        #[clap(long, conflicts_with = "peer_id")]
+        service_name: Vec<String>,
+        /// Only show entries logged since this time.
+        ///
+        /// This is either a duration before now, such as '30s', '10m', '2h' or '1d', or an RFC 3339
+        /// timestamp.
+        #[clap(long, value_parser = parse_since)]
+        since: Option<DateTime<Utc>>,
+    },
+    /// Move the data directory of a node to another directory, e.g. on another drive.
+    ///
+    /// The data directory holds the keypair, record store and quoting metrics of the node. The
+    /// node is stopped while it is copied and the copy is verified, then the service is
+    /// reinstalled to use the new directory, and started again if it was running.
+    ///
+    /// On Windows, this command must run as the administrative user. On Linux/macOS, run using
+    /// sudo if you defined system-wide services; otherwise, do not run the command elevated.
+    #[clap(name = "migrate")]
+    Migrate {
+        /// Keep the original data directory, rather than removing it once the data is moved.
+        #[clap(long)]
+        keep_source: bool,
+        /// The name of the service of the node to migrate.
+        #[clap(long)]
+        service_name: String,
+        /// The directory to move the data to.
+        ///
+        /// This path is a prefix. The node will have its own directory underneath it.
+        #[clap(long)]
+        to: PathBuf,
+    },
     #[clap(subcommand)]
     NatDetection(NatDetectionSubCmd),
     /// Remove antnode service(s).
@@ -354,9 +600,19 @@ pub enum SubCmd {
         /// Set this flag to display more details
         #[clap(long)]
         details: bool,
-        /// Set this flag to return an error if any nodes are not running
+        /// Set this flag to return an error if any nodes are not running.
+        ///
+        /// With --health, an error is returned if any nodes stand out from the fleet.
         #[clap(long)]
         fail: bool,
+        /// Set this flag to query the health of the running nodes over RPC and metrics.
+        ///
+        /// The report covers record counts, connected peers, how many close group peers shun each
+        /// node, relay reservation health, record PUT errors and earnings. Nodes that stand out from
+        /// the rest of the fleet are flagged. The metrics are only available for nodes with a
+        /// metrics server.
+        #[clap(long, conflicts_with = "details")]
+        health: bool,
         /// Set this flag to output the status as a JSON document
         #[clap(long, conflicts_with = "details")]
         json: bool,
@@ -385,6 +641,12 @@ pub enum SubCmd {
         #[clap(long, conflicts_with = "peer_id")]
         service_name: Vec<String>,
     },
+    /// Run the supervisor of a service. This is used by the supervisor backend.
+    #[clap(name = "supervise", hide = true)]
+    Supervise {
+        /// The name of the service to supervise.
+        service_name: String,
+    },
     /// Upgrade antnode services.
     ///
     /// By default, each node service is started after the previous node has successfully connected to the network or
@@ -398,6 +660,11 @@ pub enum SubCmd {
     /// sudo if you defined system-wide services; otherwise, do not run the command elevated.
     #[clap(name = "upgrade")]
     Upgrade {
+        /// Remove the resource limits of the services.
+        ///
+        /// Any limits given with the other arguments are applied instead.
+        #[clap(long)]
+        clear_resource_limits: bool,
         /// The max time in seconds to wait for a node to connect to the network. If the node does not connect to the
         /// network within this time, the node is considered failed.
         ///
@@ -406,6 +673,13 @@ pub enum SubCmd {
         /// Defaults to 300s.
         #[clap(long, default_value_t = DEFAULT_NODE_STARTUP_CONNECTION_TIMEOUT_S, conflicts_with = "interval")]
         connection_timeout: u64,
+        /// Limit the share of a CPU each node can use, in percent.
+        ///
+        /// Above 100 allows more than one CPU. Only applies to services managed by systemd.
+        ///
+        /// The service definitions are updated with the new limit, even if no upgrade is required.
+        #[clap(long, value_parser = clap::value_parser!(u16).range(1..))]
+        cpu_quota: Option<u16>,
         /// Set this flag to upgrade the nodes without automatically starting them.
         ///
         /// Can be useful for testing scenarios.
@@ -427,6 +701,19 @@ pub enum SubCmd {
         /// Required if we want to downgrade, or for testing purposes.
         #[clap(long)]
         force: bool,
+        /// Set to check the health of each service after its upgrade, before upgrading the next.
+        ///
+        /// A service is healthy once it runs the new version, is connected to enough peers and
+        /// holds at least half the records it held before the upgrade. A service that does not
+        /// become healthy within the health check window is rolled back to its previous binary and
+        /// version, and the remaining services are not upgraded.
+        #[clap(long, conflicts_with = "do_not_start")]
+        health_check: bool,
+        /// How long a service has to become healthy after its upgrade.
+        ///
+        /// Units are seconds.
+        #[clap(long, default_value_t = DEFAULT_HEALTH_CHECK_WINDOW_S, requires = "health_check")]
+        health_check_window: u64,
         /// An interval applied between upgrading each service.
         ///
         /// Use connection-timeout to scale the interval automatically. This argument is mutually exclusive with the
@@ -435,6 +722,21 @@ pub enum SubCmd {
         /// Units are milliseconds.
         #[clap(long, conflicts_with = "connection_timeout")]
         interval: Option<u64>,
+        /// Limit the memory each node can use, e.g. '512M' or '2G'.
+        ///
+        /// With the process supervisor, this limits the size of the data segment of the node.
+        ///
+        /// The service definitions are updated with the new limit, even if no upgrade is required.
+        #[clap(long, value_parser = parse_memory_size)]
+        max_memory: Option<u64>,
+        /// The number of peers a service must be connected to after its upgrade to be healthy.
+        #[clap(long, default_value_t = DEFAULT_MIN_CONNECTED_PEERS, requires = "health_check")]
+        min_connected_peers: usize,
+        /// Limit the number of files each node can have open.
+        ///
+        /// The service definitions are updated with the new limit, even if no upgrade is required.
+        #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+        nofile: Option<u64>,
         /// Provide a path for the antnode binary to be used by the service.
         ///
         /// Useful for upgrading the service using a custom built binary.
@@ -821,7 +1123,16 @@ async fn main() -> Result<()> {
         None
     };
 
-    configure_winsw(verbosity).await?;
+    let service_control: SharedServiceControl = if args.backend == ServiceBackend::Supervisor {
+        Arc::new(SupervisorController::new(
+            config::get_supervisor_dir_path()?,
+            std::env::current_exe()?,
+            vec!["supervise".into()],
+        ))
+    } else {
+        configure_winsw(verbosity).await?;
+        Arc::new(ServiceController {})
+    };
 
     tracing::info!("Executing cmd: {:?}", args.cmd);
 
@@ -832,29 +1143,44 @@ async fn main() -> Result<()> {
             auto_restart,
             auto_set_nat_flags,
             count,
+            cpu_quota,
             data_dir_path,
             enable_metrics_server,
             env_variables,
+            evm_config,
             evm_network,
             relay,
             log_dir_path,
             log_format,
             max_archived_log_files,
+            max_download_rate,
             max_log_files,
+            max_memory,
+            max_peer_download_rate,
+            max_peer_upload_rate,
+            max_upload_rate,
             metrics_port,
             network_id,
             node_ip,
             node_port,
+            nofile,
             path,
             peers,
+            quiet_hours,
             rewards_address,
             rpc_address,
             rpc_port,
+            tcp_port,
+            throttle_cpu_usage,
+            throttle_memory_usage,
+            throttled_download_rate,
+            throttled_upload_rate,
             url,
             no_upnp,
             user,
             version,
             write_older_cache_files,
+            ws_port,
         }) => {
             cmd::node::add(
                 alpha,
@@ -864,36 +1190,74 @@ async fn main() -> Result<()> {
                 data_dir_path,
                 enable_metrics_server,
                 env_variables,
+                evm_config,
                 Some(evm_network.try_into()?),
                 log_dir_path,
                 log_format,
                 max_archived_log_files,
+                max_download_rate,
                 max_log_files,
+                max_peer_download_rate,
+                max_peer_upload_rate,
+                max_upload_rate,
                 metrics_port,
                 network_id,
                 node_ip,
                 node_port,
                 node_registry,
+                service_control,
                 peers,
+                quiet_hours,
                 relay,
+                ResourceLimits {
+                    cpu_quota,
+                    max_memory,
+                    nofile,
+                },
                 rewards_address,
                 rpc_address,
                 rpc_port,
                 path,
                 no_upnp,
+                tcp_port,
+                throttle_cpu_usage,
+                throttle_memory_usage,
+                throttled_download_rate,
+                throttled_upload_rate,
                 url,
                 user,
                 version,
                 verbosity,
                 write_older_cache_files,
+                ws_port,
             )
             .await?;
             Ok(())
         }
+        Some(SubCmd::Apply { dry_run, file, yes }) => {
+            cmd::node::apply(
+                dry_run,
+                file,
+                node_registry,
+                service_control,
+                verbosity,
+                yes,
+            )
+            .await
+        }
         Some(SubCmd::Balance {
             peer_id: peer_ids,
             service_name: service_names,
-        }) => cmd::node::balance(peer_ids, node_registry, service_names, verbosity).await,
+        }) => {
+            cmd::node::balance(
+                peer_ids,
+                node_registry,
+                service_control,
+                service_names,
+                verbosity,
+            )
+            .await
+        }
         Some(SubCmd::Daemon(DaemonSubCmd::Add {
             address,
             env_variables,
@@ -904,6 +1268,41 @@ async fn main() -> Result<()> {
         })) => cmd::daemon::add(address, env_variables, port, path, url, version, verbosity).await,
         Some(SubCmd::Daemon(DaemonSubCmd::Start {})) => cmd::daemon::start(verbosity).await,
         Some(SubCmd::Daemon(DaemonSubCmd::Stop {})) => cmd::daemon::stop(verbosity).await,
+        Some(SubCmd::Export { output }) => cmd::node::export(node_registry, output).await,
+        Some(SubCmd::ExportNode {
+            output,
+            service_name,
+        }) => {
+            cmd::node::export_node(
+                node_registry,
+                service_control,
+                output,
+                service_name,
+                verbosity,
+            )
+            .await
+        }
+        Some(SubCmd::ImportNode {
+            file,
+            data_dir_path,
+            log_dir_path,
+            path,
+            url,
+            version,
+        }) => {
+            cmd::node::import_node(
+                file,
+                data_dir_path,
+                log_dir_path,
+                node_registry,
+                service_control,
+                path,
+                url,
+                version,
+                verbosity,
+            )
+            .await
+        }
         Some(SubCmd::Local(local_command)) => match local_command {
             LocalSubCmd::Join {
                 build,
@@ -992,6 +1391,43 @@ async fn main() -> Result<()> {
                 json,
             } => cmd::local::status(details, fail, json).await,
         },
+        Some(SubCmd::Logs {
+            follow,
+            grep,
+            json,
+            level,
+            lines,
+            peer_id: peer_ids,
+            service_name: service_names,
+            since,
+        }) => {
+            let filter = LogFilter { grep, level, since };
+            cmd::node::logs(
+                filter,
+                follow,
+                json,
+                lines,
+                node_registry,
+                peer_ids,
+                service_names,
+            )
+            .await
+        }
+        Some(SubCmd::Migrate {
+            keep_source,
+            service_name,
+            to,
+        }) => {
+            cmd::node::migrate(
+                keep_source,
+                node_registry,
+                service_control,
+                service_name,
+                to,
+                verbosity,
+            )
+            .await
+        }
         Some(SubCmd::NatDetection(NatDetectionSubCmd::Run {
             path,
             servers,
@@ -1010,12 +1446,15 @@ async fn main() -> Result<()> {
                 keep_directories,
                 peer_ids,
                 node_registry,
+                service_control,
                 service_names,
                 verbosity,
             )
             .await
         }
-        Some(SubCmd::Reset { force }) => cmd::node::reset(force, node_registry, verbosity).await,
+        Some(SubCmd::Reset { force }) => {
+            cmd::node::reset(force, node_registry, service_control, verbosity).await
+        }
         Some(SubCmd::Start {
             connection_timeout,
             interval,
@@ -1026,6 +1465,7 @@ async fn main() -> Result<()> {
                 connection_timeout,
                 interval,
                 node_registry,
+                service_control,
                 peer_ids,
                 service_names,
                 verbosity,
@@ -1035,18 +1475,42 @@ async fn main() -> Result<()> {
         Some(SubCmd::Status {
             details,
             fail,
+            health,
             json,
-        }) => cmd::node::status(details, fail, json, node_registry).await,
+        }) => cmd::node::status(details, fail, health, json, node_registry, service_control).await,
         Some(SubCmd::Stop {
             interval,
             peer_id: peer_ids,
             service_name: service_names,
-        }) => cmd::node::stop(interval, node_registry, peer_ids, service_names, verbosity).await,
+        }) => {
+            cmd::node::stop(
+                interval,
+                node_registry,
+                service_control,
+                peer_ids,
+                service_names,
+                verbosity,
+            )
+            .await
+        }
+        Some(SubCmd::Supervise { service_name }) => {
+            Ok(ant_service_management::supervisor::supervise(
+                &config::get_supervisor_dir_path()?,
+                &service_name,
+            )?)
+        }
         Some(SubCmd::Upgrade {
+            clear_resource_limits,
             connection_timeout,
+            cpu_quota,
             do_not_start,
             force,
+            health_check,
+            health_check_window,
             interval,
+            max_memory,
+            min_connected_peers,
+            nofile,
             path,
             peer_id: peer_ids,
             service_name: service_names,
@@ -1060,9 +1524,20 @@ async fn main() -> Result<()> {
                 path,
                 force,
                 interval,
+                health_check.then(|| HealthCheckOptions {
+                    window: Duration::from_secs(health_check_window),
+                    min_connected_peers,
+                }),
                 node_registry,
+                service_control,
                 peer_ids,
                 provided_env_variable,
+                ResourceLimits {
+                    cpu_quota,
+                    max_memory,
+                    nofile,
+                },
+                clear_resource_limits,
                 service_names,
                 url,
                 version,
@@ -6,26 +6,71 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+// The tonic `Status` error is large, but it is what the generated service requires.
+#![allow(clippy::result_large_err)]
+
 #[macro_use]
 extern crate tracing;
 
+use ant_bootstrap::InitialPeersConfig;
+use ant_evm::{EvmNetwork, RewardsAddress};
 use ant_logging::LogBuilder;
-use ant_node_manager::{DAEMON_DEFAULT_PORT, config::get_node_registry_path, rpc};
+use ant_node_manager::{
+    DAEMON_DEFAULT_PORT, DEFAULT_NODE_STARTUP_CONNECTION_TIMEOUT_S, ServiceBackend, VerbosityLevel,
+    add_services::config::PortRange,
+    cmd,
+    config::{
+        DaemonConfig, get_daemon_config_path, get_healing_audit_log_path, get_node_registry_path,
+        get_supervisor_dir_path,
+    },
+    healing::{
+        AuditEntry, Decision, HealingConfig, HealingPolicy, Remediation, append_audit_entry,
+        probe_node,
+    },
+    refresh_node_registry, rpc,
+};
 use ant_service_management::{
-    NodeRegistryManager,
+    NodeRegistryManager, NodeServiceData, ResourceLimits,
     antctl_proto::{
-        GetStatusRequest, GetStatusResponse, NodeServiceRestartRequest, NodeServiceRestartResponse,
+        AddNodesRequest, AddNodesResponse, EventsRequest, GetStatusRequest, GetStatusResponse,
+        NodeEvent, NodeServiceRestartRequest, NodeServiceRestartResponse, RemoveNodesRequest,
+        RemoveNodesResponse, ResetNodesRequest, ResetNodesResponse, StartNodesRequest,
+        StartNodesResponse, StopNodesRequest, StopNodesResponse, UpgradeNodesRequest,
+        UpgradeNodesResponse,
         ant_ctl_server::{AntCtl, AntCtlServer},
-        get_status_response::Node,
+        get_status_response::{Node, ServiceStatus},
     },
+    control::{ServiceController, SharedServiceControl},
+    daemon::{get_daemon_token_path, is_request_authenticated, read_daemon_token},
+    supervisor::{SupervisorController, supervise},
 };
+use chrono::Utc;
 use clap::Parser;
 use color_eyre::eyre::{Result, eyre};
+use libp2p::Multiaddr;
 use libp2p_identity::PeerId;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+    collections::HashMap,
+    future::Future,
+    io::Write,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{Mutex, broadcast};
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
 use tonic::{Code, Request, Response, Status, transport::Server};
 use tracing::Level;
 
+/// While there are subscribers to the events feed, the registry is refreshed at this interval to
+/// pick up status changes made outwith the daemon, such as a node crashing.
+const EVENTS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// A subscriber that falls further behind than this misses the events it lagged on.
+const EVENTS_CHANNEL_CAPACITY: usize = 256;
+
 #[derive(Parser, Debug)]
 #[command(disable_version_flag = true)]
 struct Args {
@@ -34,6 +79,16 @@ struct Args {
     /// If not set, the daemon listens locally for commands.
     #[clap(long, default_value_t = Ipv4Addr::new(127, 0, 0, 1))]
     address: Ipv4Addr,
+    /// Choose how the antnode services are managed.
+    ///
+    /// Use the same backend as antctl, e.g. by setting the ANTCTL_BACKEND environment variable.
+    #[clap(long, env = "ANTCTL_BACKEND", value_enum, default_value_t = ServiceBackend::ServiceManager)]
+    backend: ServiceBackend,
+    /// The path of the daemon config, which sets the thresholds of the self-healing of nodes.
+    ///
+    /// It defaults to antctld.toml next to the node registry.
+    #[clap(long)]
+    config: Option<PathBuf>,
     /// Print the crate version.
     #[clap(long)]
     pub crate_version: bool,
@@ -44,39 +99,47 @@ struct Args {
     /// Specify a port for the daemon to listen for RPCs. It defaults to 12500 if not set.
     #[clap(long, default_value_t = DAEMON_DEFAULT_PORT)]
     port: u16,
+    /// Run the supervisor of a service. This is used by the supervisor backend.
+    #[clap(long, hide = true)]
+    supervise: Option<String>,
     /// Print version information.
     #[clap(long)]
     version: bool,
 }
 
-struct AntCtlDaemon {}
+#[derive(Clone)]
+struct AntCtlDaemon {
+    events: broadcast::Sender<NodeEvent>,
+    /// The last event published for each service, used to only publish changes.
+    last_events: Arc<Mutex<HashMap<String, NodeEvent>>>,
+    /// Operations are serialised, so that concurrent requests do not overwrite each other's
+    /// changes to the registry.
+    operation_lock: Arc<Mutex<()>>,
+    /// Manages the services, with the service manager of the OS or the process supervisor.
+    service_control: SharedServiceControl,
+}
 
 // Implementing RPC interface for service defined in .proto
 #[tonic::async_trait]
 impl AntCtl for AntCtlDaemon {
+    type EventsStream = Pin<Box<dyn Stream<Item = Result<NodeEvent, Status>> + Send + 'static>>;
+
     async fn restart_node_service(
         &self,
         request: Request<NodeServiceRestartRequest>,
     ) -> Result<Response<NodeServiceRestartResponse>, Status> {
         println!("RPC request received {:?}", request.get_ref());
         info!("RPC request received {:?}", request.get_ref());
-        let node_registry = Self::load_node_registry().await.map_err(|err| {
-            Status::new(
-                Code::Internal,
-                format!("Failed to load node registry: {err}"),
-            )
-        })?;
-
         let peer_id = PeerId::from_bytes(&request.get_ref().peer_id).map_err(|err| {
             error!("Failed to parse PeerId: {err}");
             Status::new(Code::Internal, format!("Failed to parse PeerId: {err}"))
         })?;
 
-        Self::restart_handler(node_registry, peer_id, request.get_ref().retain_peer_id)
-            .await
-            .map_err(|err| {
-                Status::new(Code::Internal, format!("Failed to restart the node: {err}"))
-            })?;
+        let retain_peer_id = request.get_ref().retain_peer_id;
+        self.run_operation("restart the node", |node_registry, service_control| {
+            Self::restart_handler(node_registry, service_control, peer_id, retain_peer_id)
+        })
+        .await?;
 
         info!("Node service restarted for {peer_id:?}");
         Ok(Response::new(NodeServiceRestartResponse {}))
@@ -109,9 +172,283 @@ impl AntCtl for AntCtlDaemon {
         info!("Node status retrieved, nod len: {:?}", nodes_info.len());
         Ok(Response::new(GetStatusResponse { nodes: nodes_info }))
     }
+
+    async fn add_nodes(
+        &self,
+        request: Request<AddNodesRequest>,
+    ) -> Result<Response<AddNodesResponse>, Status> {
+        info!("RPC request received {:?}", request.get_ref());
+        let request = request.into_inner();
+
+        let count = match request.count {
+            0 => None,
+            count => Some(u16::try_from(count).map_err(|_| {
+                Status::invalid_argument(format!("The count ({count}) is too large"))
+            })?),
+        };
+        let rewards_address = RewardsAddress::from_str(&request.rewards_address)
+            .map_err(|err| Status::invalid_argument(format!("Invalid rewards address: {err}")))?;
+        let evm_network = match request.evm_custom {
+            Some(custom) => EvmNetwork::new_custom(
+                &custom.rpc_url,
+                &custom.payment_token_address,
+                &custom.data_payments_address,
+            ),
+            None => EvmNetwork::from_str(&request.evm_network).map_err(|_| {
+                Status::invalid_argument(format!(
+                    "Unsupported EVM network '{}'",
+                    request.evm_network
+                ))
+            })?,
+        };
+        let network_id = request
+            .network_id
+            .map(|id| {
+                u8::try_from(id).map_err(|_| {
+                    Status::invalid_argument(format!("The network ID ({id}) is too large"))
+                })
+            })
+            .transpose()?;
+        let addrs = request
+            .peers
+            .iter()
+            .map(|peer| {
+                Multiaddr::from_str(peer).map_err(|err| {
+                    Status::invalid_argument(format!("Invalid peer address '{peer}': {err}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let node_port = parse_port_range(request.node_port.as_deref())?;
+        let metrics_port = parse_port_range(request.metrics_port.as_deref())?;
+        let rpc_port = parse_port_range(request.rpc_port.as_deref())?;
+
+        let service_names = self
+            .run_operation("add the nodes", |node_registry, service_control| {
+                cmd::node::add(
+                    false,
+                    request.auto_restart,
+                    false,
+                    count,
+                    request.data_dir_path.map(PathBuf::from),
+                    request.enable_metrics_server,
+                    None,
+                    request.evm_config.map(PathBuf::from),
+                    Some(evm_network),
+                    request.log_dir_path.map(PathBuf::from),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    metrics_port,
+                    network_id,
+                    None,
+                    node_port,
+                    node_registry,
+                    service_control,
+                    InitialPeersConfig {
+                        addrs,
+                        network_contacts_url: request.network_contacts_url.into_iter().collect(),
+                        ..Default::default()
+                    },
+                    None,
+                    request.relay,
+                    ResourceLimits::default(),
+                    rewards_address,
+                    None,
+                    rpc_port,
+                    None,
+                    request.no_upnp,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    request.url,
+                    None,
+                    request.version,
+                    VerbosityLevel::Minimal,
+                    false,
+                    None,
+                )
+            })
+            .await?;
+
+        info!("Added node services: {service_names:?}");
+        Ok(Response::new(AddNodesResponse { service_names }))
+    }
+
+    async fn remove_nodes(
+        &self,
+        request: Request<RemoveNodesRequest>,
+    ) -> Result<Response<RemoveNodesResponse>, Status> {
+        info!("RPC request received {:?}", request.get_ref());
+        let request = request.into_inner();
+        let peer_ids = parse_peer_ids(&request.peer_ids)?;
+
+        self.run_operation("remove the nodes", |node_registry, service_control| {
+            cmd::node::remove(
+                request.keep_directories,
+                peer_ids,
+                node_registry,
+                service_control,
+                request.service_names,
+                VerbosityLevel::Minimal,
+            )
+        })
+        .await?;
+
+        Ok(Response::new(RemoveNodesResponse {}))
+    }
+
+    async fn start_nodes(
+        &self,
+        request: Request<StartNodesRequest>,
+    ) -> Result<Response<StartNodesResponse>, Status> {
+        info!("RPC request received {:?}", request.get_ref());
+        let request = request.into_inner();
+        let peer_ids = parse_peer_ids(&request.peer_ids)?;
+
+        self.run_operation("start the nodes", |node_registry, service_control| {
+            cmd::node::start(
+                request
+                    .connection_timeout_secs
+                    .unwrap_or(DEFAULT_NODE_STARTUP_CONNECTION_TIMEOUT_S),
+                request.interval_millis,
+                node_registry,
+                service_control,
+                peer_ids,
+                request.service_names,
+                VerbosityLevel::Minimal,
+            )
+        })
+        .await?;
+
+        Ok(Response::new(StartNodesResponse {}))
+    }
+
+    async fn stop_nodes(
+        &self,
+        request: Request<StopNodesRequest>,
+    ) -> Result<Response<StopNodesResponse>, Status> {
+        info!("RPC request received {:?}", request.get_ref());
+        let request = request.into_inner();
+        let peer_ids = parse_peer_ids(&request.peer_ids)?;
+
+        self.run_operation("stop the nodes", |node_registry, service_control| {
+            cmd::node::stop(
+                request.interval_millis,
+                node_registry,
+                service_control,
+                peer_ids,
+                request.service_names,
+                VerbosityLevel::Minimal,
+            )
+        })
+        .await?;
+
+        Ok(Response::new(StopNodesResponse {}))
+    }
+
+    async fn upgrade_nodes(
+        &self,
+        request: Request<UpgradeNodesRequest>,
+    ) -> Result<Response<UpgradeNodesResponse>, Status> {
+        info!("RPC request received {:?}", request.get_ref());
+        let request = request.into_inner();
+        let peer_ids = parse_peer_ids(&request.peer_ids)?;
+
+        self.run_operation("upgrade the nodes", |node_registry, service_control| {
+            cmd::node::upgrade(
+                request
+                    .connection_timeout_secs
+                    .unwrap_or(DEFAULT_NODE_STARTUP_CONNECTION_TIMEOUT_S),
+                request.do_not_start,
+                None,
+                request.force,
+                request.interval_millis,
+                None,
+                node_registry,
+                service_control,
+                peer_ids,
+                None,
+                ResourceLimits::default(),
+                false,
+                request.service_names,
+                request.url,
+                request.version,
+                VerbosityLevel::Minimal,
+            )
+        })
+        .await?;
+
+        Ok(Response::new(UpgradeNodesResponse {}))
+    }
+
+    async fn reset_nodes(
+        &self,
+        request: Request<ResetNodesRequest>,
+    ) -> Result<Response<ResetNodesResponse>, Status> {
+        info!("RPC request received {:?}", request.get_ref());
+
+        // There is nobody to answer the confirmation prompt, so the reset is always forced.
+        self.run_operation("reset the nodes", |node_registry, service_control| {
+            cmd::node::reset(
+                true,
+                node_registry,
+                service_control,
+                VerbosityLevel::Minimal,
+            )
+        })
+        .await?;
+
+        Ok(Response::new(ResetNodesResponse {}))
+    }
+
+    async fn events(
+        &self,
+        request: Request<EventsRequest>,
+    ) -> Result<Response<Self::EventsStream>, Status> {
+        info!("RPC request received {:?}", request.get_ref());
+
+        // Subscribe before reading the current statuses, so no change can fall between the two.
+        let receiver = self.events.subscribe();
+        let node_registry = Self::load_node_registry().await.map_err(|err| {
+            Status::new(
+                Code::Internal,
+                format!("Failed to load node registry: {err}"),
+            )
+        })?;
+        let current_events = Self::node_events(&node_registry).await;
+
+        let stream = tokio_stream::iter(current_events.into_iter().map(Ok)).chain(
+            BroadcastStream::new(receiver).filter_map(|event| match event {
+                Ok(event) => Some(Ok(event)),
+                Err(err) => {
+                    warn!("Events subscriber fell behind: {err}");
+                    None
+                }
+            }),
+        );
+        Ok(Response::new(Box::pin(stream)))
+    }
 }
 
+// The SafeNodeManager trait returns `Status` as its error. So the actual logic is here and we can easily map the errors
+// into Status inside the trait fns.
 impl AntCtlDaemon {
+    fn new(service_control: SharedServiceControl) -> Self {
+        let (events, _) = broadcast::channel(EVENTS_CHANNEL_CAPACITY);
+        Self {
+            events,
+            last_events: Arc::new(Mutex::new(HashMap::new())),
+            operation_lock: Arc::new(Mutex::new(())),
+            service_control,
+        }
+    }
+
     async fn load_node_registry() -> Result<NodeRegistryManager> {
         let node_registry_path = get_node_registry_path()
             .map_err(|err| eyre!("Could not obtain node registry path: {err:?}"))?;
@@ -123,23 +460,263 @@ impl AntCtlDaemon {
 
     async fn restart_handler(
         node_registry: NodeRegistryManager,
+        service_control: SharedServiceControl,
         peer_id: PeerId,
         retain_peer_id: bool,
     ) -> Result<()> {
-        let res = rpc::restart_node_service(node_registry.clone(), peer_id, retain_peer_id).await;
+        let res = rpc::restart_node_service(
+            node_registry.clone(),
+            service_control,
+            peer_id,
+            retain_peer_id,
+        )
+        .await;
 
         // make sure to save the state even if the above fn fails.
         node_registry.save().await?;
 
         res
     }
+
+    /// Run an operation against the registry, then publish any status changes it made, whether
+    /// or not it succeeded.
+    async fn run_operation<F, Fut, T>(&self, description: &str, operation: F) -> Result<T, Status>
+    where
+        F: FnOnce(NodeRegistryManager, SharedServiceControl) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let _guard = self.operation_lock.lock().await;
+        let node_registry = Self::load_node_registry().await.map_err(|err| {
+            Status::new(
+                Code::Internal,
+                format!("Failed to load node registry: {err}"),
+            )
+        })?;
+
+        let result = operation(node_registry.clone(), Arc::clone(&self.service_control)).await;
+        self.publish_status_changes(&node_registry).await;
+
+        result.map_err(|err| {
+            error!("Failed to {description}: {err:?}");
+            Status::new(Code::Internal, format!("Failed to {description}: {err}"))
+        })
+    }
+
+    async fn node_events(node_registry: &NodeRegistryManager) -> Vec<NodeEvent> {
+        let mut events = Vec::new();
+        for node in node_registry.nodes.read().await.iter() {
+            let node = node.read().await;
+            events.push(NodeEvent {
+                service_name: node.service_name.clone(),
+                peer_id: node.peer_id.map(|id| id.to_bytes()),
+                status: node.status.clone() as i32,
+                number: node.number as u32,
+            });
+        }
+        events
+    }
+
+    async fn publish_status_changes(&self, node_registry: &NodeRegistryManager) {
+        let mut last_events = self.last_events.lock().await;
+        let events = Self::node_events(node_registry).await;
+
+        // A reset deletes the registry, so the nodes it contained are reported as removed.
+        let mut vanished = last_events.clone();
+        for event in events.iter() {
+            let _ = vanished.remove(&event.service_name);
+        }
+        let vanished = vanished
+            .into_values()
+            .filter(|event| event.status != ServiceStatus::Removed as i32)
+            .map(|event| NodeEvent {
+                status: ServiceStatus::Removed as i32,
+                ..event
+            });
+
+        for event in events.into_iter().chain(vanished) {
+            if last_events.get(&event.service_name) == Some(&event) {
+                continue;
+            }
+            debug!("Publishing node event {event:?}");
+            let _ = last_events.insert(event.service_name.clone(), event.clone());
+            // An error only means there are no subscribers.
+            let _ = self.events.send(event);
+        }
+    }
+
+    /// Refresh the statuses in the registry and publish their changes.
+    async fn refresh_statuses(&self) -> Result<NodeRegistryManager> {
+        let _guard = self.operation_lock.lock().await;
+        let node_registry = Self::load_node_registry().await?;
+        refresh_node_registry(
+            node_registry.clone(),
+            &self.service_control,
+            false,
+            false,
+            VerbosityLevel::Minimal,
+        )
+        .await
+        .map_err(|err| eyre!("Failed to refresh node registry: {err:?}"))?;
+        if let Err(err) = node_registry.save().await {
+            error!("Failed to save node registry: {err:?}");
+        }
+        self.publish_status_changes(&node_registry).await;
+        Ok(node_registry)
+    }
+
+    async fn poll_status_changes(self) {
+        let mut interval = tokio::time::interval(EVENTS_POLL_INTERVAL);
+        loop {
+            let _ = interval.tick().await;
+            if self.events.receiver_count() == 0 {
+                continue;
+            }
+            if let Err(err) = self.refresh_statuses().await {
+                error!("{err:?}");
+            }
+        }
+    }
+
+    /// Probe the nodes periodically and remediate those the policy finds unhealthy.
+    async fn heal(self, config: HealingConfig, audit_log_path: PathBuf) {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(config.probe_interval_secs.max(1)));
+        let mut policy = HealingPolicy::new(config);
+        loop {
+            let _ = interval.tick().await;
+            let node_registry = match self.refresh_statuses().await {
+                Ok(node_registry) => node_registry,
+                Err(err) => {
+                    error!("{err:?}");
+                    continue;
+                }
+            };
+            let mut nodes = Vec::new();
+            for node in node_registry.nodes.read().await.iter() {
+                nodes.push(node.read().await.clone());
+            }
+            let service_names: Vec<String> =
+                nodes.iter().map(|node| node.service_name.clone()).collect();
+            policy.retain(&service_names);
+
+            for node in nodes {
+                if node.status == ant_service_management::ServiceStatus::Removed {
+                    continue;
+                }
+                let probe = probe_node(&node).await;
+                debug!("Probed {}: {probe:?}", node.service_name);
+                if let Some(decision) = policy.evaluate(&node.service_name, &probe, Instant::now())
+                {
+                    self.remediate(&node, decision, &audit_log_path).await;
+                }
+            }
+        }
+    }
+
+    async fn remediate(&self, node: &NodeServiceData, decision: Decision, audit_log_path: &Path) {
+        let service_name = &node.service_name;
+        let action = decision.action;
+        warn!(
+            "{service_name} is unhealthy: {}. Remediation: {action}",
+            decision.reason
+        );
+
+        let result = match (action, node.peer_id) {
+            (Remediation::Alert, _) => Ok(()),
+            (_, None) => {
+                error!("Cannot {action} {service_name}, since it has no peer ID");
+                Err("the node has no peer ID".to_string())
+            }
+            (_, Some(peer_id)) => {
+                // Restarting without retaining the peer ID replaces the node with a new one.
+                let retain_peer_id = action == Remediation::Restart;
+                self.run_operation(
+                    &format!("{action} {service_name}"),
+                    |node_registry, service_control| {
+                        Self::restart_handler(
+                            node_registry,
+                            service_control,
+                            peer_id,
+                            retain_peer_id,
+                        )
+                    },
+                )
+                .await
+                .map_err(|status| status.message().to_string())
+            }
+        };
+
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            service_name: service_name.clone(),
+            peer_id: node.peer_id.map(|peer_id| peer_id.to_string()),
+            action,
+            reason: decision.reason,
+            error: result.err(),
+        };
+        if let Err(err) = append_audit_entry(audit_log_path, &entry) {
+            error!(
+                "Failed to record the remediation of {service_name} in {audit_log_path:?}: {err:?}"
+            );
+        }
+    }
 }
 
-// The SafeNodeManager trait returns `Status` as its error. So the actual logic is here and we can easily map the errors
-// into Status inside the trait fns.
-impl AntCtlDaemon {}
+fn parse_peer_ids(peer_ids: &[Vec<u8>]) -> Result<Vec<String>, Status> {
+    peer_ids
+        .iter()
+        .map(|bytes| {
+            PeerId::from_bytes(bytes)
+                .map(|peer_id| peer_id.to_string())
+                .map_err(|err| Status::invalid_argument(format!("Failed to parse PeerId: {err}")))
+        })
+        .collect()
+}
 
-#[tokio::main(flavor = "current_thread")]
+fn parse_port_range(port_range: Option<&str>) -> Result<Option<PortRange>, Status> {
+    port_range
+        .map(|range| {
+            PortRange::parse(range).map_err(|err| {
+                Status::invalid_argument(format!("Invalid port range '{range}': {err}"))
+            })
+        })
+        .transpose()
+}
+
+/// Get the token clients must present, generating it if it does not exist yet. An existing token
+/// is kept so clients continue to work when the daemon restarts; delete the file to rotate it.
+fn get_or_create_token() -> Result<String> {
+    let path = get_daemon_token_path()?;
+    if path.exists() {
+        let token = read_daemon_token(&path)?;
+        if !token.is_empty() {
+            return Ok(token);
+        }
+    }
+
+    let bytes: [u8; 32] = rand::random();
+    let token: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if path.exists() {
+        // The existing token file is empty.
+        std::fs::remove_file(&path)?;
+    }
+    // The file is only ever readable by us, even before the token is written to it.
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(&path)?.write_all(token.as_bytes())?;
+    info!("Generated a new token at {path:?}");
+    Ok(token)
+}
+
+#[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
@@ -166,12 +743,46 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(service_name) = args.supervise {
+        return Ok(supervise(&get_supervisor_dir_path()?, &service_name)?);
+    }
+
     let _log_handles = get_log_builder()?.initialize()?;
     println!("Starting antctld");
-    let service = AntCtlDaemon {};
+    let token = get_or_create_token()?;
+    let config_path = match args.config {
+        Some(path) => path,
+        None => get_daemon_config_path()?,
+    };
+    let config = DaemonConfig::load(&config_path)?;
+    let service_control: SharedServiceControl = match args.backend {
+        ServiceBackend::ServiceManager => Arc::new(ServiceController {}),
+        ServiceBackend::Supervisor => Arc::new(SupervisorController::new(
+            get_supervisor_dir_path()?,
+            std::env::current_exe()?,
+            vec!["--supervise".into()],
+        )),
+    };
+    let service = AntCtlDaemon::new(service_control);
+    let _status_poller = tokio::spawn(service.clone().poll_status_changes());
+    if config.healing.enabled {
+        let audit_log_path = match config.healing.audit_log_path.clone() {
+            Some(path) => path,
+            None => get_healing_audit_log_path()?,
+        };
+        info!("Self-healing is enabled; remediations are recorded in {audit_log_path:?}");
+        let _healer = tokio::spawn(service.clone().heal(config.healing, audit_log_path));
+    }
 
+    let authenticate = move |request: Request<()>| {
+        if is_request_authenticated(&request, &token) {
+            Ok(request)
+        } else {
+            Err(Status::unauthenticated("A valid token is required"))
+        }
+    };
     if let Err(err) = Server::builder()
-        .add_service(AntCtlServer::new(service))
+        .add_service(AntCtlServer::with_interceptor(service, authenticate))
         .serve(SocketAddr::new(IpAddr::V4(args.address), args.port))
         .await
     {
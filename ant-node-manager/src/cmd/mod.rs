@@ -125,6 +125,16 @@ pub fn print_upgrade_summary(upgrade_summary: Vec<(String, UpgradeResult)>) {
                     service_name
                 );
             }
+            UpgradeResult::RolledBack(previous_version, target_version, reason) => {
+                println!(
+                    "{} {} was rolled back from {target_version} to {previous_version}: {reason}",
+                    "✕".red(),
+                    service_name
+                );
+            }
+            UpgradeResult::Skipped => {
+                println!("- {service_name} was skipped because the rollout was stopped");
+            }
             UpgradeResult::Error(msg) => {
                 println!("{} {} was not upgraded: {}", "✕".red(), service_name, msg);
             }
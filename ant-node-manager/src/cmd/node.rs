@@ -16,17 +16,21 @@ use crate::{
         config::{AddNodeServiceOptions, PortRange},
     },
     config::{self, is_running_as_root},
+    fleet::{self, FleetAction, FleetConfig},
+    format_status,
+    health::{HealthCheckOptions, HealthReport, health_report, record_count, wait_until_healthy},
     helpers::{download_and_extract_release, get_bin_version},
-    print_banner, refresh_node_registry, status_report,
+    logs::{LogEntry, LogFilter, NodeLogReader, merge_entries},
+    node_data, print_banner, refresh_node_registry, status_report,
 };
 use ant_bootstrap::{Bootstrap, InitialPeersConfig};
-use ant_evm::{EvmNetwork, RewardsAddress};
+use ant_evm::{EvmNetwork, RewardsAddress, evm_config::EvmConfig};
 use ant_logging::LogFormat;
 use ant_releases::{AntReleaseRepoActions, ReleaseType};
 use ant_service_management::{
-    NodeRegistryManager, NodeService, NodeServiceData, ServiceStateActions, ServiceStatus,
-    UpgradeOptions, UpgradeResult,
-    control::{ServiceControl, ServiceController},
+    NodeRegistryManager, NodeService, NodeServiceData, ResourceLimits, ServiceStateActions,
+    ServiceStatus, UpgradeOptions, UpgradeResult,
+    control::{ServiceControl, SharedServiceControl},
     rpc::RpcClient,
 };
 use color_eyre::{Help, Result, eyre::eyre};
@@ -39,6 +43,9 @@ use std::{
 use tokio::sync::RwLock;
 use tracing::debug;
 
+/// How often new log entries are checked for when following the logs.
+const LOG_FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Returns the added service names
 pub async fn add(
     alpha: bool,
@@ -48,42 +55,69 @@ pub async fn add(
     data_dir_path: Option<PathBuf>,
     enable_metrics_server: bool,
     env_variables: Option<Vec<(String, String)>>,
+    evm_config: Option<PathBuf>,
     evm_network: Option<EvmNetwork>,
     log_dir_path: Option<PathBuf>,
     log_format: Option<LogFormat>,
     max_archived_log_files: Option<usize>,
+    max_download_rate: Option<u64>,
     max_log_files: Option<usize>,
+    max_peer_download_rate: Option<u64>,
+    max_peer_upload_rate: Option<u64>,
+    max_upload_rate: Option<u64>,
     metrics_port: Option<PortRange>,
     network_id: Option<u8>,
     node_ip: Option<Ipv4Addr>,
     node_port: Option<PortRange>,
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
     mut init_peers_config: InitialPeersConfig,
+    quiet_hours: Option<String>,
     relay: bool,
+    resource_limits: ResourceLimits,
     rewards_address: RewardsAddress,
     rpc_address: Option<Ipv4Addr>,
     rpc_port: Option<PortRange>,
     src_path: Option<PathBuf>,
     no_upnp: bool,
+    tcp_port: Option<PortRange>,
+    throttle_cpu_usage: Option<u8>,
+    throttle_memory_usage: Option<u8>,
+    throttled_download_rate: Option<u64>,
+    throttled_upload_rate: Option<u64>,
     url: Option<String>,
     user: Option<String>,
     version: Option<String>,
     verbosity: VerbosityLevel,
     write_older_cache_files: bool,
+    ws_port: Option<PortRange>,
 ) -> Result<Vec<String>> {
-    let user_mode = !is_running_as_root();
+    let user_mode = is_user_mode(is_running_as_root(), &*service_control);
+    // The nodes use the network of the config, which is what the registry records.
+    let (evm_config, evm_network) = match evm_config {
+        Some(path) => {
+            // The services do not run from the current directory, so they are given an absolute
+            // path.
+            let path = std::fs::canonicalize(&path)
+                .map_err(|err| eyre!("Could not find the EVM config file {path:?}: {err}"))?;
+            let config = EvmConfig::from_file(&path)?;
+            // The network ID defaults as it does for antnode.
+            config.check_network_id(network_id.unwrap_or(if alpha { 2 } else { 1 }))?;
+            (Some(path), Some(config.network()?))
+        }
+        None => (None, evm_network),
+    };
 
     if verbosity != VerbosityLevel::Minimal {
         print_banner("Add Antnode Services");
         println!("{} service(s) to be added", count.unwrap_or(1));
     }
 
-    let service_manager = ServiceController {};
     let service_user = if user_mode {
         None
     } else {
         let service_user = user.unwrap_or_else(|| "ant".to_string());
-        service_manager.create_service_user(&service_user)?;
+        service_control.create_service_user(&service_user)?;
         Some(service_user)
     };
 
@@ -126,12 +160,18 @@ pub async fn add(
         count,
         delete_antnode_src: src_path.is_none(),
         enable_metrics_server,
+        evm_config,
         evm_network: evm_network.unwrap_or(EvmNetwork::ArbitrumOne),
         env_variables,
         relay,
+        resource_limits,
         log_format,
         max_archived_log_files,
+        max_download_rate,
         max_log_files,
+        max_peer_download_rate,
+        max_peer_upload_rate,
+        max_upload_rate,
         metrics_port,
         network_id,
         node_ip,
@@ -145,14 +185,21 @@ pub async fn add(
         service_data_dir_path,
         service_log_dir_path,
         no_upnp,
+        quiet_hours,
+        tcp_port,
+        throttle_cpu_usage,
+        throttle_memory_usage,
+        throttled_download_rate,
+        throttled_upload_rate,
         user: service_user,
         user_mode,
         version,
         write_older_cache_files,
+        ws_port,
     };
     info!("Adding node service(s)");
     let added_services_names =
-        add_node(options, node_registry.clone(), &service_manager, verbosity).await?;
+        add_node(options, node_registry.clone(), &service_control, verbosity).await?;
 
     node_registry.save().await?;
     debug!("Node registry saved");
@@ -160,9 +207,253 @@ pub async fn add(
     Ok(added_services_names)
 }
 
+/// Bring the antnode services in line with a fleet file.
+///
+/// The plan of changes is printed and confirmed before it is executed. Nodes beyond the desired
+/// count are removed, differing nodes are reconfigured or upgraded, and missing nodes are added.
+pub async fn apply(
+    dry_run: bool,
+    fleet_path: PathBuf,
+    node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
+    verbosity: VerbosityLevel,
+    yes: bool,
+) -> Result<()> {
+    let mut config = FleetConfig::from_file(&fleet_path)?;
+    // The services do not run from the current directory, so they are given an absolute path.
+    config.evm_config = config
+        .evm_config
+        .map(|path| {
+            std::fs::canonicalize(&path)
+                .map_err(|err| eyre!("Could not find the EVM config file {path:?}: {err}"))
+        })
+        .transpose()?;
+
+    if verbosity != VerbosityLevel::Minimal {
+        print_banner("Apply Fleet Config");
+    }
+    info!("Applying the fleet config at {fleet_path:?}");
+
+    refresh_node_registry(
+        node_registry.clone(),
+        &service_control,
+        verbosity != VerbosityLevel::Minimal,
+        false,
+        verbosity,
+    )
+    .await?;
+
+    let actions = fleet::plan(
+        &config,
+        &node_registry.get_node_service_data().await,
+        node_registry.environment_variables.read().await.as_ref(),
+    )?;
+    if actions.is_empty() {
+        println!("{} The services match the fleet config", "✓".green());
+        return Ok(());
+    }
+    println!("Plan:");
+    for action in &actions {
+        println!("  {action}");
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    if !yes {
+        println!("Do you wish to proceed? [y/n]");
+        std::io::stdout().flush()?;
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if input.trim().to_lowercase() != "y" {
+            println!("Apply aborted");
+            return Ok(());
+        }
+    }
+
+    let services_to_remove: Vec<String> = actions
+        .iter()
+        .filter_map(|action| match action {
+            FleetAction::Remove { service_name } => Some(service_name.clone()),
+            _ => None,
+        })
+        .collect();
+    if !services_to_remove.is_empty() {
+        stop(
+            None,
+            node_registry.clone(),
+            Arc::clone(&service_control),
+            vec![],
+            services_to_remove.clone(),
+            verbosity,
+        )
+        .await?;
+        remove(
+            false,
+            vec![],
+            node_registry.clone(),
+            Arc::clone(&service_control),
+            services_to_remove,
+            verbosity,
+        )
+        .await?;
+    }
+
+    let env_variables = config.env_variables();
+    *node_registry.environment_variables.write().await = env_variables.clone();
+    node_registry.save().await?;
+
+    let upgrade_bin = if actions.iter().any(|action| {
+        matches!(
+            action,
+            FleetAction::Update {
+                upgrade: Some(_),
+                ..
+            }
+        )
+    }) {
+        Some(
+            download_and_get_upgrade_bin_path(
+                None,
+                ReleaseType::AntNode,
+                None,
+                config.version.clone(),
+                verbosity,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    let mut failed_services = Vec::new();
+    for action in &actions {
+        let FleetAction::Update {
+            index,
+            service_name,
+            upgrade,
+            ..
+        } = action
+        else {
+            continue;
+        };
+        let services =
+            get_services_for_ops(&node_registry, vec![], vec![service_name.clone()]).await?;
+        let Some(node) = services.first() else {
+            continue;
+        };
+
+        let _ = config.reconcile(*index, &mut *node.write().await)?;
+        let (auto_restart, is_running, rpc_socket_addr) = {
+            let node = node.read().await;
+            (
+                node.auto_restart,
+                node.status == ServiceStatus::Running,
+                node.rpc_socket_addr,
+            )
+        };
+        let rpc_client = RpcClient::from_socket_addr(rpc_socket_addr);
+        let service = NodeService::new(Arc::clone(node), Box::new(rpc_client));
+        let mut service_manager =
+            ServiceManager::new(service, Box::new(Arc::clone(&service_control)), verbosity);
+
+        let result = match (upgrade, &upgrade_bin) {
+            (Some(_), Some((upgrade_bin_path, target_version))) => service_manager
+                .upgrade(UpgradeOptions {
+                    auto_restart,
+                    env_variables: env_variables.clone(),
+                    // The fleet pins a version, which may be older than the current one.
+                    force: true,
+                    start_service: is_running,
+                    target_bin_path: upgrade_bin_path.clone(),
+                    target_version: target_version.clone(),
+                })
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|upgrade_result| match upgrade_result {
+                    UpgradeResult::UpgradedButNotStarted(_, _, err) => Err(err),
+                    _ => Ok(()),
+                }),
+            _ => service_manager
+                .reconfigure(auto_restart, env_variables.clone())
+                .await
+                .map_err(|err| err.to_string()),
+        };
+        match result {
+            Ok(()) => debug!("Updated service {service_name}"),
+            Err(err) => {
+                error!("Failed to update service {service_name}: {err}");
+                failed_services.push((service_name.clone(), err));
+            }
+        }
+        node_registry.save().await?;
+    }
+
+    if let Some(count) = actions.iter().find_map(|action| match action {
+        FleetAction::Add { count } => Some(*count),
+        _ => None,
+    }) {
+        // The added nodes take the ports after those of the existing nodes.
+        let existing = config.count - count;
+        let _ = add(
+            config.alpha,
+            config.auto_restart,
+            false,
+            Some(count),
+            config.data_dir_path.clone(),
+            config.enable_metrics_server,
+            env_variables,
+            config.evm_config.clone(),
+            Some(config.evm_network.clone()),
+            config.log_dir_path.clone(),
+            config.log_format,
+            config.max_archived_log_files,
+            config.max_download_rate,
+            config.max_log_files,
+            config.max_peer_download_rate,
+            config.max_peer_upload_rate,
+            config.max_upload_rate,
+            FleetConfig::added_ports(&config.metrics_port, existing, count)?,
+            config.network_id,
+            config.node_ip,
+            FleetConfig::added_ports(&config.node_port, existing, count)?,
+            node_registry.clone(),
+            Arc::clone(&service_control),
+            InitialPeersConfig {
+                addrs: config.peers.clone(),
+                network_contacts_url: config.network_contacts_url.clone(),
+                ..Default::default()
+            },
+            config.quiet_hours.clone(),
+            config.relay,
+            config.resource_limits.clone(),
+            config.rewards_address,
+            config.rpc_address,
+            FleetConfig::added_ports(&config.rpc_port, existing, count)?,
+            None,
+            config.no_upnp,
+            FleetConfig::added_ports(&config.tcp_port, existing, count)?,
+            config.throttle_cpu_usage,
+            config.throttle_memory_usage,
+            config.throttled_download_rate,
+            config.throttled_upload_rate,
+            None,
+            config.user.clone(),
+            config.version.clone(),
+            verbosity,
+            config.write_older_cache_files,
+            FleetConfig::added_ports(&config.ws_port, existing, count)?,
+        )
+        .await?;
+    }
+
+    summarise_any_failed_ops(failed_services, "update", verbosity)
+}
+
 pub async fn balance(
     peer_ids: Vec<String>,
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
     service_names: Vec<String>,
     verbosity: VerbosityLevel,
 ) -> Result<()> {
@@ -172,7 +463,7 @@ pub async fn balance(
 
     refresh_node_registry(
         node_registry.clone(),
-        &ServiceController {},
+        &service_control,
         verbosity != VerbosityLevel::Minimal,
         false,
         verbosity,
@@ -196,10 +487,417 @@ pub async fn balance(
     Ok(())
 }
 
+/// Describe the antnode services of the registry as a fleet file.
+///
+/// The file is printed if no output path is given.
+pub async fn export(node_registry: NodeRegistryManager, output: Option<PathBuf>) -> Result<()> {
+    let (config, differing_nodes) = FleetConfig::export(
+        &node_registry.get_node_service_data().await,
+        node_registry.environment_variables.read().await.as_ref(),
+    )?;
+    // Warnings go to stderr, so the printed file can be redirected.
+    for (service_name, settings) in differing_nodes {
+        eprintln!(
+            "WARNING: {service_name} differs from the exported config in: {}. Applying the config \
+             would reconfigure it.",
+            settings.join(", ")
+        );
+    }
+
+    let contents = config.to_toml()?;
+    match output {
+        Some(path) => {
+            std::fs::write(&path, contents)?;
+            println!(
+                "{} Exported {} service(s) to {}",
+                "✓".green(),
+                config.count,
+                path.to_string_lossy()
+            );
+        }
+        None => print!("{contents}"),
+    }
+    Ok(())
+}
+
+/// Stop a node and write its config and data directory to a node archive, to move it to another
+/// machine with its identity intact.
+///
+/// The node is left stopped, as it must not run here once it runs elsewhere.
+pub async fn export_node(
+    node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
+    output: PathBuf,
+    service_name: String,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    if verbosity != VerbosityLevel::Minimal {
+        print_banner("Export Node");
+    }
+
+    refresh_node_registry(
+        node_registry.clone(),
+        &service_control,
+        verbosity != VerbosityLevel::Minimal,
+        false,
+        verbosity,
+    )
+    .await?;
+
+    let services = get_services_for_ops(&node_registry, vec![], vec![service_name.clone()]).await?;
+    let node = services
+        .first()
+        .ok_or_else(|| eyre!("No service named '{service_name}'"))?;
+
+    // The record store must not change while it is archived.
+    let rpc_client = RpcClient::from_socket_addr(node.read().await.rpc_socket_addr);
+    let service = NodeService::new(Arc::clone(node), Box::new(rpc_client));
+    let mut service_manager =
+        ServiceManager::new(service, Box::new(Arc::clone(&service_control)), verbosity);
+    service_manager.stop().await?;
+    node_registry.save().await?;
+
+    let node = node.read().await;
+    let (config, _) = FleetConfig::export(
+        std::slice::from_ref(&*node),
+        node_registry.environment_variables.read().await.as_ref(),
+    )?;
+    node_data::write_archive(
+        &config,
+        &node.data_dir_path,
+        std::slice::from_ref(&node.antnode_path),
+        &output,
+    )?;
+
+    println!(
+        "{} Exported {service_name} to {}",
+        "✓".green(),
+        output.to_string_lossy()
+    );
+    println!(
+        "The service has been stopped. Two nodes cannot share an identity, so it should not be \
+         started again once the node is imported elsewhere."
+    );
+    println!("It can be removed with `antctl remove --service-name {service_name}`");
+    Ok(())
+}
+
+/// Add a node from a node archive, restoring its data directory and so its identity.
+///
+/// The node keeps the settings it had, apart from its RPC and metrics ports, which only mean
+/// something on the machine it ran on. The version it ran is installed unless another binary is
+/// given.
+pub async fn import_node(
+    archive_path: PathBuf,
+    data_dir_path: Option<PathBuf>,
+    log_dir_path: Option<PathBuf>,
+    node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
+    src_path: Option<PathBuf>,
+    url: Option<String>,
+    version: Option<String>,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    let config = node_data::read_archive_config(&archive_path)?;
+    info!("Importing the node archive at {archive_path:?}");
+
+    let version = if src_path.is_none() && url.is_none() {
+        version.or_else(|| config.version.clone())
+    } else {
+        version
+    };
+    let env_variables = node_registry.environment_variables.read().await.clone();
+    let service_names = add(
+        config.alpha,
+        config.auto_restart,
+        false,
+        Some(1),
+        data_dir_path.or_else(|| config.data_dir_path.clone()),
+        config.enable_metrics_server || config.metrics_port.is_some(),
+        env_variables,
+        config.evm_config.clone(),
+        Some(config.evm_network.clone()),
+        log_dir_path.or_else(|| config.log_dir_path.clone()),
+        config.log_format,
+        config.max_archived_log_files,
+        config.max_download_rate,
+        config.max_log_files,
+        config.max_peer_download_rate,
+        config.max_peer_upload_rate,
+        config.max_upload_rate,
+        None,
+        config.network_id,
+        config.node_ip,
+        config.node_port.clone(),
+        node_registry.clone(),
+        Arc::clone(&service_control),
+        InitialPeersConfig {
+            addrs: config.peers.clone(),
+            network_contacts_url: config.network_contacts_url.clone(),
+            ..Default::default()
+        },
+        config.quiet_hours.clone(),
+        config.relay,
+        config.resource_limits.clone(),
+        config.rewards_address,
+        config.rpc_address,
+        None,
+        src_path,
+        config.no_upnp,
+        config.tcp_port.clone(),
+        config.throttle_cpu_usage,
+        config.throttle_memory_usage,
+        config.throttled_download_rate,
+        config.throttled_upload_rate,
+        url,
+        config.user.clone(),
+        version,
+        verbosity,
+        config.write_older_cache_files,
+        config.ws_port.clone(),
+    )
+    .await?;
+    let service_name = service_names
+        .first()
+        .ok_or_else(|| eyre!("The node from {archive_path:?} was not added"))?;
+
+    let services = get_services_for_ops(&node_registry, vec![], vec![service_name.clone()]).await?;
+    let node = services
+        .first()
+        .ok_or_else(|| eyre!("No service named '{service_name}'"))?;
+    let mut node = node.write().await;
+    node_data::unpack_archive_data(&archive_path, &node.data_dir_path)?;
+    if !node.user_mode
+        && let Some(user) = &node.user
+    {
+        node_data::set_owner(&node.data_dir_path, user)?;
+    }
+    node.peer_id = node_data::read_peer_id(&node.data_dir_path)?;
+    let peer_id = node.peer_id.map(|peer_id| peer_id.to_string());
+    drop(node);
+    node_registry.save().await?;
+
+    println!(
+        "{} Imported {} as {service_name}",
+        "✓".green(),
+        peer_id.unwrap_or_default()
+    );
+    println!("It can be started with `antctl start --service-name {service_name}`");
+    Ok(())
+}
+
+/// Print the log entries of the services, merged chronologically, then follow them if required.
+pub async fn logs(
+    filter: LogFilter,
+    follow: bool,
+    json: bool,
+    lines: Option<usize>,
+    node_registry: NodeRegistryManager,
+    peer_ids: Vec<String>,
+    service_names: Vec<String>,
+) -> Result<()> {
+    info!("Showing logs for: {peer_ids:?}, {service_names:?}, with {filter:?}");
+    let services = get_services_for_ops(&node_registry, peer_ids, service_names).await?;
+    if services.is_empty() {
+        info!("Services for ops is empty, cannot show logs");
+        println!("No services available");
+        return Ok(());
+    }
+
+    let mut readers = Vec::new();
+    for node in services.iter() {
+        let node = node.read().await;
+        readers.push(NodeLogReader::new(&node.service_name, &node.log_dir_path)?);
+    }
+
+    let history = readers
+        .iter()
+        .map(|reader| reader.read_history(&filter, lines))
+        .collect::<Result<Vec<_>>>()?;
+    let mut history = merge_entries(history);
+    if let Some(lines) = lines {
+        let excess = history.len().saturating_sub(lines);
+        let _ = history.drain(..excess);
+    }
+    for entry in history.iter() {
+        print_log_entry(entry, json)?;
+    }
+
+    if !follow {
+        return Ok(());
+    }
+    loop {
+        tokio::time::sleep(LOG_FOLLOW_INTERVAL).await;
+        let new_entries = readers
+            .iter_mut()
+            .map(|reader| reader.read_new(&filter))
+            .collect::<Result<Vec<_>>>()?;
+        for entry in merge_entries(new_entries).iter() {
+            print_log_entry(entry, json)?;
+        }
+    }
+}
+
+fn print_log_entry(entry: &LogEntry, json: bool) -> Result<()> {
+    if json {
+        let value = serde_json::json!({
+            "service_name": entry.service_name,
+            "timestamp": entry.timestamp.map(|timestamp| timestamp.to_rfc3339()),
+            "level": entry.level.map(|level| level.as_str()),
+            "target": entry.target,
+            "message": entry.message,
+        });
+        println!("{}", serde_json::to_string(&value)?);
+    } else {
+        // Every line is prefixed, so the output can be filtered by service with other tools.
+        for line in entry.raw.lines() {
+            println!("[{}] {line}", entry.service_name);
+        }
+    }
+    Ok(())
+}
+
+/// Move the data directory of a node to the given directory, e.g. on another drive.
+///
+/// The node is stopped while its data is copied and verified, then its service is reinstalled to
+/// use the new directory. The original directory is removed unless it is to be kept.
+pub async fn migrate(
+    keep_source: bool,
+    node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
+    service_name: String,
+    to: PathBuf,
+    verbosity: VerbosityLevel,
+) -> Result<()> {
+    if verbosity != VerbosityLevel::Minimal {
+        print_banner("Migrate Node Data");
+    }
+
+    refresh_node_registry(
+        node_registry.clone(),
+        &service_control,
+        verbosity != VerbosityLevel::Minimal,
+        false,
+        verbosity,
+    )
+    .await?;
+
+    let services = get_services_for_ops(&node_registry, vec![], vec![service_name.clone()]).await?;
+    let node = services
+        .first()
+        .ok_or_else(|| eyre!("No service named '{service_name}'"))?;
+    let (auto_restart, old_data_dir_path, is_running, owner, rpc_socket_addr) = {
+        let node = node.read().await;
+        (
+            node.auto_restart,
+            node.data_dir_path.clone(),
+            node.status == ServiceStatus::Running,
+            node.user.clone().filter(|_| !node.user_mode),
+            node.rpc_socket_addr,
+        )
+    };
+    let new_data_dir_path = to.join(&service_name);
+    if new_data_dir_path == old_data_dir_path {
+        return Err(eyre!(
+            "The data of {service_name} is already at {new_data_dir_path:?}"
+        ));
+    }
+
+    let rpc_client = RpcClient::from_socket_addr(rpc_socket_addr);
+    let service = NodeService::new(Arc::clone(node), Box::new(rpc_client));
+    let mut service_manager =
+        ServiceManager::new(service, Box::new(Arc::clone(&service_control)), verbosity);
+    service_manager.stop().await?;
+
+    if verbosity != VerbosityLevel::Minimal {
+        println!(
+            "Copying {} to {}...",
+            old_data_dir_path.to_string_lossy(),
+            new_data_dir_path.to_string_lossy()
+        );
+    }
+    let copy_result =
+        node_data::copy_dir(&old_data_dir_path, &new_data_dir_path, &[]).and_then(|bytes_copied| {
+            node_data::verify_copy(&old_data_dir_path, &new_data_dir_path, &[])?;
+            Ok(bytes_copied)
+        });
+    let bytes_copied = match copy_result {
+        Ok(bytes_copied) => bytes_copied,
+        Err(err) => {
+            error!("Failed to copy the data of {service_name}: {err}");
+            let _ = std::fs::remove_dir_all(&new_data_dir_path);
+            if is_running {
+                service_manager.start().await?;
+            }
+            return Err(err).suggestion(
+                "The node still uses its original data directory. Check the destination has \
+                 enough free space and try again.",
+            );
+        }
+    };
+    debug!("Copied {bytes_copied} bytes of data for {service_name}");
+    if let Some(owner) = &owner {
+        node_data::set_owner(&new_data_dir_path, owner)?;
+    }
+
+    let old_antnode_path = {
+        let mut node = node.write().await;
+        let old_antnode_path = node.antnode_path.clone();
+        node.data_dir_path = new_data_dir_path.clone();
+        if let Ok(relative_path) = node.antnode_path.strip_prefix(&old_data_dir_path) {
+            node.antnode_path = new_data_dir_path.join(relative_path);
+        }
+        old_antnode_path
+    };
+
+    // The registry is only saved once the service uses the new directory.
+    let env_variables = node_registry.environment_variables.read().await.clone();
+    if let Err(err) = service_manager
+        .reconfigure(auto_restart, env_variables.clone())
+        .await
+    {
+        error!("Failed to reconfigure {service_name} for its new data directory: {err}");
+        {
+            let mut node = node.write().await;
+            node.data_dir_path = old_data_dir_path.clone();
+            node.antnode_path = old_antnode_path;
+        }
+        if let Err(err) = service_manager
+            .reconfigure(auto_restart, env_variables)
+            .await
+        {
+            error!("Failed to restore the service definition of {service_name}: {err}");
+        }
+        let _ = std::fs::remove_dir_all(&new_data_dir_path);
+        if is_running {
+            service_manager.start().await?;
+        }
+        return Err(err).suggestion("The node still uses its original data directory.");
+    }
+    // Saved before starting the node, so a failed start does not leave the registry pointing
+    // at the old data directory the service no longer uses.
+    node_registry.save().await?;
+    if is_running {
+        service_manager.start().await?;
+    }
+
+    if !keep_source {
+        std::fs::remove_dir_all(&old_data_dir_path)?;
+    }
+
+    println!(
+        "{} Migrated {service_name} to {}",
+        "✓".green(),
+        new_data_dir_path.to_string_lossy()
+    );
+    Ok(())
+}
+
 pub async fn remove(
     keep_directories: bool,
     peer_ids: Vec<String>,
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
     service_names: Vec<String>,
     verbosity: VerbosityLevel,
 ) -> Result<()> {
@@ -212,7 +910,7 @@ pub async fn remove(
 
     refresh_node_registry(
         node_registry.clone(),
-        &ServiceController {},
+        &service_control,
         verbosity != VerbosityLevel::Minimal,
         false,
         verbosity,
@@ -235,7 +933,7 @@ pub async fn remove(
         let rpc_client = RpcClient::from_socket_addr(node.read().await.rpc_socket_addr);
         let service = NodeService::new(Arc::clone(node), Box::new(rpc_client));
         let mut service_manager =
-            ServiceManager::new(service, Box::new(ServiceController {}), verbosity);
+            ServiceManager::new(service, Box::new(Arc::clone(&service_control)), verbosity);
         match service_manager.remove(keep_directories).await {
             Ok(()) => {
                 debug!("Removed service {service_name}");
@@ -254,6 +952,7 @@ pub async fn remove(
 pub async fn reset(
     force: bool,
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
     verbosity: VerbosityLevel,
 ) -> Result<()> {
     if verbosity != VerbosityLevel::Minimal {
@@ -273,8 +972,24 @@ pub async fn reset(
         }
     }
 
-    stop(None, node_registry.clone(), vec![], vec![], verbosity).await?;
-    remove(false, vec![], node_registry, vec![], verbosity).await?;
+    stop(
+        None,
+        node_registry.clone(),
+        Arc::clone(&service_control),
+        vec![],
+        vec![],
+        verbosity,
+    )
+    .await?;
+    remove(
+        false,
+        vec![],
+        node_registry,
+        service_control,
+        vec![],
+        verbosity,
+    )
+    .await?;
 
     // Due the possibility of repeated runs of the `reset` command, we need to check for the
     // existence of this file before attempting to delete it, since `remove_file` will return an
@@ -292,6 +1007,7 @@ pub async fn start(
     connection_timeout_s: u64,
     fixed_interval: Option<u64>,
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
     peer_ids: Vec<String>,
     service_names: Vec<String>,
     verbosity: VerbosityLevel,
@@ -303,7 +1019,7 @@ pub async fn start(
 
     refresh_node_registry(
         node_registry.clone(),
-        &ServiceController {},
+        &service_control,
         verbosity != VerbosityLevel::Minimal,
         false,
         verbosity,
@@ -335,7 +1051,7 @@ pub async fn start(
         };
 
         let mut service_manager =
-            ServiceManager::new(service, Box::new(ServiceController {}), verbosity);
+            ServiceManager::new(service, Box::new(Arc::clone(&service_control)), verbosity);
         if service_manager.service.status().await != ServiceStatus::Running {
             // It would be possible here to check if the service *is* running and then just
             // continue without applying the delay. The reason for not doing so is because when
@@ -343,7 +1059,7 @@ pub async fn start(
             // started, which I think is useful behaviour to retain.
             if let Some(interval) = fixed_interval {
                 debug!("Sleeping for {} milliseconds", interval);
-                std::thread::sleep(std::time::Duration::from_millis(interval));
+                tokio::time::sleep(std::time::Duration::from_millis(interval)).await;
             }
         }
         match service_manager.start().await {
@@ -365,30 +1081,134 @@ pub async fn start(
 pub async fn status(
     details: bool,
     fail: bool,
+    health: bool,
     json: bool,
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
 ) -> Result<()> {
+    if health {
+        return health_status(fail, json, node_registry, service_control).await;
+    }
     if !node_registry.nodes.read().await.is_empty() {
         if !json && !details {
             print_banner("Antnode Services");
         }
-        status_report(
-            &node_registry,
-            &ServiceController {},
-            details,
-            json,
-            fail,
-            false,
-        )
-        .await?;
+        status_report(&node_registry, &service_control, details, json, fail, false).await?;
         node_registry.save().await?;
     }
     Ok(())
 }
 
+/// Report the health of the running nodes, flagging those that stand out from the rest.
+///
+/// With `fail`, an error is returned if any node was flagged.
+async fn health_status(
+    fail: bool,
+    json: bool,
+    node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
+) -> Result<()> {
+    if node_registry.nodes.read().await.is_empty() {
+        return Ok(());
+    }
+    if !json {
+        print_banner("Antnode Health");
+    }
+    refresh_node_registry(
+        node_registry.clone(),
+        &service_control,
+        !json,
+        false,
+        VerbosityLevel::Normal,
+    )
+    .await?;
+    node_registry.save().await?;
+
+    let nodes: Vec<_> = node_registry
+        .get_node_service_data()
+        .await
+        .into_iter()
+        .filter(|node| node.status != ServiceStatus::Removed)
+        .collect();
+    let report = health_report(&nodes).await;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_health_report(&report);
+    }
+
+    let outliers: Vec<_> = report
+        .nodes
+        .iter()
+        .filter(|node| !node.outliers.is_empty())
+        .map(|node| node.service_name.clone())
+        .collect();
+    if fail && !outliers.is_empty() {
+        error!("One or more nodes stand out from the fleet: {outliers:?}");
+        return Err(eyre!(
+            "One or more nodes stand out from the fleet: {}",
+            outliers.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+fn print_health_report(report: &HealthReport) {
+    fn or_dash<T: ToString>(value: Option<T>) -> String {
+        value.map_or("-".to_string(), |value| value.to_string())
+    }
+
+    println!(
+        "{:<18} {:<7} {:>8} {:>6} {:>8} {:>6} {:>10} {:>22}",
+        "Service Name",
+        "Status",
+        "Records",
+        "Peers",
+        "Shunned",
+        "Relay",
+        "Put Err/h",
+        "Earned Today"
+    );
+    for node in &report.nodes {
+        println!(
+            "{:<18} {:<7} {:>8} {:>6} {:>8} {:>6} {:>10} {:>22}",
+            node.service_name,
+            format_status(&node.status),
+            or_dash(node.record_count),
+            or_dash(node.connected_peers),
+            or_dash(node.shunned_by_close_group),
+            or_dash(
+                node.relay_reservation_health
+                    .map(|health| format!("{health:.2}"))
+            ),
+            or_dash(
+                node.put_record_errors_per_hour
+                    .map(|errors| format!("{errors:.1}"))
+            ),
+            or_dash(node.earned_today),
+        );
+    }
+
+    println!();
+    if report.has_outliers() {
+        println!("Outliers:");
+        for node in report.nodes.iter() {
+            for outlier in &node.outliers {
+                println!("{} {}: {outlier}", "✕".red(), node.service_name);
+            }
+        }
+    } else {
+        println!(
+            "{} No node stands out from the rest of the fleet",
+            "✓".green()
+        );
+    }
+}
+
 pub async fn stop(
     interval: Option<u64>,
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
     peer_ids: Vec<String>,
     service_names: Vec<String>,
     verbosity: VerbosityLevel,
@@ -400,7 +1220,7 @@ pub async fn stop(
 
     refresh_node_registry(
         node_registry.clone(),
-        &ServiceController {},
+        &service_control,
         verbosity != VerbosityLevel::Minimal,
         false,
         verbosity,
@@ -423,13 +1243,13 @@ pub async fn stop(
         let rpc_client = RpcClient::from_socket_addr(node.read().await.rpc_socket_addr);
         let service = NodeService::new(Arc::clone(node), Box::new(rpc_client));
         let mut service_manager =
-            ServiceManager::new(service, Box::new(ServiceController {}), verbosity);
+            ServiceManager::new(service, Box::new(Arc::clone(&service_control)), verbosity);
 
         if service_manager.service.status().await == ServiceStatus::Running
             && let Some(interval) = interval
         {
             debug!("Sleeping for {} milliseconds", interval);
-            std::thread::sleep(std::time::Duration::from_millis(interval));
+            tokio::time::sleep(std::time::Duration::from_millis(interval)).await;
         }
         match service_manager.stop().await {
             Ok(()) => {
@@ -452,9 +1272,13 @@ pub async fn upgrade(
     custom_bin_path: Option<PathBuf>,
     force: bool,
     fixed_interval: Option<u64>,
+    health_check: Option<HealthCheckOptions>,
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
     peer_ids: Vec<String>,
     provided_env_variables: Option<Vec<(String, String)>>,
+    resource_limits: ResourceLimits,
+    clear_resource_limits: bool,
     service_names: Vec<String>,
     url: Option<String>,
     version: Option<String>,
@@ -483,7 +1307,7 @@ pub async fn upgrade(
 
     refresh_node_registry(
         node_registry.clone(),
-        &ServiceController {},
+        &service_control,
         verbosity != VerbosityLevel::Minimal,
         false,
         verbosity,
@@ -497,6 +1321,21 @@ pub async fn upgrade(
         debug!("There are no nodes currently added or active");
     }
 
+    if clear_resource_limits || !resource_limits.is_empty() {
+        let services =
+            get_services_for_ops(&node_registry, peer_ids.clone(), service_names.clone()).await?;
+        let failed_services = update_resource_limits(
+            &services,
+            &resource_limits,
+            clear_resource_limits,
+            &service_control,
+            verbosity,
+        )
+        .await;
+        node_registry.save().await?;
+        summarise_any_failed_ops(failed_services, "update the resource limits of", verbosity)?;
+    }
+
     if !use_force {
         let mut node_versions = Vec::new();
 
@@ -522,8 +1361,16 @@ pub async fn upgrade(
     let services_for_ops = get_services_for_ops(&node_registry, peer_ids, service_names).await?;
     trace!("services_for_ops len: {}", services_for_ops.len());
     let mut upgrade_summary = Vec::new();
+    // Set when a service was rolled back, after which the remaining services are not upgraded.
+    let mut rollout_stopped = false;
 
     for node in &services_for_ops {
+        let service_name = node.read().await.service_name.clone();
+        if rollout_stopped {
+            upgrade_summary.push((service_name, UpgradeResult::Skipped));
+            continue;
+        }
+
         let env_variables = if provided_env_variables.is_some() {
             provided_env_variables.clone()
         } else {
@@ -537,9 +1384,25 @@ pub async fn upgrade(
             target_bin_path: upgrade_bin_path.clone(),
             target_version: target_version.clone(),
         };
-        let service_name = node.read().await.service_name.clone();
 
-        let rpc_client = RpcClient::from_socket_addr(node.read().await.rpc_socket_addr);
+        let rpc_socket_addr = node.read().await.rpc_socket_addr;
+        let rollback = match &health_check {
+            Some(_) => match prepare_rollback(node).await {
+                Ok(rollback) => Some(rollback),
+                Err(err) => {
+                    // The service is not upgraded if it could not be rolled back.
+                    error!("Could not prepare the rollback of service {service_name}: {err}");
+                    upgrade_summary.push((
+                        service_name,
+                        UpgradeResult::Error(format!("Could not prepare the rollback: {err}")),
+                    ));
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        let rpc_client = RpcClient::from_socket_addr(rpc_socket_addr);
         let service = NodeService::new(Arc::clone(node), Box::new(rpc_client));
         // set dynamic startup delay if fixed_interval is not set
         let service = if fixed_interval.is_none() {
@@ -549,37 +1412,110 @@ pub async fn upgrade(
         };
 
         let mut service_manager =
-            ServiceManager::new(service, Box::new(ServiceController {}), verbosity);
+            ServiceManager::new(service, Box::new(Arc::clone(&service_control)), verbosity);
 
-        match service_manager.upgrade(options).await {
+        let mut upgrade_result = match service_manager.upgrade(options).await {
             Ok(upgrade_result) => {
                 info!("Service: {service_name} has been upgraded, result: {upgrade_result:?}",);
-                if upgrade_result != UpgradeResult::NotRequired {
-                    // It doesn't seem useful to apply the interval if there was no upgrade
-                    // required for the previous service.
-                    if let Some(interval) = fixed_interval {
-                        debug!("Sleeping for {interval} milliseconds",);
-                        std::thread::sleep(std::time::Duration::from_millis(interval));
-                    }
-                }
-                upgrade_summary.push((service_name.clone(), upgrade_result));
-                node_registry.save().await?;
+                upgrade_result
             }
             Err(err) => {
                 error!("Error upgrading service {service_name}: {err}");
-                upgrade_summary.push((
-                    service_name.clone(),
-                    UpgradeResult::Error(format!("Error: {err}")),
-                ));
-                node_registry.save().await?;
+                UpgradeResult::Error(format!("Error: {err}"))
             }
+        };
+
+        if let (Some(health_check), Some(rollback)) = (&health_check, rollback) {
+            let unhealthy_reason = match &upgrade_result {
+                UpgradeResult::NotRequired => None,
+                UpgradeResult::Upgraded(_, _) | UpgradeResult::Forced(_, _) => {
+                    if verbosity != VerbosityLevel::Minimal {
+                        println!("Checking the health of {service_name}...");
+                    }
+                    wait_until_healthy(
+                        &RpcClient::from_socket_addr(rpc_socket_addr),
+                        &target_version,
+                        rollback.record_count,
+                        health_check,
+                    )
+                    .await
+                    .err()
+                }
+                UpgradeResult::UpgradedButNotStarted(_, _, err) => Some(err.clone()),
+                UpgradeResult::Error(err) => Some(err.clone()),
+                UpgradeResult::RolledBack(_, _, _) | UpgradeResult::Skipped => None,
+            };
+
+            if let Some(reason) = unhealthy_reason {
+                warn!("Service {service_name} is not healthy after the upgrade: {reason}");
+                if verbosity != VerbosityLevel::Minimal {
+                    println!(
+                        "{} {service_name} is not healthy after the upgrade: {reason}",
+                        "✕".red()
+                    );
+                    println!("Rolling back {service_name} to {}...", rollback.version);
+                }
+                upgrade_result = match service_manager
+                    .upgrade(UpgradeOptions {
+                        auto_restart: false,
+                        env_variables,
+                        force: true,
+                        start_service: !do_not_start,
+                        target_bin_path: rollback.bin_path.clone(),
+                        target_version: rollback.version.clone(),
+                    })
+                    .await
+                {
+                    Ok(_) => UpgradeResult::RolledBack(
+                        rollback.version.to_string(),
+                        target_version.to_string(),
+                        reason,
+                    ),
+                    Err(err) => {
+                        error!("Error rolling back service {service_name}: {err}");
+                        UpgradeResult::Error(format!(
+                            "{reason}, and the rollback failed with error: {err}"
+                        ))
+                    }
+                };
+                rollout_stopped = true;
+            }
+            if let Err(err) = std::fs::remove_file(&rollback.bin_path) {
+                warn!(
+                    "Could not remove the backup binary {:?}: {err}",
+                    rollback.bin_path
+                );
+            }
+        }
+
+        if !matches!(
+            upgrade_result,
+            UpgradeResult::NotRequired | UpgradeResult::Error(_)
+        ) && let Some(interval) = fixed_interval
+        {
+            // It doesn't seem useful to apply the interval if there was no upgrade
+            // required for the previous service.
+            debug!("Sleeping for {interval} milliseconds",);
+            tokio::time::sleep(std::time::Duration::from_millis(interval)).await;
         }
+        upgrade_summary.push((service_name.clone(), upgrade_result));
+        node_registry.save().await?;
     }
 
     if verbosity != VerbosityLevel::Minimal {
         print_upgrade_summary(upgrade_summary.clone());
     }
 
+    if rollout_stopped {
+        return Err(eyre!(
+            "The upgrade was stopped because a service was not healthy after upgrading"
+        )
+        .suggestion(
+            "The service was rolled back to its previous version. Check its logs before \
+                     upgrading again.",
+        ));
+    }
+
     if upgrade_summary.iter().any(|(_, r)| {
         matches!(r, UpgradeResult::Error(_))
             || matches!(r, UpgradeResult::UpgradedButNotStarted(_, _, _))
@@ -604,18 +1540,26 @@ pub async fn maintain_n_running_nodes(
     data_dir_path: Option<PathBuf>,
     enable_metrics_server: bool,
     env_variables: Option<Vec<(String, String)>>,
+    evm_config: Option<PathBuf>,
     evm_network: Option<EvmNetwork>,
     log_dir_path: Option<PathBuf>,
     log_format: Option<LogFormat>,
     max_archived_log_files: Option<usize>,
+    max_download_rate: Option<u64>,
     max_log_files: Option<usize>,
+    max_peer_download_rate: Option<u64>,
+    max_peer_upload_rate: Option<u64>,
+    max_upload_rate: Option<u64>,
     metrics_port: Option<PortRange>,
     network_id: Option<u8>,
     node_ip: Option<Ipv4Addr>,
     node_port: Option<PortRange>,
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
     peers_args: InitialPeersConfig,
+    quiet_hours: Option<String>,
     relay: bool,
+    resource_limits: ResourceLimits,
     rewards_address: RewardsAddress,
     rpc_address: Option<Ipv4Addr>,
     rpc_port: Option<PortRange>,
@@ -626,6 +1570,10 @@ pub async fn maintain_n_running_nodes(
     version: Option<String>,
     verbosity: VerbosityLevel,
     start_node_interval: Option<u64>,
+    throttle_cpu_usage: Option<u8>,
+    throttle_memory_usage: Option<u8>,
+    throttled_download_rate: Option<u64>,
+    throttled_upload_rate: Option<u64>,
     write_older_cache_files: bool,
 ) -> Result<()> {
     let mut running_nodes = Vec::new();
@@ -661,6 +1609,7 @@ pub async fn maintain_n_running_nodes(
             stop(
                 None,
                 node_registry.clone(),
+                Arc::clone(&service_control),
                 vec![],
                 services_to_stop,
                 verbosity,
@@ -689,6 +1638,7 @@ pub async fn maintain_n_running_nodes(
                     connection_timeout_s,
                     start_node_interval,
                     node_registry.clone(),
+                    Arc::clone(&service_control),
                     vec![],
                     nodes_to_start,
                     verbosity,
@@ -719,28 +1669,42 @@ pub async fn maintain_n_running_nodes(
                         data_dir_path.clone(),
                         enable_metrics_server,
                         env_variables.clone(),
+                        evm_config.clone(),
                         evm_network.clone(),
                         log_dir_path.clone(),
                         log_format,
                         max_archived_log_files,
+                        max_download_rate,
                         max_log_files,
+                        max_peer_download_rate,
+                        max_peer_upload_rate,
+                        max_upload_rate,
                         metrics_port.clone(),
                         network_id,
                         node_ip,
                         Some(PortRange::Single(port)),
                         node_registry.clone(),
+                        Arc::clone(&service_control),
                         peers_args.clone(),
+                        quiet_hours.clone(),
                         relay,
+                        resource_limits.clone(),
                         rewards_address,
                         rpc_address,
                         rpc_port.clone(),
                         src_path.clone(),
                         no_upnp,
+                        None,
+                        throttle_cpu_usage,
+                        throttle_memory_usage,
+                        throttled_download_rate,
+                        throttled_upload_rate,
                         url.clone(),
                         user.clone(),
                         version.clone(),
                         verbosity,
                         write_older_cache_files,
+                        None,
                     )
                     .await?;
 
@@ -749,6 +1713,7 @@ pub async fn maintain_n_running_nodes(
                             connection_timeout_s,
                             start_node_interval,
                             node_registry.clone(),
+                            Arc::clone(&service_control),
                             vec![],
                             added_service,
                             verbosity,
@@ -762,6 +1727,7 @@ pub async fn maintain_n_running_nodes(
                         connection_timeout_s,
                         start_node_interval,
                         node_registry.clone(),
+                        Arc::clone(&service_control),
                         vec![],
                         inactive_nodes,
                         verbosity,
@@ -796,6 +1762,94 @@ pub async fn maintain_n_running_nodes(
     Ok(())
 }
 
+/// What is needed to restore a service to the state before its upgrade.
+struct Rollback {
+    /// A copy of the binary of the service.
+    bin_path: PathBuf,
+    /// The number of records the node held, if it was running.
+    record_count: Option<usize>,
+    version: Version,
+}
+
+async fn prepare_rollback(node: &Arc<RwLock<NodeServiceData>>) -> Result<Rollback> {
+    let node = node.read().await;
+    let mut file_name = node
+        .antnode_path
+        .file_name()
+        .ok_or_else(|| eyre!("Could not get the file name of {:?}", node.antnode_path))?
+        .to_os_string();
+    file_name.push(".previous");
+    let bin_path = node.antnode_path.with_file_name(file_name);
+    debug!("Backing up {:?} to {bin_path:?}", node.antnode_path);
+    let _ = std::fs::copy(&node.antnode_path, &bin_path)?;
+
+    let record_count = if node.status == ServiceStatus::Running {
+        record_count(&RpcClient::from_socket_addr(node.rpc_socket_addr)).await
+    } else {
+        None
+    };
+
+    Ok(Rollback {
+        bin_path,
+        record_count,
+        version: Version::parse(&node.version)
+            .map_err(|_| eyre!("Failed to parse Version for node {}", node.service_name))?,
+    })
+}
+
+/// Rewrite the service definitions of the services with new resource limits, without reinstalling
+/// their binaries. Some limits only take effect the next time a service is started.
+///
+/// Limits that are not given keep their current values, unless `clear` is set, in which case only
+/// the given limits remain.
+async fn update_resource_limits(
+    services: &[Arc<RwLock<NodeServiceData>>],
+    limits: &ResourceLimits,
+    clear: bool,
+    service_control: &dyn ServiceControl,
+    verbosity: VerbosityLevel,
+) -> Vec<(String, String)> {
+    let mut failed_services = Vec::new();
+    for node in services {
+        let mut node = node.write().await;
+        let new_limits = if clear {
+            limits.clone()
+        } else {
+            limits.or(&node.resource_limits)
+        };
+        if new_limits == node.resource_limits {
+            continue;
+        }
+
+        debug!(
+            "Updating the resource limits of {} to {new_limits}",
+            node.service_name
+        );
+        match service_control.set_resource_limits(&node.service_name, &new_limits, node.user_mode) {
+            Ok(()) => {
+                // The registry only records the limits once the service definition has them.
+                node.resource_limits = new_limits;
+                if verbosity != VerbosityLevel::Minimal {
+                    println!(
+                        "{} Updated the resource limits of {}: {}",
+                        "✓".green(),
+                        node.service_name,
+                        node.resource_limits
+                    );
+                }
+            }
+            Err(err) => {
+                error!(
+                    "Failed to update the resource limits of {}: {err}",
+                    node.service_name
+                );
+                failed_services.push((node.service_name.clone(), err.to_string()));
+            }
+        }
+    }
+    failed_services
+}
+
 async fn get_services_for_ops(
     node_registry: &NodeRegistryManager,
     peer_ids: Vec<String>,
@@ -874,3 +1928,29 @@ fn summarise_any_failed_ops(
     }
     Ok(())
 }
+
+/// Services added by root run as a service user, and any others in user mode. Supervised services
+/// always run as the current user, so they are added in user mode, even by root.
+fn is_user_mode(is_root: bool, service_control: &dyn ServiceControl) -> bool {
+    !is_root || service_control.runs_as_current_user()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_service_management::{control::ServiceController, supervisor::SupervisorController};
+
+    #[test]
+    fn supervised_services_added_by_root_should_run_as_the_current_user() {
+        let supervisor = SupervisorController::new(
+            PathBuf::from("/var/antctl/supervisor"),
+            PathBuf::from("antctl"),
+            vec!["supervise".into()],
+        );
+        assert!(is_user_mode(true, &supervisor));
+        assert!(is_user_mode(false, &supervisor));
+
+        assert!(!is_user_mode(true, &ServiceController {}));
+        assert!(is_user_mode(false, &ServiceController {}));
+    }
+}
@@ -6,9 +6,31 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use crate::healing::HealingConfig;
 use ant_releases::ReleaseType;
 use color_eyre::{Result, eyre::eyre};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The settings of the daemon, as read from a TOML file.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DaemonConfig {
+    pub healing: HealingConfig,
+}
+
+impl DaemonConfig {
+    /// Read the config, which takes the defaults if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            debug!("The daemon config does not exist at {path:?}; using the defaults");
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        toml::from_str(&contents)
+            .map_err(|err| eyre!("Could not parse the daemon config at {path:?}: {err}"))
+    }
+}
 
 #[cfg(unix)]
 pub fn get_daemon_install_path() -> PathBuf {
@@ -55,7 +77,6 @@ pub fn get_node_manager_path() -> Result<PathBuf> {
 
 #[cfg(windows)]
 pub fn get_node_manager_path() -> Result<PathBuf> {
-    use std::path::Path;
     let path = Path::new("C:\\ProgramData\\antctl");
     debug!("Running as root, creating node_manager_path at: {path:?}");
 
@@ -96,7 +117,6 @@ pub fn get_node_registry_path() -> Result<PathBuf> {
 
 #[cfg(windows)]
 pub fn get_node_registry_path() -> Result<PathBuf> {
-    use std::path::Path;
     let path = Path::new("C:\\ProgramData\\antctl");
     if !path.exists() {
         std::fs::create_dir_all(path)?;
@@ -106,6 +126,23 @@ pub fn get_node_registry_path() -> Result<PathBuf> {
     Ok(path.join("node_registry.json"))
 }
 
+/// Get the directory the supervisor backend keeps its services in.
+pub fn get_supervisor_dir_path() -> Result<PathBuf> {
+    let path = get_node_manager_path()?.join("supervisor");
+    std::fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+/// Get the path of the config file of the daemon.
+pub fn get_daemon_config_path() -> Result<PathBuf> {
+    Ok(get_node_manager_path()?.join("antctld.toml"))
+}
+
+/// Get the path of the file the daemon records the remediations it applies to nodes in.
+pub fn get_healing_audit_log_path() -> Result<PathBuf> {
+    Ok(get_node_manager_path()?.join("healing_audit.log"))
+}
+
 /// Get the data directory for the service.
 ///
 /// It's a little counter-intuitive, but the owner will be `None` in the case of a user-mode
@@ -0,0 +1,922 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Declarative configuration of the antnode services on a machine.
+//!
+//! A fleet file describes the nodes that should exist. It is compared against the node registry to
+//! plan which nodes to add, remove, reconfigure and upgrade. The nodes are matched to the file in
+//! the order of their numbers, so the n-th node takes the n-th port of each port range.
+
+use crate::add_services::config::{PortRange, parse_quiet_hours};
+use ant_evm::{EvmNetwork, RewardsAddress};
+use ant_logging::LogFormat;
+use ant_service_management::{NodeServiceData, ResourceLimits, ServiceStatus};
+use color_eyre::{Result, eyre::eyre};
+use libp2p::Multiaddr;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+};
+
+/// The RPC address the node manager binds node RPC servers to by default.
+const DEFAULT_RPC_ADDRESS: Ipv4Addr = Ipv4Addr::new(127, 0, 0, 1);
+
+/// Service names of nodes, with the names of their settings that differ from a fleet config.
+pub type DifferingNodes = Vec<(String, Vec<&'static str>)>;
+
+/// The desired set of antnode services, as read from a TOML fleet file.
+///
+/// Settings that are not given take the defaults of `antctl add`. The data and log directories and
+/// the user only apply to added nodes, since existing nodes keep their directories.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FleetConfig {
+    /// The number of nodes.
+    pub count: u16,
+    /// The antnode version of the nodes. Added nodes use the latest version if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub rewards_address: RewardsAddress,
+    #[serde(default)]
+    pub alpha: bool,
+    #[serde(default)]
+    pub auto_restart: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_dir_path: Option<PathBuf>,
+    #[serde(default)]
+    pub enable_metrics_server: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evm_config: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_dir_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_format: Option<LogFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_archived_log_files: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_download_rate: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_log_files: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_peer_download_rate: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_peer_upload_rate: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_upload_rate: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_port: Option<PortRange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_id: Option<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub network_contacts_url: Vec<String>,
+    #[serde(default)]
+    pub no_upnp: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_ip: Option<Ipv4Addr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_port: Option<PortRange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub peers: Vec<Multiaddr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<String>,
+    #[serde(default)]
+    pub relay: bool,
+    #[serde(default, skip_serializing_if = "ResourceLimits::is_empty")]
+    pub resource_limits: ResourceLimits,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_address: Option<Ipv4Addr>,
+    /// If not given, nodes keep their RPC ports and added nodes are given free ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc_port: Option<PortRange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_port: Option<PortRange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throttle_cpu_usage: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throttle_memory_usage: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throttled_download_rate: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throttled_upload_rate: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub write_older_cache_files: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws_port: Option<PortRange>,
+    /// Environment variables for the antnode services.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub evm_network: EvmNetwork,
+}
+
+impl FleetConfig {
+    /// Read and validate a fleet file.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| eyre!("Could not read the fleet file {path:?}: {err}"))?;
+        let config: Self = toml::from_str(&contents)
+            .map_err(|err| eyre!("Could not parse the fleet file {path:?}: {err}"))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).map_err(|err| eyre!("Could not serialize the fleet config: {err}"))
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(version) = &self.version {
+            let _ = Version::parse(version)
+                .map_err(|err| eyre!("The version '{version}' is not valid: {err}"))?;
+        }
+        if self.count > 0 {
+            for port_range in [
+                &self.metrics_port,
+                &self.node_port,
+                &self.rpc_port,
+                &self.tcp_port,
+                &self.ws_port,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                port_range.validate(self.count)?;
+            }
+        }
+        if let Some(quiet_hours) = &self.quiet_hours {
+            let _ = parse_quiet_hours(quiet_hours)?;
+        }
+        Ok(())
+    }
+
+    /// The environment variables in the form the node registry keeps them.
+    pub fn env_variables(&self) -> Option<Vec<(String, String)>> {
+        if self.env.is_empty() {
+            None
+        } else {
+            Some(
+                self.env
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            )
+        }
+    }
+
+    /// Describe the nodes of the registry as a fleet.
+    ///
+    /// The settings of the first node are used. Returns the nodes whose settings differ from those,
+    /// along with the differing settings, as applying the fleet would reconfigure them.
+    pub fn export(
+        nodes: &[NodeServiceData],
+        env_variables: Option<&Vec<(String, String)>>,
+    ) -> Result<(Self, DifferingNodes)> {
+        let nodes = active_nodes(nodes);
+        let first = nodes
+            .first()
+            .ok_or_else(|| eyre!("There are no nodes to export"))?;
+
+        let metrics_ports: Vec<_> = nodes.iter().map(|node| node.metrics_port).collect();
+        let metrics_port = port_range(&metrics_ports);
+        let rpc_ports: Vec<_> = nodes
+            .iter()
+            .map(|node| Some(node.rpc_socket_addr.port()))
+            .collect();
+        let rpc_address = match first.rpc_socket_addr.ip() {
+            IpAddr::V4(ip) if ip != DEFAULT_RPC_ADDRESS => Some(ip),
+            _ => None,
+        };
+        let version = if nodes.iter().all(|node| node.version == first.version) {
+            Some(first.version.clone())
+        } else {
+            None
+        };
+        // The directories of a node are named after it, underneath the given paths.
+        let data_dir_path = first.data_dir_path.parent().map(Path::to_path_buf);
+        let log_dir_path = if first.log_dir_path.ends_with("logs") {
+            first.log_dir_path.parent().and_then(Path::parent)
+        } else {
+            first.log_dir_path.parent()
+        }
+        .map(Path::to_path_buf);
+
+        let config = Self {
+            count: nodes.len() as u16,
+            version,
+            rewards_address: first.rewards_address,
+            alpha: first.alpha,
+            auto_restart: first.auto_restart,
+            data_dir_path,
+            enable_metrics_server: metrics_port.is_none()
+                && metrics_ports.iter().any(Option::is_some),
+            evm_config: first.evm_config.clone(),
+            log_dir_path,
+            log_format: first.log_format,
+            max_archived_log_files: first.max_archived_log_files,
+            max_download_rate: first.max_download_rate,
+            max_log_files: first.max_log_files,
+            max_peer_download_rate: first.max_peer_download_rate,
+            max_peer_upload_rate: first.max_peer_upload_rate,
+            max_upload_rate: first.max_upload_rate,
+            metrics_port,
+            network_id: first.network_id,
+            network_contacts_url: first.initial_peers_config.network_contacts_url.clone(),
+            no_upnp: first.no_upnp,
+            node_ip: first.node_ip,
+            node_port: port_range(&nodes.iter().map(|node| node.node_port).collect::<Vec<_>>()),
+            peers: first.initial_peers_config.addrs.clone(),
+            quiet_hours: first.quiet_hours.clone(),
+            relay: first.relay,
+            resource_limits: first.resource_limits.clone(),
+            rpc_address,
+            rpc_port: port_range(&rpc_ports),
+            tcp_port: port_range(&nodes.iter().map(|node| node.tcp_port).collect::<Vec<_>>()),
+            throttle_cpu_usage: first.throttle_cpu_usage,
+            throttle_memory_usage: first.throttle_memory_usage,
+            throttled_download_rate: first.throttled_download_rate,
+            throttled_upload_rate: first.throttled_upload_rate,
+            user: first.user.clone(),
+            write_older_cache_files: first.write_older_cache_files,
+            ws_port: port_range(&nodes.iter().map(|node| node.ws_port).collect::<Vec<_>>()),
+            env: env_variables.into_iter().flatten().cloned().collect(),
+            evm_network: first.evm_network.clone(),
+        };
+
+        let mut differing_nodes = Vec::new();
+        for (index, node) in nodes.iter().enumerate() {
+            let settings = config.reconcile(index as u16, &mut (*node).clone())?;
+            if !settings.is_empty() {
+                differing_nodes.push((node.service_name.clone(), settings));
+            }
+        }
+
+        Ok((config, differing_nodes))
+    }
+
+    /// Set the settings of the node at the given position of the fleet to the desired ones.
+    ///
+    /// Returns the names of the settings that changed, or an error if a port range of the fleet
+    /// has no port for the position.
+    pub fn reconcile(&self, index: u16, node: &mut NodeServiceData) -> Result<Vec<&'static str>> {
+        let mut changed = Vec::new();
+
+        let metrics_port = match port_at(&self.metrics_port, index)? {
+            Some(port) => Some(port),
+            // A node keeps the free port it was given.
+            None if self.enable_metrics_server => node.metrics_port,
+            None => None,
+        };
+        let rpc_socket_addr = SocketAddr::new(
+            IpAddr::V4(self.rpc_address.unwrap_or(DEFAULT_RPC_ADDRESS)),
+            port_at(&self.rpc_port, index)?.unwrap_or(node.rpc_socket_addr.port()),
+        );
+
+        update(&mut changed, "alpha", &mut node.alpha, self.alpha);
+        update(
+            &mut changed,
+            "auto_restart",
+            &mut node.auto_restart,
+            self.auto_restart,
+        );
+        update(
+            &mut changed,
+            "evm_config",
+            &mut node.evm_config,
+            self.evm_config.clone(),
+        );
+        update(
+            &mut changed,
+            "evm_network",
+            &mut node.evm_network,
+            self.evm_network.clone(),
+        );
+        update(
+            &mut changed,
+            "log_format",
+            &mut node.log_format,
+            self.log_format,
+        );
+        update(
+            &mut changed,
+            "max_archived_log_files",
+            &mut node.max_archived_log_files,
+            self.max_archived_log_files,
+        );
+        update(
+            &mut changed,
+            "max_download_rate",
+            &mut node.max_download_rate,
+            self.max_download_rate,
+        );
+        update(
+            &mut changed,
+            "max_log_files",
+            &mut node.max_log_files,
+            self.max_log_files,
+        );
+        update(
+            &mut changed,
+            "max_peer_download_rate",
+            &mut node.max_peer_download_rate,
+            self.max_peer_download_rate,
+        );
+        update(
+            &mut changed,
+            "max_peer_upload_rate",
+            &mut node.max_peer_upload_rate,
+            self.max_peer_upload_rate,
+        );
+        update(
+            &mut changed,
+            "max_upload_rate",
+            &mut node.max_upload_rate,
+            self.max_upload_rate,
+        );
+        update(
+            &mut changed,
+            "metrics_port",
+            &mut node.metrics_port,
+            metrics_port,
+        );
+        update(
+            &mut changed,
+            "network_id",
+            &mut node.network_id,
+            self.network_id,
+        );
+        update(
+            &mut changed,
+            "network_contacts_url",
+            &mut node.initial_peers_config.network_contacts_url,
+            self.network_contacts_url.clone(),
+        );
+        update(&mut changed, "no_upnp", &mut node.no_upnp, self.no_upnp);
+        update(&mut changed, "node_ip", &mut node.node_ip, self.node_ip);
+        update(
+            &mut changed,
+            "node_port",
+            &mut node.node_port,
+            port_at(&self.node_port, index)?,
+        );
+        update(
+            &mut changed,
+            "peers",
+            &mut node.initial_peers_config.addrs,
+            self.peers.clone(),
+        );
+        update(
+            &mut changed,
+            "quiet_hours",
+            &mut node.quiet_hours,
+            self.quiet_hours.clone(),
+        );
+        update(&mut changed, "relay", &mut node.relay, self.relay);
+        update(
+            &mut changed,
+            "resource_limits",
+            &mut node.resource_limits,
+            self.resource_limits.clone(),
+        );
+        update(
+            &mut changed,
+            "rewards_address",
+            &mut node.rewards_address,
+            self.rewards_address,
+        );
+        update(
+            &mut changed,
+            "rpc_port",
+            &mut node.rpc_socket_addr,
+            rpc_socket_addr,
+        );
+        update(
+            &mut changed,
+            "tcp_port",
+            &mut node.tcp_port,
+            port_at(&self.tcp_port, index)?,
+        );
+        update(
+            &mut changed,
+            "throttle_cpu_usage",
+            &mut node.throttle_cpu_usage,
+            self.throttle_cpu_usage,
+        );
+        update(
+            &mut changed,
+            "throttle_memory_usage",
+            &mut node.throttle_memory_usage,
+            self.throttle_memory_usage,
+        );
+        update(
+            &mut changed,
+            "throttled_download_rate",
+            &mut node.throttled_download_rate,
+            self.throttled_download_rate,
+        );
+        update(
+            &mut changed,
+            "throttled_upload_rate",
+            &mut node.throttled_upload_rate,
+            self.throttled_upload_rate,
+        );
+        update(
+            &mut changed,
+            "write_older_cache_files",
+            &mut node.write_older_cache_files,
+            self.write_older_cache_files,
+        );
+        update(
+            &mut changed,
+            "ws_port",
+            &mut node.ws_port,
+            port_at(&self.ws_port, index)?,
+        );
+
+        Ok(changed)
+    }
+
+    /// The ports of a port range for the nodes added after the first `from` nodes of the fleet.
+    pub fn added_ports(
+        port_range: &Option<PortRange>,
+        from: u16,
+        count: u16,
+    ) -> Result<Option<PortRange>> {
+        let Some(first) = port_at(port_range, from)? else {
+            return Ok(None);
+        };
+        match count {
+            0 | 1 => Ok(Some(PortRange::Single(first))),
+            _ => {
+                let last = first.checked_add(count - 1).ok_or_else(|| {
+                    eyre!("There are not {count} ports left from port {first} for the added nodes")
+                })?;
+                Ok(Some(PortRange::Range(first, last)))
+            }
+        }
+    }
+}
+
+/// A step of applying a fleet config to the node registry.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FleetAction {
+    Add {
+        count: u16,
+    },
+    Remove {
+        service_name: String,
+    },
+    /// Reconfigure the node at the given position of the fleet, upgrading it if a version is given.
+    Update {
+        index: u16,
+        service_name: String,
+        settings: Vec<&'static str>,
+        upgrade: Option<(String, String)>,
+    },
+}
+
+impl fmt::Display for FleetAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Add { count } => write!(f, "+ add {count} node(s)"),
+            Self::Remove { service_name } => write!(f, "- remove {service_name}"),
+            Self::Update {
+                service_name,
+                settings,
+                upgrade,
+                ..
+            } => {
+                write!(f, "~ update {service_name}:")?;
+                if !settings.is_empty() {
+                    write!(f, " reconfigure {}", settings.join(", "))?;
+                }
+                if let Some((from, to)) = upgrade {
+                    if !settings.is_empty() {
+                        write!(f, ";")?;
+                    }
+                    write!(f, " upgrade from {from} to {to}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Compare the fleet config against the nodes of the registry.
+///
+/// The nodes beyond the desired count are removed, starting with the highest numbered ones.
+pub fn plan(
+    config: &FleetConfig,
+    nodes: &[NodeServiceData],
+    env_variables: Option<&Vec<(String, String)>>,
+) -> Result<Vec<FleetAction>> {
+    let nodes = active_nodes(nodes);
+    let env_changed = sorted(config.env_variables().as_ref()) != sorted(env_variables);
+    let mut actions = Vec::new();
+
+    for (index, node) in nodes.iter().enumerate() {
+        if index >= config.count as usize {
+            actions.push(FleetAction::Remove {
+                service_name: node.service_name.clone(),
+            });
+            continue;
+        }
+
+        let index = index as u16;
+        let mut settings = config.reconcile(index, &mut (*node).clone())?;
+        if env_changed {
+            settings.push("env");
+        }
+        let upgrade = config
+            .version
+            .as_ref()
+            .filter(|version| **version != node.version)
+            .map(|version| (node.version.clone(), version.clone()));
+        if !settings.is_empty() || upgrade.is_some() {
+            actions.push(FleetAction::Update {
+                index,
+                service_name: node.service_name.clone(),
+                settings,
+                upgrade,
+            });
+        }
+    }
+
+    if nodes.len() < config.count as usize {
+        actions.push(FleetAction::Add {
+            count: config.count - nodes.len() as u16,
+        });
+    }
+
+    Ok(actions)
+}
+
+/// The nodes that have not been removed, in the order of their numbers.
+pub fn active_nodes(nodes: &[NodeServiceData]) -> Vec<&NodeServiceData> {
+    let mut nodes: Vec<_> = nodes
+        .iter()
+        .filter(|node| node.status != ServiceStatus::Removed)
+        .collect();
+    nodes.sort_by_key(|node| node.number);
+    nodes
+}
+
+fn update<T: PartialEq>(
+    changed: &mut Vec<&'static str>,
+    name: &'static str,
+    current: &mut T,
+    desired: T,
+) {
+    if *current != desired {
+        *current = desired;
+        changed.push(name);
+    }
+}
+
+/// The port of the node at the given position of the fleet, if the fleet sets one.
+fn port_at(port_range: &Option<PortRange>, index: u16) -> Result<Option<u16>> {
+    let Some(port_range) = port_range else {
+        return Ok(None);
+    };
+    let first = match port_range {
+        PortRange::Single(port) => *port,
+        PortRange::Range(start, _) => *start,
+    };
+    first.checked_add(index).map(Some).ok_or_else(|| {
+        eyre!("The ports {port_range} have no port for the node at position {index}")
+    })
+}
+
+/// The port range of the given ports, if they are consecutive.
+fn port_range(ports: &[Option<u16>]) -> Option<PortRange> {
+    let ports = ports.iter().copied().collect::<Option<Vec<u16>>>()?;
+    let first = *ports.first()?;
+    let consecutive = ports.iter().enumerate().all(|(index, port)| {
+        u16::try_from(index)
+            .ok()
+            .and_then(|index| first.checked_add(index))
+            == Some(*port)
+    });
+    match (consecutive, ports.len()) {
+        (false, _) => None,
+        (true, 1) => Some(PortRange::Single(first)),
+        (true, count) => Some(PortRange::Range(first, ports[count - 1])),
+    }
+}
+
+fn sorted(env_variables: Option<&Vec<(String, String)>>) -> Vec<(String, String)> {
+    let mut env_variables = env_variables.cloned().unwrap_or_default();
+    env_variables.sort();
+    env_variables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_bootstrap::InitialPeersConfig;
+    use ant_service_management::node::NODE_SERVICE_DATA_SCHEMA_LATEST;
+    use std::str::FromStr;
+
+    const REWARDS_ADDRESS: &str = "0x03B770D9cD32077cC0bF330c13C114a87643B124";
+
+    fn node(number: u16) -> NodeServiceData {
+        let service_name = format!("antnode{number}");
+        NodeServiceData {
+            alpha: false,
+            antnode_path: PathBuf::from(format!("/var/antctl/services/{service_name}/antnode")),
+            auto_restart: false,
+            connected_peers: None,
+            data_dir_path: PathBuf::from(format!("/var/antctl/services/{service_name}")),
+            evm_config: None,
+            evm_network: EvmNetwork::ArbitrumOne,
+            initial_peers_config: InitialPeersConfig::default(),
+            listen_addr: None,
+            log_dir_path: PathBuf::from(format!("/var/log/antnode/{service_name}")),
+            log_format: None,
+            max_archived_log_files: None,
+            max_download_rate: None,
+            max_log_files: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            metrics_port: None,
+            network_id: None,
+            no_upnp: false,
+            node_ip: None,
+            node_port: Some(12000 + number - 1),
+            number,
+            peer_id: None,
+            pid: None,
+            quiet_hours: None,
+            relay: false,
+            resource_limits: ResourceLimits::default(),
+            reward_balance: None,
+            rewards_address: RewardsAddress::from_str(REWARDS_ADDRESS).unwrap(),
+            rpc_socket_addr: SocketAddr::new(IpAddr::V4(DEFAULT_RPC_ADDRESS), 13000 + number - 1),
+            schema_version: NODE_SERVICE_DATA_SCHEMA_LATEST,
+            service_name,
+            status: ServiceStatus::Running,
+            tcp_port: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            user: Some("ant".to_string()),
+            user_mode: false,
+            version: "0.4.1".to_string(),
+            write_older_cache_files: false,
+            ws_port: None,
+        }
+    }
+
+    fn fleet(count: u16) -> FleetConfig {
+        FleetConfig {
+            count,
+            version: Some("0.4.1".to_string()),
+            rewards_address: RewardsAddress::from_str(REWARDS_ADDRESS).unwrap(),
+            node_port: Some(PortRange::Range(12000, 12000 + count - 1)),
+            rpc_port: Some(PortRange::Range(13000, 13000 + count - 1)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_a_fleet_file() {
+        let config: FleetConfig = toml::from_str(
+            r#"
+            count = 3
+            version = "0.4.3"
+            rewards_address = "0x03B770D9cD32077cC0bF330c13C114a87643B124"
+            node_port = "12000-12002"
+            metrics_port = 14000
+            relay = true
+            data_dir_path = "/mnt/data/antnode"
+
+            [env]
+            ANT_LOG = "info"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.count, 3);
+        assert_eq!(config.node_port, Some(PortRange::Range(12000, 12002)));
+        assert_eq!(config.metrics_port, Some(PortRange::Single(14000)));
+        assert!(config.relay);
+        assert!(!config.no_upnp);
+        assert_eq!(config.evm_network, EvmNetwork::ArbitrumOne);
+        assert_eq!(
+            config.env_variables(),
+            Some(vec![("ANT_LOG".to_string(), "info".to_string())])
+        );
+        // The metrics port range does not match the count.
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_settings() {
+        let result: Result<FleetConfig, _> = toml::from_str(
+            r#"
+            count = 1
+            rewards_address = "0x03B770D9cD32077cC0bF330c13C114a87643B124"
+            node_ports = "12000"
+            "#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let mut config = fleet(2);
+        config.quiet_hours = Some("22:00-07:00".to_string());
+        config.resource_limits.nofile = Some(4096);
+        let _ = config.env.insert("ANT_LOG".to_string(), "all".to_string());
+
+        let parsed: FleetConfig = toml::from_str(&config.to_toml().unwrap()).unwrap();
+
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn plans_nothing_for_a_matching_registry() {
+        let nodes = vec![node(1), node(2)];
+
+        assert!(plan(&fleet(2), &nodes, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn plans_additions_and_removals_to_reach_the_count() {
+        let nodes = vec![node(1), node(2), node(3)];
+
+        assert_eq!(
+            plan(&fleet(2), &nodes, None).unwrap(),
+            vec![FleetAction::Remove {
+                service_name: "antnode3".to_string()
+            }]
+        );
+        assert_eq!(
+            plan(&fleet(5), &nodes, None).unwrap(),
+            vec![FleetAction::Add { count: 2 }]
+        );
+    }
+
+    #[test]
+    fn removed_nodes_are_not_part_of_the_fleet() {
+        let mut removed = node(1);
+        removed.status = ServiceStatus::Removed;
+        let mut second = node(2);
+        second.node_port = Some(12000);
+        second.rpc_socket_addr.set_port(13000);
+
+        assert!(
+            plan(&fleet(1), &[removed, second], None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn plans_reconfiguration_and_upgrades_of_differing_nodes() {
+        let mut config = fleet(2);
+        config.version = Some("0.4.3".to_string());
+        config.relay = true;
+        let mut nodes = vec![node(1), node(2)];
+        nodes[1].version = "0.4.3".to_string();
+        nodes[1].relay = true;
+        nodes[1].node_port = Some(12005);
+
+        assert_eq!(
+            plan(&config, &nodes, None).unwrap(),
+            vec![
+                FleetAction::Update {
+                    index: 0,
+                    service_name: "antnode1".to_string(),
+                    settings: vec!["relay"],
+                    upgrade: Some(("0.4.1".to_string(), "0.4.3".to_string())),
+                },
+                FleetAction::Update {
+                    index: 1,
+                    service_name: "antnode2".to_string(),
+                    settings: vec!["node_port"],
+                    upgrade: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_environment_variables_reconfigure_every_node() {
+        let mut config = fleet(2);
+        let _ = config.env.insert("ANT_LOG".to_string(), "all".to_string());
+        let nodes = vec![node(1), node(2)];
+
+        let actions = plan(&config, &nodes, None).unwrap();
+        assert_eq!(actions.len(), 2);
+        assert!(actions.iter().all(|action| matches!(
+            action,
+            FleetAction::Update { settings, .. } if settings == &vec!["env"]
+        )));
+
+        let env_variables = vec![("ANT_LOG".to_string(), "all".to_string())];
+        assert!(
+            plan(&config, &nodes, Some(&env_variables))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn reconcile_applies_the_settings_of_the_node_position() {
+        let mut config = fleet(2);
+        config.no_upnp = true;
+        let mut node = node(2);
+        node.node_port = None;
+
+        let changed = config.reconcile(1, &mut node).unwrap();
+
+        assert_eq!(changed, vec!["no_upnp", "node_port"]);
+        assert!(node.no_upnp);
+        assert_eq!(node.node_port, Some(12001));
+        assert!(config.reconcile(1, &mut node).unwrap().is_empty());
+    }
+
+    #[test]
+    fn added_nodes_take_the_remaining_ports() {
+        let node_port = Some(PortRange::Range(12000, 12004));
+
+        assert_eq!(
+            FleetConfig::added_ports(&node_port, 2, 3).unwrap(),
+            Some(PortRange::Range(12002, 12004))
+        );
+        assert_eq!(
+            FleetConfig::added_ports(&node_port, 4, 1).unwrap(),
+            Some(PortRange::Single(12004))
+        );
+        assert_eq!(FleetConfig::added_ports(&None, 2, 3).unwrap(), None);
+        // Ports beyond the last one are rejected rather than wrapping around.
+        assert!(FleetConfig::added_ports(&Some(PortRange::Single(65535)), 0, 2).is_err());
+        assert!(FleetConfig::added_ports(&Some(PortRange::Range(65534, 65535)), 2, 1).is_err());
+    }
+
+    #[test]
+    fn export_describes_the_registry() {
+        let nodes = vec![node(1), node(2), node(3)];
+        let env_variables = vec![("ANT_LOG".to_string(), "info".to_string())];
+
+        let (config, differing_nodes) = FleetConfig::export(&nodes, Some(&env_variables)).unwrap();
+
+        assert_eq!(config.count, 3);
+        assert_eq!(config.version, Some("0.4.1".to_string()));
+        assert_eq!(config.node_port, Some(PortRange::Range(12000, 12002)));
+        assert_eq!(config.rpc_port, Some(PortRange::Range(13000, 13002)));
+        assert_eq!(config.rpc_address, None);
+        assert_eq!(
+            config.data_dir_path,
+            Some(PathBuf::from("/var/antctl/services"))
+        );
+        assert_eq!(config.log_dir_path, Some(PathBuf::from("/var/log/antnode")));
+        assert_eq!(config.user, Some("ant".to_string()));
+        assert_eq!(config.env_variables(), Some(env_variables.clone()));
+        assert!(differing_nodes.is_empty());
+        assert!(
+            plan(&config, &nodes, Some(&env_variables))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn export_reports_nodes_with_differing_settings() {
+        let mut nodes = vec![node(1), node(2), node(3)];
+        nodes[2].relay = true;
+        nodes[2].node_port = Some(12010);
+        nodes[2].version = "0.4.2".to_string();
+
+        let (config, differing_nodes) = FleetConfig::export(&nodes, None).unwrap();
+
+        assert_eq!(config.node_port, None);
+        assert_eq!(config.version, None);
+        assert_eq!(
+            differing_nodes,
+            vec![
+                ("antnode1".to_string(), vec!["node_port"]),
+                ("antnode2".to_string(), vec!["node_port"]),
+                ("antnode3".to_string(), vec!["node_port", "relay"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn export_fails_without_nodes() {
+        assert!(FleetConfig::export(&[], None).is_err());
+    }
+}
@@ -0,0 +1,560 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Self-healing of node services by the daemon.
+//!
+//! The daemon probes each node periodically. The probes are fed to a [`HealingPolicy`], which
+//! tracks how long each node has been unhealthy and decides when to remediate it, according to the
+//! thresholds in the daemon config. Every remediation is recorded in an audit file.
+
+use crate::metrics::{SHUNNED_BY_CLOSE_GROUP_METRIC, fetch_node_metrics};
+use ant_service_management::{
+    NodeServiceData, ServiceStatus,
+    rpc::{RpcActions, RpcClient},
+};
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// The reason a node gives when it terminates because it could not write to its disk.
+const HARD_DISK_WRITE_ERROR: &str = "HardDiskWriteError";
+
+/// What to do with an unhealthy node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Remediation {
+    /// Only log the problem and record it in the audit file.
+    Alert,
+    /// Replace the node with a new one, which has a new identity.
+    Reset,
+    /// Restart the node, keeping its identity.
+    Restart,
+}
+
+impl fmt::Display for Remediation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Remediation::Alert => write!(f, "alert"),
+            Remediation::Reset => write!(f, "reset"),
+            Remediation::Restart => write!(f, "restart"),
+        }
+    }
+}
+
+/// The thresholds of the self-healing, as read from the `[healing]` table of the daemon config.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealingConfig {
+    pub enabled: bool,
+    /// How often the nodes are probed.
+    pub probe_interval_secs: u64,
+    /// The least time between two remediations of the same node, to leave it time to recover.
+    pub cooldown_secs: u64,
+    /// The file remediations are recorded in. It defaults to a file next to the node registry.
+    pub audit_log_path: Option<PathBuf>,
+    pub no_peers: NoPeersRule,
+    pub disk_write_errors: DiskWriteErrorsRule,
+    pub shunned: ShunnedRule,
+}
+
+impl Default for HealingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            probe_interval_secs: 60,
+            cooldown_secs: 900,
+            audit_log_path: None,
+            no_peers: NoPeersRule::default(),
+            disk_write_errors: DiskWriteErrorsRule::default(),
+            shunned: ShunnedRule::default(),
+        }
+    }
+}
+
+/// A running node that has had no connected peers, or not answered over RPC, for a while.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NoPeersRule {
+    pub after_secs: u64,
+    pub action: Remediation,
+}
+
+impl Default for NoPeersRule {
+    fn default() -> Self {
+        Self {
+            after_secs: 600,
+            action: Remediation::Restart,
+        }
+    }
+}
+
+/// A node that terminated because it could not write to its disk several times within a window.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiskWriteErrorsRule {
+    pub count: usize,
+    pub window_secs: u64,
+    pub action: Remediation,
+}
+
+impl Default for DiskWriteErrorsRule {
+    fn default() -> Self {
+        Self {
+            count: 3,
+            window_secs: 3600,
+            action: Remediation::Alert,
+        }
+    }
+}
+
+/// A running node that has been shunned by at least this many of its close group peers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShunnedRule {
+    pub by_close_group: u64,
+    pub action: Remediation,
+}
+
+impl Default for ShunnedRule {
+    fn default() -> Self {
+        Self {
+            by_close_group: 3,
+            action: Remediation::Reset,
+        }
+    }
+}
+
+/// What was learned about a node in one probe.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeProbe {
+    pub running: bool,
+    /// `None` if the node did not answer over RPC.
+    pub connected_peers: Option<usize>,
+    /// `None` if the node has no metrics server or it did not answer.
+    pub shunned_by_close_group: Option<u64>,
+    /// The reason the node last terminated with an error, and when.
+    pub critical_failure: Option<(DateTime<Utc>, String)>,
+}
+
+/// Probe a node over its RPC and metrics servers.
+pub async fn probe_node(node: &NodeServiceData) -> NodeProbe {
+    let mut probe = NodeProbe {
+        running: node.status == ServiceStatus::Running,
+        critical_failure: node.get_critical_failure(),
+        ..Default::default()
+    };
+    if !probe.running {
+        return probe;
+    }
+
+    let mut rpc_client = RpcClient::from_socket_addr(node.rpc_socket_addr);
+    rpc_client.set_max_attempts(1);
+    probe.connected_peers = rpc_client
+        .network_info()
+        .await
+        .inspect_err(|err| {
+            debug!(
+                "Could not obtain the network info of {}: {err}",
+                node.service_name
+            )
+        })
+        .ok()
+        .map(|network_info| network_info.connected_peers.len());
+
+    if let Some(metrics_port) = node.metrics_port {
+        probe.shunned_by_close_group = fetch_node_metrics(metrics_port)
+            .await
+            .inspect_err(|err| {
+                debug!(
+                    "Could not obtain the metrics of {}: {err}",
+                    node.service_name
+                )
+            })
+            .ok()
+            .and_then(|metrics| metrics.value(SHUNNED_BY_CLOSE_GROUP_METRIC))
+            .map(|value| value as u64);
+    }
+    probe
+}
+
+/// A remediation the policy decided to apply to a node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decision {
+    pub action: Remediation,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+struct NodeHealth {
+    no_peers_since: Option<Instant>,
+    disk_write_errors: Vec<DateTime<Utc>>,
+    last_remediation: Option<Instant>,
+}
+
+/// Decides when to remediate nodes, from the probes of them over time.
+#[derive(Debug)]
+pub struct HealingPolicy {
+    config: HealingConfig,
+    nodes: HashMap<String, NodeHealth>,
+}
+
+impl HealingPolicy {
+    pub fn new(config: HealingConfig) -> Self {
+        Self {
+            config,
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Record a probe of a node and decide whether it should be remediated.
+    ///
+    /// Once a node is remediated, what was recorded about it is cleared, and it is not remediated
+    /// again until the cooldown has passed.
+    pub fn evaluate(
+        &mut self,
+        service_name: &str,
+        probe: &NodeProbe,
+        now: Instant,
+    ) -> Option<Decision> {
+        let config = &self.config;
+        let health = self.nodes.entry(service_name.to_string()).or_default();
+
+        if let Some((terminated_at, reason)) = &probe.critical_failure
+            && reason.contains(HARD_DISK_WRITE_ERROR)
+            && !health.disk_write_errors.contains(terminated_at)
+        {
+            health.disk_write_errors.push(*terminated_at);
+        }
+        // The window ends at the latest error, since a node only keeps a record of its last one.
+        if let Some(latest) = health.disk_write_errors.iter().max().copied() {
+            let window = chrono::Duration::seconds(
+                i64::try_from(config.disk_write_errors.window_secs).unwrap_or(i64::MAX),
+            );
+            health
+                .disk_write_errors
+                .retain(|terminated_at| latest - *terminated_at <= window);
+        }
+
+        let no_peers = probe.running && probe.connected_peers.unwrap_or(0) == 0;
+        if no_peers {
+            let _ = health.no_peers_since.get_or_insert(now);
+        } else {
+            health.no_peers_since = None;
+        }
+
+        let decision = if health.disk_write_errors.len() >= config.disk_write_errors.count {
+            Some(Decision {
+                action: config.disk_write_errors.action,
+                reason: format!(
+                    "the node failed to write to its disk {} time(s) within {}s",
+                    health.disk_write_errors.len(),
+                    config.disk_write_errors.window_secs
+                ),
+            })
+        } else if probe.running
+            && let Some(shunned) = probe.shunned_by_close_group
+            && shunned >= config.shunned.by_close_group
+        {
+            Some(Decision {
+                action: config.shunned.action,
+                reason: format!("the node is shunned by {shunned} of its close group peers"),
+            })
+        } else if let Some(since) = health.no_peers_since
+            && now.duration_since(since) >= Duration::from_secs(config.no_peers.after_secs)
+        {
+            let problem = if probe.connected_peers.is_some() {
+                "has had no connected peers"
+            } else {
+                "has not answered over RPC"
+            };
+            Some(Decision {
+                action: config.no_peers.action,
+                reason: format!(
+                    "the node {problem} for {}s",
+                    now.duration_since(since).as_secs()
+                ),
+            })
+        } else {
+            None
+        }?;
+
+        if let Some(last_remediation) = health.last_remediation
+            && now.duration_since(last_remediation) < Duration::from_secs(config.cooldown_secs)
+        {
+            debug!(
+                "Not remediating {service_name} yet, since it was remediated recently: {}",
+                decision.reason
+            );
+            return None;
+        }
+
+        *health = NodeHealth {
+            last_remediation: Some(now),
+            ..Default::default()
+        };
+        Some(decision)
+    }
+
+    /// Forget the nodes that are no longer in the registry.
+    pub fn retain(&mut self, service_names: &[String]) {
+        self.nodes
+            .retain(|service_name, _| service_names.contains(service_name));
+    }
+}
+
+/// A remediation, as recorded in the audit file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub service_name: String,
+    pub peer_id: Option<String>,
+    pub action: Remediation,
+    pub reason: String,
+    /// Why the remediation failed, if it did.
+    pub error: Option<String>,
+}
+
+/// Append an entry to the audit file, as a line of JSON.
+pub fn append_audit_entry(path: &Path, entry: &AuditEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DaemonConfig;
+    use assert_fs::TempDir;
+
+    fn config() -> HealingConfig {
+        HealingConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    fn running(connected_peers: Option<usize>) -> NodeProbe {
+        NodeProbe {
+            running: true,
+            connected_peers,
+            ..Default::default()
+        }
+    }
+
+    fn disk_write_error(minutes_ago: i64) -> NodeProbe {
+        NodeProbe {
+            critical_failure: Some((
+                Utc::now() - chrono::Duration::minutes(minutes_ago),
+                "HardDiskWriteError".to_string(),
+            )),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn a_node_without_peers_should_be_restarted_once_the_threshold_has_passed() {
+        let mut policy = HealingPolicy::new(config());
+        let start = Instant::now();
+
+        assert_eq!(policy.evaluate("antnode1", &running(Some(0)), start), None);
+        assert_eq!(
+            policy.evaluate(
+                "antnode1",
+                &running(Some(0)),
+                start + Duration::from_secs(599)
+            ),
+            None
+        );
+        let decision = policy
+            .evaluate("antnode1", &running(None), start + Duration::from_secs(600))
+            .unwrap();
+
+        assert_eq!(decision.action, Remediation::Restart);
+        assert_eq!(
+            decision.reason,
+            "the node has not answered over RPC for 600s"
+        );
+    }
+
+    #[test]
+    fn a_node_that_regains_peers_should_not_be_remediated() {
+        let mut policy = HealingPolicy::new(config());
+        let start = Instant::now();
+
+        assert_eq!(policy.evaluate("antnode1", &running(Some(0)), start), None);
+        assert_eq!(
+            policy.evaluate(
+                "antnode1",
+                &running(Some(5)),
+                start + Duration::from_secs(300)
+            ),
+            None
+        );
+        assert_eq!(
+            policy.evaluate(
+                "antnode1",
+                &running(Some(0)),
+                start + Duration::from_secs(700)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn a_shunned_node_should_be_reset() {
+        let mut policy = HealingPolicy::new(config());
+        let probe = NodeProbe {
+            shunned_by_close_group: Some(3),
+            ..running(Some(20))
+        };
+
+        let decision = policy.evaluate("antnode1", &probe, Instant::now()).unwrap();
+
+        assert_eq!(decision.action, Remediation::Reset);
+        assert_eq!(
+            decision.reason,
+            "the node is shunned by 3 of its close group peers"
+        );
+    }
+
+    #[test]
+    fn repeated_disk_write_errors_within_the_window_should_raise_an_alert() {
+        let mut policy = HealingPolicy::new(config());
+        let now = Instant::now();
+
+        // The same failure seen again is only counted once, and one outside the window not at all.
+        let seen_twice = disk_write_error(50);
+        assert_eq!(
+            policy.evaluate("antnode1", &disk_write_error(200), now),
+            None
+        );
+        assert_eq!(policy.evaluate("antnode1", &seen_twice, now), None);
+        assert_eq!(policy.evaluate("antnode1", &seen_twice, now), None);
+        assert_eq!(
+            policy.evaluate("antnode1", &disk_write_error(30), now),
+            None
+        );
+        let decision = policy
+            .evaluate("antnode1", &disk_write_error(10), now)
+            .unwrap();
+
+        assert_eq!(decision.action, Remediation::Alert);
+        assert_eq!(
+            decision.reason,
+            "the node failed to write to its disk 3 time(s) within 3600s"
+        );
+    }
+
+    #[test]
+    fn a_node_should_not_be_remediated_again_within_the_cooldown() {
+        let mut policy = HealingPolicy::new(HealingConfig {
+            cooldown_secs: 900,
+            no_peers: NoPeersRule {
+                after_secs: 0,
+                action: Remediation::Restart,
+            },
+            ..config()
+        });
+        let start = Instant::now();
+
+        assert!(
+            policy
+                .evaluate("antnode1", &running(Some(0)), start)
+                .is_some()
+        );
+        assert_eq!(
+            policy.evaluate(
+                "antnode1",
+                &running(Some(0)),
+                start + Duration::from_secs(899)
+            ),
+            None
+        );
+        assert!(
+            policy
+                .evaluate(
+                    "antnode1",
+                    &running(Some(0)),
+                    start + Duration::from_secs(900)
+                )
+                .is_some()
+        );
+        assert!(
+            policy
+                .evaluate("antnode2", &running(Some(0)), start)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn the_thresholds_should_be_read_from_the_daemon_config() {
+        let config: DaemonConfig = toml::from_str(
+            r#"
+            [healing]
+            enabled = true
+            probe_interval_secs = 30
+
+            [healing.no_peers]
+            after_secs = 120
+            action = "reset"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.healing,
+            HealingConfig {
+                enabled: true,
+                probe_interval_secs: 30,
+                no_peers: NoPeersRule {
+                    after_secs: 120,
+                    action: Remediation::Reset,
+                },
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn audit_entries_should_be_appended_as_lines_of_json() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("audit").join("healing_audit.log");
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            service_name: "antnode1".to_string(),
+            peer_id: None,
+            action: Remediation::Restart,
+            reason: "the node has had no connected peers for 600s".to_string(),
+            error: None,
+        };
+
+        append_audit_entry(&path, &entry).unwrap();
+        append_audit_entry(&path, &entry).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let entries: Vec<AuditEntry> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries, vec![entry.clone(), entry]);
+    }
+}
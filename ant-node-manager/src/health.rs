@@ -0,0 +1,607 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Health checks of node services over their RPC servers, and reports on the health of the
+//! fleet from their RPC and metrics servers.
+
+use crate::{
+    metrics::{
+        NodeMetrics, PUT_RECORD_ERRORS_METRIC, RELAY_RESERVATION_HEALTH_METRIC,
+        SHUNNED_BY_CLOSE_GROUP_METRIC, fetch_node_metrics,
+    },
+    start_of_today,
+};
+use ant_evm::AttoTokens;
+use ant_service_management::{
+    NodeServiceData, ServiceStatus,
+    rpc::{RpcActions, RpcClient},
+};
+use semver::Version;
+use serde::{Serialize, Serializer};
+use std::time::Duration;
+use tokio::time::Instant;
+
+pub const DEFAULT_HEALTH_CHECK_WINDOW_S: u64 = 300;
+pub const DEFAULT_MIN_CONNECTED_PEERS: usize = 1;
+/// The share of its records a node must still hold after a restart, in percent.
+const MIN_RECORD_COUNT_RETAINED_PERCENT: usize = 50;
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// The fewest nodes that have to report a value before it is compared across the fleet.
+const MIN_NODES_FOR_OUTLIERS: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct HealthCheckOptions {
+    /// How long a node has to become healthy.
+    pub window: Duration,
+    pub min_connected_peers: usize,
+}
+
+impl Default for HealthCheckOptions {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(DEFAULT_HEALTH_CHECK_WINDOW_S),
+            min_connected_peers: DEFAULT_MIN_CONNECTED_PEERS,
+        }
+    }
+}
+
+/// The number of records the node holds, if it could be obtained.
+pub async fn record_count(rpc_client: &dyn RpcActions) -> Option<usize> {
+    rpc_client
+        .record_addresses()
+        .await
+        .inspect_err(|err| debug!("Could not obtain the record addresses of the node: {err}"))
+        .ok()
+        .map(|addresses| addresses.len())
+}
+
+/// Wait for the node to become healthy: to run the expected version, to be connected to enough
+/// peers and to hold at least half the records it held before, if that count is given.
+///
+/// Returns the reason the node was not healthy if it did not become healthy within the window.
+pub async fn wait_until_healthy(
+    rpc_client: &dyn RpcActions,
+    expected_version: &Version,
+    previous_record_count: Option<usize>,
+    options: &HealthCheckOptions,
+) -> Result<(), String> {
+    let deadline = Instant::now() + options.window;
+    loop {
+        match check_health(
+            rpc_client,
+            expected_version,
+            previous_record_count,
+            options.min_connected_peers,
+        )
+        .await
+        {
+            Ok(()) => return Ok(()),
+            Err(reason) if Instant::now() + HEALTH_CHECK_INTERVAL > deadline => {
+                return Err(reason);
+            }
+            Err(reason) => {
+                debug!("The node is not healthy yet: {reason}");
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+            }
+        }
+    }
+}
+
+async fn check_health(
+    rpc_client: &dyn RpcActions,
+    expected_version: &Version,
+    previous_record_count: Option<usize>,
+    min_connected_peers: usize,
+) -> Result<(), String> {
+    let node_info = rpc_client
+        .node_info()
+        .await
+        .map_err(|err| format!("the node did not respond over RPC: {err}"))?;
+    if Version::parse(&node_info.version).ok().as_ref() != Some(expected_version) {
+        return Err(format!(
+            "the node runs version {} rather than {expected_version}",
+            node_info.version
+        ));
+    }
+
+    let network_info = rpc_client
+        .network_info()
+        .await
+        .map_err(|err| format!("the node did not return its network info: {err}"))?;
+    if network_info.connected_peers.len() < min_connected_peers {
+        return Err(format!(
+            "the node is connected to {} peer(s), fewer than {min_connected_peers}",
+            network_info.connected_peers.len()
+        ));
+    }
+
+    if let Some(previous_record_count) = previous_record_count {
+        let record_count = record_count(rpc_client)
+            .await
+            .ok_or_else(|| "the node did not return its records".to_string())?;
+        if record_count * 100 < previous_record_count * MIN_RECORD_COUNT_RETAINED_PERCENT {
+            return Err(format!(
+                "the node holds {record_count} record(s), down from {previous_record_count}"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// The health of a node, as reported by its RPC and metrics servers.
+///
+/// Values are `None` if the node is not running, or did not report them.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NodeHealth {
+    pub service_name: String,
+    pub peer_id: Option<String>,
+    pub status: ServiceStatus,
+    pub record_count: Option<usize>,
+    pub connected_peers: Option<usize>,
+    pub shunned_by_close_group: Option<u64>,
+    /// Between 0 and 1. Only reported for nodes using relays.
+    pub relay_reservation_health: Option<f64>,
+    pub put_record_errors: Option<u64>,
+    /// The record PUT errors averaged over the uptime of the node.
+    pub put_record_errors_per_hour: Option<f64>,
+    #[serde(serialize_with = "serialize_tokens")]
+    pub lifetime_earnings: Option<AttoTokens>,
+    #[serde(serialize_with = "serialize_tokens")]
+    pub earned_today: Option<AttoTokens>,
+    /// The ways in which the node stands out from the rest of the fleet.
+    pub outliers: Vec<String>,
+}
+
+/// The median of each value across the nodes that reported it.
+///
+/// A median is `None` if too few nodes reported the value to compare them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct FleetMedians {
+    pub record_count: Option<f64>,
+    pub connected_peers: Option<f64>,
+    pub shunned_by_close_group: Option<f64>,
+    pub relay_reservation_health: Option<f64>,
+    pub put_record_errors_per_hour: Option<f64>,
+    /// In atto tokens.
+    pub earned_today: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct HealthReport {
+    pub nodes: Vec<NodeHealth>,
+    pub medians: FleetMedians,
+}
+
+impl HealthReport {
+    pub fn has_outliers(&self) -> bool {
+        self.nodes.iter().any(|node| !node.outliers.is_empty())
+    }
+}
+
+/// Query the health of each running node and flag those that stand out from the fleet.
+pub async fn health_report(nodes: &[NodeServiceData]) -> HealthReport {
+    let mut node_healths = Vec::new();
+    for node in nodes {
+        let mut rpc_client = RpcClient::from_socket_addr(node.rpc_socket_addr);
+        rpc_client.set_max_attempts(1);
+        let metrics = match node.metrics_port {
+            Some(metrics_port) if node.status == ServiceStatus::Running => {
+                fetch_node_metrics(metrics_port)
+                    .await
+                    .inspect_err(|err| {
+                        debug!(
+                            "Could not obtain the metrics of {}: {err}",
+                            node.service_name
+                        )
+                    })
+                    .ok()
+            }
+            _ => None,
+        };
+        node_healths.push(node_health(node, &rpc_client, metrics.as_ref()).await);
+    }
+
+    let medians = flag_outliers(&mut node_healths);
+    HealthReport {
+        nodes: node_healths,
+        medians,
+    }
+}
+
+/// Query the health of a node over RPC, along with its metrics if it has a metrics server.
+pub async fn node_health(
+    node: &NodeServiceData,
+    rpc_client: &dyn RpcActions,
+    metrics: Option<&NodeMetrics>,
+) -> NodeHealth {
+    let mut health = NodeHealth {
+        service_name: node.service_name.clone(),
+        peer_id: node.peer_id.map(|peer_id| peer_id.to_string()),
+        status: node.status.clone(),
+        record_count: None,
+        connected_peers: None,
+        shunned_by_close_group: None,
+        relay_reservation_health: None,
+        put_record_errors: None,
+        put_record_errors_per_hour: None,
+        lifetime_earnings: None,
+        earned_today: None,
+        outliers: Vec::new(),
+    };
+    if node.status != ServiceStatus::Running {
+        return health;
+    }
+
+    let uptime = match rpc_client.node_info().await {
+        Ok(node_info) => node_info.uptime,
+        Err(err) => {
+            debug!(
+                "Could not obtain the node info of {}: {err}",
+                node.service_name
+            );
+            health
+                .outliers
+                .push("the node did not respond over RPC".to_string());
+            return health;
+        }
+    };
+    health.record_count = record_count(rpc_client).await;
+    health.connected_peers = rpc_client
+        .network_info()
+        .await
+        .inspect_err(|err| {
+            debug!(
+                "Could not obtain the network info of {}: {err}",
+                node.service_name
+            )
+        })
+        .ok()
+        .map(|network_info| network_info.connected_peers.len());
+    let today = start_of_today();
+    match rpc_client.earnings(today).await {
+        Ok(earnings) => {
+            health.lifetime_earnings = Some(earnings.lifetime_total);
+            health.earned_today = Some(earnings.total_since(today));
+        }
+        Err(err) => debug!(
+            "Could not obtain the earnings of {}: {err}",
+            node.service_name
+        ),
+    }
+
+    if let Some(metrics) = metrics {
+        health.shunned_by_close_group = metrics
+            .value(SHUNNED_BY_CLOSE_GROUP_METRIC)
+            .map(|value| value as u64);
+        // Nodes that do not use relays report a health of zero.
+        if node.relay {
+            health.relay_reservation_health = metrics.value(RELAY_RESERVATION_HEALTH_METRIC);
+        }
+        health.put_record_errors = metrics
+            .value(PUT_RECORD_ERRORS_METRIC)
+            .map(|value| value as u64);
+        let uptime_hours = uptime.as_secs_f64() / 3600.0;
+        if uptime_hours > 0.0 {
+            health.put_record_errors_per_hour = health
+                .put_record_errors
+                .map(|errors| errors as f64 / uptime_hours);
+        }
+    }
+    health
+}
+
+/// Whether a node is better off with a higher or a lower value.
+#[derive(Clone, Copy)]
+enum Better {
+    Higher,
+    Lower,
+}
+
+/// A value compared across the fleet.
+struct Measure {
+    description: &'static str,
+    better: Better,
+    value: fn(&NodeHealth) -> Option<f64>,
+    format: fn(f64) -> String,
+    median: fn(&mut FleetMedians) -> &mut Option<f64>,
+}
+
+const MEASURES: [Measure; 6] = [
+    Measure {
+        description: "holds",
+        better: Better::Higher,
+        value: |node| node.record_count.map(|count| count as f64),
+        format: |value| format!("{value:.0} record(s)"),
+        median: |medians| &mut medians.record_count,
+    },
+    Measure {
+        description: "is connected to",
+        better: Better::Higher,
+        value: |node| node.connected_peers.map(|count| count as f64),
+        format: |value| format!("{value:.0} peer(s)"),
+        median: |medians| &mut medians.connected_peers,
+    },
+    Measure {
+        description: "is shunned by",
+        better: Better::Lower,
+        value: |node| node.shunned_by_close_group.map(|count| count as f64),
+        format: |value| format!("{value:.0} close group peer(s)"),
+        median: |medians| &mut medians.shunned_by_close_group,
+    },
+    Measure {
+        description: "has a relay reservation health of",
+        better: Better::Higher,
+        value: |node| node.relay_reservation_health,
+        format: |value| format!("{value:.2}"),
+        median: |medians| &mut medians.relay_reservation_health,
+    },
+    Measure {
+        description: "fails",
+        better: Better::Lower,
+        value: |node| node.put_record_errors_per_hour,
+        format: |value| format!("{value:.1} record PUT(s) an hour"),
+        median: |medians| &mut medians.put_record_errors_per_hour,
+    },
+    Measure {
+        description: "earned",
+        better: Better::Higher,
+        value: |node| node.earned_today.map(|tokens| f64::from(tokens.as_atto())),
+        format: |value| format!("{} today", AttoTokens::from_u128(value as u128)),
+        median: |medians| &mut medians.earned_today,
+    },
+];
+
+/// Flag the nodes whose values are far from the median of the fleet, in the direction that is
+/// worse for the node.
+///
+/// A value is flagged if it is less than half the median, or more than twice the median and at
+/// least one. A value is only compared if enough nodes reported it. Returns the medians.
+pub fn flag_outliers(nodes: &mut [NodeHealth]) -> FleetMedians {
+    let mut medians = FleetMedians::default();
+    for measure in &MEASURES {
+        let values: Vec<f64> = nodes.iter().filter_map(measure.value).collect();
+        let Some(median) = median(values) else {
+            continue;
+        };
+        *(measure.median)(&mut medians) = Some(median);
+
+        for node in nodes.iter_mut() {
+            let Some(value) = (measure.value)(node) else {
+                continue;
+            };
+            let comparison = match measure.better {
+                Better::Higher if value < median / 2.0 => "less than half",
+                Better::Lower if value >= 1.0 && value > median * 2.0 => "more than twice",
+                _ => continue,
+            };
+            node.outliers.push(format!(
+                "the node {} {}, {comparison} the fleet median of {}",
+                measure.description,
+                (measure.format)(value),
+                (measure.format)(median)
+            ));
+        }
+    }
+    medians
+}
+
+/// The median of the values, if there are enough of them to compare.
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.len() < MIN_NODES_FOR_OUTLIERS {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        Some((values[middle - 1] + values[middle]) / 2.0)
+    } else {
+        Some(values[middle])
+    }
+}
+
+/// Amounts are written in tokens rather than as the hexadecimal number of atto tokens.
+fn serialize_tokens<S: Serializer>(
+    tokens: &Option<AttoTokens>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match tokens {
+        Some(tokens) => serializer.serialize_str(&tokens.to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::MockRpcClient;
+    use ant_service_management::{
+        error::Error as ServiceError,
+        rpc::{NetworkInfo, NodeInfo, RecordAddress},
+    };
+    use libp2p::kad::RecordKey;
+    use libp2p_identity::PeerId;
+    use std::path::PathBuf;
+
+    fn mock_rpc_client(version: &'static str, peers: usize, records: usize) -> MockRpcClient {
+        let mut mock_rpc_client = MockRpcClient::new();
+        mock_rpc_client.expect_node_info().returning(move || {
+            Ok(NodeInfo {
+                pid: 1000,
+                peer_id: PeerId::random(),
+                data_path: PathBuf::from("/var/antctl/services/antnode1"),
+                log_path: PathBuf::from("/var/log/antnode/antnode1"),
+                version: version.to_string(),
+                uptime: Duration::from_secs(1),
+                wallet_balance: 0,
+            })
+        });
+        mock_rpc_client.expect_network_info().returning(move || {
+            Ok(NetworkInfo {
+                connected_peers: (0..peers).map(|_| PeerId::random()).collect(),
+                listeners: Vec::new(),
+            })
+        });
+        mock_rpc_client
+            .expect_record_addresses()
+            .returning(move || {
+                Ok((0..records)
+                    .map(|index: usize| RecordAddress {
+                        key: RecordKey::new(&index.to_be_bytes()),
+                    })
+                    .collect())
+            });
+        mock_rpc_client
+    }
+
+    fn options(min_connected_peers: usize) -> HealthCheckOptions {
+        HealthCheckOptions {
+            window: Duration::ZERO,
+            min_connected_peers,
+        }
+    }
+
+    #[tokio::test]
+    async fn a_node_running_the_new_version_with_peers_and_records_is_healthy() {
+        let rpc_client = mock_rpc_client("0.4.3", 5, 90);
+
+        let result =
+            wait_until_healthy(&rpc_client, &Version::new(0, 4, 3), Some(100), &options(5)).await;
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn a_node_running_another_version_is_not_healthy() {
+        let rpc_client = mock_rpc_client("0.4.1", 5, 100);
+
+        let result =
+            wait_until_healthy(&rpc_client, &Version::new(0, 4, 3), None, &options(1)).await;
+
+        assert!(result.unwrap_err().contains("version 0.4.1"));
+    }
+
+    #[tokio::test]
+    async fn a_node_without_enough_peers_is_not_healthy() {
+        let rpc_client = mock_rpc_client("0.4.3", 2, 100);
+
+        let result =
+            wait_until_healthy(&rpc_client, &Version::new(0, 4, 3), None, &options(3)).await;
+
+        assert!(result.unwrap_err().contains("2 peer(s)"));
+    }
+
+    #[tokio::test]
+    async fn a_node_whose_records_collapsed_is_not_healthy() {
+        let rpc_client = mock_rpc_client("0.4.3", 5, 40);
+
+        let result =
+            wait_until_healthy(&rpc_client, &Version::new(0, 4, 3), Some(100), &options(1)).await;
+
+        assert!(result.unwrap_err().contains("40 record(s), down from 100"));
+    }
+
+    #[tokio::test]
+    async fn an_unresponsive_node_is_not_healthy() {
+        let mut rpc_client = MockRpcClient::new();
+        rpc_client
+            .expect_node_info()
+            .returning(|| Err(ServiceError::RpcConnectionError("refused".to_string())));
+
+        let result =
+            wait_until_healthy(&rpc_client, &Version::new(0, 4, 3), None, &options(1)).await;
+
+        assert!(result.unwrap_err().contains("did not respond"));
+    }
+
+    fn node_health(
+        number: usize,
+        record_count: usize,
+        put_record_errors_per_hour: f64,
+    ) -> NodeHealth {
+        NodeHealth {
+            service_name: format!("antnode{number}"),
+            peer_id: None,
+            status: ServiceStatus::Running,
+            record_count: Some(record_count),
+            connected_peers: Some(50),
+            shunned_by_close_group: Some(0),
+            relay_reservation_health: None,
+            put_record_errors: None,
+            put_record_errors_per_hour: Some(put_record_errors_per_hour),
+            lifetime_earnings: None,
+            earned_today: None,
+            outliers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn flag_outliers_should_flag_nodes_far_from_the_fleet_median() {
+        let mut nodes = vec![
+            node_health(1, 1000, 0.0),
+            node_health(2, 900, 0.5),
+            node_health(3, 1100, 0.0),
+            node_health(4, 200, 0.0),
+            node_health(5, 1000, 6.0),
+        ];
+
+        let medians = flag_outliers(&mut nodes);
+
+        assert_eq!(medians.record_count, Some(1000.0));
+        assert_eq!(medians.put_record_errors_per_hour, Some(0.0));
+        assert_eq!(medians.relay_reservation_health, None);
+        assert_eq!(
+            nodes[3].outliers,
+            vec![
+                "the node holds 200 record(s), less than half the fleet median of 1000 record(s)"
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            nodes[4].outliers,
+            vec![
+                "the node fails 6.0 record PUT(s) an hour, more than twice the fleet median of \
+                 0.0 record PUT(s) an hour"
+                    .to_string()
+            ]
+        );
+        // An occasional error is not enough to stand out.
+        assert!(nodes[1].outliers.is_empty());
+        assert!(nodes[0].outliers.is_empty());
+    }
+
+    #[test]
+    fn flag_outliers_should_not_compare_too_few_nodes() {
+        let mut nodes = vec![node_health(1, 1000, 0.0), node_health(2, 10, 20.0)];
+
+        let medians = flag_outliers(&mut nodes);
+
+        assert_eq!(medians, FleetMedians::default());
+        assert!(nodes.iter().all(|node| node.outliers.is_empty()));
+    }
+
+    #[test]
+    fn flag_outliers_should_only_compare_the_nodes_that_reported_a_value() {
+        let mut nodes = vec![
+            node_health(1, 1000, 0.0),
+            node_health(2, 1000, 0.0),
+            node_health(3, 1000, 0.0),
+            node_health(4, 0, 0.0),
+        ];
+        nodes[3].status = ServiceStatus::Stopped;
+        nodes[3].record_count = None;
+        nodes[3].connected_peers = None;
+        nodes[3].shunned_by_close_group = None;
+        nodes[3].put_record_errors_per_hour = None;
+
+        let medians = flag_outliers(&mut nodes);
+
+        assert_eq!(medians.record_count, Some(1000.0));
+        assert!(nodes.iter().all(|node| node.outliers.is_empty()));
+    }
+}
@@ -403,6 +403,12 @@ pub async fn check_port_availability(
         if let Some(port) = node.node_port {
             all_ports.push(port);
         }
+        if let Some(port) = node.tcp_port {
+            all_ports.push(port);
+        }
+        if let Some(port) = node.ws_port {
+            all_ports.push(port);
+        }
         all_ports.push(node.rpc_socket_addr.port());
     }
 
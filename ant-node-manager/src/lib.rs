@@ -13,8 +13,14 @@ pub mod add_services;
 pub mod cmd;
 pub mod config;
 pub mod error;
+pub mod fleet;
+pub mod healing;
+pub mod health;
 pub mod helpers;
 pub mod local;
+pub mod logs;
+pub mod metrics;
+pub mod node_data;
 pub mod rpc;
 pub mod rpc_client;
 
@@ -27,6 +33,15 @@ pub enum VerbosityLevel {
     Full,
 }
 
+/// How the antnode services are managed.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum ServiceBackend {
+    /// The service manager of the OS, e.g., systemd, launchd or the Windows service manager.
+    ServiceManager,
+    /// A process supervisor run by antctl, which requires neither a service manager nor root.
+    Supervisor,
+}
+
 impl From<u8> for VerbosityLevel {
     fn from(verbosity: u8) -> Self {
         match verbosity {
@@ -49,7 +64,7 @@ use colored::Colorize;
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
 use semver::Version;
-use std::sync::Arc;
+use std::{sync::Arc, time::SystemTime};
 use tracing::debug;
 
 pub const DAEMON_DEFAULT_PORT: u16 = 12500;
@@ -325,12 +340,7 @@ impl<T: ServiceStateActions + Send> ServiceManager<T> {
             &self.service.name().await,
             self.service.is_user_mode().await,
         )?;
-        self.service_control.install(
-            self.service
-                .build_upgrade_install_context(options.clone())
-                .await?,
-            self.service.is_user_mode().await,
-        )?;
+        self.install(options.clone()).await?;
 
         if options.start_service {
             match self.start().await {
@@ -364,6 +374,71 @@ impl<T: ServiceStateActions + Send> ServiceManager<T> {
             ))
         }
     }
+
+    /// Reinstall the service with the settings in its service data, keeping its binary.
+    ///
+    /// The service is started again if it was running.
+    pub async fn reconfigure(
+        &mut self,
+        auto_restart: bool,
+        env_variables: Option<Vec<(String, String)>>,
+    ) -> Result<()> {
+        let service_name = self.service.name().await;
+        let was_running = self.service.status().await == ServiceStatus::Running;
+        info!("Reconfiguring the {service_name} service, which was running: {was_running}");
+
+        self.stop().await?;
+        self.service_control
+            .uninstall(&service_name, self.service.is_user_mode().await)?;
+        let options = UpgradeOptions {
+            auto_restart,
+            env_variables,
+            force: false,
+            start_service: was_running,
+            target_bin_path: self.service.bin_path().await,
+            target_version: Version::parse(&self.service.version().await)?,
+        };
+        self.install(options).await?;
+
+        if was_running {
+            self.start().await?;
+        }
+
+        if self.verbosity != VerbosityLevel::Minimal {
+            println!("{} Reconfigured {service_name}", "✓".green());
+        }
+        Ok(())
+    }
+
+    /// Install the service for the given options, then apply its resource limits, which are not
+    /// part of the service definition the service manager generates.
+    async fn install(&self, options: UpgradeOptions) -> Result<()> {
+        let service_name = self.service.name().await;
+        let user_mode = self.service.is_user_mode().await;
+        self.service_control.install(
+            self.service.build_upgrade_install_context(options).await?,
+            user_mode,
+        )?;
+
+        let limits = self.service.resource_limits().await;
+        if !limits.is_empty() {
+            debug!("Applying resource limits to {service_name}: {limits}");
+            self.service_control
+                .set_resource_limits(&service_name, &limits, user_mode)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the start of the current day, in local time.
+pub(crate) fn start_of_today() -> SystemTime {
+    chrono::Local::now()
+        .date_naive()
+        .and_time(chrono::NaiveTime::MIN)
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(SystemTime::from)
+        .unwrap_or_else(SystemTime::now)
 }
 
 pub async fn status_report(
@@ -416,6 +491,7 @@ pub async fn status_report(
             println!("Data path: {}", node.data_dir_path.to_string_lossy());
             println!("Log path: {}", node.log_dir_path.to_string_lossy());
             println!("Bin path: {}", node.antnode_path.to_string_lossy());
+            println!("Resource limits: {}", node.resource_limits);
             println!(
                 "Connected peers: {}",
                 node.connected_peers
@@ -427,6 +503,23 @@ pub async fn status_report(
                 node.reward_balance
                     .map_or("-".to_string(), |b| b.to_string())
             );
+            if node.status == ServiceStatus::Running {
+                // The ledger is kept by the node itself, so it can only be read while it runs.
+                let mut rpc_client = RpcClient::from_socket_addr(node.rpc_socket_addr);
+                rpc_client.set_max_attempts(1);
+                let today = start_of_today();
+                match rpc_client.earnings(today).await {
+                    Ok(earnings) => {
+                        println!("Lifetime earnings: {}", earnings.lifetime_total);
+                        println!("Earned today: {}", earnings.total_since(today));
+                    }
+                    Err(err) => {
+                        debug!("Failed to obtain earnings of {}: {err}", node.service_name);
+                        println!("Lifetime earnings: -");
+                        println!("Earned today: -");
+                    }
+                }
+            }
             println!("Rewards address: {}", node.rewards_address);
             println!();
         }
@@ -636,7 +729,7 @@ pub fn print_banner(text: &str) {
     println!("╚{top_bottom}╝");
 }
 
-fn format_status(status: &ServiceStatus) -> String {
+pub(crate) fn format_status(status: &ServiceStatus) -> String {
     match status {
         ServiceStatus::Running => "RUNNING".green().to_string(),
         ServiceStatus::Stopped => "STOPPED".red().to_string(),
@@ -661,10 +754,13 @@ mod tests {
     use ant_evm::{AttoTokens, CustomNetwork, EvmNetwork, RewardsAddress};
     use ant_logging::LogFormat;
     use ant_service_management::{
-        UpgradeOptions, UpgradeResult,
+        ResourceLimits, UpgradeOptions, UpgradeResult,
         error::{Error as ServiceControlError, Result as ServiceControlResult},
         node::{NODE_SERVICE_DATA_SCHEMA_LATEST, NodeService, NodeServiceData},
-        rpc::{NetworkInfo, NodeInfo, RecordAddress, RpcActions},
+        rpc::{
+            Earnings, NetworkInfo, NodeInfo, RecordAddress, RecordInfo, RpcActions,
+            StorageChallengeResult,
+        },
     };
     use assert_fs::prelude::*;
     use assert_matches::assert_matches;
@@ -691,6 +787,11 @@ mod tests {
             async fn node_info(&self) -> ServiceControlResult<NodeInfo>;
             async fn network_info(&self) -> ServiceControlResult<NetworkInfo>;
             async fn record_addresses(&self) -> ServiceControlResult<Vec<RecordAddress>>;
+            async fn record_info(&self, key: libp2p::kad::RecordKey) -> ServiceControlResult<RecordInfo>;
+            async fn trigger_replication(&self) -> ServiceControlResult<()>;
+            async fn trigger_cleanup(&self) -> ServiceControlResult<()>;
+            async fn run_storage_challenge(&self) -> ServiceControlResult<Vec<StorageChallengeResult>>;
+            async fn earnings(&self, since: std::time::SystemTime) -> ServiceControlResult<Earnings>;
             async fn node_restart(&self, delay_millis: u64, retain_peer_id: bool) -> ServiceControlResult<()>;
             async fn node_stop(&self, delay_millis: u64) -> ServiceControlResult<()>;
             async fn node_update(&self, delay_millis: u64) -> ServiceControlResult<()>;
@@ -705,6 +806,7 @@ mod tests {
             fn create_service_user(&self, username: &str) -> ServiceControlResult<()>;
             fn get_available_port(&self) -> ServiceControlResult<u16>;
             fn install(&self, install_ctx: ServiceInstallCtx, user_mode: bool) -> ServiceControlResult<()>;
+            fn set_resource_limits(&self, service_name: &str, limits: &ResourceLimits, user_mode: bool) -> ServiceControlResult<()>;
             fn get_process_pid(&self, bin_path: &Path) -> ServiceControlResult<u32>;
             fn start(&self, service_name: &str, user_mode: bool) -> ServiceControlResult<()>;
             fn stop(&self, service_name: &str, user_mode: bool) -> ServiceControlResult<()>;
@@ -772,6 +874,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -799,6 +902,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -887,6 +1002,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -916,6 +1032,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -967,6 +1095,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -996,6 +1125,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -1087,6 +1228,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1116,6 +1258,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -1180,6 +1334,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1207,6 +1362,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -1285,6 +1452,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1312,6 +1480,18 @@ mod tests {
             user_mode: true,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -1389,6 +1569,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1416,6 +1597,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client))
@@ -1463,6 +1656,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1492,6 +1686,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -1527,6 +1733,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1554,6 +1761,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -1591,6 +1810,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1620,6 +1840,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -1658,6 +1890,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1685,6 +1918,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -1736,6 +1981,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             listen_addr: None,
             initial_peers_config: InitialPeersConfig::default(),
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1765,6 +2011,18 @@ mod tests {
             user_mode: true,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -1877,6 +2135,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -1906,6 +2165,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -1979,6 +2250,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -2008,6 +2280,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -2128,6 +2412,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -2157,6 +2442,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -2287,6 +2584,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -2316,6 +2614,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -2438,6 +2748,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -2467,6 +2778,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -2594,6 +2917,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -2623,6 +2947,18 @@ mod tests {
             user_mode: true,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -2775,6 +3111,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig {
                 first: true,
                 addrs: vec![],
@@ -2811,6 +3148,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -2951,6 +3300,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config:  InitialPeersConfig {
                 first: false,
                 addrs: vec![
@@ -2989,6 +3339,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -3127,6 +3489,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -3156,6 +3519,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -3293,6 +3668,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig {
                 first: false,
                 addrs: vec![],
@@ -3329,6 +3705,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -3467,6 +3855,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig {
                 first: false,
                 addrs: vec![],
@@ -3506,6 +3895,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -3646,6 +4047,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig {
                 first: false,
                 addrs: vec![],
@@ -3682,6 +4084,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -3820,6 +4234,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig {
                 first: false,
                 addrs: vec![],
@@ -3858,6 +4273,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -4000,6 +4427,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -4029,6 +4457,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -4167,6 +4607,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -4196,6 +4637,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -4334,6 +4787,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: true,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -4363,6 +4817,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -4501,6 +4967,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -4530,6 +4997,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -4668,6 +5147,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -4697,6 +5177,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -4834,6 +5326,7 @@ mod tests {
             connected_peers: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -4864,6 +5357,18 @@ mod tests {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -5001,6 +5506,7 @@ mod tests {
             connected_peers: None,
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -5031,6 +5537,18 @@ mod tests {
                 "0x03B770D9cD32077cC0bF330c13C114a87643B124",
             )?,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -5169,6 +5687,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -5198,6 +5717,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -5336,6 +5867,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -5365,6 +5897,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -5504,6 +6048,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -5533,6 +6078,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -5683,6 +6240,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -5713,6 +6271,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -5863,6 +6433,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -5893,6 +6464,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -6032,6 +6615,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -6061,6 +6645,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client))
@@ -6200,6 +6796,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
             log_format: None,
@@ -6229,6 +6826,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: true,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
@@ -6256,6 +6865,105 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn reconfigure_should_reinstall_a_stopped_service_with_its_settings() -> Result<()> {
+        let tmp_data_dir = assert_fs::TempDir::new()?;
+        let current_node_bin = tmp_data_dir.child("antnode");
+        current_node_bin.write_binary(b"fake antnode binary")?;
+
+        let mut mock_service_control = MockServiceControl::new();
+        let mock_rpc_client = MockRpcClient::new();
+
+        mock_service_control.expect_stop().times(0);
+        mock_service_control
+            .expect_uninstall()
+            .with(eq("antnode1"), eq(false))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_service_control
+            .expect_install()
+            .withf(|install_ctx, user_mode| {
+                install_ctx.args.contains(&OsString::from("--relay"))
+                    && install_ctx.environment
+                        == Some(vec![("ANT_LOG".to_string(), "all".to_string())])
+                    && !*user_mode
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_service_control.expect_start().times(0);
+
+        let service_data = NodeServiceData {
+            alpha: false,
+            auto_restart: false,
+            connected_peers: None,
+            data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
+            evm_network: EvmNetwork::ArbitrumOne,
+            relay: true,
+            resource_limits: ResourceLimits::default(),
+            initial_peers_config: InitialPeersConfig::default(),
+            listen_addr: None,
+            log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
+            log_format: None,
+            max_archived_log_files: None,
+            max_log_files: None,
+            metrics_port: None,
+            network_id: None,
+            node_ip: None,
+            node_port: None,
+            number: 1,
+            peer_id: None,
+            pid: None,
+            rewards_address: RewardsAddress::from_str(
+                "0x03B770D9cD32077cC0bF330c13C114a87643B124",
+            )?,
+            reward_balance: Some(AttoTokens::zero()),
+            rpc_socket_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081),
+            antnode_path: current_node_bin.to_path_buf(),
+            schema_version: NODE_SERVICE_DATA_SCHEMA_LATEST,
+            service_name: "antnode1".to_string(),
+            status: ServiceStatus::Stopped,
+            no_upnp: false,
+            user: Some("ant".to_string()),
+            user_mode: false,
+            version: "0.98.1".to_string(),
+            write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
+        };
+        let service_data = Arc::new(RwLock::new(service_data));
+        let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
+
+        let mut service_manager = ServiceManager::new(
+            service,
+            Box::new(mock_service_control),
+            VerbosityLevel::Normal,
+        );
+
+        service_manager
+            .reconfigure(
+                false,
+                Some(vec![("ANT_LOG".to_string(), "all".to_string())]),
+            )
+            .await?;
+
+        let service_data = service_data.read().await;
+        assert_eq!(service_data.version, "0.98.1");
+        assert_matches!(service_data.status, ServiceStatus::Stopped);
+        current_node_bin.assert(predicate::path::is_file());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn remove_should_remove_an_added_node() -> Result<()> {
         let temp_dir = assert_fs::TempDir::new()?;
@@ -6288,6 +6996,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: log_dir.to_path_buf(),
@@ -6315,6 +7024,18 @@ mod tests {
             user: Some("ant".to_string()),
             user_mode: false,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -6358,6 +7079,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -6387,6 +7109,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -6445,6 +7179,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
@@ -6474,6 +7209,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -6527,6 +7274,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: log_dir.to_path_buf(),
@@ -6554,6 +7302,18 @@ mod tests {
             user_mode: false,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -6605,6 +7365,7 @@ mod tests {
                 )?,
             }),
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: Default::default(),
             listen_addr: None,
             log_dir_path: log_dir.to_path_buf(),
@@ -6632,6 +7393,18 @@ mod tests {
             user_mode: true,
             version: "0.98.1".to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(MockRpcClient::new()));
@@ -6761,6 +7534,7 @@ mod tests {
             data_dir_path: PathBuf::from("/var/antctl/services/antnode1"),
             evm_network: EvmNetwork::ArbitrumOne,
             relay: false,
+            resource_limits: ResourceLimits::default(),
             initial_peers_config: InitialPeersConfig {
                 first: false,
                 addrs: vec![],
@@ -6797,6 +7571,18 @@ mod tests {
             user_mode: false,
             version: current_version.to_string(),
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
         let service_data = Arc::new(RwLock::new(service_data));
         let service = NodeService::new(Arc::clone(&service_data), Box::new(mock_rpc_client));
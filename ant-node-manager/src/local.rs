@@ -17,7 +17,7 @@ use ant_logging::LogFormat;
 use ant_service_management::NodeRegistryManager;
 use ant_service_management::node::NODE_SERVICE_DATA_SCHEMA_LATEST;
 use ant_service_management::{
-    NodeServiceData, ServiceStatus,
+    NodeServiceData, ResourceLimits, ServiceStatus,
     control::ServiceControl,
     rpc::{RpcActions, RpcClient},
 };
@@ -422,6 +422,7 @@ pub async fn run_node(
         data_dir_path: node_info.data_path,
         evm_network: run_options.evm_network,
         relay: false,
+        resource_limits: ResourceLimits::default(),
         initial_peers_config: InitialPeersConfig {
             first: run_options.first,
             addrs: vec![],
@@ -453,6 +454,18 @@ pub async fn run_node(
         user_mode: false,
         version: run_options.version.to_string(),
         write_older_cache_files: false,
+        quiet_hours: None,
+        evm_config: None,
+        throttle_cpu_usage: None,
+        throttle_memory_usage: None,
+        throttled_download_rate: None,
+        throttled_upload_rate: None,
+        max_download_rate: None,
+        max_peer_download_rate: None,
+        max_peer_upload_rate: None,
+        max_upload_rate: None,
+        tcp_port: None,
+        ws_port: None,
     })
 }
 
@@ -524,7 +537,10 @@ mod tests {
     use ant_evm::utils::dummy_address;
     use ant_service_management::{
         error::Result as RpcResult,
-        rpc::{NetworkInfo, NodeInfo, RecordAddress, RpcActions},
+        rpc::{
+            Earnings, NetworkInfo, NodeInfo, RecordAddress, RecordInfo, RpcActions,
+            StorageChallengeResult,
+        },
     };
     use async_trait::async_trait;
     use evmlib::CustomNetwork;
@@ -540,6 +556,11 @@ mod tests {
             async fn node_info(&self) -> RpcResult<NodeInfo>;
             async fn network_info(&self) -> RpcResult<NetworkInfo>;
             async fn record_addresses(&self) -> RpcResult<Vec<RecordAddress>>;
+            async fn record_info(&self, key: libp2p::kad::RecordKey) -> RpcResult<RecordInfo>;
+            async fn trigger_replication(&self) -> RpcResult<()>;
+            async fn trigger_cleanup(&self) -> RpcResult<()>;
+            async fn run_storage_challenge(&self) -> RpcResult<Vec<StorageChallengeResult>>;
+            async fn earnings(&self, since: std::time::SystemTime) -> RpcResult<Earnings>;
             async fn node_restart(&self, delay_millis: u64, retain_peer_id: bool) -> RpcResult<()>;
             async fn node_stop(&self, delay_millis: u64) -> RpcResult<()>;
             async fn node_update(&self, delay_millis: u64) -> RpcResult<()>;
@@ -0,0 +1,620 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Reading the logs of nodes.
+//!
+//! A node logs to `antnode.log` in its log directory. When that file is full, `ant-logging` renames
+//! it with a timestamp suffix, e.g. `antnode.log.20250101T120000`, and starts a new one; older
+//! archives are compressed with a `.gz` extension. Lines are in either the default format, e.g.
+//! `[2025-01-01T12:00:00.123456Z INFO ant_node::node 123] Message`, or the JSON format, which has
+//! `timestamp`, `level`, `target` and `message` fields.
+
+use chrono::{DateTime, Duration, Utc};
+use color_eyre::{Result, eyre::eyre};
+use flate2::read::MultiGzDecoder;
+use std::{
+    fs::{File, Metadata},
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tracing::Level;
+
+const CURRENT_LOG_FILE_EXTENSION: &str = ".log";
+const ARCHIVED_LOG_FILE_MARKER: &str = ".log.";
+const COMPRESSED_LOG_FILE_EXTENSION: &str = ".gz";
+
+/// An entry in the log of a node.
+///
+/// Lines that do not start an entry, such as the rest of a multi-line message, belong to the entry
+/// before them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    pub service_name: String,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub level: Option<Level>,
+    pub target: Option<String>,
+    pub message: String,
+    /// The entry as it appears in the file.
+    pub raw: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LogFilter {
+    /// Only entries containing this text.
+    pub grep: Option<String>,
+    /// Only entries at this level or a more severe one.
+    pub level: Option<Level>,
+    /// Only entries logged at or after this time.
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(grep) = &self.grep
+            && !entry.raw.contains(grep.as_str())
+        {
+            return false;
+        }
+        // In `tracing`, a more verbose level is a greater one.
+        if let Some(level) = self.level
+            && entry.level.is_none_or(|entry_level| entry_level > level)
+        {
+            return false;
+        }
+        if let Some(since) = self.since
+            && entry.timestamp.is_none_or(|timestamp| timestamp < since)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// Parse the start of a time window, given either as a duration before now, such as '30s', '10m',
+/// '2h' or '1d', or as an RFC 3339 timestamp.
+pub fn parse_since(s: &str) -> Result<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(s) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+
+    let (amount, unit) = s.split_at(s.len().saturating_sub(1));
+    let amount = amount
+        .parse::<i64>()
+        .map_err(|_| eyre!("'{s}' is not a duration such as '10m' or an RFC 3339 timestamp"))?;
+    let duration = match unit {
+        "s" => Duration::seconds(amount),
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        _ => {
+            return Err(eyre!(
+                "The unit of '{s}' must be one of 's', 'm', 'h' or 'd'"
+            ));
+        }
+    };
+    Ok(Utc::now() - duration)
+}
+
+/// Merge the entries of several nodes chronologically.
+///
+/// The entries of each node are expected to be in order already, and keep that order.
+pub fn merge_entries(entries: Vec<Vec<LogEntry>>) -> Vec<LogEntry> {
+    let mut merged = entries.into_iter().flatten().collect::<Vec<_>>();
+    merged.sort_by_key(|entry| entry.timestamp);
+    merged
+}
+
+/// The log files in the directory, oldest first, with the file currently being written last.
+pub fn get_log_files(log_dir_path: &Path) -> Result<Vec<PathBuf>> {
+    if !log_dir_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut current = Vec::new();
+    let mut archived = Vec::new();
+    for entry in std::fs::read_dir(log_dir_path)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.ends_with(CURRENT_LOG_FILE_EXTENSION) {
+            current.push(entry.path());
+        } else if let Some((_, suffix)) = file_name.split_once(ARCHIVED_LOG_FILE_MARKER) {
+            // The suffix is a timestamp, whose lexical and chronological orders are the same, and
+            // a number when two files were archived within the same second.
+            let suffix = suffix
+                .strip_suffix(COMPRESSED_LOG_FILE_EXTENSION)
+                .unwrap_or(suffix);
+            let (timestamp, number) = match suffix.split_once('.') {
+                Some((timestamp, number)) => (timestamp, number.parse::<u32>().unwrap_or(0)),
+                None => (suffix, 0),
+            };
+            archived.push(((timestamp.to_string(), number), entry.path()));
+        }
+    }
+
+    archived.sort();
+    current.sort();
+    Ok(archived
+        .into_iter()
+        .map(|(_, path)| path)
+        .chain(current)
+        .collect())
+}
+
+/// Reads the logs of a node, and follows them as they are written.
+pub struct NodeLogReader {
+    service_name: String,
+    log_dir_path: PathBuf,
+    /// The file currently being written, with its metadata and how far it has been read.
+    current: Option<(PathBuf, Metadata, u64)>,
+    /// The entry most recently read, which a continuation line at the start of the next read
+    /// belongs to.
+    last_entry: Option<LogEntry>,
+}
+
+impl NodeLogReader {
+    /// The reader starts at the end of the logs: the history is read with `read_history` and
+    /// anything written afterwards with `read_new`.
+    pub fn new(service_name: &str, log_dir_path: &Path) -> Result<Self> {
+        let mut reader = Self {
+            service_name: service_name.to_string(),
+            log_dir_path: log_dir_path.to_path_buf(),
+            current: None,
+            last_entry: None,
+        };
+        if let Some(path) = reader.current_log_file()? {
+            let metadata = std::fs::metadata(&path)?;
+            let len = metadata.len();
+            reader.current = Some((path, metadata, len));
+        }
+        Ok(reader)
+    }
+
+    /// The entries written before the reader was created that match the filter, oldest first.
+    ///
+    /// If a limit is given, only that many of the most recent entries are returned, and archives
+    /// older than those entries are not read.
+    pub fn read_history(&self, filter: &LogFilter, limit: Option<usize>) -> Result<Vec<LogEntry>> {
+        let files = get_log_files(&self.log_dir_path)?;
+        let mut history: Vec<Vec<LogEntry>> = Vec::new();
+        let mut count = 0;
+        for path in files.iter().rev() {
+            // Anything written to the current file after the reader was created is left to
+            // `read_new`.
+            let contents = match &self.current {
+                Some((current_path, _, offset)) if current_path == path => {
+                    read_file_start(path, *offset)?
+                }
+                _ => read_file(path)?,
+            };
+            let entries = parse_entries(&self.service_name, &contents, None);
+            let is_before_window = match (filter.since, entries.first()) {
+                (Some(since), Some(first)) => first.timestamp.is_some_and(|ts| ts < since),
+                _ => false,
+            };
+
+            let entries = entries
+                .into_iter()
+                .filter(|entry| filter.matches(entry))
+                .collect::<Vec<_>>();
+            count += entries.len();
+            history.push(entries);
+
+            if is_before_window || limit.is_some_and(|limit| count >= limit) {
+                break;
+            }
+        }
+
+        let mut entries = history.into_iter().rev().flatten().collect::<Vec<_>>();
+        if let Some(limit) = limit {
+            let excess = entries.len().saturating_sub(limit);
+            let _ = entries.drain(..excess);
+        }
+        Ok(entries)
+    }
+
+    /// The entries written since the reader was created or last called that match the filter.
+    ///
+    /// A line is only read once it is complete. If the file was archived in the meantime, the rest
+    /// of it is read before the new one.
+    pub fn read_new(&mut self, filter: &LogFilter) -> Result<Vec<LogEntry>> {
+        let Some(path) = self.current_log_file()? else {
+            return Ok(Vec::new());
+        };
+        let metadata = std::fs::metadata(&path)?;
+
+        let mut contents = String::new();
+        let offset = match self.current.take() {
+            Some((previous_path, previous_metadata, offset))
+                if previous_path == path && is_same_file(&previous_metadata, &metadata) =>
+            {
+                if metadata.len() < offset {
+                    0
+                } else {
+                    offset
+                }
+            }
+            Some((_, _, offset)) => {
+                // The file was archived, so finish reading it under its new name, which is the
+                // most recent archive. It is not compressed until later rotations.
+                let files = get_log_files(&self.log_dir_path)?;
+                if let Some(archived) = files.iter().rev().find(|file| **file != path)
+                    && !archived
+                        .to_string_lossy()
+                        .ends_with(COMPRESSED_LOG_FILE_EXTENSION)
+                {
+                    contents.push_str(&read_complete_lines(archived, offset)?.0);
+                }
+                0
+            }
+            None => 0,
+        };
+
+        let (new_contents, read) = read_complete_lines(&path, offset)?;
+        contents.push_str(&new_contents);
+        self.current = Some((path, metadata, offset + read));
+
+        let entries = parse_entries(&self.service_name, &contents, self.last_entry.as_ref());
+        if let Some(last) = entries.last() {
+            self.last_entry = Some(last.clone());
+        }
+        Ok(entries
+            .into_iter()
+            .filter(|entry| filter.matches(entry))
+            .collect())
+    }
+
+    fn current_log_file(&self) -> Result<Option<PathBuf>> {
+        let files = get_log_files(&self.log_dir_path)?;
+        Ok(files
+            .into_iter()
+            .rev()
+            .find(|path| path.to_string_lossy().ends_with(CURRENT_LOG_FILE_EXTENSION)))
+    }
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Metadata, b: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+#[cfg(not(unix))]
+fn is_same_file(a: &Metadata, b: &Metadata) -> bool {
+    match (a.created(), b.created()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => true,
+    }
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    let mut bytes = Vec::new();
+    let mut file = File::open(path)?;
+    if path
+        .to_string_lossy()
+        .ends_with(COMPRESSED_LOG_FILE_EXTENSION)
+    {
+        let _ = MultiGzDecoder::new(file).read_to_end(&mut bytes)?;
+    } else {
+        let _ = file.read_to_end(&mut bytes)?;
+    }
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+fn read_file_start(path: &Path, len: u64) -> Result<String> {
+    let mut bytes = Vec::new();
+    let _ = File::open(path)?.take(len).read_to_end(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+/// Read the complete lines from the offset, returning them and the number of bytes they span.
+fn read_complete_lines(path: &Path, offset: u64) -> Result<(String, u64)> {
+    let mut file = File::open(path)?;
+    let _ = file.seek(SeekFrom::Start(offset))?;
+    let mut bytes = Vec::new();
+    let _ = file.read_to_end(&mut bytes)?;
+    let complete = bytes
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+    bytes.truncate(complete);
+    Ok((String::from_utf8_lossy(&bytes).to_string(), complete as u64))
+}
+
+/// Parse log contents into entries. A continuation line at the start belongs to `previous`, if
+/// given, so it takes its timestamp and level.
+fn parse_entries(service_name: &str, contents: &str, previous: Option<&LogEntry>) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in contents.lines() {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(mut entry) = parse_default_line(line).or_else(|| parse_json_line(line)) {
+            entry.service_name = service_name.to_string();
+            entries.push(entry);
+            continue;
+        }
+
+        match entries.last_mut() {
+            Some(entry) => {
+                entry.message.push('\n');
+                entry.message.push_str(line);
+                entry.raw.push('\n');
+                entry.raw.push_str(line);
+            }
+            None => entries.push(LogEntry {
+                service_name: service_name.to_string(),
+                timestamp: previous.and_then(|entry| entry.timestamp),
+                level: previous.and_then(|entry| entry.level),
+                target: previous.and_then(|entry| entry.target.clone()),
+                message: line.to_string(),
+                raw: line.to_string(),
+            }),
+        }
+    }
+    entries
+}
+
+/// Parse a line in the default format, e.g.
+/// `[2025-01-01T12:00:00.123456Z INFO ant_node::node 123/span] Message`.
+fn parse_default_line(line: &str) -> Option<LogEntry> {
+    let (header, message) = line.strip_prefix('[')?.split_once(']')?;
+    let mut parts = header.split(' ');
+    let timestamp = DateTime::<Utc>::from_str(parts.next()?).ok()?;
+    let level = Level::from_str(parts.next()?).ok()?;
+    let target = parts.next().map(|target| target.to_string());
+    Some(LogEntry {
+        service_name: String::new(),
+        timestamp: Some(timestamp),
+        level: Some(level),
+        target,
+        message: message.strip_prefix(' ').unwrap_or(message).to_string(),
+        raw: line.to_string(),
+    })
+}
+
+fn parse_json_line(line: &str) -> Option<LogEntry> {
+    if !line.starts_with('{') {
+        return None;
+    }
+    let value = serde_json::from_str::<serde_json::Value>(line).ok()?;
+    let timestamp = value
+        .get("timestamp")
+        .and_then(|timestamp| timestamp.as_str())
+        .and_then(|timestamp| DateTime::<Utc>::from_str(timestamp).ok());
+    let level = value
+        .get("level")
+        .and_then(|level| level.as_str())
+        .and_then(|level| Level::from_str(level).ok());
+    let target = value
+        .get("target")
+        .and_then(|target| target.as_str())
+        .map(|target| target.to_string());
+    let message = value
+        .get("message")
+        .and_then(|message| message.as_str())
+        .unwrap_or_default()
+        .to_string();
+    Some(LogEntry {
+        service_name: String::new(),
+        timestamp,
+        level,
+        target,
+        message,
+        raw: line.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+
+    fn write_log(path: &Path, lines: &[&str]) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        for line in lines {
+            writeln!(file, "{line}").unwrap();
+        }
+    }
+
+    #[test]
+    fn parse_entries_should_understand_both_formats_and_multi_line_messages() {
+        let contents = concat!(
+            "[2025-01-01T12:00:00.000001Z INFO ant_node::node 10] Node started\n",
+            "[2025-01-01T12:00:01.000001Z ERROR ant_node::node 20/span] Failed:\n",
+            "  caused by: disk full\n",
+            r#"{"timestamp":"2025-01-01T12:00:02.000001Z","level":"WARN","message":"Low space","target":"ant_node::node"}"#,
+            "\n"
+        );
+
+        let entries = parse_entries("antnode1", contents, None);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].level, Some(Level::INFO));
+        assert_eq!(entries[0].target.as_deref(), Some("ant_node::node"));
+        assert_eq!(entries[0].message, "Node started");
+        assert_eq!(entries[1].message, "Failed:\n  caused by: disk full");
+        assert_eq!(entries[2].level, Some(Level::WARN));
+        assert_eq!(entries[2].message, "Low space");
+        assert_eq!(
+            entries[2].timestamp,
+            Some(DateTime::<Utc>::from_str("2025-01-01T12:00:02.000001Z").unwrap())
+        );
+        assert!(entries.iter().all(|entry| entry.service_name == "antnode1"));
+    }
+
+    #[test]
+    fn filter_should_apply_level_since_and_grep() {
+        let contents = concat!(
+            "[2025-01-01T12:00:00Z DEBUG ant_node::node 10] Connected to peer\n",
+            "[2025-01-01T12:00:01Z WARN ant_node::node 10] Peer dropped\n",
+            "[2025-01-01T12:00:02Z ERROR ant_node::node 10] Disk full\n",
+        );
+        let entries = parse_entries("antnode1", contents, None);
+
+        let filter = LogFilter {
+            level: Some(Level::WARN),
+            ..Default::default()
+        };
+        assert_eq!(
+            entries.iter().filter(|entry| filter.matches(entry)).count(),
+            2
+        );
+
+        let filter = LogFilter {
+            since: Some(DateTime::<Utc>::from_str("2025-01-01T12:00:02Z").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            entries.iter().filter(|entry| filter.matches(entry)).count(),
+            1
+        );
+
+        let filter = LogFilter {
+            grep: Some("Peer".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            entries.iter().filter(|entry| filter.matches(entry)).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn parse_since_should_accept_durations_and_timestamps() {
+        let since = parse_since("10m").unwrap();
+        let expected = Utc::now() - Duration::minutes(10);
+        assert!((since - expected).num_seconds().abs() < 5);
+
+        assert_eq!(
+            parse_since("2025-01-01T12:00:00Z").unwrap(),
+            DateTime::<Utc>::from_str("2025-01-01T12:00:00Z").unwrap()
+        );
+        assert!(parse_since("10x").is_err());
+        assert!(parse_since("m").is_err());
+    }
+
+    #[test]
+    fn read_history_should_include_archived_files_in_order() {
+        let dir = TempDir::new().unwrap();
+        let gz_path = dir.path().join("antnode.log.20250101T110000.gz");
+        let mut encoder = GzEncoder::new(File::create(&gz_path).unwrap(), Compression::default());
+        encoder
+            .write_all(b"[2025-01-01T10:00:00Z INFO ant_node 1] First\n")
+            .unwrap();
+        let _ = encoder.finish().unwrap();
+        write_log(
+            &dir.path().join("antnode.log.20250101T120000"),
+            &["[2025-01-01T11:00:00Z INFO ant_node 1] Second"],
+        );
+        write_log(
+            &dir.path().join("antnode.log"),
+            &[
+                "[2025-01-01T12:00:00Z INFO ant_node 1] Third",
+                "[2025-01-01T12:00:01Z INFO ant_node 1] Fourth",
+            ],
+        );
+
+        let reader = NodeLogReader::new("antnode1", dir.path()).unwrap();
+        let messages = |entries: Vec<LogEntry>| {
+            entries
+                .into_iter()
+                .map(|entry| entry.message)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            messages(reader.read_history(&LogFilter::default(), None).unwrap()),
+            vec!["First", "Second", "Third", "Fourth"]
+        );
+        assert_eq!(
+            messages(reader.read_history(&LogFilter::default(), Some(2)).unwrap()),
+            vec!["Third", "Fourth"]
+        );
+    }
+
+    #[test]
+    fn read_new_should_follow_the_log_across_rotation() {
+        let dir = TempDir::new().unwrap();
+        let current_path = dir.path().join("antnode.log");
+        write_log(
+            &current_path,
+            &["[2025-01-01T12:00:00Z INFO ant_node 1] Old"],
+        );
+
+        let mut reader = NodeLogReader::new("antnode1", dir.path()).unwrap();
+        let filter = LogFilter::default();
+        assert!(reader.read_new(&filter).unwrap().is_empty());
+
+        // A partial line is not read until it is complete.
+        write_log(
+            &current_path,
+            &["[2025-01-01T12:00:01Z INFO ant_node 1] New"],
+        );
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&current_path)
+            .unwrap();
+        write!(file, "[2025-01-01T12:00:02Z INFO ant_node 1] Befo").unwrap();
+        let entries = reader.read_new(&filter).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message, "New");
+
+        writeln!(file, "re rotation").unwrap();
+        drop(file);
+        std::fs::rename(
+            &current_path,
+            dir.path().join("antnode.log.20250101T120003"),
+        )
+        .unwrap();
+        write_log(
+            &current_path,
+            &["[2025-01-01T12:00:04Z INFO ant_node 1] After rotation"],
+        );
+
+        let entries = reader.read_new(&filter).unwrap();
+        let messages = entries
+            .iter()
+            .map(|entry| entry.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["Before rotation", "After rotation"]);
+    }
+
+    #[test]
+    fn merge_entries_should_order_the_entries_of_nodes_by_time() {
+        let first = parse_entries(
+            "antnode1",
+            "[2025-01-01T12:00:00Z INFO ant_node 1] a\n[2025-01-01T12:00:02Z INFO ant_node 1] c\n",
+            None,
+        );
+        let second = parse_entries(
+            "antnode2",
+            "[2025-01-01T12:00:01Z INFO ant_node 1] b\n[2025-01-01T12:00:03Z INFO ant_node 1] d\n",
+            None,
+        );
+
+        let merged = merge_entries(vec![first, second]);
+        let merged = merged
+            .iter()
+            .map(|entry| format!("{} {}", entry.service_name, entry.message))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            merged,
+            vec!["antnode1 a", "antnode2 b", "antnode1 c", "antnode2 d"]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Reading the metrics of node services from their metrics servers.
+
+use color_eyre::Result;
+use prometheus_parse::{Sample, Scrape, Value};
+use std::time::Duration;
+
+pub const PUT_RECORD_ERRORS_METRIC: &str = "ant_node_put_record_err_v2";
+pub const RELAY_RESERVATION_HEALTH_METRIC: &str = "ant_networking_relay_reservation_health";
+pub const SHUNNED_BY_CLOSE_GROUP_METRIC: &str = "ant_networking_shunned_by_close_group";
+
+const METRICS_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The metrics a node exposed at the time they were fetched.
+#[derive(Clone, Debug, Default)]
+pub struct NodeMetrics {
+    samples: Vec<Sample>,
+}
+
+impl NodeMetrics {
+    pub fn parse(body: &str) -> Result<Self> {
+        let lines = body.lines().map(|line| Ok(line.to_owned()));
+        let scrape = Scrape::parse(lines)?;
+        Ok(Self {
+            samples: scrape.samples,
+        })
+    }
+
+    /// The value of a counter or gauge, summed across its labels.
+    ///
+    /// Counters are exposed with a `_total` suffix, which can be left out of the name.
+    pub fn value(&self, name: &str) -> Option<f64> {
+        self.values(name)
+            .map(|(_, value)| value)
+            .reduce(|total, value| total + value)
+    }
+
+    /// The value of a counter or gauge for each value of the given label.
+    pub fn values_by_label(&self, name: &str, label: &str) -> Vec<(String, f64)> {
+        self.values(name)
+            .filter_map(|(sample, value)| {
+                sample
+                    .labels
+                    .get(label)
+                    .map(|label| (label.to_string(), value))
+            })
+            .collect()
+    }
+
+    fn values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = (&'a Sample, f64)> + 'a {
+        self.samples
+            .iter()
+            .filter(move |sample| {
+                sample.metric == name
+                    || sample
+                        .metric
+                        .strip_prefix(name)
+                        .is_some_and(|suffix| suffix == "_total")
+            })
+            .filter_map(|sample| match sample.value {
+                Value::Counter(value) | Value::Gauge(value) | Value::Untyped(value) => {
+                    Some((sample, value))
+                }
+                _ => None,
+            })
+    }
+}
+
+/// Fetch the metrics of the node with its metrics server on the given port.
+pub async fn fetch_node_metrics(metrics_port: u16) -> Result<NodeMetrics> {
+    let client = reqwest::Client::builder()
+        .timeout(METRICS_REQUEST_TIMEOUT)
+        .build()?;
+    let body = client
+        .get(format!("http://localhost:{metrics_port}/metrics"))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    NodeMetrics::parse(&body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METRICS: &str = r#"# HELP ant_networking_shunned_by_close_group The number of close group peers that have shunned our node.
+# TYPE ant_networking_shunned_by_close_group gauge
+ant_networking_shunned_by_close_group 2
+# HELP ant_node_put_record_err_v2 Number of errors during record PUTs.
+# TYPE ant_node_put_record_err_v2 counter
+ant_node_put_record_err_v2_total{error="OutdatedRecordCounter"} 3
+ant_node_put_record_err_v2_total{error="RecordNotStored"} 4
+# EOF
+"#;
+
+    #[test]
+    fn value_should_read_a_gauge() {
+        let metrics = NodeMetrics::parse(METRICS).unwrap();
+
+        assert_eq!(
+            metrics.value("ant_networking_shunned_by_close_group"),
+            Some(2.0)
+        );
+    }
+
+    #[test]
+    fn value_should_sum_a_counter_across_its_labels() {
+        let metrics = NodeMetrics::parse(METRICS).unwrap();
+
+        assert_eq!(metrics.value("ant_node_put_record_err_v2"), Some(7.0));
+        assert_eq!(
+            metrics.values_by_label("ant_node_put_record_err_v2", "error"),
+            vec![
+                ("OutdatedRecordCounter".to_string(), 3.0),
+                ("RecordNotStored".to_string(), 4.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn value_should_be_none_for_a_missing_metric() {
+        let metrics = NodeMetrics::parse(METRICS).unwrap();
+
+        assert_eq!(metrics.value("ant_networking_shunned_by_close"), None);
+    }
+}
@@ -0,0 +1,383 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Moving the data directory of a node, which holds its keypair, record store and quoting metrics.
+//!
+//! A directory is moved to another drive by copying and verifying it, and to another machine in a
+//! node archive: a gzipped tarball with the fleet config of the node and its data directory.
+
+use crate::fleet::FleetConfig;
+use color_eyre::{
+    Result,
+    eyre::{bail, eyre},
+};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use libp2p_identity::{Keypair, PeerId, ed25519};
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::{Component, Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+/// The file within the data directory of a node that holds its keypair.
+pub const SECRET_KEY_FILE_NAME: &str = "secret-key";
+const ARCHIVE_CONFIG_FILE_NAME: &str = "node.toml";
+const ARCHIVE_DATA_DIR_NAME: &str = "data";
+const COMPARE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The peer ID of the node whose data directory is given, if it has a keypair.
+pub fn read_peer_id(data_dir_path: &Path) -> Result<Option<PeerId>> {
+    let secret_key_path = data_dir_path.join(SECRET_KEY_FILE_NAME);
+    if !secret_key_path.exists() {
+        return Ok(None);
+    }
+    let mut bytes = std::fs::read(&secret_key_path)?;
+    let secret_key = ed25519::SecretKey::try_from_bytes(&mut bytes)
+        .map_err(|err| eyre!("The keypair at {secret_key_path:?} is not valid: {err}"))?;
+    let keypair: Keypair = ed25519::Keypair::from(secret_key).into();
+    Ok(Some(keypair.public().to_peer_id()))
+}
+
+/// Copy the contents of a directory into another, which must not exist or be empty.
+///
+/// The given files of the source are skipped. Returns the number of bytes copied.
+pub fn copy_dir(src: &Path, dest: &Path, skip: &[PathBuf]) -> Result<u64> {
+    if dest.exists() && dest.read_dir()?.next().is_some() {
+        bail!("The directory {dest:?} is not empty");
+    }
+    debug!("Copying the directory {src:?} to {dest:?}");
+
+    let mut bytes_copied = 0;
+    std::fs::create_dir_all(dest)?;
+    for entry in WalkDir::new(src).min_depth(1) {
+        let entry = entry?;
+        if skip.iter().any(|path| path == entry.path()) {
+            continue;
+        }
+        let dest_path = dest.join(entry.path().strip_prefix(src)?);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&dest_path)?;
+        } else if entry.file_type().is_file() {
+            bytes_copied += std::fs::copy(entry.path(), &dest_path)?;
+        } else {
+            warn!("Skipping {:?}, which is not a file", entry.path());
+        }
+    }
+    Ok(bytes_copied)
+}
+
+/// Check that every file of the source directory has an identical copy in the destination.
+pub fn verify_copy(src: &Path, dest: &Path, skip: &[PathBuf]) -> Result<()> {
+    for entry in WalkDir::new(src).min_depth(1) {
+        let entry = entry?;
+        if !entry.file_type().is_file() || skip.iter().any(|path| path == entry.path()) {
+            continue;
+        }
+        let dest_path = dest.join(entry.path().strip_prefix(src)?);
+        if !dest_path.is_file() {
+            bail!("{dest_path:?} is missing from the copy");
+        }
+        if !files_are_equal(entry.path(), &dest_path)? {
+            bail!("{dest_path:?} differs from {:?}", entry.path());
+        }
+    }
+    Ok(())
+}
+
+fn files_are_equal(a: &Path, b: &Path) -> Result<bool> {
+    if std::fs::metadata(a)?.len() != std::fs::metadata(b)?.len() {
+        return Ok(false);
+    }
+    let mut a = BufReader::new(File::open(a)?);
+    let mut b = BufReader::new(File::open(b)?);
+    let mut a_buffer = vec![0; COMPARE_BUFFER_SIZE];
+    let mut b_buffer = vec![0; COMPARE_BUFFER_SIZE];
+    loop {
+        let read = a.read(&mut a_buffer)?;
+        if read == 0 {
+            return Ok(true);
+        }
+        b.read_exact(&mut b_buffer[..read])?;
+        if a_buffer[..read] != b_buffer[..read] {
+            return Ok(false);
+        }
+    }
+}
+
+/// Give the directory, and everything in it, to the given user.
+#[cfg(unix)]
+pub fn set_owner(path: &Path, owner: &str) -> Result<()> {
+    use nix::unistd::{Gid, Uid, chown};
+    use users::get_user_by_name;
+
+    let user = get_user_by_name(owner).ok_or_else(|| eyre!("User '{owner}' does not exist"))?;
+    let uid = Uid::from_raw(user.uid());
+    let gid = Gid::from_raw(user.primary_group_id());
+    for entry in WalkDir::new(path) {
+        chown(entry?.path(), Some(uid), Some(gid))?;
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn set_owner(_path: &Path, _owner: &str) -> Result<()> {
+    Ok(())
+}
+
+/// Write a node archive with the config of the node and the contents of its data directory.
+///
+/// The given files of the data directory, such as the node binary, are left out.
+pub fn write_archive(
+    config: &FleetConfig,
+    data_dir_path: &Path,
+    skip: &[PathBuf],
+    archive_path: &Path,
+) -> Result<()> {
+    if !data_dir_path.join(SECRET_KEY_FILE_NAME).exists() {
+        bail!("There is no keypair in {data_dir_path:?}, so the node has no identity to export");
+    }
+    debug!("Writing the node archive {archive_path:?} from {data_dir_path:?}");
+
+    let encoder = GzEncoder::new(File::create(archive_path)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    let contents = config.to_toml()?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, ARCHIVE_CONFIG_FILE_NAME, contents.as_bytes())?;
+
+    for entry in WalkDir::new(data_dir_path).min_depth(1) {
+        let entry = entry?;
+        if skip.iter().any(|path| path == entry.path()) {
+            continue;
+        }
+        let name = Path::new(ARCHIVE_DATA_DIR_NAME).join(entry.path().strip_prefix(data_dir_path)?);
+        if entry.file_type().is_dir() {
+            builder.append_dir(&name, entry.path())?;
+        } else if entry.file_type().is_file() {
+            builder.append_path_with_name(entry.path(), &name)?;
+        } else {
+            warn!("Skipping {:?}, which is not a file", entry.path());
+        }
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Read the config of the node in a node archive.
+pub fn read_archive_config(archive_path: &Path) -> Result<FleetConfig> {
+    let mut archive = open_archive(archive_path)?;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.path()? == Path::new(ARCHIVE_CONFIG_FILE_NAME) {
+            let mut contents = String::new();
+            let _ = entry.read_to_string(&mut contents)?;
+            let config: FleetConfig = toml::from_str(&contents)
+                .map_err(|err| eyre!("The config in {archive_path:?} is not valid: {err}"))?;
+            return Ok(config);
+        }
+    }
+    Err(eyre!("{archive_path:?} is not a node archive"))
+}
+
+/// Unpack the data directory of a node archive into the given directory.
+pub fn unpack_archive_data(archive_path: &Path, data_dir_path: &Path) -> Result<()> {
+    debug!("Unpacking the node archive {archive_path:?} into {data_dir_path:?}");
+    let mut has_keypair = false;
+    let mut archive = open_archive(archive_path)?;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let Ok(relative_path) = path.strip_prefix(ARCHIVE_DATA_DIR_NAME) else {
+            continue;
+        };
+        if relative_path.as_os_str().is_empty() {
+            continue;
+        }
+        if !relative_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            bail!("The archive entry {path:?} is outside the data directory");
+        }
+        // Links could point outside the data directory, which later entries would be written
+        // through.
+        let entry_type = entry.header().entry_type();
+        if !entry_type.is_file() && !entry_type.is_dir() {
+            bail!("The archive entry {path:?} is not a regular file or directory");
+        }
+        has_keypair |= relative_path == Path::new(SECRET_KEY_FILE_NAME);
+
+        let dest_path = data_dir_path.join(relative_path);
+        if let Some(parent) = dest_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let _ = entry.unpack(&dest_path)?;
+    }
+
+    if !has_keypair {
+        bail!("{archive_path:?} holds no keypair, so the node would not keep its identity");
+    }
+    Ok(())
+}
+
+fn open_archive(archive_path: &Path) -> Result<tar::Archive<GzDecoder<File>>> {
+    let file = File::open(archive_path)
+        .map_err(|err| eyre!("Could not open the node archive {archive_path:?}: {err}"))?;
+    Ok(tar::Archive::new(GzDecoder::new(file)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_evm::RewardsAddress;
+    use assert_fs::{TempDir, prelude::*};
+    use std::str::FromStr;
+
+    fn node_data_dir(temp_dir: &TempDir) -> (PathBuf, PeerId) {
+        let data_dir = temp_dir.child("antnode1");
+        let keypair = ed25519::Keypair::generate();
+        data_dir
+            .child(SECRET_KEY_FILE_NAME)
+            .write_binary(keypair.secret().as_ref())
+            .unwrap();
+        data_dir.child("antnode").write_str("binary").unwrap();
+        data_dir
+            .child("record_store/a1b2")
+            .write_binary(&[7; 200_000])
+            .unwrap();
+        let peer_id = Keypair::from(keypair).public().to_peer_id();
+        (data_dir.to_path_buf(), peer_id)
+    }
+
+    fn config() -> FleetConfig {
+        FleetConfig {
+            count: 1,
+            rewards_address: RewardsAddress::from_str("0x03B770D9cD32077cC0bF330c13C114a87643B124")
+                .unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn copy_dir_should_copy_and_verify_the_data_dir_without_the_skipped_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let (src, peer_id) = node_data_dir(&temp_dir);
+        let dest = temp_dir.child("new/antnode1");
+
+        let skip = [src.join("antnode")];
+        let bytes_copied = copy_dir(&src, &dest, &skip).unwrap();
+
+        assert_eq!(bytes_copied, 200_032);
+        verify_copy(&src, &dest, &skip).unwrap();
+        dest.child("antnode").assert(predicates::path::missing());
+        assert_eq!(read_peer_id(&dest).unwrap(), Some(peer_id));
+    }
+
+    #[test]
+    fn copy_dir_should_not_copy_into_a_dir_that_is_not_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let (src, _) = node_data_dir(&temp_dir);
+        let dest = temp_dir.child("new/antnode1");
+        dest.child("existing").write_str("data").unwrap();
+
+        assert!(copy_dir(&src, &dest, &[]).is_err());
+    }
+
+    #[test]
+    fn verify_copy_should_fail_for_a_changed_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let (src, _) = node_data_dir(&temp_dir);
+        let dest = temp_dir.child("new/antnode1");
+        let _ = copy_dir(&src, &dest, &[]).unwrap();
+
+        let mut contents = vec![7; 200_000];
+        contents[150_000] = 8;
+        dest.child("record_store/a1b2")
+            .write_binary(&contents)
+            .unwrap();
+
+        let err = verify_copy(&src, &dest, &[]).unwrap_err();
+        assert!(err.to_string().contains("differs"));
+    }
+
+    #[test]
+    fn a_node_archive_should_restore_the_config_and_the_data_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let (src, peer_id) = node_data_dir(&temp_dir);
+        let archive_path = temp_dir.child("antnode1.tar.gz");
+
+        write_archive(&config(), &src, &[src.join("antnode")], &archive_path).unwrap();
+        let dest = temp_dir.child("imported/antnode1");
+        unpack_archive_data(&archive_path, &dest).unwrap();
+
+        assert_eq!(read_archive_config(&archive_path).unwrap(), config());
+        assert_eq!(read_peer_id(&dest).unwrap(), Some(peer_id));
+        verify_copy(&src, &dest, &[src.join("antnode")]).unwrap();
+        dest.child("antnode").assert(predicates::path::missing());
+    }
+
+    #[test]
+    fn write_archive_should_fail_for_a_node_without_a_keypair() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.child("antnode1");
+        data_dir.child("antnode").write_str("binary").unwrap();
+
+        let err = write_archive(
+            &config(),
+            &data_dir,
+            &[],
+            &temp_dir.child("antnode1.tar.gz"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("no identity"));
+    }
+
+    #[test]
+    fn unpack_archive_data_should_reject_a_link_out_of_the_data_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside_dir = temp_dir.child("outside");
+        outside_dir.create_dir_all().unwrap();
+        let archive_path = temp_dir.child("antnode1.tar.gz");
+
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(&archive_path).unwrap(),
+            Compression::default(),
+        ));
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        header.set_mode(0o777);
+        header.set_link_name(outside_dir.path()).unwrap();
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "data/x", std::io::empty())
+            .unwrap();
+        let contents = b"root:x:0:0";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "data/x/passwd", &contents[..])
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let dest = temp_dir.child("imported/antnode1");
+        let err = unpack_archive_data(&archive_path, &dest).unwrap_err();
+
+        assert!(err.to_string().contains("not a regular file or directory"));
+        outside_dir
+            .child("passwd")
+            .assert(predicates::path::missing());
+    }
+}
@@ -12,9 +12,9 @@ use crate::{
 };
 use ant_service_management::{
     NodeRegistryManager, NodeService, NodeServiceData, ServiceStatus,
-    control::{ServiceControl, ServiceController},
+    control::{ServiceControl, SharedServiceControl},
     node::NODE_SERVICE_DATA_SCHEMA_LATEST,
-    rpc::RpcClient,
+    rpc::{RpcActions, RpcClient},
 };
 use color_eyre::{
     Result,
@@ -22,13 +22,14 @@ use color_eyre::{
 };
 use libp2p::PeerId;
 use std::sync::Arc;
+use tokio::sync::RwLock;
 
 pub async fn restart_node_service(
     node_registry: NodeRegistryManager,
+    service_control: SharedServiceControl,
     peer_id: PeerId,
     retain_peer_id: bool,
 ) -> Result<()> {
-    let nodes_len = node_registry.nodes.read().await.len();
     let mut current_node = None;
 
     for node in node_registry.nodes.read().await.iter() {
@@ -47,13 +48,12 @@ pub async fn restart_node_service(
     let service = NodeService::new(Arc::clone(&current_node), Box::new(rpc_client));
     let mut service_manager = ServiceManager::new(
         service,
-        Box::new(ServiceController {}),
+        Box::new(Arc::clone(&service_control)),
         VerbosityLevel::Normal,
     );
     service_manager.stop().await?;
     let service_name = current_node.read().await.service_name.clone();
 
-    let service_control = ServiceController {};
     if retain_peer_id {
         debug!("Retaining the peer id: {peer_id:?} for the node: {service_name:?}");
         // reuse the same port and root dir to retain peer id.
@@ -76,7 +76,7 @@ pub async fn restart_node_service(
             log_format: current_node_clone.log_format,
             max_archived_log_files: current_node_clone.max_archived_log_files,
             max_log_files: current_node_clone.max_log_files,
-            metrics_port: None,
+            metrics_port: current_node_clone.metrics_port,
             name: current_node_clone.service_name.clone(),
             network_id: current_node_clone.network_id,
             node_ip: current_node_clone.node_ip,
@@ -86,175 +86,403 @@ pub async fn restart_node_service(
             rpc_socket_addr: current_node_clone.rpc_socket_addr,
             service_user: current_node_clone.user.clone(),
             write_older_cache_files: current_node_clone.write_older_cache_files,
+            quiet_hours: current_node_clone.quiet_hours.clone(),
+            evm_config: current_node_clone.evm_config.clone(),
+            throttle_cpu_usage: current_node_clone.throttle_cpu_usage,
+            throttle_memory_usage: current_node_clone.throttle_memory_usage,
+            throttled_download_rate: current_node_clone.throttled_download_rate,
+            throttled_upload_rate: current_node_clone.throttled_upload_rate,
+            max_download_rate: current_node_clone.max_download_rate,
+            max_peer_download_rate: current_node_clone.max_peer_download_rate,
+            max_peer_upload_rate: current_node_clone.max_peer_upload_rate,
+            max_upload_rate: current_node_clone.max_upload_rate,
+            tcp_port: current_node_clone.tcp_port,
+            ws_port: current_node_clone.ws_port,
         }
         .build()?;
 
         service_control
             .install(install_ctx, false)
             .map_err(|err| eyre!("Error while installing node {service_name:?} with: {err:?}",))?;
+        if !current_node_clone.resource_limits.is_empty() {
+            service_control
+                .set_resource_limits(&service_name, &current_node_clone.resource_limits, false)
+                .map_err(|err| {
+                    eyre!(
+                        "Error while limiting the resources of node {service_name:?} with: {err:?}",
+                    )
+                })?;
+        }
         service_manager.start().await?;
     } else {
-        let current_node_clone = current_node.read().await.clone();
         debug!("Starting a new node since retain peer id is false.");
-        let new_node_number = nodes_len + 1;
-        let new_service_name = format!("antnode{new_node_number}");
+        let new_rpc_client = RpcClient::from_socket_addr(current_node.read().await.rpc_socket_addr);
+        replace_node(&node_registry, service_manager, Box::new(new_rpc_client)).await?;
+    };
 
-        // example path "log_dir_path":"/var/log/antnode/antnode18"
-        let log_dir_path = {
-            let mut log_dir_path = current_node_clone.log_dir_path.clone();
-            log_dir_path.pop();
-            log_dir_path.join(&new_service_name)
-        };
-        // example path "data_dir_path":"/var/antctl/services/antnode18"
-        let data_dir_path = {
-            let mut data_dir_path = current_node_clone.data_dir_path.clone();
-            data_dir_path.pop();
-            data_dir_path.join(&new_service_name)
-        };
+    Ok(())
+}
 
-        create_owned_dir(
-            log_dir_path.clone(),
-            current_node_clone.user.as_ref().ok_or_else(|| {
-                error!("The user must be set in the RPC context");
-                eyre!("The user must be set in the RPC context")
-            })?,
-        )
-        .map_err(|err| {
-            error!(
-                "Error while creating owned dir for {:?}: {err:?}",
-                current_node_clone.user
-            );
-            eyre!(
-                "Error while creating owned dir for {:?}: {err:?}",
-                current_node_clone.user
-            )
-        })?;
-        debug!("Created data dir: {data_dir_path:?} for the new node");
-        create_owned_dir(
-            data_dir_path.clone(),
-            current_node_clone
-                .user
-                .as_ref()
-                .ok_or_else(|| eyre!("The user must be set in the RPC context"))?,
-        )
-        .map_err(|err| {
+/// Replace the stopped node of the service manager with a new node, which has a new peer id.
+///
+/// The replaced node is removed along with its directories, and the new node takes over its RPC
+/// and metrics ports. The new node is registered before it is started, so it is not lost if it
+/// fails to start.
+async fn replace_node(
+    node_registry: &NodeRegistryManager,
+    mut service_manager: ServiceManager<NodeService>,
+    new_rpc_client: Box<dyn RpcActions + Send>,
+) -> Result<()> {
+    let current_node_clone = service_manager.service.service_data.read().await.clone();
+    let new_node_number = node_registry.nodes.read().await.len() + 1;
+    let new_service_name = format!("antnode{new_node_number}");
+
+    // example path "log_dir_path":"/var/log/antnode/antnode18"
+    let log_dir_path = {
+        let mut log_dir_path = current_node_clone.log_dir_path.clone();
+        log_dir_path.pop();
+        log_dir_path.join(&new_service_name)
+    };
+    // example path "data_dir_path":"/var/antctl/services/antnode18"
+    let data_dir_path = {
+        let mut data_dir_path = current_node_clone.data_dir_path.clone();
+        data_dir_path.pop();
+        data_dir_path.join(&new_service_name)
+    };
+    let user = current_node_clone.user.as_ref().ok_or_else(|| {
+        error!("The user must be set in the RPC context");
+        eyre!("The user must be set in the RPC context")
+    })?;
+
+    create_owned_dir(log_dir_path.clone(), user).map_err(|err| {
+        error!("Error while creating owned dir for {user:?}: {err:?}");
+        eyre!("Error while creating owned dir for {user:?}: {err:?}")
+    })?;
+    debug!("Created data dir: {data_dir_path:?} for the new node");
+    create_owned_dir(data_dir_path.clone(), user)
+        .map_err(|err| eyre!("Error while creating owned dir for {user:?}: {err:?}"))?;
+    // example path "antnode_path":"/var/antctl/services/antnode18/antnode"
+    let antnode_path = {
+        debug!("Copying antnode binary");
+        let mut antnode_path = current_node_clone.antnode_path.clone();
+        let antnode_file_name = antnode_path
+            .file_name()
+            .ok_or_eyre("Could not get filename from the current node's antnode path")?
+            .to_string_lossy()
+            .to_string();
+        antnode_path.pop();
+        antnode_path.pop();
+
+        let antnode_dir_path = antnode_path.join(&new_service_name);
+        create_owned_dir(antnode_dir_path.clone(), user)
+            .map_err(|err| eyre!("Error while creating owned dir for {user:?}: {err:?}"))?;
+        let antnode_path = antnode_dir_path.join(antnode_file_name);
+
+        std::fs::copy(&current_node_clone.antnode_path, &antnode_path).map_err(|err| {
             eyre!(
-                "Error while creating owned dir for {:?}: {err:?}",
-                current_node_clone.user
+                "Failed to copy antnode bin from {:?} to {antnode_path:?} with err: {err}",
+                current_node_clone.antnode_path
             )
         })?;
-        // example path "antnode_path":"/var/antctl/services/antnode18/antnode"
-        let antnode_path = {
-            debug!("Copying antnode binary");
-            let mut antnode_path = current_node_clone.antnode_path.clone();
-            let antnode_file_name = antnode_path
-                .file_name()
-                .ok_or_eyre("Could not get filename from the current node's antnode path")?
-                .to_string_lossy()
-                .to_string();
-            antnode_path.pop();
-            antnode_path.pop();
+        antnode_path
+    };
 
-            let antnode_path = antnode_path.join(&new_service_name);
-            create_owned_dir(
-                data_dir_path.clone(),
-                current_node_clone
-                    .user
-                    .as_ref()
-                    .ok_or_else(|| eyre!("The user must be set in the RPC context"))?,
+    // The replaced node goes away, freeing its ports for the new node.
+    service_manager.remove(false).await?;
+    let service_control = service_manager.service_control;
+
+    let install_ctx = InstallNodeServiceCtxBuilder {
+        alpha: current_node_clone.alpha,
+        autostart: current_node_clone.auto_restart,
+        data_dir_path: data_dir_path.clone(),
+        env_variables: node_registry.environment_variables.read().await.clone(),
+        evm_network: current_node_clone.evm_network.clone(),
+        relay: current_node_clone.relay,
+        init_peers_config: current_node_clone.initial_peers_config.clone(),
+        log_dir_path: log_dir_path.clone(),
+        log_format: current_node_clone.log_format,
+        name: new_service_name.clone(),
+        max_archived_log_files: current_node_clone.max_archived_log_files,
+        max_log_files: current_node_clone.max_log_files,
+        metrics_port: current_node_clone.metrics_port,
+        network_id: current_node_clone.network_id,
+        node_ip: current_node_clone.node_ip,
+        node_port: None,
+        no_upnp: current_node_clone.no_upnp,
+        rewards_address: current_node_clone.rewards_address,
+        rpc_socket_addr: current_node_clone.rpc_socket_addr,
+        antnode_path: antnode_path.clone(),
+        service_user: current_node_clone.user.clone(),
+        write_older_cache_files: current_node_clone.write_older_cache_files,
+        quiet_hours: current_node_clone.quiet_hours.clone(),
+        evm_config: current_node_clone.evm_config.clone(),
+        throttle_cpu_usage: current_node_clone.throttle_cpu_usage,
+        throttle_memory_usage: current_node_clone.throttle_memory_usage,
+        throttled_download_rate: current_node_clone.throttled_download_rate,
+        throttled_upload_rate: current_node_clone.throttled_upload_rate,
+        max_download_rate: current_node_clone.max_download_rate,
+        max_peer_download_rate: current_node_clone.max_peer_download_rate,
+        max_peer_upload_rate: current_node_clone.max_peer_upload_rate,
+        max_upload_rate: current_node_clone.max_upload_rate,
+        tcp_port: current_node_clone.tcp_port,
+        ws_port: current_node_clone.ws_port,
+    }
+    .build()?;
+    service_control
+        .install(install_ctx, false)
+        .map_err(|err| eyre!("Error while installing node {new_service_name:?} with: {err:?}",))?;
+    if !current_node_clone.resource_limits.is_empty() {
+        service_control
+            .set_resource_limits(
+                &new_service_name,
+                &current_node_clone.resource_limits,
+                false,
             )
             .map_err(|err| {
                 eyre!(
-                    "Error while creating owned dir for {:?}: {err:?}",
-                    current_node_clone.user
+                    "Error while limiting the resources of node {new_service_name:?} with: {err:?}",
                 )
             })?;
-            let antnode_path = antnode_path.join(antnode_file_name);
+    }
 
-            std::fs::copy(&current_node_clone.antnode_path, &antnode_path).map_err(|err| {
-                eyre!(
-                    "Failed to copy antnode bin from {:?} to {antnode_path:?} with err: {err}",
-                    current_node_clone.antnode_path
-                )
-            })?;
-            antnode_path
-        };
+    let node = NodeServiceData {
+        alpha: current_node_clone.alpha,
+        antnode_path,
+        auto_restart: current_node_clone.auto_restart,
+        connected_peers: None,
+        data_dir_path,
+        evm_network: current_node_clone.evm_network,
+        relay: current_node_clone.relay,
+        resource_limits: current_node_clone.resource_limits.clone(),
+        initial_peers_config: current_node_clone.initial_peers_config.clone(),
+        listen_addr: None,
+        log_dir_path,
+        log_format: current_node_clone.log_format,
+        max_archived_log_files: current_node_clone.max_archived_log_files,
+        max_log_files: current_node_clone.max_log_files,
+        metrics_port: current_node_clone.metrics_port,
+        network_id: current_node_clone.network_id,
+        node_ip: current_node_clone.node_ip,
+        node_port: None,
+        no_upnp: current_node_clone.no_upnp,
+        number: new_node_number as u16,
+        peer_id: None,
+        pid: None,
+        rewards_address: current_node_clone.rewards_address,
+        reward_balance: current_node_clone.reward_balance,
+        rpc_socket_addr: current_node_clone.rpc_socket_addr,
+        schema_version: NODE_SERVICE_DATA_SCHEMA_LATEST,
+        service_name: new_service_name.clone(),
+        status: ServiceStatus::Added,
+        user: current_node_clone.user.clone(),
+        user_mode: false,
+        version: current_node_clone.version.clone(),
+        write_older_cache_files: current_node_clone.write_older_cache_files,
+        quiet_hours: current_node_clone.quiet_hours.clone(),
+        evm_config: current_node_clone.evm_config.clone(),
+        throttle_cpu_usage: current_node_clone.throttle_cpu_usage,
+        throttle_memory_usage: current_node_clone.throttle_memory_usage,
+        throttled_download_rate: current_node_clone.throttled_download_rate,
+        throttled_upload_rate: current_node_clone.throttled_upload_rate,
+        max_download_rate: current_node_clone.max_download_rate,
+        max_peer_download_rate: current_node_clone.max_peer_download_rate,
+        max_peer_upload_rate: current_node_clone.max_peer_upload_rate,
+        max_upload_rate: current_node_clone.max_upload_rate,
+        tcp_port: current_node_clone.tcp_port,
+        ws_port: current_node_clone.ws_port,
+    };
+    let node = Arc::new(RwLock::new(node));
+    node_registry.nodes.write().await.push(Arc::clone(&node));
 
-        let install_ctx = InstallNodeServiceCtxBuilder {
-            alpha: current_node_clone.alpha,
-            autostart: current_node_clone.auto_restart,
-            data_dir_path: data_dir_path.clone(),
-            env_variables: node_registry.environment_variables.read().await.clone(),
-            evm_network: current_node_clone.evm_network.clone(),
-            relay: current_node_clone.relay,
-            init_peers_config: current_node_clone.initial_peers_config.clone(),
-            log_dir_path: log_dir_path.clone(),
-            log_format: current_node_clone.log_format,
-            name: new_service_name.clone(),
-            max_archived_log_files: current_node_clone.max_archived_log_files,
-            max_log_files: current_node_clone.max_log_files,
-            metrics_port: None,
-            network_id: current_node_clone.network_id,
-            node_ip: current_node_clone.node_ip,
-            node_port: None,
-            no_upnp: current_node_clone.no_upnp,
-            rewards_address: current_node_clone.rewards_address,
-            rpc_socket_addr: current_node_clone.rpc_socket_addr,
-            antnode_path: antnode_path.clone(),
-            service_user: current_node_clone.user.clone(),
-            write_older_cache_files: current_node_clone.write_older_cache_files,
-        }
-        .build()?;
-        service_control.install(install_ctx, false).map_err(|err| {
-            eyre!("Error while installing node {new_service_name:?} with: {err:?}",)
-        })?;
+    let service = NodeService::new(node, new_rpc_client);
+    let mut service_manager = ServiceManager::new(service, service_control, VerbosityLevel::Normal);
+    service_manager.start().await?;
 
-        let node = NodeServiceData {
-            alpha: current_node_clone.alpha,
-            antnode_path,
-            auto_restart: current_node_clone.auto_restart,
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{MockRpcClient, MockServiceControl};
+    use ant_bootstrap::InitialPeersConfig;
+    use ant_evm::{EvmNetwork, RewardsAddress};
+    use ant_service_management::{ResourceLimits, rpc::NetworkInfo, rpc::NodeInfo};
+    use assert_fs::prelude::*;
+    use mockall::predicate::*;
+    use std::{
+        net::{IpAddr, Ipv4Addr, SocketAddr},
+        path::PathBuf,
+        str::FromStr,
+    };
+
+    #[tokio::test]
+    async fn replace_node_should_remove_the_old_node_and_give_its_ports_to_the_new_node()
+    -> Result<()> {
+        let tmp_data_dir = assert_fs::TempDir::new()?;
+        let services_dir = tmp_data_dir.child("services");
+        let logs_dir = tmp_data_dir.child("logs");
+        let old_antnode = services_dir.child("antnode1").child("antnode");
+        old_antnode.write_binary(b"antnode")?;
+        logs_dir.child("antnode1").create_dir_all()?;
+        let new_antnode_path = services_dir
+            .child("antnode2")
+            .child("antnode")
+            .to_path_buf();
+
+        let old_peer_id = PeerId::from_str("12D3KooWS2tpXGGTmg2AHFiDh57yPQnat49YHnyqoggzXZWpqkCR")?;
+        let new_peer_id = PeerId::random();
+        let rpc_socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 13002);
+
+        let mut mock_service_control = MockServiceControl::new();
+        mock_service_control
+            .expect_get_process_pid()
+            .with(eq(old_antnode.to_path_buf()))
+            .times(1)
+            .returning(|_| Ok(1000));
+        mock_service_control
+            .expect_stop()
+            .with(eq("antnode1"), eq(false))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_service_control
+            .expect_uninstall()
+            .with(eq("antnode1"), eq(false))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_service_control
+            .expect_install()
+            .withf(|install_ctx, user_mode| {
+                let port_arg = |name: &str, value: &str| {
+                    install_ctx
+                        .args
+                        .windows(2)
+                        .any(|arg| arg[0] == *name && arg[1] == *value)
+                };
+                install_ctx.label.to_string().ends_with("antnode2")
+                    && port_arg("--rpc", "127.0.0.1:13002")
+                    && port_arg("--metrics-server-port", "13001")
+                    && !user_mode
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_service_control
+            .expect_start()
+            .with(eq("antnode2"), eq(false))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_service_control
+            .expect_wait()
+            .with(eq(3000))
+            .times(1)
+            .returning(|_| ());
+        mock_service_control
+            .expect_get_process_pid()
+            .with(eq(new_antnode_path.clone()))
+            .times(1)
+            .returning(|_| Ok(1001));
+
+        let mut new_rpc_client = MockRpcClient::new();
+        new_rpc_client
+            .expect_node_info()
+            .times(1)
+            .returning(move || {
+                Ok(NodeInfo {
+                    pid: 1001,
+                    peer_id: new_peer_id,
+                    data_path: PathBuf::new(),
+                    log_path: PathBuf::new(),
+                    version: "0.98.1".to_string(),
+                    uptime: std::time::Duration::from_secs(1),
+                    wallet_balance: 0,
+                })
+            });
+        new_rpc_client.expect_network_info().times(1).returning(|| {
+            Ok(NetworkInfo {
+                connected_peers: vec![],
+                listeners: vec![],
+            })
+        });
+
+        let old_node = NodeServiceData {
+            alpha: false,
+            antnode_path: old_antnode.to_path_buf(),
+            auto_restart: false,
             connected_peers: None,
-            data_dir_path,
-            evm_network: current_node_clone.evm_network,
-            relay: current_node_clone.relay,
-            initial_peers_config: current_node_clone.initial_peers_config.clone(),
+            data_dir_path: services_dir.child("antnode1").to_path_buf(),
+            evm_network: EvmNetwork::ArbitrumOne,
+            relay: false,
+            resource_limits: ResourceLimits::default(),
+            initial_peers_config: InitialPeersConfig::default(),
             listen_addr: None,
-            log_dir_path,
-            log_format: current_node_clone.log_format,
-            max_archived_log_files: current_node_clone.max_archived_log_files,
-            max_log_files: current_node_clone.max_log_files,
-            metrics_port: None,
-            network_id: current_node_clone.network_id,
-            node_ip: current_node_clone.node_ip,
+            log_dir_path: logs_dir.child("antnode1").to_path_buf(),
+            log_format: None,
+            max_archived_log_files: None,
+            max_log_files: None,
+            metrics_port: Some(13001),
+            network_id: None,
+            node_ip: None,
             node_port: None,
-            no_upnp: current_node_clone.no_upnp,
-            number: new_node_number as u16,
-            peer_id: None,
-            pid: None,
-            rewards_address: current_node_clone.rewards_address,
-            reward_balance: current_node_clone.reward_balance,
-            rpc_socket_addr: current_node_clone.rpc_socket_addr,
+            no_upnp: false,
+            number: 1,
+            peer_id: Some(old_peer_id),
+            pid: Some(1000),
+            rewards_address: RewardsAddress::from_str(
+                "0x03B770D9cD32077cC0bF330c13C114a87643B124",
+            )?,
+            reward_balance: None,
+            rpc_socket_addr,
             schema_version: NODE_SERVICE_DATA_SCHEMA_LATEST,
-            service_name: new_service_name.clone(),
-            status: ServiceStatus::Added,
-            user: current_node_clone.user.clone(),
+            service_name: "antnode1".to_string(),
+            status: ServiceStatus::Running,
+            user: Some(crate::helpers::get_username()?),
             user_mode: false,
-            version: current_node_clone.version.clone(),
-            write_older_cache_files: current_node_clone.write_older_cache_files,
+            version: "0.98.1".to_string(),
+            write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
+        let old_node = Arc::new(RwLock::new(old_node));
+        let node_registry =
+            NodeRegistryManager::empty(tmp_data_dir.child("node_reg.json").to_path_buf());
+        node_registry
+            .nodes
+            .write()
+            .await
+            .push(Arc::clone(&old_node));
 
-        let rpc_client = RpcClient::from_socket_addr(node.rpc_socket_addr);
-        let service = NodeService::new(Arc::clone(&current_node), Box::new(rpc_client));
         let mut service_manager = ServiceManager::new(
-            service,
-            Box::new(ServiceController {}),
+            NodeService::new(Arc::clone(&old_node), Box::new(MockRpcClient::new())),
+            Box::new(mock_service_control),
             VerbosityLevel::Normal,
         );
-        service_manager.start().await?;
-        node_registry
-            .push_node(service_manager.service.service_data.read().await.clone())
-            .await;
-    };
+        service_manager.stop().await?;
+        replace_node(&node_registry, service_manager, Box::new(new_rpc_client)).await?;
 
-    Ok(())
+        let nodes = node_registry.get_node_service_data().await;
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].status, ServiceStatus::Removed);
+        services_dir
+            .child("antnode1")
+            .assert(predicates::path::missing());
+        assert_eq!(nodes[1].service_name, "antnode2");
+        assert_eq!(nodes[1].status, ServiceStatus::Running);
+        assert_eq!(nodes[1].peer_id, Some(new_peer_id));
+        assert_eq!(nodes[1].rpc_socket_addr, rpc_socket_addr);
+        assert_eq!(nodes[1].metrics_port, Some(13001));
+        assert_eq!(nodes[1].antnode_path, new_antnode_path);
+        assert!(new_antnode_path.exists());
+
+        Ok(())
+    }
 }
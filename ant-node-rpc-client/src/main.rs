@@ -9,11 +9,17 @@
 
 use ant_logging::{Level, LogBuilder};
 use ant_node::NodeEvent;
-use ant_protocol::antnode_proto::{NodeEventsRequest, ant_node_client::AntNodeClient};
+use ant_protocol::antnode_proto::{
+    AuditRecordsRequest, NodeEventsRequest, ant_node_client::AntNodeClient,
+};
 use ant_service_management::rpc::{RpcActions, RpcClient};
 use clap::Parser;
 use color_eyre::eyre::Result;
-use std::{net::SocketAddr, time::Duration};
+use libp2p::kad::RecordKey;
+use std::{
+    net::SocketAddr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tokio_stream::StreamExt;
 use tonic::Request;
 
@@ -52,6 +58,33 @@ enum Cmd {
     /// Note this blocks the app and it will print events as they are broadcasted by the node
     #[clap(name = "events")]
     Events,
+    /// Retrieve the metadata of a record stored by the node
+    #[clap(name = "record-info")]
+    RecordInfo {
+        /// The hex-encoded key of the record
+        #[clap(value_parser = parse_record_key)]
+        key: RecordKey,
+    },
+    /// Trigger a replication round with the node's close peers
+    #[clap(name = "trigger-replication")]
+    TriggerReplication,
+    /// Trigger the cleanup of the records out of the node's responsible range.
+    /// Note the node only removes records once it has accumulated too many of them
+    #[clap(name = "trigger-cleanup")]
+    TriggerCleanup,
+    /// Challenge the node's close peers to prove they hold the records they are responsible for
+    #[clap(name = "storage-challenge")]
+    StorageChallenge,
+    /// Retrieve the payments received by the node
+    #[clap(name = "earnings")]
+    Earnings {
+        /// Only list the payments received in the last given number of days
+        #[clap(long)]
+        days: Option<u64>,
+    },
+    /// Audit the records held by the node against how it came to hold them
+    #[clap(name = "audit-records")]
+    AuditRecords,
     /// Restart the node after the specified delay
     #[clap(name = "restart")]
     Restart {
@@ -126,6 +159,12 @@ async fn main() -> Result<()> {
         Cmd::Info => node_info(addr).await,
         Cmd::Netinfo => network_info(addr).await,
         Cmd::Events => node_events(addr).await,
+        Cmd::RecordInfo { key } => record_info(addr, key).await,
+        Cmd::TriggerReplication => trigger_replication(addr).await,
+        Cmd::TriggerCleanup => trigger_cleanup(addr).await,
+        Cmd::StorageChallenge => run_storage_challenge(addr).await,
+        Cmd::Earnings { days } => earnings(addr, days).await,
+        Cmd::AuditRecords => audit_records(addr).await,
         Cmd::Restart {
             delay_millis,
             retain_peer_id,
@@ -209,6 +248,123 @@ pub async fn record_addresses(addr: SocketAddr) -> Result<()> {
     Ok(())
 }
 
+pub async fn record_info(addr: SocketAddr, key: RecordKey) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    let record_info = client.record_info(key).await?;
+
+    println!("Record info:");
+    println!("============");
+    println!("Key: {}", hex::encode(record_info.key.as_ref()));
+    println!("Record type: {}", record_info.record_type);
+    println!("Data type: {}", record_info.data_type);
+    match record_info.size {
+        Some(size) => println!("Size on disk: {size} bytes"),
+        None => println!("Size on disk: the record file is missing"),
+    }
+    match record_info
+        .stored_at
+        .and_then(|stored_at| stored_at.duration_since(UNIX_EPOCH).ok())
+    {
+        Some(since_epoch) => println!("Stored at: {} (UNIX timestamp)", since_epoch.as_secs()),
+        None => println!("Stored at: unknown"),
+    }
+    println!();
+    println!("Close peers expected to hold the record:");
+    for peer_id in record_info.holders.iter() {
+        println!("Peer: {peer_id}");
+    }
+
+    Ok(())
+}
+
+pub async fn trigger_replication(addr: SocketAddr) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    client.trigger_replication().await?;
+    println!("Node successfully received the request to trigger a replication round");
+    Ok(())
+}
+
+pub async fn trigger_cleanup(addr: SocketAddr) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    client.trigger_cleanup().await?;
+    println!("Node successfully received the request to clean up irrelevant records");
+    Ok(())
+}
+
+pub async fn run_storage_challenge(addr: SocketAddr) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    let results = client.run_storage_challenge().await?;
+
+    if results.is_empty() {
+        println!(
+            "No peers were challenged, the node may lack close peers or records. Check its logs for details."
+        );
+        return Ok(());
+    }
+    println!("Storage challenge results:");
+    for result in results.iter() {
+        let outcome = if result.healthy { "passed" } else { "FAILED" };
+        println!("Peer: {} {outcome}", result.peer_id);
+    }
+
+    Ok(())
+}
+
+pub async fn earnings(addr: SocketAddr, days: Option<u64>) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let client = RpcClient::new(&endpoint);
+    let since = days
+        .and_then(|days| SystemTime::now().checked_sub(Duration::from_secs(days * 24 * 60 * 60)))
+        .unwrap_or(UNIX_EPOCH);
+    let earnings = client.earnings(since).await?;
+
+    println!("Lifetime earnings: {}", earnings.lifetime_total);
+    println!();
+    println!("Payments received:");
+    for entry in earnings.entries.iter() {
+        let received_at = entry
+            .received_at
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or_default();
+        println!(
+            "{received_at} (UNIX timestamp): {} for {} record {}",
+            entry.amount,
+            entry.data_type,
+            hex::encode(entry.key.as_ref())
+        );
+    }
+
+    Ok(())
+}
+
+pub async fn audit_records(addr: SocketAddr) -> Result<()> {
+    let endpoint = format!("https://{addr}");
+    let mut client = AntNodeClient::connect(endpoint).await?;
+    let audit = client
+        .audit_records(Request::new(AuditRecordsRequest {}))
+        .await?
+        .into_inner();
+
+    println!("Records paid for: {}", audit.paid);
+    println!("Records replicated: {}", audit.replicated);
+    println!("Records replicated fresh: {}", audit.fresh_replicated);
+    println!("Records unjustified: {}", audit.unjustified.len());
+    for address in audit.unjustified.iter() {
+        println!("Unjustified record: {}", hex::encode(address));
+    }
+    println!("Records of unknown provenance: {}", audit.unknown.len());
+    for address in audit.unknown.iter() {
+        println!("Unknown record: {}", hex::encode(address));
+    }
+
+    Ok(())
+}
+
 pub async fn node_restart(addr: SocketAddr, delay_millis: u64, retain_peer_id: bool) -> Result<()> {
     let endpoint = format!("https://{addr}");
     let client = RpcClient::new(&endpoint);
@@ -250,3 +406,8 @@ pub async fn update_log_level(addr: SocketAddr, log_levels: String) -> Result<()
     println!("Node successfully received the request to update the log level to {log_levels:?}",);
     Ok(())
 }
+
+fn parse_record_key(s: &str) -> Result<RecordKey, String> {
+    let bytes = hex::decode(s).map_err(|err| format!("Invalid hex-encoded record key: {err}"))?;
+    Ok(RecordKey::new(&bytes))
+}
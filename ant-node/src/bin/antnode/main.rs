@@ -22,11 +22,14 @@ use crate::subcommands::EvmNetworkCommand;
 use ant_bootstrap::BootstrapConfig;
 use ant_bootstrap::InitialPeersConfig;
 use ant_bootstrap::bootstrap::Bootstrap;
-use ant_evm::{EvmNetwork, RewardsAddress, get_evm_network};
+use ant_evm::{EvmNetwork, RewardsAddress, evm_config::EvmConfig, get_evm_network};
 use ant_logging::metrics::init_metrics;
 use ant_logging::{Level, LogFormat, LogOutputDest, ReloadHandle};
 use ant_node::utils::{get_antnode_root_dir, get_root_dir_and_keypair};
-use ant_node::{Marker, NodeBuilder, NodeEvent, NodeEventsReceiver};
+use ant_node::{
+    BandwidthLimits, ConfigQuotePolicy, Marker, NodeBuilder, NodeEvent, NodeEventsReceiver,
+    QuietWindow, ThrottlePolicy,
+};
 use ant_protocol::{
     node_rpc::{NodeCtrl, StopResult},
     version,
@@ -40,6 +43,7 @@ use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
     process::{Command, Stdio},
+    sync::Arc,
     time::Duration,
 };
 use tokio::{
@@ -101,6 +105,12 @@ struct Opt {
     )]
     enable_metrics_server: bool,
 
+    /// Specify an EVM network config file, listing several RPC endpoints to fail over between.
+    ///
+    /// This takes precedence over the EVM network subcommand and environment variables.
+    #[clap(long, verbatim_doc_comment)]
+    evm_config: Option<PathBuf>,
+
     /// Specify the EVM network to use.
     /// The network can either be a pre-configured one or a custom network.
     /// When setting a custom network, you must specify the RPC URL to a fully synced node and
@@ -153,6 +163,52 @@ struct Opt {
     #[clap(long, verbatim_doc_comment)]
     max_archived_log_files: Option<usize>,
 
+    /// Limit the upload rate of the node, in KiB/s, across all connections.
+    ///
+    /// Replication slows down while the node is at the limit, to leave room for client traffic.
+    #[clap(long, verbatim_doc_comment)]
+    max_upload_rate: Option<u64>,
+
+    /// Limit the download rate of the node, in KiB/s, across all connections.
+    ///
+    /// Replication slows down while the node is at the limit, to leave room for client traffic.
+    #[clap(long, verbatim_doc_comment)]
+    max_download_rate: Option<u64>,
+
+    /// Limit the upload rate towards any single peer, in KiB/s.
+    #[clap(long)]
+    max_peer_upload_rate: Option<u64>,
+
+    /// Limit the download rate from any single peer, in KiB/s.
+    #[clap(long)]
+    max_peer_download_rate: Option<u64>,
+
+    /// Daily windows of local time during which the node is throttled, e.g. "09:00-17:00".
+    ///
+    /// Multiple windows can be given, separated by commas. A window can wrap around midnight,
+    /// e.g. "22:00-07:00".
+    ///
+    /// While throttled, the throttled rate limits apply, replication slows down, storage
+    /// challenges are paused and close peers are asked not to dial the node.
+    #[clap(long, value_delimiter = ',', verbatim_doc_comment)]
+    quiet_hours: Vec<QuietWindow>,
+
+    /// Limit the upload rate of the node while throttled, in KiB/s, across all connections.
+    #[clap(long)]
+    throttled_upload_rate: Option<u64>,
+
+    /// Limit the download rate of the node while throttled, in KiB/s, across all connections.
+    #[clap(long)]
+    throttled_download_rate: Option<u64>,
+
+    /// Throttle the node while the system-wide CPU usage is above this percentage.
+    #[clap(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    throttle_cpu_usage: Option<u8>,
+
+    /// Throttle the node while the system-wide memory usage is above this percentage.
+    #[clap(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    throttle_memory_usage: Option<u8>,
+
     #[cfg(feature = "open-metrics")]
     /// Specify the port for the OpenMetrics server.
     ///
@@ -191,6 +247,24 @@ struct Opt {
     #[clap(long, default_value_t = 0)]
     port: u16,
 
+    /// Specify the port to listen on with TCP, in addition to QUIC.
+    ///
+    /// TCP is disabled unless this is set. It can help nodes behind firewalls that block UDP traffic.
+    /// The special value `0` will cause the OS to assign a random port.
+    #[clap(long)]
+    tcp_port: Option<u16>,
+
+    /// Path to a JSON file with the policy consulted before quoting for storing a record.
+    ///
+    /// The policy can decline to quote above a storage fullness, for some data types or above a
+    /// record size, and can report the storage as fuller than it is to raise prices, e.g.:
+    ///
+    /// { "max_storage_percent": 90, "declined_data_types": ["Scratchpad"] }
+    ///
+    /// The node quotes for every record if not set.
+    #[clap(long, verbatim_doc_comment)]
+    quote_policy: Option<PathBuf>,
+
     /// Specify the rewards address.
     /// The rewards address is the address that will receive the rewards for the node.
     /// It should be a valid EVM address.
@@ -221,6 +295,13 @@ struct Opt {
     #[clap(long)]
     version: bool,
 
+    /// Specify the port to listen on with WebSocket, in addition to QUIC.
+    ///
+    /// WebSocket is disabled unless this is set.
+    /// The special value `0` will cause the OS to assign a random port.
+    #[clap(long)]
+    ws_port: Option<u16>,
+
     /// Set this to true if you want the node to write the cache files in the older formats.
     #[clap(long, default_value_t = false)]
     write_older_cache_files: bool,
@@ -283,9 +364,10 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let evm_network: EvmNetwork = match opt.evm_network.as_ref() {
-        Some(evm_network) => Ok(evm_network.clone().into()),
-        None => match get_evm_network(opt.peers.local, Some(network_id)) {
+    let evm_network: EvmNetwork = match (opt.evm_config.as_ref(), opt.evm_network.as_ref()) {
+        (Some(evm_config), _) => Ok(EvmConfig::from_file(evm_config)?.install(network_id)?),
+        (None, Some(evm_network)) => Ok(evm_network.clone().into()),
+        (None, None) => match get_evm_network(opt.peers.local, Some(network_id)) {
             Ok(net) => Ok(net),
             Err(_) => Err(eyre!(
                 "EVM network not specified. Please specify a network using the subcommand or by setting the `EVM_NETWORK` environment variable."
@@ -338,6 +420,27 @@ fn main() -> Result<()> {
         node_builder.local(opt.peers.local);
         node_builder.no_upnp(opt.no_upnp);
         node_builder.relay_client(opt.relay);
+        node_builder.tcp_port(opt.tcp_port);
+        node_builder.ws_port(opt.ws_port);
+        node_builder.bandwidth_limits(BandwidthLimits {
+            upload: opt.max_upload_rate.map(kib_to_bytes),
+            download: opt.max_download_rate.map(kib_to_bytes),
+            peer_upload: opt.max_peer_upload_rate.map(kib_to_bytes),
+            peer_download: opt.max_peer_download_rate.map(kib_to_bytes),
+        });
+        node_builder.throttle_policy(ThrottlePolicy {
+            quiet_hours: opt.quiet_hours,
+            max_cpu_usage: opt.throttle_cpu_usage.map(f32::from),
+            max_memory_usage: opt.throttle_memory_usage.map(f32::from),
+            bandwidth_limits: BandwidthLimits {
+                upload: opt.throttled_upload_rate.map(kib_to_bytes),
+                download: opt.throttled_download_rate.map(kib_to_bytes),
+                ..Default::default()
+            },
+        });
+        if let Some(path) = &opt.quote_policy {
+            node_builder.quote_policy(Arc::new(ConfigQuotePolicy::from_file(path)?));
+        }
         #[cfg(feature = "open-metrics")]
         let mut node_builder = node_builder;
         // if enable flag is provided or only if the port is specified then enable the server by setting Some()
@@ -559,6 +662,11 @@ fn monitor_node_events(mut node_events_rx: NodeEventsReceiver, ctrl_tx: mpsc::Se
     });
 }
 
+/// Converts a rate given in KiB/s on the command line into bytes per second.
+fn kib_to_bytes(rate: u64) -> u64 {
+    rate.saturating_mul(1024)
+}
+
 fn init_logging(opt: &Opt, peer_id: PeerId) -> Result<(String, ReloadHandle, Option<WorkerGuard>)> {
     let logging_targets = vec![
         ("ant_bootstrap".to_string(), Level::INFO),
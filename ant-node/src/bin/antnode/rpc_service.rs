@@ -9,21 +9,26 @@
 use ant_logging::ReloadHandle;
 use ant_node::RunningNode;
 use ant_protocol::antnode_proto::{
-    KBucketsRequest, KBucketsResponse, NetworkInfoRequest, NetworkInfoResponse, NodeEvent,
-    NodeEventsRequest, NodeInfoRequest, NodeInfoResponse, RecordAddressesRequest,
-    RecordAddressesResponse, RestartRequest, RestartResponse, StopRequest, StopResponse,
-    UpdateLogLevelRequest, UpdateLogLevelResponse, UpdateRequest, UpdateResponse,
+    AuditRecordsRequest, AuditRecordsResponse, EarningsRequest, EarningsResponse,
+    GetRecordInfoRequest, GetRecordInfoResponse, KBucketsRequest, KBucketsResponse,
+    NetworkInfoRequest, NetworkInfoResponse, NodeEvent, NodeEventsRequest, NodeInfoRequest,
+    NodeInfoResponse, RecordAddressesRequest, RecordAddressesResponse, RestartRequest,
+    RestartResponse, RunStorageChallengeRequest, RunStorageChallengeResponse, StopRequest,
+    StopResponse, TriggerCleanupRequest, TriggerCleanupResponse, TriggerReplicationRequest,
+    TriggerReplicationResponse, UpdateLogLevelRequest, UpdateLogLevelResponse, UpdateRequest,
+    UpdateResponse,
     ant_node_server::{AntNode, AntNodeServer},
-    k_buckets_response,
+    earnings_response, k_buckets_response, run_storage_challenge_response,
 };
 use ant_protocol::node_rpc::{NodeCtrl, StopResult};
 use eyre::{ErrReport, Result};
+use libp2p::kad::RecordKey;
 use std::{
     collections::HashMap,
     env,
     net::SocketAddr,
     process,
-    time::{Duration, Instant},
+    time::{Duration, Instant, UNIX_EPOCH},
 };
 use tokio::sync::mpsc::{self, Sender};
 use tokio_stream::wrappers::ReceiverStream;
@@ -163,6 +168,175 @@ impl AntNode for SafeNodeRpcService {
         Ok(Response::new(RecordAddressesResponse { addresses }))
     }
 
+    async fn get_record_info(
+        &self,
+        request: Request<GetRecordInfoRequest>,
+    ) -> Result<Response<GetRecordInfoResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        let key = RecordKey::new(&request.get_ref().key);
+        let record_info = match self.running_node.get_record_info(&key).await {
+            Ok(Some(record_info)) => record_info,
+            Ok(None) => {
+                return Err(Status::not_found(format!(
+                    "Record {} is not held by the node",
+                    hex::encode(key.as_ref())
+                )));
+            }
+            Err(err) => {
+                return Err(Status::internal(format!(
+                    "Failed to get record info: {err:?}"
+                )));
+            }
+        };
+
+        let stored_at_secs = record_info
+            .stored_at
+            .and_then(|stored_at| stored_at.duration_since(UNIX_EPOCH).ok())
+            .map(|since_epoch| since_epoch.as_secs());
+        Ok(Response::new(GetRecordInfoResponse {
+            address: record_info.address.as_bytes(),
+            record_type: format!("{:?}", record_info.record_type),
+            data_type: format!("{:?}", record_info.data_type),
+            size: record_info.size,
+            stored_at_secs,
+            holders: record_info
+                .holders
+                .iter()
+                .map(|peer| peer.to_bytes())
+                .collect(),
+        }))
+    }
+
+    async fn trigger_replication(
+        &self,
+        request: Request<TriggerReplicationRequest>,
+    ) -> Result<Response<TriggerReplicationResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        self.running_node.trigger_replication();
+        Ok(Response::new(TriggerReplicationResponse {}))
+    }
+
+    async fn trigger_cleanup(
+        &self,
+        request: Request<TriggerCleanupRequest>,
+    ) -> Result<Response<TriggerCleanupResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        self.running_node.trigger_cleanup();
+        Ok(Response::new(TriggerCleanupResponse {}))
+    }
+
+    async fn run_storage_challenge(
+        &self,
+        request: Request<RunStorageChallengeRequest>,
+    ) -> Result<Response<RunStorageChallengeResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        let results = self
+            .running_node
+            .run_storage_challenge()
+            .await
+            .into_iter()
+            .map(
+                |(peer_id, healthy)| run_storage_challenge_response::PeerResult {
+                    peer_id: peer_id.to_bytes(),
+                    healthy,
+                },
+            )
+            .collect();
+        Ok(Response::new(RunStorageChallengeResponse { results }))
+    }
+
+    async fn earnings(
+        &self,
+        request: Request<EarningsRequest>,
+    ) -> Result<Response<EarningsResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        let entries = self
+            .running_node
+            .get_earnings(request.get_ref().since_secs)
+            .await
+            .into_iter()
+            .map(|entry| earnings_response::Entry {
+                quote_hashes: entry
+                    .quote_hashes
+                    .iter()
+                    .map(|hash| hash.to_vec())
+                    .collect(),
+                address: entry.address.as_bytes(),
+                data_type: format!("{:?}", entry.data_type),
+                amount: entry.amount.as_atto().to_string(),
+                timestamp_secs: entry.timestamp,
+            })
+            .collect();
+        Ok(Response::new(EarningsResponse {
+            lifetime_total: self
+                .running_node
+                .get_lifetime_earnings()
+                .as_atto()
+                .to_string(),
+            entries,
+        }))
+    }
+
+    async fn audit_records(
+        &self,
+        request: Request<AuditRecordsRequest>,
+    ) -> Result<Response<AuditRecordsResponse>, Status> {
+        debug!(
+            "RPC request received at {}: {:?}",
+            self.addr,
+            request.get_ref()
+        );
+
+        let audit = match self.running_node.audit_records().await {
+            Ok(audit) => audit,
+            Err(err) => {
+                return Err(Status::internal(format!(
+                    "Failed to audit the records: {err:?}"
+                )));
+            }
+        };
+        Ok(Response::new(AuditRecordsResponse {
+            paid: audit.paid as u64,
+            replicated: audit.replicated as u64,
+            fresh_replicated: audit.fresh_replicated as u64,
+            unjustified: audit
+                .unjustified
+                .iter()
+                .map(|address| address.as_bytes())
+                .collect(),
+            unknown: audit
+                .unknown
+                .iter()
+                .map(|address| address.as_bytes())
+                .collect(),
+        }))
+    }
+
     async fn k_buckets(
         &self,
         request: Request<KBucketsRequest>,
@@ -0,0 +1,226 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Durable record of the payments received by the node.
+//!
+//! Every verified payment is appended as a JSON line to a file in the node's root dir, so the
+//! earnings survive restarts. Only the lifetime total and the quotes already paid are kept in
+//! memory; the entries themselves are read back from the file when asked for.
+
+use ant_bootstrap::jsonl::JsonLinesFile;
+use ant_evm::{AttoTokens, QuoteHash};
+use ant_protocol::{NetworkAddress, storage::DataTypes};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    io,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const EARNINGS_LEDGER_FILENAME: &str = "earnings_ledger";
+
+/// A verified payment received by the node for storing a record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EarningsEntry {
+    /// Hashes of the node's own quotes covered by the payment.
+    pub quote_hashes: Vec<QuoteHash>,
+    /// Address of the record the payment was made for.
+    pub address: NetworkAddress,
+    /// Type of the record the payment was made for.
+    pub data_type: DataTypes,
+    /// Amount paid to the node.
+    pub amount: AttoTokens,
+    /// Seconds since the UNIX epoch at which the payment was verified.
+    pub timestamp: u64,
+}
+
+impl EarningsEntry {
+    /// Creates an entry for a payment verified now.
+    pub(crate) fn new(
+        quote_hashes: Vec<QuoteHash>,
+        address: NetworkAddress,
+        data_type: DataTypes,
+        amount: AttoTokens,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or_default();
+        Self {
+            quote_hashes,
+            address,
+            data_type,
+            amount,
+            timestamp,
+        }
+    }
+}
+
+/// Append-only ledger of the payments received by the node.
+///
+/// The file is only accessed off the async runtime, apart from the read when the node starts.
+#[derive(Debug)]
+pub(crate) struct EarningsLedger {
+    file: Arc<JsonLinesFile>,
+    totals: Mutex<LedgerTotals>,
+}
+
+#[derive(Debug)]
+struct LedgerTotals {
+    /// The quotes paid by the entries, so a payment verified again is only recorded once.
+    quote_hashes: HashSet<QuoteHash>,
+    lifetime_total: AttoTokens,
+}
+
+impl LedgerTotals {
+    /// Adds the entry to the totals, unless its quotes were paid by an entry already added.
+    /// Returns whether it was added.
+    fn add(&mut self, entry: &EarningsEntry) -> bool {
+        if entry
+            .quote_hashes
+            .iter()
+            .any(|quote_hash| self.quote_hashes.contains(quote_hash))
+        {
+            return false;
+        }
+        self.quote_hashes.extend(entry.quote_hashes.iter().copied());
+        self.lifetime_total = self
+            .lifetime_total
+            .checked_add(entry.amount)
+            .unwrap_or(AttoTokens::from_atto(ant_evm::U256::MAX));
+        true
+    }
+}
+
+impl EarningsLedger {
+    /// Opens the ledger kept in the given root dir. The file is created on the first append.
+    pub(crate) fn new(root_dir: &Path) -> Self {
+        let file = JsonLinesFile::new(root_dir.join(EARNINGS_LEDGER_FILENAME));
+        let mut totals = LedgerTotals {
+            quote_hashes: HashSet::new(),
+            lifetime_total: AttoTokens::zero(),
+        };
+        // Only the totals are kept, so the entries are dropped as they are read.
+        if let Err(err) = file.read(|entry: &EarningsEntry| {
+            let _ = totals.add(entry);
+            false
+        }) {
+            error!("Failed to read the earnings ledger: {err}");
+        }
+        Self {
+            file: Arc::new(file),
+            totals: Mutex::new(totals),
+        }
+    }
+
+    /// Appends an entry to the ledger, unless its quotes were paid by an entry already recorded.
+    pub(crate) async fn append(&self, entry: EarningsEntry) -> io::Result<()> {
+        let is_new = self
+            .totals
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .add(&entry);
+        if !is_new {
+            debug!(
+                "The payment for {:?} is already in the earnings ledger",
+                entry.address
+            );
+            return Ok(());
+        }
+        let file = Arc::clone(&self.file);
+        tokio::task::spawn_blocking(move || file.append(std::slice::from_ref(&entry)))
+            .await
+            .map_err(io::Error::other)?
+    }
+
+    /// Returns the entries verified at or after the given UNIX timestamp, oldest first.
+    pub(crate) async fn entries_since(&self, since_secs: u64) -> io::Result<Vec<EarningsEntry>> {
+        let file = Arc::clone(&self.file);
+        tokio::task::spawn_blocking(move || {
+            file.read(|entry: &EarningsEntry| entry.timestamp >= since_secs)
+        })
+        .await
+        .map_err(io::Error::other)?
+    }
+
+    /// Returns the total amount earned over the lifetime of the node.
+    pub(crate) fn lifetime_total(&self) -> AttoTokens {
+        self.totals
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .lifetime_total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_protocol::storage::ChunkAddress;
+    use xor_name::XorName;
+
+    fn entry(amount: u64, timestamp: u64) -> EarningsEntry {
+        EarningsEntry {
+            quote_hashes: vec![QuoteHash::repeat_byte(amount as u8)],
+            address: NetworkAddress::from(ChunkAddress::new(XorName::random(
+                &mut rand::thread_rng(),
+            ))),
+            data_type: DataTypes::Chunk,
+            amount: AttoTokens::from_u64(amount),
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn ledger_survives_reopening() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let ledger = EarningsLedger::new(temp_dir.path());
+        assert!(ledger.entries_since(0).await?.is_empty());
+
+        let first = entry(10, 100);
+        let second = entry(32, 200);
+        ledger.append(first.clone()).await?;
+        ledger.append(second.clone()).await?;
+
+        let reopened = EarningsLedger::new(temp_dir.path());
+        assert_eq!(
+            reopened.entries_since(0).await?,
+            vec![first, second.clone()]
+        );
+        assert_eq!(reopened.entries_since(150).await?, vec![second]);
+        assert_eq!(reopened.lifetime_total(), AttoTokens::from_u64(42));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn payment_verified_again_is_recorded_once() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let ledger = EarningsLedger::new(temp_dir.path());
+
+        let payment = entry(10, 100);
+        ledger.append(payment.clone()).await?;
+        ledger
+            .append(EarningsEntry {
+                timestamp: 200,
+                ..payment.clone()
+            })
+            .await?;
+
+        assert_eq!(ledger.entries_since(0).await?, vec![payment.clone()]);
+        assert_eq!(ledger.lifetime_total(), AttoTokens::from_u64(10));
+        let reopened = EarningsLedger::new(temp_dir.path());
+        assert_eq!(reopened.entries_since(0).await?, vec![payment.clone()]);
+        // The quotes paid are remembered across restarts too.
+        reopened.append(payment.clone()).await?;
+        assert_eq!(reopened.entries_since(0).await?, vec![payment]);
+        assert_eq!(reopened.lifetime_total(), AttoTokens::from_u64(10));
+        Ok(())
+    }
+}
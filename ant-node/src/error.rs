@@ -8,6 +8,8 @@
 
 use ant_protocol::PrettyPrintRecordKey;
 use libp2p::PeerId;
+use std::path::PathBuf;
+use std::sync::Arc;
 use thiserror::Error;
 
 pub(super) type Result<T, E = Error> = std::result::Result<T, E>;
@@ -58,7 +60,7 @@ pub enum PutValidationError {
     #[error("Failed to verify payment with EVM network for record: {record_key:?}. Error: {error}")]
     PaymentVerificationFailed {
         record_key: PrettyPrintRecordKey<'static>,
-        error: ant_evm::payment_vault::error::Error,
+        error: Arc<ant_evm::payment_vault::error::Error>,
     },
 
     // ---------- Chunk errors
@@ -97,6 +99,9 @@ pub enum Error {
     #[error("Failed to obtain node's current port")]
     FailedToGetNodePort,
 
+    #[error("Failed to load the quote policy from {path:?}: {reason}")]
+    QuotePolicyLoadFailed { path: PathBuf, reason: String },
+
     // ---------- Quote Errors
     #[error("The content of the payment quote is invalid")]
     InvalidQuoteContent,
@@ -27,6 +27,7 @@
 #[macro_use]
 extern crate tracing;
 
+mod earnings;
 mod error;
 mod event;
 mod log_markers;
@@ -34,35 +35,49 @@ mod log_markers;
 mod metrics;
 mod networking;
 mod node;
+mod payment_verification;
+mod provenance;
 mod put_validation;
 #[cfg(feature = "extension-module")]
 mod python;
 mod quote;
+mod quote_policy;
 mod replication;
+mod throttle;
 #[allow(missing_docs)]
 pub mod spawn;
 #[allow(missing_docs)]
 pub mod utils;
 
 pub use self::{
+    earnings::EarningsEntry,
     error::{Error, PutValidationError},
     event::{NodeEvent, NodeEventsChannel, NodeEventsReceiver},
     log_markers::Marker,
     networking::sort_peers_by_key,
     node::{NodeBuilder, PERIODIC_REPLICATION_INTERVAL_MAX_S},
+    provenance::{RecordAudit, RecordProvenance},
+    quote_policy::{
+        ConfigQuotePolicy, DefaultQuotePolicy, QuoteDecision, QuotePolicy, QuotePolicyConfig,
+    },
+    throttle::{QuietWindow, ThrottlePolicy},
 };
 pub use ant_bootstrap::{Bootstrap, BootstrapCacheStore, BootstrapConfig, InitialPeersConfig};
 
+use crate::earnings::EarningsLedger;
 use crate::error::Result;
+use crate::provenance::ProvenanceLedger;
 
-use crate::networking::Network;
-pub use crate::networking::SwarmLocalState;
-use ant_evm::RewardsAddress;
+pub use crate::networking::{BandwidthLimits, RecordInfo, SwarmLocalState};
+use crate::networking::{Network, NodeRecordStore};
+use crate::node::Node;
+use ant_evm::{AttoTokens, RewardsAddress};
 use ant_protocol::{NetworkAddress, get_port_from_multiaddr};
-use libp2p::{Multiaddr, PeerId};
+use libp2p::{Multiaddr, PeerId, kad::RecordKey};
 use std::{
     collections::{BTreeMap, HashSet},
     path::PathBuf,
+    sync::Arc,
 };
 use tokio::sync::watch;
 
@@ -75,6 +90,8 @@ pub struct RunningNode {
     node_events_channel: NodeEventsChannel,
     root_dir_path: PathBuf,
     rewards_address: RewardsAddress,
+    earnings_ledger: Arc<EarningsLedger>,
+    provenance_ledger: Arc<ProvenanceLedger>,
 }
 
 impl RunningNode {
@@ -148,6 +165,71 @@ impl RunningNode {
         Ok(addresses)
     }
 
+    /// Returns the metadata of a Record held by the node, or `None` if the node does not hold it
+    pub async fn get_record_info(&self, key: &RecordKey) -> Result<Option<RecordInfo>> {
+        let Some((mut record_info, file_path)) = self.network.get_local_record_info(key).await?
+        else {
+            return Ok(None);
+        };
+        let metadata =
+            tokio::task::spawn_blocking(move || NodeRecordStore::record_file_metadata(&file_path))
+                .await
+                .unwrap_or_else(|err| {
+                    error!("Failed to read the metadata of the record file: {err}");
+                    None
+                });
+        record_info.size = metadata.map(|(size, _)| size);
+        record_info.stored_at = metadata.and_then(|(_, stored_at)| stored_at);
+        Ok(Some(record_info))
+    }
+
+    /// Triggers a replication round with the close peers, without waiting for the periodic one
+    pub fn trigger_replication(&self) {
+        self.network.trigger_interval_replication();
+    }
+
+    /// Triggers the cleanup of the Records out of the node's responsible range.
+    /// The cleanup only removes Records once the node has accumulated too many of them.
+    pub fn trigger_cleanup(&self) {
+        self.network.trigger_irrelevant_record_cleanup();
+    }
+
+    /// Challenges the close peers to prove they hold the Records they are responsible for,
+    /// and returns whether each challenged peer passed.
+    pub async fn run_storage_challenge(&self) -> Vec<(PeerId, bool)> {
+        Node::storage_challenge(self.network.clone()).await
+    }
+
+    /// Returns the payments received by the node at or after the given UNIX timestamp, oldest first.
+    /// These are kept across restarts.
+    pub async fn get_earnings(&self, since_secs: u64) -> Vec<EarningsEntry> {
+        self.earnings_ledger
+            .entries_since(since_secs)
+            .await
+            .unwrap_or_else(|err| {
+                error!("Failed to read the earnings ledger: {err}");
+                vec![]
+            })
+    }
+
+    /// Returns the total amount received by the node over its lifetime.
+    pub fn get_lifetime_earnings(&self) -> AttoTokens {
+        self.earnings_ledger.lifetime_total()
+    }
+
+    /// Returns how the node came to hold the record, if known.
+    pub fn get_record_provenance(&self, key: &RecordKey) -> Option<RecordProvenance> {
+        self.provenance_ledger.provenance(key)
+    }
+
+    /// Audits the records held by the node against the provenance kept for them, counting them
+    /// per provenance and flagging the ones the node has no justification for holding.
+    pub async fn audit_records(&self) -> Result<RecordAudit> {
+        #[allow(clippy::mutable_key_type)] // for Bytes in NetworkAddress
+        let addresses = self.network.get_all_local_record_addresses().await?;
+        Ok(self.provenance_ledger.audit(addresses.keys()).await)
+    }
+
     /// Returns a map where each key is the ilog2 distance of that Kbucket and each value is a vector of peers in that
     /// bucket.
     pub async fn get_kbuckets(&self) -> Result<BTreeMap<u32, Vec<PeerId>>> {
@@ -38,6 +38,11 @@ pub(crate) struct NodeMetricsRecorder {
     peer_added_to_routing_table: Counter,
     peer_removed_from_routing_table: Counter,
 
+    // payment verification
+    pub(crate) payment_verification_cache_hits: Counter,
+    pub(crate) payment_verification_cache_misses: Counter,
+    pub(crate) payment_verification_rpc_duration: Histogram,
+
     // wallet
     pub(crate) current_reward_wallet_balance: Gauge,
     pub(crate) _total_forwarded_rewards: Gauge,
@@ -118,6 +123,28 @@ impl NodeMetricsRecorder {
             peer_removed_from_routing_table.clone(),
         );
 
+        let payment_verification_cache_hits = Counter::default();
+        sub_registry.register(
+            "payment_verification_cache_hits",
+            "Number of payment verifications answered from the cache of verified payments",
+            payment_verification_cache_hits.clone(),
+        );
+
+        let payment_verification_cache_misses = Counter::default();
+        sub_registry.register(
+            "payment_verification_cache_misses",
+            "Number of payment verifications that had to query the EVM network",
+            payment_verification_cache_misses.clone(),
+        );
+
+        // From 50ms up to ~25s
+        let payment_verification_rpc_duration = Histogram::new(exponential_buckets(0.05, 2.0, 10));
+        sub_registry.register(
+            "payment_verification_rpc_duration_seconds",
+            "Time taken by the EVM network to verify a payment",
+            payment_verification_rpc_duration.clone(),
+        );
+
         let current_reward_wallet_balance = Gauge::default();
         sub_registry.register(
             "current_reward_wallet_balance",
@@ -147,6 +174,9 @@ impl NodeMetricsRecorder {
             replication_keys_to_fetch,
             peer_added_to_routing_table,
             peer_removed_from_routing_table,
+            payment_verification_cache_hits,
+            payment_verification_cache_misses,
+            payment_verification_rpc_duration,
             current_reward_wallet_balance,
             _total_forwarded_rewards: total_forwarded_rewards,
             started_instant: Instant::now(),
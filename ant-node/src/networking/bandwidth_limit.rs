@@ -0,0 +1,571 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Token bucket based bandwidth limiting.
+//!
+//! Every substream opened over a limited connection draws from a global bucket shared by all
+//! connections, and from a bucket shared by all connections to the same peer. A stream that finds
+//! one of its buckets empty is parked until enough tokens have been refilled.
+//!
+//! The limiter holds a second, tighter set of limits which is switched to while the node is
+//! throttled.
+
+use futures::{AsyncRead, AsyncWrite, ready};
+use libp2p::{
+    PeerId, Transport,
+    core::{
+        muxing::{StreamMuxer, StreamMuxerBox, StreamMuxerEvent, StreamMuxerExt, SubstreamBox},
+        transport::Boxed,
+    },
+};
+use std::{
+    collections::HashMap,
+    io,
+    pin::Pin,
+    sync::{
+        Arc, Mutex, MutexGuard, Weak,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::time::Sleep;
+
+/// A stream having been throttled within this window means the node is running at its
+/// bandwidth budget, and background traffic such as replication shall back off.
+const CONGESTION_WINDOW: Duration = Duration::from_secs(5);
+
+/// Upload and download rate limits of a node, in bytes per second.
+///
+/// `None` leaves the corresponding direction unlimited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BandwidthLimits {
+    /// Upload limit across all connections.
+    pub upload: Option<u64>,
+    /// Download limit across all connections.
+    pub download: Option<u64>,
+    /// Upload limit towards a single peer.
+    pub peer_upload: Option<u64>,
+    /// Download limit from a single peer.
+    pub peer_download: Option<u64>,
+}
+
+impl BandwidthLimits {
+    /// Returns true if none of the limits are set.
+    pub fn is_unlimited(&self) -> bool {
+        self.upload.is_none()
+            && self.download.is_none()
+            && self.peer_upload.is_none()
+            && self.peer_download.is_none()
+    }
+
+    /// Returns the stricter of both limits, for each of the directions.
+    pub fn tightened(&self, other: &BandwidthLimits) -> BandwidthLimits {
+        let min = |a: Option<u64>, b: Option<u64>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        BandwidthLimits {
+            upload: min(self.upload, other.upload),
+            download: min(self.download, other.download),
+            peer_upload: min(self.peer_upload, other.peer_upload),
+            peer_download: min(self.peer_download, other.peer_download),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Direction {
+    Upload,
+    Download,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    /// Refill rate, in bytes per second.
+    rate: f64,
+    /// Tokens available. Can go negative when a read overdraws the bucket,
+    /// in which case the debt has to be paid back before any further traffic.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64) -> Self {
+        // Allow a burst of up to one second worth of traffic.
+        let rate = rate.max(1) as f64;
+        Self {
+            rate,
+            tokens: rate,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last_refill = now;
+    }
+
+    /// Returns how long to wait until the bucket has tokens again, if it is empty.
+    fn delay(&mut self, now: Instant) -> Option<Duration> {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+
+    fn available(&self) -> usize {
+        self.tokens.max(0.0) as usize
+    }
+
+    fn consume(&mut self, bytes: usize) {
+        self.tokens -= bytes as f64;
+    }
+}
+
+/// The pair of buckets limiting a scope, i.e. the whole node or a single peer.
+/// A direction without a bucket is unlimited.
+#[derive(Debug, Default)]
+struct Buckets {
+    upload: Mutex<Option<TokenBucket>>,
+    download: Mutex<Option<TokenBucket>>,
+}
+
+impl Buckets {
+    fn new(upload: Option<u64>, download: Option<u64>) -> Self {
+        Self {
+            upload: Mutex::new(upload.map(TokenBucket::new)),
+            download: Mutex::new(download.map(TokenBucket::new)),
+        }
+    }
+
+    fn bucket(&self, direction: Direction) -> MutexGuard<'_, Option<TokenBucket>> {
+        let bucket = match direction {
+            Direction::Upload => &self.upload,
+            Direction::Download => &self.download,
+        };
+        // A poisoned bucket still holds a usable token count.
+        bucket
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Replaces the buckets whose rate has changed. The new buckets start full.
+    fn set_rates(&self, upload: Option<u64>, download: Option<u64>) {
+        for (direction, rate) in [(Direction::Upload, upload), (Direction::Download, download)] {
+            let mut bucket = self.bucket(direction);
+            let current = bucket.as_ref().map(|bucket| bucket.rate as u64);
+            if current != rate.map(|rate| rate.max(1)) {
+                *bucket = rate.map(TokenBucket::new);
+            }
+        }
+    }
+}
+
+/// Shared handle to the bandwidth budget of the node.
+#[derive(Clone, Debug)]
+pub(crate) struct BandwidthLimiter {
+    limits: BandwidthLimits,
+    throttled_limits: BandwidthLimits,
+    is_throttled: Arc<AtomicBool>,
+    global: Arc<Buckets>,
+    peers: Arc<Mutex<HashMap<PeerId, Weak<Buckets>>>>,
+    last_throttled: Arc<Mutex<Option<Instant>>>,
+}
+
+impl BandwidthLimiter {
+    /// Creates a limiter enforcing `limits`, which switches to `throttled_limits` on top of
+    /// them while the node is throttled.
+    pub(crate) fn new(limits: BandwidthLimits, throttled_limits: BandwidthLimits) -> Self {
+        Self {
+            limits,
+            throttled_limits: limits.tightened(&throttled_limits),
+            is_throttled: Arc::new(AtomicBool::new(false)),
+            global: Arc::new(Buckets::new(limits.upload, limits.download)),
+            peers: Arc::new(Mutex::new(HashMap::new())),
+            last_throttled: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Switches between the regular and the throttled limits, for all connections.
+    pub(crate) fn set_throttled(&self, throttled: bool) {
+        if self.is_throttled.swap(throttled, Ordering::Relaxed) == throttled {
+            return;
+        }
+        let limits = self.current_limits();
+        info!("Switching bandwidth limits to {limits:?}");
+
+        self.global.set_rates(limits.upload, limits.download);
+        let peers = self
+            .peers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for buckets in peers.values().filter_map(Weak::upgrade) {
+            buckets.set_rates(limits.peer_upload, limits.peer_download);
+        }
+    }
+
+    fn current_limits(&self) -> BandwidthLimits {
+        if self.is_throttled.load(Ordering::Relaxed) {
+            self.throttled_limits
+        } else {
+            self.limits
+        }
+    }
+
+    /// Returns true if traffic has recently been held back to stay within the limits.
+    pub(crate) fn is_congested(&self) -> bool {
+        self.last_throttled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .is_some_and(|at| at.elapsed() < CONGESTION_WINDOW)
+    }
+
+    /// Wraps the transport so that all connections it produces are subject to the limits.
+    pub(crate) fn limit_transport(
+        &self,
+        transport: Boxed<(PeerId, StreamMuxerBox)>,
+    ) -> Boxed<(PeerId, StreamMuxerBox)> {
+        let limiter = self.clone();
+        transport
+            .map(move |(peer_id, muxer), _| {
+                let muxer = LimitedMuxer {
+                    inner: muxer,
+                    buckets: [Arc::clone(&limiter.global), limiter.peer_buckets(peer_id)],
+                    limiter: limiter.clone(),
+                };
+                (peer_id, StreamMuxerBox::new(muxer))
+            })
+            .boxed()
+    }
+
+    /// Returns the buckets of the peer, shared by all of the connections to it.
+    fn peer_buckets(&self, peer_id: PeerId) -> Arc<Buckets> {
+        // Per peer limits only ever get tighter when throttled.
+        if self.throttled_limits.peer_upload.is_none()
+            && self.throttled_limits.peer_download.is_none()
+        {
+            return Arc::new(Buckets::default());
+        }
+
+        let mut peers = self
+            .peers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(buckets) = peers.get(&peer_id).and_then(Weak::upgrade) {
+            return buckets;
+        }
+
+        // Drop the entries of peers we are no longer connected to.
+        peers.retain(|_, buckets| buckets.strong_count() > 0);
+        let limits = self.current_limits();
+        let buckets = Arc::new(Buckets::new(limits.peer_upload, limits.peer_download));
+        let _ = peers.insert(peer_id, Arc::downgrade(&buckets));
+        buckets
+    }
+
+    fn mark_throttled(&self) {
+        *self
+            .last_throttled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Instant::now());
+    }
+}
+
+struct LimitedMuxer {
+    inner: StreamMuxerBox,
+    buckets: [Arc<Buckets>; 2],
+    limiter: BandwidthLimiter,
+}
+
+impl LimitedMuxer {
+    fn wrap(&self, inner: SubstreamBox) -> LimitedStream {
+        LimitedStream {
+            inner,
+            buckets: self.buckets.clone(),
+            limiter: self.limiter.clone(),
+            read_delay: None,
+            write_delay: None,
+        }
+    }
+}
+
+impl StreamMuxer for LimitedMuxer {
+    type Substream = LimitedStream;
+    type Error = io::Error;
+
+    fn poll_inbound(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Self::Substream, Self::Error>> {
+        let this = self.get_mut();
+        let inner = ready!(this.inner.poll_inbound_unpin(cx))?;
+        Poll::Ready(Ok(this.wrap(inner)))
+    }
+
+    fn poll_outbound(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Self::Substream, Self::Error>> {
+        let this = self.get_mut();
+        let inner = ready!(this.inner.poll_outbound_unpin(cx))?;
+        Poll::Ready(Ok(this.wrap(inner)))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().inner.poll_close_unpin(cx)
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<StreamMuxerEvent, Self::Error>> {
+        self.get_mut().inner.poll_unpin(cx)
+    }
+}
+
+struct LimitedStream {
+    inner: SubstreamBox,
+    buckets: [Arc<Buckets>; 2],
+    limiter: BandwidthLimiter,
+    read_delay: Option<Pin<Box<Sleep>>>,
+    write_delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl LimitedStream {
+    /// Waits until all the buckets of the direction have tokens available,
+    /// then returns the number of bytes that can be transferred right away.
+    fn poll_budget(&mut self, cx: &mut Context<'_>, direction: Direction) -> Poll<usize> {
+        loop {
+            let delay = match direction {
+                Direction::Upload => &mut self.write_delay,
+                Direction::Download => &mut self.read_delay,
+            };
+            if let Some(sleep) = delay.as_mut() {
+                ready!(sleep.as_mut().poll(cx));
+                *delay = None;
+            }
+
+            let now = Instant::now();
+            let mut wait = None;
+            let mut available = usize::MAX;
+            for buckets in &self.buckets {
+                if let Some(bucket) = buckets.bucket(direction).as_mut() {
+                    match bucket.delay(now) {
+                        Some(bucket_wait) => wait = wait.max(Some(bucket_wait)),
+                        None => available = available.min(bucket.available()),
+                    }
+                }
+            }
+
+            match wait {
+                Some(wait) => {
+                    self.limiter.mark_throttled();
+                    *delay = Some(Box::pin(tokio::time::sleep(wait)));
+                }
+                None => return Poll::Ready(available),
+            }
+        }
+    }
+
+    fn consume(&self, direction: Direction, bytes: usize) {
+        for buckets in &self.buckets {
+            if let Some(bucket) = buckets.bucket(direction).as_mut() {
+                bucket.consume(bytes);
+            }
+        }
+    }
+}
+
+impl AsyncRead for LimitedStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        // The amount of data that arrives is up to the sender, hence the read is allowed to
+        // overdraw the buckets. The debt is paid back by delaying the next read.
+        let _ = ready!(this.poll_budget(cx, Direction::Download));
+        let read = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.consume(Direction::Download, read);
+        Poll::Ready(Ok(read))
+    }
+}
+
+impl AsyncWrite for LimitedStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let available = ready!(this.poll_budget(cx, Direction::Upload));
+        let len = buf.len().min(available.max(1));
+        let written = ready!(Pin::new(&mut this.inner).poll_write(cx, &buf[..len]))?;
+        this.consume(Direction::Upload, written);
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket_refills_at_rate_up_to_one_second_burst() {
+        let mut bucket = TokenBucket::new(1000);
+        let start = bucket.last_refill;
+        assert_eq!(bucket.delay(start), None);
+        assert_eq!(bucket.available(), 1000);
+
+        bucket.consume(1500);
+        // 500 bytes of debt plus one byte, at 1000 bytes per second.
+        let wait = bucket.delay(start).expect("bucket is in debt");
+        assert!(wait > Duration::from_millis(500) && wait < Duration::from_millis(502));
+
+        assert_eq!(bucket.delay(start + Duration::from_secs(1)), None);
+        assert_eq!(bucket.available(), 500);
+
+        // Idle time does not accumulate beyond the burst size.
+        assert_eq!(bucket.delay(start + Duration::from_secs(10)), None);
+        assert_eq!(bucket.available(), 1000);
+    }
+
+    #[test]
+    fn peer_buckets_are_shared_while_the_peer_is_connected() {
+        let limiter = BandwidthLimiter::new(
+            BandwidthLimits {
+                peer_upload: Some(1000),
+                ..Default::default()
+            },
+            BandwidthLimits::default(),
+        );
+        let peer_id = PeerId::random();
+
+        let first = limiter.peer_buckets(peer_id);
+        let second = limiter.peer_buckets(peer_id);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(first.bucket(Direction::Upload).is_some());
+        assert!(first.bucket(Direction::Download).is_none());
+
+        drop(first);
+        drop(second);
+        let _ = limiter.peer_buckets(PeerId::random());
+        assert_eq!(limiter.peers.lock().expect("not poisoned").len(), 1);
+    }
+
+    /// In memory stream that swallows everything written to it.
+    #[derive(Default)]
+    struct Sink;
+
+    impl AsyncRead for Sink {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            _: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(Ok(0))
+        }
+    }
+
+    impl AsyncWrite for Sink {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_are_throttled_once_the_budget_is_spent() {
+        use futures::AsyncWriteExt;
+
+        let limiter = BandwidthLimiter::new(
+            BandwidthLimits {
+                upload: Some(10_000),
+                ..Default::default()
+            },
+            BandwidthLimits::default(),
+        );
+        let mut stream = LimitedStream {
+            inner: SubstreamBox::new(Sink),
+            buckets: [
+                Arc::clone(&limiter.global),
+                limiter.peer_buckets(PeerId::random()),
+            ],
+            limiter: limiter.clone(),
+            read_delay: None,
+            write_delay: None,
+        };
+
+        // The first second worth of data goes out as a burst.
+        stream.write_all(&[0u8; 10_000]).await.expect("write");
+        assert!(!limiter.is_congested());
+
+        let start = Instant::now();
+        stream.write_all(&[0u8; 5_000]).await.expect("write");
+        assert!(start.elapsed() >= Duration::from_millis(450));
+        assert!(limiter.is_congested());
+    }
+
+    #[test]
+    fn throttling_switches_live_buckets_to_the_tighter_limits() {
+        let limiter = BandwidthLimiter::new(
+            BandwidthLimits {
+                upload: Some(10_000),
+                peer_download: Some(5_000),
+                ..Default::default()
+            },
+            BandwidthLimits {
+                upload: Some(20_000),
+                download: Some(1_000),
+                ..Default::default()
+            },
+        );
+        let peer = limiter.peer_buckets(PeerId::random());
+        let rate = |buckets: &Buckets, direction| {
+            buckets
+                .bucket(direction)
+                .as_ref()
+                .map(|bucket| bucket.rate as u64)
+        };
+
+        limiter.set_throttled(true);
+        assert_eq!(rate(&limiter.global, Direction::Upload), Some(10_000));
+        assert_eq!(rate(&limiter.global, Direction::Download), Some(1_000));
+        assert_eq!(rate(&peer, Direction::Download), Some(5_000));
+
+        limiter.set_throttled(false);
+        assert_eq!(rate(&limiter.global, Direction::Upload), Some(10_000));
+        assert_eq!(rate(&limiter.global, Direction::Download), None);
+    }
+}
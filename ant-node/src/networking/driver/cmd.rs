@@ -7,7 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::networking::{
-    Addresses, CLOSE_GROUP_SIZE, NetworkEvent, NodeIssue, SwarmLocalState,
+    Addresses, CLOSE_GROUP_SIZE, NetworkEvent, NodeIssue, RecordInfo, SwarmLocalState,
     driver::{PendingGetClosestType, SwarmDriver, event::MsgResponder},
     error::{NetworkError, Result},
     interface::{LocalSwarmCmd, NetworkSwarmCmd, TerminateNodeReason},
@@ -228,6 +228,36 @@ impl SwarmDriver {
                     .map(|rec| rec.into_owned());
                 let _ = sender.send(record);
             }
+            LocalSwarmCmd::GetLocalRecordInfo { key, sender } => {
+                cmd_string = "GetLocalRecordInfo";
+                let store = self.swarm.behaviour_mut().kademlia.store_mut();
+                let stored = store
+                    .record_addresses_ref()
+                    .get(&key)
+                    .cloned()
+                    .map(|entry| (entry, store.record_file_path(&key)));
+                // The file metadata is left to the caller, to keep the disk off the driver loop.
+                let record_info = stored.map(|((address, record_type, data_type), file_path)| {
+                    let holders = self
+                        .swarm
+                        .behaviour_mut()
+                        .kademlia
+                        .get_closest_local_peers(&address.as_kbucket_key())
+                        .map(|key| key.into_preimage())
+                        .take(CLOSE_GROUP_SIZE)
+                        .collect();
+                    let record_info = RecordInfo {
+                        address,
+                        record_type,
+                        data_type,
+                        size: None,
+                        stored_at: None,
+                        holders,
+                    };
+                    (record_info, file_path)
+                });
+                let _ = sender.send(record_info);
+            }
 
             LocalSwarmCmd::PutLocalRecord {
                 record,
@@ -489,6 +519,10 @@ impl SwarmDriver {
                 cmd_string = "NotifyPeerVersion";
                 self.record_node_version(peer, version);
             }
+            LocalSwarmCmd::GetPeersVersion { sender } => {
+                cmd_string = "GetPeersVersion";
+                let _ = sender.send(self.peers_version.clone());
+            }
             LocalSwarmCmd::GetNetworkDensity { sender } => {
                 cmd_string = "GetNetworkDensity";
                 let density = self
@@ -505,6 +539,10 @@ impl SwarmDriver {
                     self.update_on_peer_removal(*dead_peer.node.key.preimage());
                 }
             }
+            LocalSwarmCmd::SetThrottled { throttled } => {
+                cmd_string = "SetThrottled";
+                self.set_throttled(throttled);
+            }
         }
 
         self.log_handling(cmd_string.to_string(), start.elapsed());
@@ -125,13 +125,25 @@ impl SwarmDriver {
         let has_dialed = self.dialed_peers.contains(&peer_id);
         let is_relayed_peer = is_a_relayed_peer(info.listen_addrs.iter());
         let addrs = if !is_relayed_peer {
-            let addr = craft_valid_multiaddr_without_p2p(addr_fom_connection);
-            let Some(addr) = addr else {
+            let mut addrs: Vec<Multiaddr> = craft_valid_multiaddr_without_p2p(addr_fom_connection)
+                .into_iter()
+                .collect();
+            // The TCP and WebSocket listeners cannot be deduced from the connection,
+            // hence use the ones advertised by the peer, as long as they share the connection's IP.
+            addrs.extend(
+                info.listen_addrs
+                    .iter()
+                    .filter_map(|addr| {
+                        craft_valid_stream_multiaddr_without_p2p(addr, addr_fom_connection)
+                    })
+                    .unique(),
+            );
+            if addrs.is_empty() {
                 warn!("identify: no valid multiaddr found for {peer_id:?} on {connection_id:?}");
                 return;
-            };
-            debug!("Peer {peer_id:?} is a normal peer, crafted valid multiaddress : {addr:?}.");
-            vec![addr]
+            }
+            debug!("Peer {peer_id:?} is a normal peer, crafted valid multiaddresses : {addrs:?}.");
+            addrs
         } else {
             let p2p_addrs = info
                 .listen_addrs
@@ -404,6 +416,40 @@ fn craft_valid_multiaddr_without_p2p(addr: &Multiaddr) -> Option<Multiaddr> {
     Some(new_multiaddr)
 }
 
+/// Craft valid multiaddr like /ip4/68.183.39.80/tcp/31055 or /ip4/68.183.39.80/tcp/31055/ws
+/// from an advertised listen addr, only if it has the same IP as the connection.
+fn craft_valid_stream_multiaddr_without_p2p(
+    addr: &Multiaddr,
+    addr_from_connection: &Multiaddr,
+) -> Option<Multiaddr> {
+    if addr.iter().any(|p| matches!(p, Protocol::P2pCircuit)) {
+        return None;
+    }
+    let get_ip = |addr: &Multiaddr| {
+        addr.iter().find_map(|p| match p {
+            Protocol::Ip4(ip) => Some(ip),
+            _ => None,
+        })
+    };
+    let ip = get_ip(addr)?;
+    if Some(ip) != get_ip(addr_from_connection) {
+        return None;
+    }
+    let port = addr.iter().find_map(|p| match p {
+        Protocol::Tcp(port) => Some(port),
+        _ => None,
+    })?;
+
+    let mut new_multiaddr = Multiaddr::empty();
+    new_multiaddr.push(Protocol::Ip4(ip));
+    new_multiaddr.push(Protocol::Tcp(port));
+    if let Some(ws) = addr.iter().find(|p| matches!(p, Protocol::Ws(_))) {
+        new_multiaddr.push(ws);
+    }
+
+    Some(new_multiaddr)
+}
+
 /// Build a `Multiaddr` with the p2p protocol filtered out.
 /// If it is a relayed address, then the relay's P2P address is preserved.
 fn multiaddr_strip_p2p(multiaddr: &Multiaddr) -> Multiaddr {
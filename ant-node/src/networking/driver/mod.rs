@@ -18,9 +18,10 @@ use network_discovery::{NETWORK_DISCOVER_INTERVAL, NetworkDiscovery};
 #[cfg(feature = "open-metrics")]
 use crate::networking::metrics::NetworkMetricsRecorder;
 use crate::networking::{
-    Addresses, CLOSE_GROUP_SIZE, NodeIssue, NodeRecordStore, circular_vec::CircularVec,
-    driver::kad::U256, error::Result, external_address::ExternalAddressManager,
-    log_markers::Marker, relay_manager::RelayManager, replication_fetcher::ReplicationFetcher,
+    Addresses, BandwidthLimiter, CLOSE_GROUP_SIZE, NodeIssue, NodeRecordStore,
+    circular_vec::CircularVec, driver::kad::U256, error::Result,
+    external_address::ExternalAddressManager, log_markers::Marker, relay_manager::RelayManager,
+    replication_fetcher::ReplicationFetcher,
 };
 use ant_bootstrap::bootstrap::Bootstrap;
 use ant_evm::PaymentQuote;
@@ -69,6 +70,11 @@ const DIAL_QUEUE_CHECK_INTERVAL: Duration = Duration::from_secs(2);
 pub(crate) const BOOTSTRAP_CHECK_INTERVAL: std::time::Duration =
     std::time::Duration::from_millis(100);
 
+/// While throttled, close peers are asked again not to dial us ahead of their previous
+/// do-not-disturb request expiring.
+const DO_NOT_DISTURB_REFRESH_INTERVAL: Duration =
+    Duration::from_secs(behaviour::do_not_disturb::MAX_DO_NOT_DISTURB_DURATION - 60);
+
 /// The ways in which the Get Closest queries are used.
 pub(crate) enum PendingGetClosestType {
     /// The network discovery method is present at the networking layer
@@ -120,6 +126,10 @@ pub(crate) struct SwarmDriver {
     pub(crate) connected_relay_clients: HashSet<PeerId>,
     /// The peers that are closer to our PeerId. Includes self.
     pub(crate) replication_fetcher: ReplicationFetcher,
+    /// Bandwidth budget of the node, if limited.
+    pub(crate) bandwidth_limiter: Option<BandwidthLimiter>,
+    /// When close peers were last asked not to dial us, while the node is throttled.
+    pub(crate) last_do_not_disturb_request: Option<Instant>,
     #[cfg(feature = "open-metrics")]
     pub(crate) metrics_recorder: Option<NetworkMetricsRecorder>,
 
@@ -500,6 +510,51 @@ impl SwarmDriver {
         }
     }
 
+    /// Switch the node in or out of its throttled state.
+    ///
+    /// While throttled, the throttled bandwidth limits apply, replication fetches one record at
+    /// a time, and connected close peers are asked not to dial us.
+    pub(crate) fn set_throttled(&mut self, throttled: bool) {
+        if let Some(limiter) = &self.bandwidth_limiter {
+            limiter.set_throttled(throttled);
+        }
+        self.replication_fetcher.set_throttled(throttled);
+
+        if !throttled {
+            // The outstanding requests expire on their own.
+            self.last_do_not_disturb_request = None;
+            return;
+        }
+        if self
+            .last_do_not_disturb_request
+            .is_some_and(|at| at.elapsed() < DO_NOT_DISTURB_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.last_do_not_disturb_request = Some(Instant::now());
+
+        let close_peers = self
+            .get_closest_k_local_peers_to_self()
+            .into_iter()
+            .map(|(peer_id, _)| peer_id)
+            .filter(|peer_id| *peer_id != self.self_peer_id && self.swarm.is_connected(peer_id))
+            .take(CLOSE_GROUP_SIZE)
+            .collect::<Vec<_>>();
+        debug!(
+            "Throttled, asking {} close peers not to dial us",
+            close_peers.len()
+        );
+        for peer_id in close_peers {
+            self.swarm
+                .behaviour_mut()
+                .do_not_disturb
+                .send_do_not_disturb_request(
+                    peer_id,
+                    behaviour::do_not_disturb::MAX_DO_NOT_DISTURB_DURATION,
+                );
+        }
+    }
+
     /// Listen on the provided address. Also records it within RelayManager
     pub(crate) fn listen_on(&mut self, addr: Multiaddr) -> Result<()> {
         let id = self.swarm.listen_on(addr.clone())?;
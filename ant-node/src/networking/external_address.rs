@@ -396,9 +396,10 @@ impl ExternalAddressManager {
         Self::print_swarm_state(swarm);
     }
 
-    /// Craft a proper address Ws or Quic address to avoid any ill formed addresses
+    /// Craft a proper address Ws, Tcp or Quic address to avoid any ill formed addresses
     /// Example:
     /// /ip4/131.131.131.131/tcp/53620/ws/p2p/12D3KooWD2aV1f3qkhggzEFaJ24CEFYkSdZF5RKoMLpU6CwExYV5
+    /// /ip4/131.131.131.131/tcp/53620/p2p/12D3KooWD2aV1f3qkhggzEFaJ24CEFYkSdZF5RKoMLpU6CwExYV5
     /// /ip4/131.131.131.131/udp/53620/quic-v1/p2p/12D3KooWD2aV1f3qkhggzEFaJ24CEFYkSdZF5RKoMLpU6CwExYV5
     fn craft_external_address(&self, given_address: &Multiaddr) -> Option<Multiaddr> {
         let mut output_address = Multiaddr::empty();
@@ -426,6 +427,11 @@ impl ExternalAddressManager {
                 .find(|protocol| matches!(protocol, Protocol::Udp(_)))?;
             output_address.push(port);
             output_address.push(Protocol::QuicV1);
+        } else if let Some(port) = given_address
+            .iter()
+            .find(|protocol| matches!(protocol, Protocol::Tcp(_)))
+        {
+            output_address.push(port);
         } else {
             return None;
         }
@@ -9,6 +9,8 @@
 use std::{
     collections::{BTreeMap, HashMap},
     fmt::Debug,
+    path::PathBuf,
+    time::SystemTime,
 };
 
 use ant_evm::{PaymentQuote, QuotingMetrics};
@@ -86,6 +88,12 @@ pub(crate) enum LocalSwarmCmd {
         key: RecordKey,
         sender: oneshot::Sender<Option<Record>>,
     },
+    /// Get the metadata of a record held in the local RecordStore, along with the path of its
+    /// file, whose metadata is left for the caller to read off the disk
+    GetLocalRecordInfo {
+        key: RecordKey,
+        sender: oneshot::Sender<Option<(RecordInfo, PathBuf)>>,
+    },
     /// GetLocalQuotingMetrics for this node
     /// Returns the quoting metrics and whether the record at `key` is already stored locally
     GetLocalQuotingMetrics {
@@ -153,6 +161,10 @@ pub(crate) enum LocalSwarmCmd {
         peer: PeerId,
         version: String,
     },
+    /// Get the versions fetched from the peers
+    GetPeersVersion {
+        sender: oneshot::Sender<HashMap<PeerId, String>>,
+    },
     /// Get responsible distance range.
     GetNetworkDensity {
         sender: oneshot::Sender<Option<Distance>>,
@@ -161,6 +173,10 @@ pub(crate) enum LocalSwarmCmd {
     RemovePeer {
         peer: PeerId,
     },
+    /// Switch the node in or out of its throttled state
+    SetThrottled {
+        throttled: bool,
+    },
 }
 
 /// Debug impl for LocalSwarmCmd to avoid printing full Record, instead only RecodKey
@@ -215,6 +231,13 @@ impl Debug for LocalSwarmCmd {
                     PrettyPrintRecordKey::from(key)
                 )
             }
+            LocalSwarmCmd::GetLocalRecordInfo { key, .. } => {
+                write!(
+                    f,
+                    "LocalSwarmCmd::GetLocalRecordInfo {{ key: {:?} }}",
+                    PrettyPrintRecordKey::from(key)
+                )
+            }
             LocalSwarmCmd::GetAllLocalRecordAddresses { .. } => {
                 write!(f, "LocalSwarmCmd::GetAllLocalRecordAddresses")
             }
@@ -278,12 +301,18 @@ impl Debug for LocalSwarmCmd {
             LocalSwarmCmd::NotifyPeerVersion { peer, version } => {
                 write!(f, "LocalSwarmCmd::NotifyPeerVersion({peer:?}, {version:?})")
             }
+            LocalSwarmCmd::GetPeersVersion { .. } => {
+                write!(f, "LocalSwarmCmd::GetPeersVersion")
+            }
             LocalSwarmCmd::GetNetworkDensity { .. } => {
                 write!(f, "LocalSwarmCmd::GetNetworkDensity")
             }
             LocalSwarmCmd::RemovePeer { peer } => {
                 write!(f, "LocalSwarmCmd::RemovePeer({peer:?})")
             }
+            LocalSwarmCmd::SetThrottled { throttled } => {
+                write!(f, "LocalSwarmCmd::SetThrottled({throttled:?})")
+            }
         }
     }
 }
@@ -298,3 +327,20 @@ pub struct SwarmLocalState {
     /// List of addresses the node is currently listening on
     pub listeners: Vec<Multiaddr>,
 }
+
+/// Metadata of a record held in the local RecordStore
+#[derive(Debug, Clone)]
+pub struct RecordInfo {
+    /// The address of the record
+    pub address: NetworkAddress,
+    /// How the record gets validated, with the content hash of non-chunk records
+    pub record_type: ValidationType,
+    /// The type of data held by the record
+    pub data_type: DataTypes,
+    /// Size of the encrypted record file, or `None` if the file is missing from disk
+    pub size: Option<u64>,
+    /// When the record file was last written to disk
+    pub stored_at: Option<SystemTime>,
+    /// The closest peers to the record in the local RoutingTable, expected to hold it as well
+    pub holders: Vec<PeerId>,
+}
@@ -10,7 +10,7 @@ mod local_cmd;
 mod network_cmd;
 mod network_event;
 
-pub use local_cmd::SwarmLocalState;
 pub(crate) use local_cmd::{LocalSwarmCmd, NodeIssue};
+pub use local_cmd::{RecordInfo, SwarmLocalState};
 pub(crate) use network_cmd::NetworkSwarmCmd;
 pub(crate) use network_event::{NetworkEvent, TerminateNodeReason};
@@ -9,6 +9,7 @@
 #![allow(clippy::large_enum_variant)]
 #![allow(clippy::result_large_err)]
 
+mod bandwidth_limit;
 mod circular_vec;
 mod driver;
 mod error;
@@ -24,13 +25,17 @@ mod replication_fetcher;
 mod transport;
 
 // re-export arch dependent deps for use in the crate, or above
-pub use self::interface::SwarmLocalState;
 pub(crate) use self::{
+    bandwidth_limit::BandwidthLimiter,
     error::NetworkError,
     interface::{NetworkEvent, NodeIssue},
     network::{Network, NetworkConfig},
     record_store::NodeRecordStore,
 };
+pub use self::{
+    bandwidth_limit::BandwidthLimits,
+    interface::{RecordInfo, SwarmLocalState},
+};
 
 #[cfg(feature = "open-metrics")]
 pub(crate) use metrics::service::MetricsRegistries;
@@ -9,7 +9,7 @@
 use ant_protocol::constants::{KAD_STREAM_PROTOCOL_ID, MAX_PACKET_SIZE, REPLICATION_FACTOR};
 
 use crate::networking::{
-    CLOSE_GROUP_SIZE, NetworkEvent,
+    BandwidthLimiter, BandwidthLimits, CLOSE_GROUP_SIZE, NetworkEvent,
     circular_vec::CircularVec,
     driver::{
         InitialBootstrapTrigger, NodeBehaviour, SwarmDriver, network_discovery::NetworkDiscovery,
@@ -76,6 +76,14 @@ pub(crate) struct NetworkConfig {
     pub keypair: Keypair,
     pub local: bool,
     pub listen_addr: SocketAddr,
+    /// Port to listen on with TCP, alongside QUIC. `None` to disable TCP.
+    pub tcp_port: Option<u16>,
+    /// Port to listen on with WebSocket, alongside QUIC. `None` to disable WebSocket.
+    pub ws_port: Option<u16>,
+    /// Upload and download rate limits of the node.
+    pub bandwidth_limits: BandwidthLimits,
+    /// Rate limits applied on top of `bandwidth_limits` while the node is throttled.
+    pub throttled_bandwidth_limits: BandwidthLimits,
     pub root_dir: PathBuf,
     pub shutdown_rx: tokio::sync::watch::Receiver<bool>,
     pub bootstrap: Bootstrap,
@@ -170,6 +178,8 @@ pub(super) fn init_driver(
 
     // Listen on the provided address
     let listen_socket_addr = config.listen_addr;
+    let tcp_port = config.tcp_port;
+    let ws_port = config.ws_port;
 
     let (events_receiver, mut swarm_driver) =
         init_swarm_driver(kad_cfg, store_cfg, ProtocolSupport::Full, config);
@@ -182,6 +192,24 @@ pub(super) fn init_driver(
         .listen_on(addr_quic)
         .expect("Multiaddr should be supported by our configured transports");
 
+    // Listen on TCP
+    if let Some(tcp_port) = tcp_port {
+        let addr_tcp = Multiaddr::from(listen_socket_addr.ip()).with(Protocol::Tcp(tcp_port));
+        swarm_driver
+            .listen_on(addr_tcp)
+            .expect("Multiaddr should be supported by our configured transports");
+    }
+
+    // Listen on WebSocket
+    if let Some(ws_port) = ws_port {
+        let addr_ws = Multiaddr::from(listen_socket_addr.ip())
+            .with(Protocol::Tcp(ws_port))
+            .with(Protocol::Ws("/".into()));
+        swarm_driver
+            .listen_on(addr_ws)
+            .expect("Multiaddr should be supported by our configured transports");
+    }
+
     Ok((swarm_driver, events_receiver))
 }
 
@@ -213,9 +241,18 @@ fn init_swarm_driver(
 
     // ==== Transport ====
     #[cfg(feature = "open-metrics")]
-    let main_transport = transport::build_transport(&config.keypair, &mut metrics_registries);
+    let main_transport = transport::build_transport(
+        &config.keypair,
+        config.tcp_port.is_some(),
+        config.ws_port.is_some(),
+        &mut metrics_registries,
+    );
     #[cfg(not(feature = "open-metrics"))]
-    let main_transport = transport::build_transport(&config.keypair);
+    let main_transport = transport::build_transport(
+        &config.keypair,
+        config.tcp_port.is_some(),
+        config.ws_port.is_some(),
+    );
     let transport = if !config.local {
         debug!("Preventing non-global dials");
         // Wrap upper in a transport that prevents dialing local addresses.
@@ -242,6 +279,25 @@ fn init_swarm_driver(
         })
         .boxed();
 
+    let bandwidth_limiter = if config.bandwidth_limits.is_unlimited()
+        && config.throttled_bandwidth_limits.is_unlimited()
+    {
+        None
+    } else {
+        info!(
+            "Limiting bandwidth to {:?}, or {:?} while throttled",
+            config.bandwidth_limits, config.throttled_bandwidth_limits
+        );
+        Some(BandwidthLimiter::new(
+            config.bandwidth_limits,
+            config.throttled_bandwidth_limits,
+        ))
+    };
+    let transport = match &bandwidth_limiter {
+        Some(limiter) => limiter.limit_transport(transport),
+        None => transport,
+    };
+
     #[cfg(feature = "open-metrics")]
     let metrics_recorder = if let Some(port) = config.metrics_server_port {
         let metrics_recorder = NetworkMetricsRecorder::new(&mut metrics_registries);
@@ -375,7 +431,11 @@ fn init_swarm_driver(
 
     let swarm = Swarm::new(transport, behaviour, peer_id, swarm_config);
 
-    let replication_fetcher = ReplicationFetcher::new(peer_id, network_event_sender.clone());
+    let replication_fetcher = ReplicationFetcher::new(
+        peer_id,
+        network_event_sender.clone(),
+        bandwidth_limiter.clone(),
+    );
 
     // Enable relay manager to allow the node to act as a relay client and connect via relay servers to the network
     let relay_manager = if config.relay_client {
@@ -415,6 +475,8 @@ fn init_swarm_driver(
         connected_relay_clients: Default::default(),
         external_address_manager,
         replication_fetcher,
+        bandwidth_limiter,
+        last_do_not_disturb_request: None,
         #[cfg(feature = "open-metrics")]
         metrics_recorder,
         // kept here to ensure we can push messages to the channel
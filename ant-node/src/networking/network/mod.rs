@@ -7,6 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use ant_evm::{PaymentQuote, QuotingMetrics};
@@ -22,7 +23,7 @@ use tokio::sync::{mpsc, oneshot};
 use super::driver::event::MsgResponder;
 use super::error::{NetworkError, Result};
 use super::interface::{LocalSwarmCmd, NetworkSwarmCmd};
-use super::{Addresses, NetworkEvent, NodeIssue, SwarmLocalState};
+use super::{Addresses, NetworkEvent, NodeIssue, RecordInfo, SwarmLocalState};
 
 mod init;
 
@@ -183,6 +184,23 @@ impl Network {
             .map_err(|_e| NetworkError::InternalMsgChannelDropped)
     }
 
+    /// Get the metadata of a `Record` held in the local RecordStore, along with the path of its
+    /// file
+    pub(crate) async fn get_local_record_info(
+        &self,
+        key: &RecordKey,
+    ) -> Result<Option<(RecordInfo, PathBuf)>> {
+        let (sender, receiver) = oneshot::channel();
+        self.send_local_swarm_cmd(LocalSwarmCmd::GetLocalRecordInfo {
+            key: key.clone(),
+            sender,
+        });
+
+        receiver
+            .await
+            .map_err(|_e| NetworkError::InternalMsgChannelDropped)
+    }
+
     /// Whether the target peer is considered blacklisted by self
     pub(crate) async fn is_peer_shunned(&self, target: NetworkAddress) -> Result<bool> {
         let (sender, receiver) = oneshot::channel();
@@ -300,6 +318,12 @@ impl Network {
         self.send_local_swarm_cmd(LocalSwarmCmd::TriggerIrrelevantRecordCleanup)
     }
 
+    /// Switch the node in or out of its throttled state.
+    /// While throttled, this is to be called periodically to keep close peers backing off.
+    pub(crate) fn set_throttled(&self, throttled: bool) {
+        self.send_local_swarm_cmd(LocalSwarmCmd::SetThrottled { throttled })
+    }
+
     pub(crate) fn notify_peer_scores(&self, peer_scores: Vec<(PeerId, bool)>) {
         self.send_local_swarm_cmd(LocalSwarmCmd::NotifyPeerScores { peer_scores })
     }
@@ -308,6 +332,15 @@ impl Network {
         self.send_local_swarm_cmd(LocalSwarmCmd::NotifyPeerVersion { peer, version })
     }
 
+    /// Returns the versions fetched from the peers, for those the node keeps track of
+    pub(crate) async fn get_peers_version(&self) -> Result<HashMap<PeerId, String>> {
+        let (sender, receiver) = oneshot::channel();
+        self.send_local_swarm_cmd(LocalSwarmCmd::GetPeersVersion { sender });
+        receiver
+            .await
+            .map_err(|_e| NetworkError::InternalMsgChannelDropped)
+    }
+
     pub(crate) fn remove_peer(&self, peer: PeerId) {
         self.send_local_swarm_cmd(LocalSwarmCmd::RemovePeer { peer })
     }
@@ -622,6 +622,17 @@ impl NodeRecordStore {
         &self.records
     }
 
+    /// Returns the path of the file the record is stored in.
+    pub(crate) fn record_file_path(&self, key: &Key) -> PathBuf {
+        self.config.storage_dir.join(Self::generate_filename(key))
+    }
+
+    /// Returns the size of a record file on disk, and when it was last written.
+    pub(crate) fn record_file_metadata(file_path: &Path) -> Option<(u64, Option<SystemTime>)> {
+        let metadata = fs::metadata(file_path).ok()?;
+        Some((metadata.len(), metadata.modified().ok()))
+    }
+
     /// The follow up to `put_verified`, this only registers the RecordKey
     /// in the RecordStore records set. After this it should be safe
     /// to return the record as stored.
@@ -1274,6 +1285,53 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn record_file_metadata_reflects_the_stored_file() -> eyre::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let current_test_dir = tmp_dir.child("record_file_metadata");
+        current_test_dir.create_dir_all()?;
+
+        let store_config = NodeRecordStoreConfig {
+            storage_dir: current_test_dir.to_path_buf(),
+            ..Default::default()
+        };
+        let (network_event_sender, _network_event_receiver) = mpsc::channel(1);
+        let (swarm_cmd_sender, mut swarm_cmd_receiver) = mpsc::channel(1);
+        let mut store = NodeRecordStore::with_config(
+            PeerId::random(),
+            store_config,
+            network_event_sender,
+            swarm_cmd_sender,
+            #[cfg(feature = "open-metrics")]
+            None,
+        );
+
+        let chunk = Chunk::new(Bytes::from_static(b"Test chunk data"));
+        let value = try_serialize_record(&chunk, RecordKind::DataOnly(DataTypes::Chunk))?.to_vec();
+        let record = Record {
+            key: NetworkAddress::ChunkAddress(*chunk.address()).to_record_key(),
+            value: value.clone(),
+            expires: None,
+            publisher: None,
+        };
+        let file_path = store.record_file_path(&record.key);
+        assert!(NodeRecordStore::record_file_metadata(&file_path).is_none());
+
+        store.put_verified(record.clone(), ValidationType::Chunk, false)?;
+        match swarm_cmd_receiver.recv().await {
+            Some(LocalSwarmCmd::AddLocalRecordAsStored { .. }) => {}
+            other => panic!("Unexpected command: {other:?}"),
+        }
+
+        let (size, stored_at) = NodeRecordStore::record_file_metadata(&file_path)
+            .expect("The record file should be on disk");
+        // The encrypted file holds the record along with its authentication tag.
+        assert!(size > value.len() as u64);
+        assert!(stored_at.is_some_and(|stored_at| stored_at <= SystemTime::now()));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn can_store_and_retrieve_chunk() {
         let temp_dir = std::env::temp_dir();
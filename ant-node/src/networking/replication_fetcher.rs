@@ -7,7 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 #![allow(clippy::mutable_key_type)]
 
-use crate::networking::interface::NetworkEvent;
+use crate::networking::{BandwidthLimiter, interface::NetworkEvent};
 use ant_protocol::{
     NetworkAddress, PrettyPrintRecordKey,
     constants::CLOSE_GROUP_SIZE,
@@ -25,6 +25,10 @@ use tokio::{sync::mpsc, time::Duration};
 // Max parallel fetches that can be undertaken at the same time.
 const MAX_PARALLEL_FETCH: usize = 5;
 
+// Max parallel fetches while the node is running at its bandwidth limit or is throttled,
+// leaving the budget to client GET/PUT traffic.
+const MAX_PARALLEL_FETCH_WHEN_CONGESTED: usize = 1;
+
 // The duration after which a peer will be considered failed to fetch data from,
 // if no response got from that peer.
 // Note this will also cover the period that node self write the fetched copy to disk.
@@ -59,11 +63,20 @@ pub(crate) struct ReplicationFetcher {
     /// only records got `majority` of replicated in copies shall be trusted.
     /// This is the temp container to accumulate those intitial replicated in records.
     initial_replicates: HashMap<(NetworkAddress, ValidationType), HashSet<PeerId>>,
+    /// Bandwidth budget of the node, if limited.
+    /// Replication backs off while the budget is tight.
+    bandwidth_limiter: Option<BandwidthLimiter>,
+    /// Whether the node is throttled, during which replication backs off as well.
+    is_throttled: bool,
 }
 
 impl ReplicationFetcher {
     /// Instantiate a new replication fetcher with passed PeerId.
-    pub(crate) fn new(self_peer_id: PeerId, event_sender: mpsc::Sender<NetworkEvent>) -> Self {
+    pub(crate) fn new(
+        self_peer_id: PeerId,
+        event_sender: mpsc::Sender<NetworkEvent>,
+        bandwidth_limiter: Option<BandwidthLimiter>,
+    ) -> Self {
         Self {
             self_peer_id,
             to_be_fetched: HashMap::new(),
@@ -73,9 +86,30 @@ impl ReplicationFetcher {
             farthest_acceptable_distance: None,
             peers_scores: HashMap::new(),
             initial_replicates: HashMap::new(),
+            bandwidth_limiter,
+            is_throttled: false,
         }
     }
 
+    // Max number of fetches that can be undertaken at the moment.
+    fn max_parallel_fetch(&self) -> usize {
+        if self.is_throttled
+            || self
+                .bandwidth_limiter
+                .as_ref()
+                .is_some_and(|limiter| limiter.is_congested())
+        {
+            MAX_PARALLEL_FETCH_WHEN_CONGESTED
+        } else {
+            MAX_PARALLEL_FETCH
+        }
+    }
+
+    /// Set whether the node is throttled.
+    pub(crate) fn set_throttled(&mut self, throttled: bool) {
+        self.is_throttled = throttled;
+    }
+
     /// Set the distance range.
     pub(crate) fn set_replication_distance_range(&mut self, distance_range: Distance) {
         self.distance_range = Some(distance_range);
@@ -206,13 +240,16 @@ impl ReplicationFetcher {
 
     // Returns the set of keys that has to be fetched from the peer/network.
     // Target must not be under-fetching
-    // and no more than MAX_PARALLEL_FETCH fetches to be undertaken at the same time.
+    // and no more than MAX_PARALLEL_FETCH fetches to be undertaken at the same time,
+    // or MAX_PARALLEL_FETCH_WHEN_CONGESTED while the bandwidth budget is tight or the node is throttled.
     pub(crate) fn next_keys_to_fetch(&mut self) -> Vec<(PeerId, RecordKey)> {
         self.prune_expired_keys_and_slow_nodes();
 
         debug!("Next to fetch....");
 
-        if self.on_going_fetches.len() >= MAX_PARALLEL_FETCH {
+        let max_parallel_fetch = self.max_parallel_fetch();
+
+        if self.on_going_fetches.len() >= max_parallel_fetch {
             warn!(
                 "Replication Fetcher doesn't have free fetch capacity. Currently has {} entries in queue.",
                 self.to_be_fetched.len()
@@ -231,7 +268,7 @@ impl ReplicationFetcher {
         );
 
         // Pre-allocate vectors with known capacity
-        let remaining_capacity = MAX_PARALLEL_FETCH - self.on_going_fetches.len();
+        let remaining_capacity = max_parallel_fetch - self.on_going_fetches.len();
         let mut data_to_fetch = Vec::with_capacity(remaining_capacity);
 
         // Sort to_be_fetched by key closeness to our PeerId
@@ -249,7 +286,7 @@ impl ReplicationFetcher {
             // Already carried out expiration pruning above.
             // Hence here only need to check whether is ongoing fetching.
             // Also avoid fetching same record from different nodes.
-            if self.on_going_fetches.len() < MAX_PARALLEL_FETCH
+            if self.on_going_fetches.len() < max_parallel_fetch
                 && !self
                     .on_going_fetches
                     .contains_key(&(key.clone(), t.clone()))
@@ -262,7 +299,7 @@ impl ReplicationFetcher {
             }
 
             // break out the loop early if we can do no more now
-            if self.on_going_fetches.len() >= MAX_PARALLEL_FETCH {
+            if self.on_going_fetches.len() >= max_parallel_fetch {
                 break;
             }
         }
@@ -620,7 +657,7 @@ mod tests {
         //random peer_id
         let peer_id = PeerId::random();
         let (event_sender, _event_receiver) = mpsc::channel(4);
-        let mut replication_fetcher = ReplicationFetcher::new(peer_id, event_sender);
+        let mut replication_fetcher = ReplicationFetcher::new(peer_id, event_sender, None);
         let locally_stored_keys = HashMap::new();
 
         let mut incoming_keys = Vec::new();
@@ -694,7 +731,7 @@ mod tests {
         let peer_id = PeerId::random();
         let self_address = NetworkAddress::from(peer_id);
         let (event_sender, _event_receiver) = mpsc::channel(4);
-        let mut replication_fetcher = ReplicationFetcher::new(peer_id, event_sender);
+        let mut replication_fetcher = ReplicationFetcher::new(peer_id, event_sender, None);
 
         // Set distance range
         let distance_target = NetworkAddress::from(PeerId::random());
@@ -8,21 +8,53 @@
 
 #[cfg(feature = "open-metrics")]
 use crate::networking::MetricsRegistries;
+use futures::future::Either;
 use libp2p::{
-    core::{muxing::StreamMuxerBox, transport},
-    identity::Keypair,
     PeerId, Transport as _,
+    core::{
+        muxing::StreamMuxerBox,
+        transport::{self, OptionalTransport},
+        upgrade::Version,
+    },
+    identity::Keypair,
 };
 
+/// Builds the main transport of the node.
+///
+/// QUIC is always enabled. TCP and WebSocket (both secured with noise and multiplexed with yamux)
+/// are opt-in, for nodes that sit behind firewalls which are hostile to UDP traffic.
 pub(crate) fn build_transport(
     keypair: &Keypair,
+    enable_tcp: bool,
+    enable_websocket: bool,
     #[cfg(feature = "open-metrics")] registries: &mut MetricsRegistries,
 ) -> transport::Boxed<(PeerId, StreamMuxerBox)> {
-    let trans = generate_quic_transport(keypair);
-    #[cfg(feature = "open-metrics")]
-    let trans = libp2p::metrics::BandwidthTransport::new(trans, &mut registries.standard_metrics);
+    let quic = generate_quic_transport(keypair)
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
 
-    let trans = trans.map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
+    let websocket = if enable_websocket {
+        OptionalTransport::some(generate_websocket_transport(keypair))
+    } else {
+        OptionalTransport::none()
+    };
+    let tcp = if enable_tcp {
+        OptionalTransport::some(generate_tcp_transport(keypair))
+    } else {
+        OptionalTransport::none()
+    };
+
+    let trans = quic
+        .or_transport(websocket)
+        .or_transport(tcp)
+        .map(|either_output, _| match either_output {
+            Either::Left(Either::Left(output))
+            | Either::Left(Either::Right(output))
+            | Either::Right(output) => output,
+        });
+
+    #[cfg(feature = "open-metrics")]
+    let trans = libp2p::metrics::BandwidthTransport::new(trans, &mut registries.standard_metrics)
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
 
     trans.boxed()
 }
@@ -36,3 +68,122 @@ fn generate_quic_transport(
 
     libp2p::quic::tokio::Transport::new(quic_config)
 }
+
+fn generate_tcp_transport(keypair: &Keypair) -> transport::Boxed<(PeerId, StreamMuxerBox)> {
+    libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default().nodelay(true))
+        .upgrade(Version::V1Lazy)
+        .authenticate(
+            libp2p::noise::Config::new(keypair)
+                .expect("Signing libp2p-noise static DH keypair failed."),
+        )
+        .multiplex(libp2p::yamux::Config::default())
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+        .boxed()
+}
+
+fn generate_websocket_transport(keypair: &Keypair) -> transport::Boxed<(PeerId, StreamMuxerBox)> {
+    let tcp = libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default().nodelay(true));
+    libp2p::websocket::Config::new(tcp)
+        .upgrade(Version::V1Lazy)
+        .authenticate(
+            libp2p::noise::Config::new(keypair)
+                .expect("Signing libp2p-noise static DH keypair failed."),
+        )
+        .multiplex(libp2p::yamux::Config::default())
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use libp2p::{
+        Multiaddr, Swarm,
+        swarm::{SwarmEvent, dummy},
+    };
+    use std::time::Duration;
+
+    fn new_swarm(enable_tcp: bool, enable_websocket: bool) -> Swarm<dummy::Behaviour> {
+        let keypair = Keypair::generate_ed25519();
+        let peer_id = keypair.public().to_peer_id();
+        #[cfg(feature = "open-metrics")]
+        let transport = build_transport(
+            &keypair,
+            enable_tcp,
+            enable_websocket,
+            &mut MetricsRegistries::default(),
+        );
+        #[cfg(not(feature = "open-metrics"))]
+        let transport = build_transport(&keypair, enable_tcp, enable_websocket);
+
+        Swarm::new(
+            transport,
+            dummy::Behaviour,
+            peer_id,
+            libp2p::swarm::Config::with_tokio_executor()
+                .with_idle_connection_timeout(Duration::from_secs(10)),
+        )
+    }
+
+    async fn connect_on_loopback(listen_addr: Multiaddr, enable_tcp: bool, enable_ws: bool) {
+        let mut listener = new_swarm(enable_tcp, enable_ws);
+        let mut dialer = new_swarm(enable_tcp, enable_ws);
+        let listener_peer_id = *listener.local_peer_id();
+
+        let _ = listener.listen_on(listen_addr).unwrap();
+        let addr = loop {
+            if let SwarmEvent::NewListenAddr { address, .. } = listener.select_next_some().await {
+                break address;
+            }
+        };
+
+        dialer.dial(addr).unwrap();
+        let connected = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                tokio::select! {
+                    event = dialer.select_next_some() => {
+                        if let SwarmEvent::ConnectionEstablished { peer_id, .. } = event {
+                            break peer_id;
+                        }
+                    }
+                    _ = listener.select_next_some() => {}
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(connected, listener_peer_id);
+    }
+
+    #[tokio::test]
+    async fn quic_connects_on_loopback() {
+        connect_on_loopback(
+            "/ip4/127.0.0.1/udp/0/quic-v1".parse().unwrap(),
+            false,
+            false,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn tcp_connects_on_loopback() {
+        connect_on_loopback("/ip4/127.0.0.1/tcp/0".parse().unwrap(), true, false).await;
+    }
+
+    #[tokio::test]
+    async fn websocket_connects_on_loopback() {
+        connect_on_loopback("/ip4/127.0.0.1/tcp/0/ws".parse().unwrap(), false, true).await;
+    }
+
+    #[test]
+    fn tcp_is_rejected_when_disabled() {
+        let mut swarm = new_swarm(false, false);
+        assert!(
+            swarm
+                .listen_on("/ip4/127.0.0.1/tcp/0".parse().unwrap())
+                .is_err()
+        );
+    }
+}
@@ -9,11 +9,18 @@
 use super::{
     Marker, NodeEvent, error::Result, event::NodeEventsChannel, quote::quotes_verification,
 };
+use crate::earnings::EarningsLedger;
 #[cfg(feature = "open-metrics")]
 use crate::metrics::NodeMetricsRecorder;
 #[cfg(feature = "open-metrics")]
 use crate::networking::MetricsRegistries;
-use crate::networking::{Addresses, Network, NetworkConfig, NetworkError, NetworkEvent, NodeIssue};
+use crate::networking::{
+    Addresses, BandwidthLimits, Network, NetworkConfig, NetworkError, NetworkEvent, NodeIssue,
+};
+use crate::payment_verification::PaymentVerifier;
+use crate::provenance::ProvenanceLedger;
+use crate::quote_policy::{DefaultQuotePolicy, QuoteDecision, QuotePolicy, within_protocol_bounds};
+use crate::throttle::{ResourceMonitor, ThrottlePolicy, ThrottleReason};
 use crate::{PutValidationError, RunningNode};
 use ant_bootstrap::bootstrap::Bootstrap;
 use ant_evm::EvmNetwork;
@@ -22,7 +29,10 @@ use ant_protocol::{
     CLOSE_GROUP_SIZE, NetworkAddress, PrettyPrintRecordKey,
     error::Error as ProtocolError,
     messages::{ChunkProof, CmdResponse, Nonce, Query, QueryResponse, Request, Response},
-    storage::ValidationType,
+    storage::{
+        DataTypes, GraphEntry, Pointer, RecordHeader, RecordKind, Scratchpad, ValidationType,
+        try_deserialize_record,
+    },
 };
 use bytes::Bytes;
 use itertools::Itertools;
@@ -53,6 +63,7 @@ use tokio::{
     sync::mpsc::Receiver,
     task::{JoinSet, spawn},
 };
+use xor_name::XorName;
 
 /// Interval to trigger replication of all records to all peers.
 /// This is the max time it should take. Minimum interval at any node will be half this
@@ -62,6 +73,9 @@ pub const PERIODIC_REPLICATION_INTERVAL_MAX_S: u64 = 180;
 /// This is the max time it should take. Minimum interval at any node will be half this
 const STORE_CHALLENGE_INTERVAL_MAX_S: u64 = 7200;
 
+/// Interval to evaluate the throttle policy of the node
+const THROTTLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
 /// Interval to update the nodes uptime metric
 const UPTIME_METRICS_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
 
@@ -79,9 +93,14 @@ const MIN_ACCEPTABLE_HEALTHY_SCORE: usize = 3000;
 /// in ms, expecting average StorageChallenge complete time to be around 250ms.
 const TIME_STEP: usize = 20;
 
+/// The first release proving non-chunk records during StorageChallenge.
+/// Peers advertising an older version are scored on the chunks alone.
+const FIRST_VERSION_PROVING_NON_CHUNKS: [u64; 4] = [2025, 11, 1, 1];
+
 /// Helper to build and run a Node
 pub struct NodeBuilder {
     addr: SocketAddr,
+    bandwidth_limits: BandwidthLimits,
     bootstrap: Bootstrap,
     evm_address: RewardsAddress,
     evm_network: EvmNetwork,
@@ -91,8 +110,12 @@ pub struct NodeBuilder {
     /// Set to Some to enable the metrics server
     metrics_server_port: Option<u16>,
     no_upnp: bool,
+    quote_policy: Arc<dyn QuotePolicy>,
     relay_client: bool,
     root_dir: PathBuf,
+    tcp_port: Option<u16>,
+    throttle_policy: ThrottlePolicy,
+    ws_port: Option<u16>,
 }
 
 impl NodeBuilder {
@@ -108,6 +131,7 @@ impl NodeBuilder {
     ) -> Self {
         Self {
             addr,
+            bandwidth_limits: BandwidthLimits::default(),
             bootstrap: bootstrap_flow,
             evm_address,
             evm_network,
@@ -116,8 +140,12 @@ impl NodeBuilder {
             #[cfg(feature = "open-metrics")]
             metrics_server_port: None,
             no_upnp: false,
+            quote_policy: Arc::new(DefaultQuotePolicy),
             relay_client: false,
             root_dir,
+            tcp_port: None,
+            throttle_policy: ThrottlePolicy::default(),
+            ws_port: None,
         }
     }
 
@@ -142,6 +170,34 @@ impl NodeBuilder {
         self.no_upnp = no_upnp;
     }
 
+    /// Set the port to listen on with TCP, in addition to QUIC. TCP is disabled if not set.
+    /// The special value `0` will cause the OS to assign a random port.
+    pub fn tcp_port(&mut self, port: Option<u16>) {
+        self.tcp_port = port;
+    }
+
+    /// Set the port to listen on with WebSocket, in addition to QUIC. WebSocket is disabled if not set.
+    /// The special value `0` will cause the OS to assign a random port.
+    pub fn ws_port(&mut self, port: Option<u16>) {
+        self.ws_port = port;
+    }
+
+    /// Set the upload and download rate limits of the node. Unlimited if not set.
+    pub fn bandwidth_limits(&mut self, limits: BandwidthLimits) {
+        self.bandwidth_limits = limits;
+    }
+
+    /// Set when the node throttles itself, i.e. its quiet hours and resource pressure thresholds.
+    /// The node is never throttled if not set.
+    pub fn throttle_policy(&mut self, policy: ThrottlePolicy) {
+        self.throttle_policy = policy;
+    }
+
+    /// Set the policy consulted before quoting for storing a record. Quotes for every record if not set.
+    pub fn quote_policy(&mut self, policy: Arc<dyn QuotePolicy>) {
+        self.quote_policy = policy;
+    }
+
     /// Asynchronously runs a new node instance, setting up the swarm driver,
     /// creating a data storage, and handling network events. Returns the
     /// created `RunningNode` which contains a `NodeEventsChannel` for listening
@@ -175,6 +231,10 @@ impl NodeBuilder {
             keypair: self.identity_keypair,
             local: self.local,
             listen_addr: self.addr,
+            tcp_port: self.tcp_port,
+            ws_port: self.ws_port,
+            bandwidth_limits: self.bandwidth_limits,
+            throttled_bandwidth_limits: self.throttle_policy.bandwidth_limits,
             root_dir: self.root_dir.clone(),
             shutdown_rx: shutdown_rx.clone(),
             bootstrap: self.bootstrap,
@@ -190,26 +250,56 @@ impl NodeBuilder {
 
         // init node
         let node_events_channel = NodeEventsChannel::default();
+        let earnings_ledger = Arc::new(EarningsLedger::new(&self.root_dir));
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = &metrics_recorder {
+            // Carry the earnings over from previous runs, rather than restarting from zero.
+            let lifetime_total = earnings_ledger.lifetime_total().as_atto();
+            let _ = metrics_recorder
+                .current_reward_wallet_balance
+                .set(lifetime_total.try_into().unwrap_or(i64::MAX));
+        }
+        let provenance_ledger = Arc::new(ProvenanceLedger::new(&self.root_dir));
+        let payment_verifier = Arc::new(PaymentVerifier::new(
+            self.evm_network,
+            #[cfg(feature = "open-metrics")]
+            metrics_recorder.clone(),
+        ));
         let node = NodeInner {
             network: network.clone(),
             events_channel: node_events_channel.clone(),
             reward_address: self.evm_address,
             #[cfg(feature = "open-metrics")]
             metrics_recorder,
-            evm_network: self.evm_network,
+            earnings_ledger: Arc::clone(&earnings_ledger),
+            provenance_ledger: Arc::clone(&provenance_ledger),
+            payment_verifier,
+            quote_policy: self.quote_policy,
         };
         let node = Node {
             inner: Arc::new(node),
         };
 
+        // The records reloaded from disk were paid for before the restart, verify their payments
+        // again in the background.
+        let reverifying_node = node.clone();
+        let _handle = spawn(async move {
+            reverifying_node
+                .provenance_ledger()
+                .reverify_payments(reverifying_node.payment_verifier())
+                .await;
+        });
+
         // Run the node
-        node.run(network_event_receiver, shutdown_rx);
+        node.run(network_event_receiver, shutdown_rx, self.throttle_policy);
         let running_node = RunningNode {
             shutdown_sender: shutdown_tx,
             network,
             node_events_channel,
             root_dir_path: self.root_dir,
             rewards_address: self.evm_address,
+            earnings_ledger,
+            provenance_ledger,
         };
 
         Ok(running_node)
@@ -232,7 +322,10 @@ struct NodeInner {
     #[cfg(feature = "open-metrics")]
     metrics_recorder: Option<NodeMetricsRecorder>,
     reward_address: RewardsAddress,
-    evm_network: EvmNetwork,
+    earnings_ledger: Arc<EarningsLedger>,
+    provenance_ledger: Arc<ProvenanceLedger>,
+    payment_verifier: Arc<PaymentVerifier>,
+    quote_policy: Arc<dyn QuotePolicy>,
 }
 
 impl Node {
@@ -258,8 +351,24 @@ impl Node {
         &self.inner.reward_address
     }
 
-    pub(crate) fn evm_network(&self) -> &EvmNetwork {
-        &self.inner.evm_network
+    /// Returns the verifier shared by all the payments received by the node
+    pub(crate) fn payment_verifier(&self) -> &Arc<PaymentVerifier> {
+        &self.inner.payment_verifier
+    }
+
+    /// Returns the policy consulted before quoting for storing a record
+    pub(crate) fn quote_policy(&self) -> &dyn QuotePolicy {
+        self.inner.quote_policy.as_ref()
+    }
+
+    /// Returns the ledger of the payments received by the node
+    pub(crate) fn earnings_ledger(&self) -> &EarningsLedger {
+        &self.inner.earnings_ledger
+    }
+
+    /// Returns the ledger of how the node came to hold each of its records
+    pub(crate) fn provenance_ledger(&self) -> &ProvenanceLedger {
+        &self.inner.provenance_ledger
     }
 
     /// Spawns a task to process for `NetworkEvents`.
@@ -268,6 +377,7 @@ impl Node {
         self,
         mut network_event_receiver: Receiver<NetworkEvent>,
         mut shutdown_rx: watch::Receiver<bool>,
+        throttle_policy: ThrottlePolicy,
     ) {
         let mut rng = StdRng::from_entropy();
 
@@ -312,6 +422,15 @@ impl Node {
                 tokio::time::interval(storage_challenge_interval_time);
             let _ = storage_challenge_interval.tick().await; // first tick completes immediately
 
+            if throttle_policy.is_enabled() {
+                info!("Throttle policy set to {throttle_policy:?}");
+            }
+            let mut throttle_check_interval = tokio::time::interval(THROTTLE_CHECK_INTERVAL);
+            let mut resource_monitor = (throttle_policy.max_cpu_usage.is_some()
+                || throttle_policy.max_memory_usage.is_some())
+            .then(ResourceMonitor::new);
+            let mut throttle_reason: Option<ThrottleReason> = None;
+
             loop {
                 let peers_connected = &peers_connected;
 
@@ -364,14 +483,36 @@ impl Node {
                             Self::trigger_irrelevant_record_cleanup(network);
                         });
                     }
+                    _ = throttle_check_interval.tick(), if throttle_policy.is_enabled() => {
+                        let usage = resource_monitor.as_mut().and_then(ResourceMonitor::sample);
+                        let reason = throttle_policy.evaluate(
+                            chrono::Local::now().time(),
+                            usage,
+                            throttle_reason.is_some(),
+                        );
+                        match (&throttle_reason, &reason) {
+                            (None, Some(reason)) => info!("Throttling the node due to {reason}"),
+                            (Some(_), None) => info!("The node is no longer throttled"),
+                            _ => {}
+                        }
+                        // Re-sent while throttled, for close peers to keep backing off.
+                        if reason.is_some() || throttle_reason.is_some() {
+                            self.network().set_throttled(reason.is_some());
+                        }
+                        throttle_reason = reason;
+                    }
                     // runs every storage_challenge_interval time
                     _ = storage_challenge_interval.tick() => {
+                        if let Some(reason) = &throttle_reason {
+                            debug!("Periodic storage challenge skipped, the node is throttled due to {reason}");
+                            continue;
+                        }
                         let start = Instant::now();
                         debug!("Periodic storage challenge triggered");
                         let network = self.network().clone();
 
                         let _handle = spawn(async move {
-                            Self::storage_challenge(network).await;
+                            let _ = Self::storage_challenge(network).await;
                             trace!("Periodic storage challenge took {:?}", start.elapsed());
                         });
                     }
@@ -636,13 +777,25 @@ impl Node {
                                 storage_proofs,
                             }
                         } else {
+                            let quote = match node.quote_policy().decide(&key, &quoting_metrics) {
+                                QuoteDecision::Quote(reported_metrics) => {
+                                    Self::create_quote_for_storecost(
+                                        network,
+                                        &key,
+                                        &within_protocol_bounds(
+                                            &quoting_metrics,
+                                            &reported_metrics,
+                                        ),
+                                        &payment_address,
+                                    )
+                                }
+                                QuoteDecision::Decline(reason) => {
+                                    info!("Declined to quote for {key:?}: {reason}");
+                                    Err(ProtocolError::from(reason))
+                                }
+                            };
                             QueryResponse::GetStoreQuote {
-                                quote: Self::create_quote_for_storecost(
-                                    network,
-                                    &key,
-                                    &quoting_metrics,
-                                    &payment_address,
-                                ),
+                                quote,
                                 peer_address: NetworkAddress::from(self_id),
                                 storage_proofs,
                             }
@@ -814,14 +967,15 @@ impl Node {
         }
     }
 
-    // Nodes only check ChunkProof each other, to avoid `multi-version` issue
+    // Nodes check ChunkProof of chunks against each other, and versioned proofs
+    // (content hash + counter) of the other record types, see `record_proof`.
     // Client check proof against all records, as have to fetch from network anyway.
     async fn respond_x_closest_record_proof(
         network: &Network,
         key: NetworkAddress,
         nonce: Nonce,
         difficulty: usize,
-        chunk_only: bool,
+        node_challenge: bool,
     ) -> Vec<(NetworkAddress, Result<ChunkProof, ProtocolError>)> {
         let start = Instant::now();
         let mut results = vec![];
@@ -841,33 +995,42 @@ impl Node {
             let all_local_records = network.get_all_local_record_addresses().await;
 
             if let Ok(all_local_records) = all_local_records {
-                let mut all_chunk_addrs: Vec<_> = if chunk_only {
-                    all_local_records
-                        .iter()
-                        .filter_map(|(addr, record_type)| {
-                            if *record_type == ValidationType::Chunk {
-                                Some(addr.clone())
-                            } else {
-                                None
-                            }
-                        })
+                // TODO: this shall be deduced from resource usage dynamically
+                let workload_factor = std::cmp::min(difficulty, CLOSE_GROUP_SIZE);
+
+                let addrs_to_prove: Vec<_> = if node_challenge {
+                    // Chunks and non-chunks are selected separately, so that the closest chunks
+                    // remain the same set as the one expected by the challenger.
+                    let (mut chunk_addrs, mut non_chunk_addrs): (Vec<_>, Vec<_>) =
+                        all_local_records.keys().cloned().partition(|addr| {
+                            all_local_records.get(addr) == Some(&ValidationType::Chunk)
+                        });
+                    chunk_addrs.sort_by_key(|addr| key.distance(addr));
+                    non_chunk_addrs.sort_by_key(|addr| key.distance(addr));
+                    chunk_addrs
+                        .into_iter()
+                        .take(workload_factor)
+                        .chain(non_chunk_addrs.into_iter().take(workload_factor))
                         .collect()
                 } else {
-                    all_local_records.keys().cloned().collect()
+                    let mut all_addrs: Vec<_> = all_local_records.keys().cloned().collect();
+                    // Sort by distance and only take first X closest entries
+                    all_addrs.sort_by_key(|addr| key.distance(addr));
+                    all_addrs.into_iter().take(workload_factor).collect()
                 };
 
-                // Sort by distance and only take first X closest entries
-                all_chunk_addrs.sort_by_key(|addr| key.distance(addr));
-
-                // TODO: this shall be deduced from resource usage dynamically
-                let workload_factor = std::cmp::min(difficulty, CLOSE_GROUP_SIZE);
-
-                for addr in all_chunk_addrs.iter().take(workload_factor) {
+                for addr in addrs_to_prove.iter() {
                     if let Ok(Some(record)) = network.get_local_record(&addr.to_record_key()).await
                     {
-                        let proof = ChunkProof::new(&record.value, nonce);
-                        debug!("Chunk proof for {key:?} is {proof:?}");
-                        results.push((addr.clone(), Ok(proof)));
+                        let proof = if node_challenge {
+                            record_proof(&record, nonce)
+                        } else {
+                            Some(ChunkProof::new(&record.value, nonce))
+                        };
+                        if let Some(proof) = proof {
+                            debug!("Record proof for {addr:?} is {proof:?}");
+                            results.push((addr.clone(), Ok(proof)));
+                        }
                     }
                 }
             }
@@ -882,10 +1045,13 @@ impl Node {
         results
     }
 
-    /// Check among all chunk type records that we have,
+    /// Check among all records that we have,
     /// and randomly pick one as the verification candidate.
     /// This will challenge all closest peers at once.
-    async fn storage_challenge(network: Network) {
+    ///
+    /// Returns whether each challenged peer passed the challenge. Nothing is returned if the
+    /// challenge could not be carried out, due to too few neighbours or records.
+    pub(crate) async fn storage_challenge(network: Network) -> Vec<(PeerId, bool)> {
         let start = Instant::now();
         let closest_peers: Vec<(PeerId, Addresses)> = if let Ok(closest_peers) =
             network.get_k_closest_local_peers_to_the_target(None).await
@@ -896,7 +1062,7 @@ impl Node {
                 .collect_vec()
         } else {
             error!("Cannot get local neighbours");
-            return;
+            return vec![];
         };
         if closest_peers.len() < CLOSE_GROUP_SIZE {
             debug!(
@@ -904,29 +1070,20 @@ impl Node {
                 closest_peers.len(),
                 CLOSE_GROUP_SIZE
             );
-            return;
+            return vec![];
         }
 
-        let mut verify_candidates: Vec<NetworkAddress> =
-            if let Ok(all_keys) = network.get_all_local_record_addresses().await {
-                all_keys
-                    .iter()
-                    .filter_map(|(addr, record_type)| {
-                        if ValidationType::Chunk == *record_type {
-                            Some(addr.clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect()
-            } else {
-                error!("Failed to get local record addresses.");
-                return;
-            };
+        let all_keys = if let Ok(all_keys) = network.get_all_local_record_addresses().await {
+            all_keys
+        } else {
+            error!("Failed to get local record addresses.");
+            return vec![];
+        };
+        let mut verify_candidates: Vec<NetworkAddress> = all_keys.keys().cloned().collect();
         let num_of_targets = verify_candidates.len();
         if num_of_targets < 50 {
             debug!("Not enough candidates({num_of_targets}/50) to be checked against neighbours.");
-            return;
+            return vec![];
         }
 
         // To ensure the neighbours sharing same knowledge as to us,
@@ -938,28 +1095,57 @@ impl Node {
         // TODO: workload shall be dynamically deduced from resource usage
         let difficulty = CLOSE_GROUP_SIZE;
         verify_candidates.sort_by_key(|addr| target.distance(addr));
-        let expected_targets = verify_candidates.into_iter().take(difficulty);
+        // The closest chunks and the closest non-chunks are expected separately,
+        // mirroring the selection done by the responder.
+        let (chunk_targets, non_chunk_targets): (Vec<_>, Vec<_>) = verify_candidates
+            .into_iter()
+            .partition(|addr| all_keys.get(addr) == Some(&ValidationType::Chunk));
+        let expected_targets = chunk_targets
+            .into_iter()
+            .take(difficulty)
+            .chain(non_chunk_targets.into_iter().take(difficulty));
         let nonce: Nonce = thread_rng().r#gen::<u64>();
         let mut expected_proofs = HashMap::new();
         for addr in expected_targets {
+            let Some(validation_type) = all_keys.get(&addr) else {
+                continue;
+            };
             if let Ok(Some(record)) = network.get_local_record(&addr.to_record_key()).await {
-                let expected_proof = ChunkProof::new(&record.value, nonce);
-                let _ = expected_proofs.insert(addr, expected_proof);
+                if let Some(expected_proof) = record_proof(&record, nonce) {
+                    let _ = expected_proofs.insert(addr, (validation_type.clone(), expected_proof));
+                } else {
+                    error!("Cannot produce the proof of local record {addr:?}.");
+                }
             } else {
                 error!("Local record {addr:?} cann't be loaded from disk.");
             }
         }
+        if !expected_proofs
+            .values()
+            .any(|(validation_type, _)| *validation_type == ValidationType::Chunk)
+        {
+            // Peers running an older version can only be scored on chunks.
+            debug!("No chunk close to {target:?} to challenge the neighbours with.");
+            return vec![];
+        }
         let request = Request::Query(Query::GetChunkExistenceProof {
             key: target.clone(),
             nonce,
             difficulty,
         });
+        let peers_version = network.get_peers_version().await.unwrap_or_else(|err| {
+            error!("Failed to get the versions of the peers: {err:?}");
+            HashMap::new()
+        });
 
         let mut tasks = JoinSet::new();
         for (peer_id, addresses) in closest_peers {
             if peer_id == network.peer_id() {
                 continue;
             }
+            let proves_non_chunks = !peers_version
+                .get(&peer_id)
+                .is_some_and(|version| predates_non_chunk_proofs(version));
             let network_clone = network.clone();
             let request_clone = request.clone();
             let expected_proofs_clone = expected_proofs.clone();
@@ -969,6 +1155,7 @@ impl Node {
                     (peer_id, addresses),
                     request_clone,
                     expected_proofs_clone,
+                    proves_non_chunks,
                 )
                 .await;
                 (peer_id, res)
@@ -978,12 +1165,18 @@ impl Node {
         let mut peer_scores = vec![];
         while let Some(res) = tasks.join_next().await {
             match res {
-                Ok((peer_id, score)) => {
+                Ok((peer_id, (score, stale_answers))) => {
                     let is_healthy = score > MIN_ACCEPTABLE_HEALTHY_SCORE;
                     if !is_healthy {
                         info!(
                             "Peer {peer_id:?} failed storage challenge with low score {score}/{MIN_ACCEPTABLE_HEALTHY_SCORE}."
                         );
+                    } else if stale_answers > 0 {
+                        info!(
+                            "Peer {peer_id:?} proved {stale_answers} stale record(s) during storage challenge."
+                        );
+                    }
+                    if !is_healthy || stale_answers > 0 {
                         // TODO: shall the challenge failure immediately triggers the node to be removed?
                         network.record_node_issues(peer_id, NodeIssue::FailedChunkProofCheck);
                     }
@@ -995,13 +1188,14 @@ impl Node {
             }
         }
         if !peer_scores.is_empty() {
-            network.notify_peer_scores(peer_scores);
+            network.notify_peer_scores(peer_scores.clone());
         }
 
         info!(
             "Completed node StorageChallenge against neighbours in {:?}!",
             start.elapsed()
         );
+        peer_scores
     }
 
     /// Query peers' versions and update local knowledge.
@@ -1041,12 +1235,14 @@ impl Node {
     }
 }
 
+/// Returns the score of the peer, along with the number of stale non-chunk records it proved.
 async fn scoring_peer(
     network: Network,
     peer: (PeerId, Addresses),
     request: Request,
-    expected_proofs: HashMap<NetworkAddress, ChunkProof>,
-) -> usize {
+    expected_proofs: HashMap<NetworkAddress, (ValidationType, ChunkProof)>,
+    proves_non_chunks: bool,
+) -> (usize, usize) {
     let peer_id = peer.0;
     let start = Instant::now();
     let responses = network
@@ -1058,7 +1254,7 @@ async fn scoring_peer(
     {
         if answers.is_empty() {
             info!("Peer {peer_id:?} didn't answer the ChunkProofChallenge.");
-            return 0;
+            return (0, 0);
         }
         let elapsed = start.elapsed();
 
@@ -1069,15 +1265,20 @@ async fn scoring_peer(
             }
         }
 
-        let score = mark_peer(elapsed, received_proofs, &expected_proofs);
+        let (score, stale_answers) = mark_peer(
+            elapsed,
+            received_proofs,
+            &expected_proofs,
+            proves_non_chunks,
+        );
         info!(
             "Received {} answers from peer {peer_id:?} after {elapsed:?}, score it as {score}.",
             answers.len()
         );
-        score
+        (score, stale_answers)
     } else {
         info!("Peer {peer_id:?} doesn't reply the ChunkProofChallenge, or replied with error.");
-        0
+        (0, 0)
     }
 }
 
@@ -1089,12 +1290,24 @@ async fn scoring_peer(
 fn mark_peer(
     duration: Duration,
     answers: Vec<(NetworkAddress, ChunkProof)>,
-    expected_proofs: &HashMap<NetworkAddress, ChunkProof>,
-) -> usize {
+    expected_proofs: &HashMap<NetworkAddress, (ValidationType, ChunkProof)>,
+    proves_non_chunks: bool,
+) -> (usize, usize) {
     let duration_score = duration_score_scheme(duration);
-    let challenge_score = challenge_score_scheme(answers, expected_proofs);
+    let (challenge_score, stale_answers) =
+        challenge_score_scheme(answers, expected_proofs, proves_non_chunks);
 
-    duration_score * challenge_score
+    (duration_score * challenge_score, stale_answers)
+}
+
+// Peers advertising a release older than the first one proving non-chunk records.
+// Versions that cannot be parsed are not known to be older, so they are not exempted.
+fn predates_non_chunk_proofs(version: &str) -> bool {
+    let parts: Option<Vec<u64>> = version.split('.').map(|part| part.parse().ok()).collect();
+    parts.is_some_and(|parts| {
+        parts.len() == FIRST_VERSION_PROVING_NON_CHUNKS.len()
+            && parts.as_slice() < FIRST_VERSION_PROVING_NON_CHUNKS.as_slice()
+    })
 }
 
 // Less duration shall get higher score
@@ -1111,20 +1324,42 @@ fn duration_score_scheme(duration: Duration) -> usize {
     HIGHEST_SCORE - step
 }
 
-// Any false answer shall result in 0 score immediately
+// Any false answer to a chunk shall result in 0 score immediately.
+// A mismatching answer to a non-chunk record earns no credit, and is counted as stale, as it
+// could be either the peer or us holding a stale version of that record.
+// Returns the score along with the number of stale answers.
 fn challenge_score_scheme(
     answers: Vec<(NetworkAddress, ChunkProof)>,
-    expected_proofs: &HashMap<NetworkAddress, ChunkProof>,
-) -> usize {
+    expected_proofs: &HashMap<NetworkAddress, (ValidationType, ChunkProof)>,
+    proves_non_chunks: bool,
+) -> (usize, usize) {
+    // Peers known to run an older version only prove chunks, so are scored on those alone.
+    let expected_answers = if proves_non_chunks {
+        expected_proofs.len()
+    } else {
+        expected_proofs
+            .values()
+            .filter(|(validation_type, _)| *validation_type == ValidationType::Chunk)
+            .count()
+    };
+    if expected_answers == 0 {
+        return (0, 0);
+    }
     let mut correct_answers = 0;
+    let mut stale_answers = 0;
     for (addr, chunk_proof) in answers {
-        if let Some(expected_proof) = expected_proofs.get(&addr) {
+        if let Some((validation_type, expected_proof)) = expected_proofs.get(&addr) {
             if expected_proof.verify(&chunk_proof) {
                 correct_answers += 1;
-            } else {
+            } else if *validation_type == ValidationType::Chunk {
                 info!("Spot a false answer to the challenge regarding {addr:?}");
                 // Any false answer shall result in 0 score immediately
-                return 0;
+                return (0, stale_answers);
+            } else {
+                warn!(
+                    "Spot a mismatching version in the answer to the challenge regarding {addr:?}"
+                );
+                stale_answers += 1;
             }
         }
     }
@@ -1134,10 +1369,32 @@ fn challenge_score_scheme(
     //         * set the target being close to us, so that neighbours sharing same knowledge in higher chance
     //         * fetch from local to testify
     //         * fetch from network to testify
-    std::cmp::min(
+    let score = std::cmp::min(
         HIGHEST_SCORE,
-        HIGHEST_SCORE * correct_answers / expected_proofs.len(),
-    )
+        HIGHEST_SCORE * correct_answers / expected_answers,
+    );
+    (score, stale_answers)
+}
+
+/// Produce the storage challenge proof of a locally held record.
+/// Chunks are proven over their whole content, while the mutable record types are proven over
+/// the content hash and counter of the latest version held, to spot peers holding stale copies.
+/// For a `GraphEntry` record, the counter is the number of entries held at that address.
+fn record_proof(record: &Record, nonce: Nonce) -> Option<ChunkProof> {
+    let header = RecordHeader::from_record(record).ok()?;
+    let data_type = match header.kind {
+        RecordKind::DataOnly(data_type) | RecordKind::DataWithPayment(data_type) => data_type,
+    };
+    let counter = match data_type {
+        DataTypes::Chunk => return Some(ChunkProof::new(&record.value, nonce)),
+        DataTypes::Pointer => try_deserialize_record::<Pointer>(record).ok()?.counter(),
+        DataTypes::Scratchpad => try_deserialize_record::<Scratchpad>(record).ok()?.counter(),
+        DataTypes::GraphEntry => try_deserialize_record::<Vec<GraphEntry>>(record)
+            .ok()?
+            .len() as u64,
+    };
+    let content_hash = XorName::from_content(&record.value);
+    Some(ChunkProof::new_versioned(&content_hash, counter, nonce))
 }
 
 #[cfg(test)]
@@ -1239,4 +1496,109 @@ mod tests {
 
         assert_eq!(expected_result, result);
     }
+
+    fn pointer_record(owner: &bls::SecretKey, counter: u64) -> Record {
+        let target = ant_protocol::storage::PointerTarget::ChunkAddress(
+            ant_protocol::storage::ChunkAddress::new(XorName::random(&mut thread_rng())),
+        );
+        let pointer = Pointer::new(owner, counter, target);
+        let value = ant_protocol::storage::try_serialize_record(
+            &pointer,
+            RecordKind::DataOnly(DataTypes::Pointer),
+        )
+        .unwrap();
+        Record::new(
+            NetworkAddress::from(pointer.address()).to_record_key(),
+            value.to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_record_proof_differs_for_stale_pointer_counter() {
+        let owner = bls::SecretKey::random();
+        let nonce = 42;
+        let latest = pointer_record(&owner, 2);
+        let stale = pointer_record(&owner, 1);
+
+        let latest_proof = record_proof(&latest, nonce).unwrap();
+        assert!(latest_proof.verify(&record_proof(&latest, nonce).unwrap()));
+        assert!(!latest_proof.verify(&record_proof(&stale, nonce).unwrap()));
+        assert!(!latest_proof.verify(&record_proof(&latest, nonce + 1).unwrap()));
+    }
+
+    #[test]
+    fn test_challenge_score_scheme_with_non_chunk_records() {
+        let nonce = 7;
+        let chunk_addr = NetworkAddress::from(PeerId::random());
+        let pointer_addr = NetworkAddress::from(PeerId::random());
+        let chunk_proof = ChunkProof::new(b"chunk content", nonce);
+        let pointer_hash = XorName::random(&mut thread_rng());
+        let pointer_proof = ChunkProof::new_versioned(&pointer_hash, 2, nonce);
+        let expected_proofs = HashMap::from([
+            (
+                chunk_addr.clone(),
+                (ValidationType::Chunk, chunk_proof.clone()),
+            ),
+            (
+                pointer_addr.clone(),
+                (
+                    ValidationType::NonChunk(pointer_hash),
+                    pointer_proof.clone(),
+                ),
+            ),
+        ]);
+
+        // All answers correct
+        let answers = vec![
+            (chunk_addr.clone(), chunk_proof.clone()),
+            (pointer_addr.clone(), pointer_proof),
+        ];
+        assert_eq!(
+            challenge_score_scheme(answers, &expected_proofs, true),
+            (HIGHEST_SCORE, 0)
+        );
+
+        // Stale pointer version loses the credit of that record, and is reported
+        let stale_proof = ChunkProof::new_versioned(&pointer_hash, 1, nonce);
+        let answers = vec![
+            (chunk_addr.clone(), chunk_proof.clone()),
+            (pointer_addr, stale_proof),
+        ];
+        assert_eq!(
+            challenge_score_scheme(answers, &expected_proofs, true),
+            (HIGHEST_SCORE / 2, 1)
+        );
+
+        // Peer dropping its non-chunk records and only proving chunks gets a reduced score
+        let answers = vec![(chunk_addr.clone(), chunk_proof.clone())];
+        assert_eq!(
+            challenge_score_scheme(answers.clone(), &expected_proofs, true),
+            (HIGHEST_SCORE / 2, 0)
+        );
+
+        // Unless it is known to run an older version, which only proves chunks
+        assert_eq!(
+            challenge_score_scheme(answers, &expected_proofs, false),
+            (HIGHEST_SCORE, 0)
+        );
+
+        // False chunk answer results in 0 score
+        let answers = vec![(chunk_addr, ChunkProof::new(b"other content", nonce))];
+        assert_eq!(
+            challenge_score_scheme(answers, &expected_proofs, true),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn test_predates_non_chunk_proofs() {
+        assert!(predates_non_chunk_proofs("2025.10.2.3"));
+        assert!(predates_non_chunk_proofs("2024.12.2.1"));
+        assert!(!predates_non_chunk_proofs("2025.11.1.1"));
+        assert!(!predates_non_chunk_proofs("2025.11.2.1"));
+        // Versions not known to be older are not exempted
+        assert!(!predates_non_chunk_proofs("old"));
+        assert!(!predates_non_chunk_proofs("none"));
+        assert!(!predates_non_chunk_proofs("2025.10"));
+    }
 }
@@ -0,0 +1,479 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! On-chain verification of the payments made to the node, shared by all the incoming records.
+//!
+//! The payments to verify are queued for a short while and then verified together, with one
+//! `verifyPayment` call per payment sent to the EVM RPC in a single JSON-RPC batch request.
+//! Concurrent verifications of the same payment wait on a single call, verified payments are
+//! cached for a while, the number of batches in flight is bounded, and all verifications back off
+//! together whenever the EVM RPC rate limits the node.
+
+#[cfg(feature = "open-metrics")]
+use crate::metrics::NodeMetricsRecorder;
+use ant_evm::payment_vault::{
+    amount_paid_to, error::Error, fetch_batch_payment_verifications,
+    interface::IPaymentVault::PaymentVerificationResult, verify_data_payment,
+};
+use ant_evm::{Amount, EvmNetwork, QuoteHash, QuotingMetrics, RewardsAddress};
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+use tokio::sync::{Semaphore, oneshot};
+
+/// How long a verified payment is trusted without asking the chain again.
+const VERIFIED_PAYMENT_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Upper bound on the number of verified payments kept in the cache.
+const MAX_CACHED_PAYMENTS: usize = 10_000;
+
+/// How long a payment waits for others to be verified in the same batch.
+const BATCH_WINDOW: Duration = Duration::from_millis(100);
+
+/// Upper bound on the number of payments verified in a single batch.
+const MAX_BATCH_SIZE: usize = 64;
+
+/// Upper bound on the number of batches in flight against the EVM RPC.
+const MAX_CONCURRENT_RPC_CALLS: usize = 8;
+
+/// Backoff applied to all verifications after the EVM RPC first rate limits the node.
+/// It doubles on every further rate limited call, up to `MAX_RATE_LIMIT_BACKOFF`.
+const INITIAL_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The quotes of a payment, as sent to the payment vault for verification.
+pub(crate) type PaymentDigest = Vec<(QuoteHash, QuotingMetrics, RewardsAddress)>;
+
+/// The outcome of verifying a payment, shared by everyone waiting for it.
+type VerificationResults = Result<Vec<PaymentVerificationResult>, Arc<Error>>;
+
+/// Verifies payments against the payment vault on behalf of the whole node.
+pub(crate) struct PaymentVerifier {
+    evm_network: EvmNetwork,
+    cache: Mutex<VerifiedPayments>,
+    pending: Mutex<PendingVerifications>,
+    rpc_permits: Semaphore,
+    backoff: Mutex<RateLimitBackoff>,
+    #[cfg(feature = "open-metrics")]
+    metrics_recorder: Option<NodeMetricsRecorder>,
+}
+
+impl PaymentVerifier {
+    pub(crate) fn new(
+        evm_network: EvmNetwork,
+        #[cfg(feature = "open-metrics")] metrics_recorder: Option<NodeMetricsRecorder>,
+    ) -> Self {
+        Self {
+            evm_network,
+            cache: Mutex::new(VerifiedPayments::default()),
+            pending: Mutex::new(PendingVerifications::default()),
+            rpc_permits: Semaphore::new(MAX_CONCURRENT_RPC_CALLS),
+            backoff: Mutex::new(RateLimitBackoff::default()),
+            #[cfg(feature = "open-metrics")]
+            metrics_recorder,
+        }
+    }
+
+    /// Verifies the payment on chain, along with the other payments waiting to be verified, or
+    /// from the cache if it was verified recently.
+    /// Returns the amount paid to the owned quote hashes.
+    pub(crate) async fn verify(
+        self: &Arc<Self>,
+        owned_quote_hashes: &[QuoteHash],
+        payment: PaymentDigest,
+    ) -> Result<Amount, Arc<Error>> {
+        let key = payment_key(&payment);
+        let fallback = payment.clone();
+        let (waiter, receiver) = oneshot::channel();
+        {
+            let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+            // Checked while holding the pending verifications, as a batch caches its results
+            // before releasing its waiters, so a payment verified meanwhile is never missed.
+            if let Some(results) = self.cached(&key) {
+                return amount_paid_to(owned_quote_hashes, &results).map_err(Arc::new);
+            }
+            if let Some(queued) = pending.enqueue(key, payment, waiter) {
+                self.record_cache_miss();
+                let verifier = Arc::clone(self);
+                if queued == 1 {
+                    // The first payment of a batch sends it once the batch window is over.
+                    let _handle = tokio::spawn(async move {
+                        tokio::time::sleep(BATCH_WINDOW).await;
+                        let batch = verifier
+                            .pending
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .take_batch();
+                        verifier.verify_batch(batch).await;
+                    });
+                } else if queued >= MAX_BATCH_SIZE {
+                    let batch = pending.take_batch();
+                    let _handle = tokio::spawn(async move {
+                        verifier.verify_batch(batch).await;
+                    });
+                }
+            }
+        }
+
+        match receiver.await {
+            Ok(results) => amount_paid_to(owned_quote_hashes, &results?).map_err(Arc::new),
+            Err(_) => {
+                // The batch was dropped before it could answer, e.g. the task panicked.
+                warn!("Payment verification batch was dropped, verifying the payment on its own");
+                verify_data_payment(&self.evm_network, owned_quote_hashes.to_vec(), fallback)
+                    .await
+                    .map_err(Arc::new)
+            }
+        }
+    }
+
+    /// Returns the cached verification of the payment, if any, recording the cache hit.
+    fn cached(&self, key: &[QuoteHash]) -> Option<Vec<PaymentVerificationResult>> {
+        let results = self
+            .cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key, Instant::now())?;
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = &self.metrics_recorder {
+            let _ = metrics_recorder.payment_verification_cache_hits.inc();
+        }
+        Some(results)
+    }
+
+    fn record_cache_miss(&self) {
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = &self.metrics_recorder {
+            let _ = metrics_recorder.payment_verification_cache_misses.inc();
+        }
+    }
+
+    /// Verifies a batch of payments, caches the valid ones and answers everyone waiting on them.
+    async fn verify_batch(&self, batch: Vec<(Vec<QuoteHash>, PaymentDigest)>) {
+        if batch.is_empty() {
+            return;
+        }
+        let (keys, payments): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+        let results: Vec<VerificationResults> = match self.fetch(payments).await {
+            Ok(results) => results
+                .into_iter()
+                .map(|result| result.map(|results| results.to_vec()).map_err(Arc::new))
+                .collect(),
+            Err(err) => {
+                let err = Arc::new(err);
+                keys.iter().map(|_| Err(Arc::clone(&err))).collect()
+            }
+        };
+
+        {
+            let mut cache = self.cache.lock().unwrap_or_else(PoisonError::into_inner);
+            let now = Instant::now();
+            for (key, result) in keys.iter().zip(&results) {
+                if let Ok(results) = result
+                    && results.iter().all(|result| result.isValid)
+                {
+                    cache.insert(key.clone(), results.clone(), now);
+                }
+            }
+        }
+
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        for (key, result) in keys.iter().zip(results) {
+            for waiter in pending.release(key) {
+                let _ = waiter.send(result.clone());
+            }
+        }
+    }
+
+    /// Verifies the payments against the payment vault in one batch, honouring the rate limit
+    /// backoff.
+    async fn fetch(
+        &self,
+        payments: Vec<PaymentDigest>,
+    ) -> Result<Vec<Result<[PaymentVerificationResult; 3], Error>>, Error> {
+        let backoff_until = self
+            .backoff
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .until;
+        if let Some(until) = backoff_until {
+            tokio::time::sleep_until(until.into()).await;
+        }
+
+        let _permit = self
+            .rpc_permits
+            .acquire()
+            .await
+            .expect("The semaphore is never closed");
+        let batch_size = payments.len();
+        let started = Instant::now();
+        let result = fetch_batch_payment_verifications(&self.evm_network, payments).await;
+        #[cfg(feature = "open-metrics")]
+        if let Some(metrics_recorder) = &self.metrics_recorder {
+            metrics_recorder
+                .payment_verification_rpc_duration
+                .observe(started.elapsed().as_secs_f64());
+        }
+        debug!(
+            "Verifying a batch of {batch_size} payments took {:?}",
+            started.elapsed()
+        );
+
+        let rate_limited = match &result {
+            Ok(results) => results
+                .iter()
+                .any(|result| result.as_ref().is_err_and(Error::is_rate_limited)),
+            Err(err) => err.is_rate_limited(),
+        };
+        let mut backoff = self.backoff.lock().unwrap_or_else(PoisonError::into_inner);
+        if rate_limited {
+            let delay = backoff.extend(Instant::now());
+            warn!("EVM RPC is rate limiting payment verifications, backing off for {delay:?}");
+        } else if result.is_ok() {
+            backoff.reset();
+        }
+        result
+    }
+}
+
+/// The payments waiting for a batch to verify them.
+#[derive(Default)]
+struct PendingVerifications {
+    /// Payments queued for the next batch, in arrival order.
+    queued: Vec<(Vec<QuoteHash>, PaymentDigest)>,
+    /// Everyone waiting on a payment, whether it is still queued or in a batch being verified.
+    waiters: HashMap<Vec<QuoteHash>, Vec<oneshot::Sender<VerificationResults>>>,
+}
+
+impl PendingVerifications {
+    /// Waits on the payment, queueing it unless it is already waiting for a batch.
+    /// Returns the number of payments queued if it was queued.
+    fn enqueue(
+        &mut self,
+        key: Vec<QuoteHash>,
+        payment: PaymentDigest,
+        waiter: oneshot::Sender<VerificationResults>,
+    ) -> Option<usize> {
+        match self.waiters.entry(key) {
+            Entry::Occupied(mut entry) => {
+                entry.get_mut().push(waiter);
+                None
+            }
+            Entry::Vacant(entry) => {
+                self.queued.push((entry.key().clone(), payment));
+                let _ = entry.insert(vec![waiter]);
+                Some(self.queued.len())
+            }
+        }
+    }
+
+    /// Takes the queued payments, leaving their waiters until the batch releases them.
+    fn take_batch(&mut self) -> Vec<(Vec<QuoteHash>, PaymentDigest)> {
+        std::mem::take(&mut self.queued)
+    }
+
+    /// Stops waiting on the payment, returning who was waiting on it.
+    fn release(&mut self, key: &[QuoteHash]) -> Vec<oneshot::Sender<VerificationResults>> {
+        self.waiters.remove(key).unwrap_or_default()
+    }
+}
+
+/// Identifies a payment by its quote hashes, in a stable order.
+fn payment_key(payment: &PaymentDigest) -> Vec<QuoteHash> {
+    let mut key: Vec<_> = payment.iter().map(|(hash, _, _)| *hash).collect();
+    key.sort();
+    key
+}
+
+/// Payments verified recently, along with when they were verified.
+#[derive(Default)]
+struct VerifiedPayments {
+    entries: HashMap<Vec<QuoteHash>, (Vec<PaymentVerificationResult>, Instant)>,
+}
+
+impl VerifiedPayments {
+    fn get(&self, key: &[QuoteHash], now: Instant) -> Option<Vec<PaymentVerificationResult>> {
+        self.entries
+            .get(key)
+            .filter(|(_, verified_at)| now.duration_since(*verified_at) < VERIFIED_PAYMENT_TTL)
+            .map(|(results, _)| results.clone())
+    }
+
+    fn insert(
+        &mut self,
+        key: Vec<QuoteHash>,
+        results: Vec<PaymentVerificationResult>,
+        now: Instant,
+    ) {
+        if self.entries.len() >= MAX_CACHED_PAYMENTS {
+            self.entries.retain(|_, (_, verified_at)| {
+                now.duration_since(*verified_at) < VERIFIED_PAYMENT_TTL
+            });
+        }
+        if self.entries.len() >= MAX_CACHED_PAYMENTS
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, verified_at))| *verified_at)
+                .map(|(key, _)| key.clone())
+        {
+            let _ = self.entries.remove(&oldest);
+        }
+        let _ = self.entries.insert(key, (results, now));
+    }
+}
+
+/// Global backoff shared by all verifications while the EVM RPC rate limits the node.
+#[derive(Default)]
+struct RateLimitBackoff {
+    until: Option<Instant>,
+    delay: Duration,
+}
+
+impl RateLimitBackoff {
+    /// Backs off for longer than last time, returning the new delay.
+    fn extend(&mut self, now: Instant) -> Duration {
+        self.delay = (self.delay * 2).clamp(INITIAL_RATE_LIMIT_BACKOFF, MAX_RATE_LIMIT_BACKOFF);
+        self.until = Some(now + self.delay);
+        self.delay
+    }
+
+    fn reset(&mut self) {
+        self.until = None;
+        self.delay = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(quote_hash: QuoteHash, amount_paid: u64) -> PaymentVerificationResult {
+        PaymentVerificationResult {
+            quoteHash: quote_hash,
+            amountPaid: Amount::from(amount_paid),
+            isValid: true,
+        }
+    }
+
+    #[test]
+    fn verified_payments_expire_after_the_ttl() {
+        let key = vec![QuoteHash::repeat_byte(1)];
+        let results = vec![result(key[0], 10)];
+        let verified_at = Instant::now();
+
+        let mut cache = VerifiedPayments::default();
+        cache.insert(key.clone(), results, verified_at);
+
+        let cached = cache
+            .get(&key, verified_at + VERIFIED_PAYMENT_TTL / 2)
+            .expect("The payment is still cached");
+        assert_eq!(amount_paid_to(&key, &cached).ok(), Some(Amount::from(10)));
+        assert!(
+            cache
+                .get(&key, verified_at + VERIFIED_PAYMENT_TTL)
+                .is_none()
+        );
+        assert!(
+            cache
+                .get(&[QuoteHash::repeat_byte(2)], verified_at)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn pending_verifications_queue_each_payment_once_per_batch() {
+        let first = vec![QuoteHash::repeat_byte(1)];
+        let second = vec![QuoteHash::repeat_byte(2)];
+        let mut pending = PendingVerifications::default();
+
+        let (waiter, mut first_receiver) = oneshot::channel();
+        assert_eq!(pending.enqueue(first.clone(), vec![], waiter), Some(1));
+        let (waiter, mut joined_receiver) = oneshot::channel();
+        assert_eq!(pending.enqueue(first.clone(), vec![], waiter), None);
+        let (waiter, _second_receiver) = oneshot::channel();
+        assert_eq!(pending.enqueue(second.clone(), vec![], waiter), Some(2));
+
+        let batch = pending.take_batch();
+        assert_eq!(batch.len(), 2);
+        assert!(pending.take_batch().is_empty());
+
+        // The payment is being verified, so a new verification of it waits on the same batch.
+        let (waiter, mut late_receiver) = oneshot::channel();
+        assert_eq!(pending.enqueue(first.clone(), vec![], waiter), None);
+        assert!(pending.take_batch().is_empty());
+
+        let results = vec![result(first[0], 10)];
+        for waiter in pending.release(&first) {
+            let _ = waiter.send(Ok(results.clone()));
+        }
+        for receiver in [
+            &mut first_receiver,
+            &mut joined_receiver,
+            &mut late_receiver,
+        ] {
+            let received = receiver
+                .try_recv()
+                .expect("Every waiter is answered")
+                .expect("The payment is valid");
+            assert_eq!(
+                amount_paid_to(&first, &received).ok(),
+                Some(Amount::from(10))
+            );
+        }
+
+        // Once released, the payment is queued again for the next batch.
+        let (waiter, _receiver) = oneshot::channel();
+        assert_eq!(pending.enqueue(first, vec![], waiter), Some(1));
+    }
+
+    #[test]
+    fn rate_limit_backoff_doubles_up_to_the_max_and_resets() {
+        let now = Instant::now();
+        let mut backoff = RateLimitBackoff::default();
+
+        assert_eq!(backoff.extend(now), INITIAL_RATE_LIMIT_BACKOFF);
+        assert_eq!(backoff.extend(now), INITIAL_RATE_LIMIT_BACKOFF * 2);
+        for _ in 0..10 {
+            let _ = backoff.extend(now);
+        }
+        assert_eq!(backoff.until, Some(now + MAX_RATE_LIMIT_BACKOFF));
+
+        backoff.reset();
+        assert_eq!(backoff.until, None);
+        assert_eq!(backoff.extend(now), INITIAL_RATE_LIMIT_BACKOFF);
+    }
+
+    #[test]
+    fn payment_key_ignores_the_quote_order() {
+        let metrics = QuotingMetrics {
+            data_type: 0,
+            data_size: 0,
+            close_records_stored: 0,
+            records_per_type: vec![],
+            max_records: 0,
+            received_payment_count: 0,
+            live_time: 0,
+            network_density: None,
+            network_size: None,
+        };
+        let first = (
+            QuoteHash::repeat_byte(1),
+            metrics.clone(),
+            RewardsAddress::ZERO,
+        );
+        let second = (QuoteHash::repeat_byte(2), metrics, RewardsAddress::ZERO);
+
+        assert_eq!(
+            payment_key(&vec![first.clone(), second.clone()]),
+            payment_key(&vec![second, first])
+        );
+    }
+}
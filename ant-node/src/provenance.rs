@@ -0,0 +1,477 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Durable record of how the node came to hold each of its records.
+//!
+//! The record store reloads every record from disk on restart, without knowing whether it was
+//! paid for or replicated. The provenance of each record is therefore appended as a JSON line to
+//! a file in the node's root dir as the record is stored, the latest line for a key winning. The
+//! file is compacted after a restart and on audit, so it does not grow with records long gone.
+//!
+//! The line of a paid record also keeps the quotes of its payment, so the payment can be verified
+//! on chain again after a restart. Records whose payment is no longer found valid are flagged by
+//! the audit. Only the provenance itself is kept in memory.
+
+use crate::payment_verification::{PaymentDigest, PaymentVerifier};
+use ant_bootstrap::jsonl::JsonLinesFile;
+use ant_evm::{QuoteHash, payment_vault::error::Error as PaymentError};
+use ant_protocol::{NetworkAddress, PrettyPrintRecordKey};
+use futures::{StreamExt, stream};
+use libp2p::kad::RecordKey;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const PROVENANCE_LEDGER_FILENAME: &str = "record_provenance";
+
+/// Upper bound on the number of fresh replications awaiting their fetch.
+const MAX_PENDING_FRESH_REPLICATES: usize = 10_000;
+
+/// Upper bound on the number of payments being verified again at once, enough to fill the
+/// batches of the payment verifier.
+const MAX_CONCURRENT_REVERIFICATIONS: usize = 64;
+
+/// How the node came to hold a record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordProvenance {
+    /// Put by a client along with a payment verified on chain, covering these quotes of ours.
+    Paid { quote_hashes: Vec<QuoteHash> },
+    /// Fetched from a holder during replication.
+    Replicated { holder: NetworkAddress },
+    /// Fetched from a holder announcing a fresh record, along with a payment verified on chain.
+    FreshReplicate { holder: NetworkAddress },
+}
+
+/// Counts of the records held by the node per provenance, as reported by an audit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecordAudit {
+    pub paid: usize,
+    pub replicated: usize,
+    pub fresh_replicated: usize,
+    /// Records paid for with none of our quotes, or whose payment was not found valid on chain
+    /// when verified again after a restart.
+    pub unjustified: Vec<NetworkAddress>,
+    /// Records held with no provenance kept for them, e.g. ones stored before provenance was kept.
+    pub unknown: Vec<NetworkAddress>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct ProvenanceLine {
+    address: NetworkAddress,
+    provenance: RecordProvenance,
+    /// The quotes of the payment of a paid record, to verify it on chain again after a restart.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    payment: PaymentDigest,
+    /// Seconds since the UNIX epoch at which the provenance was recorded.
+    timestamp: u64,
+}
+
+#[derive(Default)]
+struct ProvenanceState {
+    entries: HashMap<RecordKey, RecordProvenance>,
+    /// Records validated for a fresh replication, along with their holder, yet to be fetched.
+    pending_fresh_replicates: HashMap<RecordKey, NetworkAddress>,
+    /// Paid records whose payment was not found valid on chain when verified after a restart.
+    invalid_payments: HashSet<RecordKey>,
+}
+
+impl ProvenanceState {
+    fn set(&mut self, key: &RecordKey, provenance: RecordProvenance) {
+        let _ = self.invalid_payments.remove(key);
+        let _ = self.entries.insert(key.clone(), provenance);
+    }
+}
+
+/// Provenance of the records held by the node, kept across restarts.
+///
+/// The file is only accessed off the async runtime, apart from the read when the node starts.
+pub(crate) struct ProvenanceLedger {
+    file: Arc<JsonLinesFile>,
+    state: Mutex<ProvenanceState>,
+    /// Held while a change is written, so the lines of the file follow the order of the changes
+    /// and a compaction never loses a line appended meanwhile.
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl ProvenanceLedger {
+    /// Opens the ledger kept in the given root dir.
+    pub(crate) fn new(root_dir: &Path) -> Self {
+        let file = JsonLinesFile::new(root_dir.join(PROVENANCE_LEDGER_FILENAME));
+        let mut state = ProvenanceState::default();
+        // Only the provenance is kept in memory, every line is dropped once read.
+        if let Err(err) = file.read(|line: &ProvenanceLine| {
+            let _ = state
+                .entries
+                .insert(line.address.to_record_key(), line.provenance.clone());
+            false
+        }) {
+            warn!("Failed to read the record provenance ledger: {err}");
+        }
+
+        Self {
+            file: Arc::new(file),
+            state: Mutex::new(state),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Records that the record was put by a client along with a payment to us.
+    /// This supersedes any earlier provenance of the record.
+    pub(crate) async fn record_paid(
+        &self,
+        key: &RecordKey,
+        quote_hashes: Vec<QuoteHash>,
+        payment: PaymentDigest,
+    ) {
+        let _write = self.write_lock.lock().await;
+        let provenance = RecordProvenance::Paid { quote_hashes };
+        {
+            let mut state = self.lock();
+            let _ = state.pending_fresh_replicates.remove(key);
+            state.set(key, provenance.clone());
+        }
+        self.append(key, provenance, payment).await;
+    }
+
+    /// Records that a fresh replication of the record, with a valid payment, was announced by
+    /// `holder`. It becomes the provenance of the record once fetched.
+    pub(crate) fn note_fresh_replicate(&self, key: RecordKey, holder: NetworkAddress) {
+        let mut state = self.lock();
+        if state.pending_fresh_replicates.len() >= MAX_PENDING_FRESH_REPLICATES {
+            state.pending_fresh_replicates.clear();
+        }
+        let _ = state.pending_fresh_replicates.insert(key, holder);
+    }
+
+    /// Records that the record was fetched from `holder` by replication, unless the node already
+    /// knew where it came from, e.g. a replicated update to a record the node was paid for.
+    pub(crate) async fn record_replicated(&self, key: &RecordKey, holder: NetworkAddress) {
+        let _write = self.write_lock.lock().await;
+        let provenance = {
+            let mut state = self.lock();
+            let provenance = match state.pending_fresh_replicates.remove(key) {
+                Some(holder) => RecordProvenance::FreshReplicate { holder },
+                None if state.entries.contains_key(key) => return,
+                None => RecordProvenance::Replicated { holder },
+            };
+            state.set(key, provenance.clone());
+            provenance
+        };
+        self.append(key, provenance, vec![]).await;
+    }
+
+    /// Returns the provenance of the record, if known.
+    pub(crate) fn provenance(&self, key: &RecordKey) -> Option<RecordProvenance> {
+        self.lock().entries.get(key).cloned()
+    }
+
+    /// Counts the held records per provenance, flagging the ones whose provenance cannot be
+    /// justified or is unknown. The provenance of records no longer held is dropped.
+    pub(crate) async fn audit<'a>(
+        &self,
+        held: impl Iterator<Item = &'a NetworkAddress>,
+    ) -> RecordAudit {
+        let mut audit = RecordAudit::default();
+        let mut held_keys = HashSet::new();
+        let dropped = {
+            let mut state = self.lock();
+            for address in held {
+                let key = address.to_record_key();
+                match state.entries.get(&key) {
+                    Some(RecordProvenance::Paid { quote_hashes })
+                        if quote_hashes.is_empty() || state.invalid_payments.contains(&key) =>
+                    {
+                        audit.unjustified.push(address.clone())
+                    }
+                    Some(RecordProvenance::Paid { .. }) => audit.paid += 1,
+                    Some(RecordProvenance::Replicated { .. }) => audit.replicated += 1,
+                    Some(RecordProvenance::FreshReplicate { .. }) => audit.fresh_replicated += 1,
+                    None => audit.unknown.push(address.clone()),
+                }
+                let _ = held_keys.insert(key);
+            }
+
+            let known = state.entries.len();
+            state.entries.retain(|key, _| held_keys.contains(key));
+            state.invalid_payments.retain(|key| held_keys.contains(key));
+            known - state.entries.len()
+        };
+
+        if dropped > 0 {
+            debug!("Dropping the provenance of {dropped} records no longer held");
+            if let Err(err) = self.compact(move |key| held_keys.contains(key)).await {
+                warn!("Failed to compact the record provenance ledger: {err}");
+            }
+        }
+        audit
+    }
+
+    /// Compacts the ledger, then verifies on chain again the payments of the paid records, as
+    /// the record store reloads them from disk without them. Records whose payment is no longer
+    /// found valid are flagged as unjustified by the audit.
+    pub(crate) async fn reverify_payments(&self, verifier: &Arc<PaymentVerifier>) {
+        let lines = match self.compact(|_| true).await {
+            Ok(lines) => lines,
+            Err(err) => {
+                warn!("Failed to compact the record provenance ledger: {err}");
+                return;
+            }
+        };
+        let paid: Vec<_> = lines
+            .into_iter()
+            .filter_map(|line| match line.provenance {
+                RecordProvenance::Paid { quote_hashes } if !line.payment.is_empty() => {
+                    Some((line.address.to_record_key(), quote_hashes, line.payment))
+                }
+                _ => None,
+            })
+            .collect();
+        let total = paid.len();
+        info!("Verifying again the payments of {total} paid records");
+
+        let mut verifications = stream::iter(paid)
+            .map(|(key, quote_hashes, payment)| async move {
+                let result = verifier.verify(&quote_hashes, payment).await;
+                (key, quote_hashes, result)
+            })
+            .buffer_unordered(MAX_CONCURRENT_REVERIFICATIONS);
+        let mut invalid = 0;
+        while let Some((key, quote_hashes, result)) = verifications.next().await {
+            match result {
+                Ok(amount) if !amount.is_zero() => continue,
+                Ok(_) => {}
+                Err(err) if matches!(err.as_ref(), PaymentError::PaymentInvalid) => {}
+                Err(err) => {
+                    warn!(
+                        "Could not verify again the payment of record {:?}: {err}",
+                        PrettyPrintRecordKey::from(&key)
+                    );
+                    continue;
+                }
+            }
+            warn!(
+                "Payment of record {:?} was not found valid on chain after a restart",
+                PrettyPrintRecordKey::from(&key)
+            );
+            invalid += 1;
+            self.flag_invalid_payment(&key, quote_hashes);
+        }
+        info!("Verified again the payments of {total} paid records, {invalid} were not valid");
+    }
+
+    /// Flags the payment of the record as invalid, unless it was paid for again meanwhile.
+    fn flag_invalid_payment(&self, key: &RecordKey, quote_hashes: Vec<QuoteHash>) {
+        let mut state = self.lock();
+        if state.entries.get(key) == Some(&RecordProvenance::Paid { quote_hashes }) {
+            let _ = state.invalid_payments.insert(key.clone());
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ProvenanceState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Appends the line of the change off the runtime. The write lock must be held.
+    async fn append(&self, key: &RecordKey, provenance: RecordProvenance, payment: PaymentDigest) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or_default();
+        let line = ProvenanceLine {
+            address: NetworkAddress::from(key),
+            provenance,
+            payment,
+            timestamp,
+        };
+        let file = Arc::clone(&self.file);
+        let result = tokio::task::spawn_blocking(move || file.append(std::slice::from_ref(&line)))
+            .await
+            .map_err(io::Error::other)
+            .and_then(|result| result);
+        if let Err(err) = result {
+            error!(
+                "Failed to record the provenance of record {:?}: {err}",
+                PrettyPrintRecordKey::from(key)
+            );
+        }
+    }
+
+    /// Rewrites the file off the runtime with the latest line of each record kept, returning
+    /// those lines.
+    async fn compact(
+        &self,
+        mut keep: impl FnMut(&RecordKey) -> bool + Send + 'static,
+    ) -> io::Result<Vec<ProvenanceLine>> {
+        let _write = self.write_lock.lock().await;
+        let file = Arc::clone(&self.file);
+        tokio::task::spawn_blocking(move || {
+            let mut latest = HashMap::new();
+            // Every line is dropped once read, only the latest of each record is kept.
+            let _ = file.read(|line: &ProvenanceLine| {
+                let key = line.address.to_record_key();
+                if keep(&key) {
+                    let _ = latest.insert(key, line.clone());
+                }
+                false
+            })?;
+            let lines: Vec<_> = latest.into_values().collect();
+            file.rewrite(&lines)?;
+            Ok(lines)
+        })
+        .await
+        .map_err(io::Error::other)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_protocol::storage::ChunkAddress;
+    use libp2p::PeerId;
+    use std::fs;
+    use xor_name::XorName;
+
+    fn address() -> NetworkAddress {
+        NetworkAddress::from(ChunkAddress::new(XorName::random(&mut rand::thread_rng())))
+    }
+
+    #[tokio::test]
+    async fn provenance_survives_reopening_and_paid_supersedes_replicated() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let ledger = ProvenanceLedger::new(temp_dir.path());
+        let holder = NetworkAddress::from(PeerId::random());
+
+        let paid = address();
+        let replicated = address();
+        let fresh = address();
+        ledger
+            .record_replicated(&paid.to_record_key(), holder.clone())
+            .await;
+        ledger
+            .record_paid(
+                &paid.to_record_key(),
+                vec![QuoteHash::repeat_byte(1)],
+                vec![],
+            )
+            .await;
+        // A replicated update does not override the payment.
+        ledger
+            .record_replicated(&paid.to_record_key(), holder.clone())
+            .await;
+        ledger
+            .record_replicated(&replicated.to_record_key(), holder.clone())
+            .await;
+        ledger.note_fresh_replicate(fresh.to_record_key(), holder.clone());
+        ledger
+            .record_replicated(&fresh.to_record_key(), holder.clone())
+            .await;
+
+        let reopened = ProvenanceLedger::new(temp_dir.path());
+        assert_eq!(
+            reopened.provenance(&paid.to_record_key()),
+            Some(RecordProvenance::Paid {
+                quote_hashes: vec![QuoteHash::repeat_byte(1)]
+            })
+        );
+        assert_eq!(
+            reopened.provenance(&replicated.to_record_key()),
+            Some(RecordProvenance::Replicated {
+                holder: holder.clone()
+            })
+        );
+        assert_eq!(
+            reopened.provenance(&fresh.to_record_key()),
+            Some(RecordProvenance::FreshReplicate { holder })
+        );
+
+        // Compacting leaves a line per record.
+        let lines = reopened.compact(|_| true).await?;
+        assert_eq!(lines.len(), 3);
+        let content = fs::read_to_string(temp_dir.path().join(PROVENANCE_LEDGER_FILENAME))?;
+        assert_eq!(content.lines().count(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn audit_reports_records_without_provenance_as_unknown_and_drops_the_gone_ones()
+    -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let ledger = ProvenanceLedger::new(temp_dir.path());
+        let holder = NetworkAddress::from(PeerId::random());
+
+        let paid = address();
+        let unpaid = address();
+        let replicated = address();
+        let gone = address();
+        let unknown = address();
+        ledger
+            .record_paid(
+                &paid.to_record_key(),
+                vec![QuoteHash::repeat_byte(1)],
+                vec![],
+            )
+            .await;
+        ledger
+            .record_paid(&unpaid.to_record_key(), vec![], vec![])
+            .await;
+        ledger
+            .record_replicated(&replicated.to_record_key(), holder.clone())
+            .await;
+        ledger
+            .record_replicated(&gone.to_record_key(), holder)
+            .await;
+
+        let held = [paid, unpaid.clone(), replicated, unknown.clone()];
+        let audit = ledger.audit(held.iter()).await;
+        assert_eq!(
+            audit,
+            RecordAudit {
+                paid: 1,
+                replicated: 1,
+                fresh_replicated: 0,
+                unjustified: vec![unpaid],
+                unknown: vec![unknown],
+            }
+        );
+        assert_eq!(ledger.provenance(&gone.to_record_key()), None);
+        let reopened = ProvenanceLedger::new(temp_dir.path());
+        assert_eq!(reopened.provenance(&gone.to_record_key()), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn audit_flags_records_whose_payment_was_not_found_valid_until_paid_again()
+    -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let ledger = ProvenanceLedger::new(temp_dir.path());
+        let paid = address();
+        let key = paid.to_record_key();
+        ledger
+            .record_paid(&key, vec![QuoteHash::repeat_byte(1)], vec![])
+            .await;
+
+        // A verification of an older payment does not flag a record paid for again meanwhile.
+        ledger.flag_invalid_payment(&key, vec![QuoteHash::repeat_byte(2)]);
+        assert_eq!(ledger.audit([paid.clone()].iter()).await.paid, 1);
+
+        ledger.flag_invalid_payment(&key, vec![QuoteHash::repeat_byte(1)]);
+        let audit = ledger.audit([paid.clone()].iter()).await;
+        assert_eq!(audit.unjustified, vec![paid.clone()]);
+
+        ledger
+            .record_paid(&key, vec![QuoteHash::repeat_byte(3)], vec![])
+            .await;
+        assert_eq!(ledger.audit([paid].iter()).await.paid, 1);
+        Ok(())
+    }
+}
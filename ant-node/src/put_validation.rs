@@ -8,10 +8,10 @@
 
 use std::collections::BTreeSet;
 
+use crate::earnings::EarningsEntry;
 use crate::error::PutValidationError;
 use crate::{Marker, Result, node::Node};
-use ant_evm::ProofOfPayment;
-use ant_evm::payment_vault::verify_data_payment;
+use ant_evm::{AttoTokens, ProofOfPayment};
 use ant_protocol::storage::GraphEntry;
 use ant_protocol::{
     NetworkAddress, PrettyPrintRecordKey,
@@ -92,6 +92,7 @@ impl Node {
                 let store_chunk_result = self.store_chunk(&chunk, true);
 
                 if store_chunk_result.is_ok() {
+                    self.record_paid_provenance(&record.key, &payment).await;
                     Marker::ValidPaidChunkPutFromClient(&PrettyPrintRecordKey::from(&record.key))
                         .log();
                     // Client changed to upload to ALL payees, hence no longer need this.
@@ -154,7 +155,7 @@ impl Node {
                         scratchpad,
                         record_key.clone(),
                         true,
-                        Some(payment),
+                        Some(payment.clone()),
                     )
                     .await;
 
@@ -163,6 +164,9 @@ impl Node {
                     // we eagerly retry replicaiton as it seems like other nodes are having trouble
                     // did not manage to get this scratchpad as yet.
                     Ok(_) | Err(PutValidationError::OutdatedRecordCounter { .. }) => {
+                        if store_scratchpad_result.is_ok() {
+                            self.record_paid_provenance(&record_key, &payment).await;
+                        }
                         let content_hash = XorName::from_content(&record.value);
                         Marker::ValidScratchpadRecordPutFromClient(&PrettyPrintRecordKey::from(
                             &record_key,
@@ -238,14 +242,14 @@ impl Node {
                 // The payment shall get deposit to self even the GraphEntry already presents.
                 // However, if the GraphEntry is already present, the incoming one shall be
                 // appended with the existing one, if content is different.
-                if let Err(err) = self
+                let payment_res = self
                     .payment_for_us_exists_and_is_still_valid(
                         &net_addr,
                         DataTypes::GraphEntry,
                         payment.clone(),
                     )
-                    .await
-                {
+                    .await;
+                if let Err(err) = &payment_res {
                     if already_exists {
                         debug!(
                             "Payment of the incoming existing GraphEntry {pretty_key:?} having error {err:?}"
@@ -254,7 +258,7 @@ impl Node {
                         error!(
                             "Payment of the incoming new GraphEntry {pretty_key:?} having error {err:?}"
                         );
-                        return Err(err);
+                        return payment_res;
                     }
                 }
 
@@ -262,6 +266,9 @@ impl Node {
                     .validate_merge_and_store_graphentries(vec![graph_entry], &key, true)
                     .await;
                 if res.is_ok() {
+                    if payment_res.is_ok() {
+                        self.record_paid_provenance(&record.key, &payment).await;
+                    }
                     let content_hash = XorName::from_content(&record.value);
                     Marker::ValidGraphEntryPutFromClient(&PrettyPrintRecordKey::from(&record.key))
                         .log();
@@ -338,14 +345,14 @@ impl Node {
 
                 // The pointer may already exist during the replication.
                 // The payment shall get deposit to self even if the pointer already exists.
-                if let Err(err) = self
+                let payment_res = self
                     .payment_for_us_exists_and_is_still_valid(
                         &net_addr,
                         DataTypes::Pointer,
                         payment.clone(),
                     )
-                    .await
-                {
+                    .await;
+                if let Err(err) = &payment_res {
                     if already_exists {
                         debug!(
                             "Payment of the incoming exists pointer {pretty_key:?} having error {err:?}"
@@ -354,7 +361,7 @@ impl Node {
                         error!(
                             "Payment of the incoming non-exist pointer {pretty_key:?} having error {err:?}"
                         );
-                        return Err(err);
+                        return payment_res;
                     }
                 }
 
@@ -363,10 +370,13 @@ impl Node {
                         pointer,
                         record.key.clone(),
                         true,
-                        Some(payment),
+                        Some(payment.clone()),
                     )
                     .await;
                 if res.is_ok() {
+                    if payment_res.is_ok() {
+                        self.record_paid_provenance(&record.key, &payment).await;
+                    }
                     let content_hash = XorName::from_content(&record.value);
                     Marker::ValidPointerPutFromClient(&pretty_key).log();
 
@@ -801,12 +811,10 @@ impl Node {
         // check if payment is valid on chain
         let payments_to_verify = payment.digest();
         let owned_payment_quotes: Vec<_> = own_quotes.iter().map(|quote| quote.hash()).collect();
-        let reward_amount = match verify_data_payment(
-            self.evm_network(),
-            owned_payment_quotes.clone(),
-            payments_to_verify.clone(),
-        )
-        .await
+        let reward_amount = match self
+            .payment_verifier()
+            .verify(&owned_payment_quotes, payments_to_verify.clone())
+            .await
         {
             Ok(amount) => amount,
             Err(e) => {
@@ -816,7 +824,8 @@ impl Node {
                     RETRY_PAYMENT_VERIFICATION_WAIT_TIME_SECS,
                 ))
                 .await;
-                verify_data_payment(self.evm_network(), owned_payment_quotes, payments_to_verify)
+                self.payment_verifier()
+                    .verify(&owned_payment_quotes, payments_to_verify)
                     .await
                     .inspect_err(|e| {
                         warn!("Failed to verify record payment on the second attempt: {e}");
@@ -845,12 +854,21 @@ impl Node {
                     .set(new_value);
             }
 
-            // TODO: currently ignored, re-enable once going to handle this.
-            // self.events_channel()
-            //     .broadcast(crate::NodeEvent::RewardReceived(
-            //         AttoTokens::from(reward_amount),
-            //         address.clone(),
-            //     ));
+            let entry = EarningsEntry::new(
+                own_quotes.iter().map(|quote| quote.hash()).collect(),
+                address.clone(),
+                data_type,
+                AttoTokens::from_atto(reward_amount),
+            );
+            let amount = entry.amount;
+            if let Err(err) = self.earnings_ledger().append(entry).await {
+                error!(
+                    "Failed to record the payment for record {pretty_key} in the earnings ledger: {err}"
+                );
+            }
+
+            self.events_channel()
+                .broadcast(crate::NodeEvent::RewardReceived(amount, address.clone()));
 
             // vdash metric (if modified please notify at https://github.com/happybeing/vdash/issues):
             info!(
@@ -871,6 +889,19 @@ impl Node {
         Ok(())
     }
 
+    /// Records that the record was stored along with a valid payment to us, so the node can
+    /// account for holding it after a restart.
+    async fn record_paid_provenance(&self, key: &RecordKey, payment: &ProofOfPayment) {
+        let quote_hashes = payment
+            .quotes_by_peer(&self.network().peer_id())
+            .iter()
+            .map(|quote| quote.hash())
+            .collect();
+        self.provenance_ledger()
+            .record_paid(key, quote_hashes, payment.digest())
+            .await;
+    }
+
     /// Get the local GraphEntries for the provided `GraphEntryAddress`
     /// This only fetches the GraphEntries from the local store and does not perform any network operations.
     async fn get_local_graphentries(
@@ -0,0 +1,284 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Operator policy consulted before the node quotes for storing a record.
+//!
+//! A policy can decline to quote, in which case the client receives the typed reason, or adjust
+//! the metrics the quote is priced from. Adjustments are kept within the protocol bounds: a node
+//! may report itself as fuller than it is, but never emptier nor fuller than clients deem
+//! plausible, and may not alter any other metric.
+
+use crate::error::{Error, Result};
+use ant_evm::QuotingMetrics;
+use ant_protocol::{
+    NetworkAddress, constants::max_plausible_close_records, error::QuoteDeclineReason,
+    storage::DataTypes,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The outcome of consulting a [`QuotePolicy`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QuoteDecision {
+    /// Quote, pricing the quote from the given metrics.
+    Quote(QuotingMetrics),
+    /// Decline to quote, for the given reason.
+    Decline(QuoteDeclineReason),
+}
+
+/// Decides whether, and from which metrics, the node quotes for storing a record.
+pub trait QuotePolicy: Send + Sync {
+    /// Decides on a quote for the record at `address`, given the node's actual metrics.
+    fn decide(&self, address: &NetworkAddress, metrics: &QuotingMetrics) -> QuoteDecision;
+}
+
+/// Quotes for every record, from the node's actual metrics.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultQuotePolicy;
+
+impl QuotePolicy for DefaultQuotePolicy {
+    fn decide(&self, _address: &NetworkAddress, metrics: &QuotingMetrics) -> QuoteDecision {
+        QuoteDecision::Quote(metrics.clone())
+    }
+}
+
+/// Settings of a [`ConfigQuotePolicy`], as read from its JSON file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuotePolicyConfig {
+    /// Decline to quote once the node's storage is at least this full, in percent.
+    pub max_storage_percent: Option<u8>,
+    /// Data types the node declines to quote for.
+    pub declined_data_types: Vec<DataTypes>,
+    /// Decline to quote for records larger than this many bytes.
+    pub max_record_size: Option<usize>,
+    /// Report the node's storage as at least this full, in percent, which raises its prices.
+    pub min_reported_storage_percent: Option<u8>,
+}
+
+/// A policy driven by a [`QuotePolicyConfig`].
+#[derive(Clone, Debug, Default)]
+pub struct ConfigQuotePolicy {
+    config: QuotePolicyConfig,
+}
+
+impl ConfigQuotePolicy {
+    /// Creates the policy from its settings.
+    pub fn new(config: QuotePolicyConfig) -> Self {
+        Self { config }
+    }
+
+    /// Reads the settings of the policy from a JSON file.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let load_failed = |reason: String| Error::QuotePolicyLoadFailed {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let content = std::fs::read_to_string(path).map_err(|err| load_failed(err.to_string()))?;
+        let config = serde_json::from_str(&content).map_err(|err| load_failed(err.to_string()))?;
+        Ok(Self::new(config))
+    }
+}
+
+impl QuotePolicy for ConfigQuotePolicy {
+    fn decide(&self, _address: &NetworkAddress, metrics: &QuotingMetrics) -> QuoteDecision {
+        if DataTypes::from_index(metrics.data_type)
+            .is_some_and(|data_type| self.config.declined_data_types.contains(&data_type))
+        {
+            return QuoteDecision::Decline(QuoteDeclineReason::DataTypeNotAccepted);
+        }
+        if self
+            .config
+            .max_record_size
+            .is_some_and(|max_size| metrics.data_size > max_size)
+        {
+            return QuoteDecision::Decline(QuoteDeclineReason::RecordTooLarge);
+        }
+        if self
+            .config
+            .max_storage_percent
+            .is_some_and(|max_percent| storage_percent(metrics) >= usize::from(max_percent))
+        {
+            return QuoteDecision::Decline(QuoteDeclineReason::StorageFull);
+        }
+
+        let mut reported = metrics.clone();
+        if let Some(min_percent) = self.config.min_reported_storage_percent {
+            let min_stored =
+                (metrics.max_records * usize::from(min_percent.min(100))).div_ceil(100);
+            reported.close_records_stored = reported.close_records_stored.max(min_stored);
+        }
+        QuoteDecision::Quote(reported)
+    }
+}
+
+/// How full the node's storage is, in percent.
+fn storage_percent(metrics: &QuotingMetrics) -> usize {
+    if metrics.max_records == 0 {
+        return 100;
+    }
+    metrics.close_records_stored.saturating_mul(100) / metrics.max_records
+}
+
+/// Keeps the metrics reported by a policy within the protocol bounds: the node may only report
+/// itself as fuller than it actually is, up to the close records clients deem plausible, and
+/// every other metric is taken from the actual ones.
+pub(crate) fn within_protocol_bounds(
+    actual: &QuotingMetrics,
+    reported: &QuotingMetrics,
+) -> QuotingMetrics {
+    let max_records = reported
+        .max_records
+        .min(actual.max_records)
+        .max(actual.close_records_stored.min(actual.max_records));
+    let close_records_stored = reported
+        .close_records_stored
+        .min(max_plausible_close_records(actual.close_records_stored))
+        .max(actual.close_records_stored)
+        .min(max_records.max(actual.close_records_stored));
+
+    QuotingMetrics {
+        close_records_stored,
+        max_records,
+        ..actual.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_protocol::storage::ChunkAddress;
+    use xor_name::XorName;
+
+    fn metrics(data_type: DataTypes, data_size: usize, stored: usize) -> QuotingMetrics {
+        QuotingMetrics {
+            data_type: data_type.get_index(),
+            data_size,
+            close_records_stored: stored,
+            records_per_type: vec![],
+            max_records: 1000,
+            received_payment_count: 0,
+            live_time: 0,
+            network_density: None,
+            network_size: None,
+        }
+    }
+
+    fn address() -> NetworkAddress {
+        NetworkAddress::from(ChunkAddress::new(XorName::random(&mut rand::thread_rng())))
+    }
+
+    #[test]
+    fn config_policy_declines_with_typed_reasons() {
+        let policy = ConfigQuotePolicy::new(QuotePolicyConfig {
+            max_storage_percent: Some(90),
+            declined_data_types: vec![DataTypes::Scratchpad],
+            max_record_size: Some(4096),
+            min_reported_storage_percent: None,
+        });
+
+        assert_eq!(
+            policy.decide(&address(), &metrics(DataTypes::Scratchpad, 10, 0)),
+            QuoteDecision::Decline(QuoteDeclineReason::DataTypeNotAccepted)
+        );
+        assert_eq!(
+            policy.decide(&address(), &metrics(DataTypes::Chunk, 4097, 0)),
+            QuoteDecision::Decline(QuoteDeclineReason::RecordTooLarge)
+        );
+        assert_eq!(
+            policy.decide(&address(), &metrics(DataTypes::Chunk, 10, 900)),
+            QuoteDecision::Decline(QuoteDeclineReason::StorageFull)
+        );
+        let accepted = metrics(DataTypes::Chunk, 10, 899);
+        assert_eq!(
+            policy.decide(&address(), &accepted),
+            QuoteDecision::Quote(accepted)
+        );
+    }
+
+    #[test]
+    fn config_policy_reports_at_least_the_minimum_fullness() {
+        let policy = ConfigQuotePolicy::new(QuotePolicyConfig {
+            min_reported_storage_percent: Some(50),
+            ..Default::default()
+        });
+
+        let QuoteDecision::Quote(reported) =
+            policy.decide(&address(), &metrics(DataTypes::Chunk, 10, 100))
+        else {
+            panic!("The policy should quote");
+        };
+        assert_eq!(reported.close_records_stored, 500);
+
+        let QuoteDecision::Quote(reported) =
+            policy.decide(&address(), &metrics(DataTypes::Chunk, 10, 700))
+        else {
+            panic!("The policy should quote");
+        };
+        assert_eq!(reported.close_records_stored, 700);
+    }
+
+    #[test]
+    fn adjustments_are_kept_within_protocol_bounds() {
+        let actual = metrics(DataTypes::Chunk, 10, 400);
+
+        // Reporting an emptier node is not allowed.
+        let mut emptier = actual.clone();
+        emptier.close_records_stored = 0;
+        emptier.max_records = 5000;
+        assert_eq!(within_protocol_bounds(&actual, &emptier), actual);
+
+        // Reporting a fuller node is, but only up to full.
+        let mut fuller = actual.clone();
+        fuller.close_records_stored = 5000;
+        fuller.max_records = 200;
+        fuller.data_size = 1;
+        let bounded = within_protocol_bounds(&actual, &fuller);
+        assert_eq!(bounded.max_records, 400);
+        assert_eq!(bounded.close_records_stored, 400);
+        assert_eq!(bounded.data_size, actual.data_size);
+    }
+
+    #[test]
+    fn reported_fullness_stays_plausible_to_clients() {
+        let policy = ConfigQuotePolicy::new(QuotePolicyConfig {
+            min_reported_storage_percent: Some(50),
+            ..Default::default()
+        });
+
+        for stored in [0, 10, 100, 400] {
+            let actual = metrics(DataTypes::Chunk, 10, stored);
+            let QuoteDecision::Quote(reported) = policy.decide(&address(), &actual) else {
+                panic!("The policy should quote");
+            };
+            let bounded = within_protocol_bounds(&actual, &reported);
+            // As screened by clients against peers storing as many records.
+            assert!(bounded.close_records_stored <= max_plausible_close_records(stored));
+            assert!(bounded.close_records_stored <= bounded.max_records);
+            assert!(bounded.close_records_stored >= stored);
+        }
+    }
+
+    #[test]
+    fn config_is_read_from_json() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("quote_policy.json");
+        std::fs::write(
+            &path,
+            r#"{ "max_storage_percent": 90, "declined_data_types": ["Pointer"] }"#,
+        )?;
+
+        let policy = ConfigQuotePolicy::from_file(&path)?;
+        assert_eq!(policy.config.max_storage_percent, Some(90));
+        assert_eq!(policy.config.declined_data_types, vec![DataTypes::Pointer]);
+
+        std::fs::write(&path, r#"{ "max_storage": 90 }"#)?;
+        assert!(ConfigQuotePolicy::from_file(&path).is_err());
+        Ok(())
+    }
+}
@@ -83,6 +83,7 @@ impl Node {
                     }
                 };
 
+                let key = record.key.clone();
                 if let Err(err) = node.store_replicated_in_record(record).await {
                     error!(
                         "During store replication fetched {pretty_key:?} from holder {holder:?}, got error {err:?}"
@@ -91,6 +92,9 @@ impl Node {
                     debug!(
                         "Completed storing Replication Record {pretty_key:?} from holder {holder:?}."
                     );
+                    node.provenance_ledger()
+                        .record_replicated(&key, NetworkAddress::from(holder))
+                        .await;
                 }
             });
         }
@@ -204,7 +208,9 @@ impl Node {
                         .payment_for_us_exists_and_is_still_valid(&addr, data_type, payment)
                         .await
                     {
-                        Ok(_) => {}
+                        Ok(_) => node
+                            .provenance_ledger()
+                            .note_fresh_replicate(addr.to_record_key(), holder.clone()),
                         Err(err) => {
                             info!("ProofOfPayment of {addr:?} is invalid with error {err:?}");
                             continue;
@@ -0,0 +1,253 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Policy deciding when a node should hold back on background work.
+//!
+//! A node is throttled during its quiet hours, and whenever the host is under CPU or memory
+//! pressure. While throttled, the node applies its throttled bandwidth limits, fetches one
+//! replication record at a time, skips storage challenges and asks its close peers not to dial it.
+
+use crate::networking::BandwidthLimits;
+use chrono::NaiveTime;
+use std::{fmt, str::FromStr};
+
+/// Once throttled because of resource pressure, the usage has to drop this many percentage
+/// points below the threshold before the node resumes, to avoid flapping around the threshold.
+const PRESSURE_HYSTERESIS_PERCENT: f32 = 10.0;
+
+/// A daily window of local time, such as `22:00-07:00`.
+///
+/// The start is inclusive and the end exclusive. A window whose end is before its start wraps
+/// around midnight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuietWindow {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl QuietWindow {
+    /// Returns true if the time of day falls within the window.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl FromStr for QuietWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Quiet window '{s}' must be in the form HH:MM-HH:MM"))?;
+        let parse_time = |time: &str| {
+            NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .map_err(|err| format!("Invalid time '{time}' in quiet window '{s}': {err}"))
+        };
+        let window = Self {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        };
+        if window.start == window.end {
+            return Err(format!("Quiet window '{s}' is empty"));
+        }
+        Ok(window)
+    }
+}
+
+impl fmt::Display for QuietWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+/// When and how a node throttles itself.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ThrottlePolicy {
+    /// Daily windows of local time during which the node is throttled.
+    pub quiet_hours: Vec<QuietWindow>,
+    /// System-wide CPU usage, in percent, above which the node is throttled.
+    pub max_cpu_usage: Option<f32>,
+    /// System-wide memory usage, in percent, above which the node is throttled.
+    pub max_memory_usage: Option<f32>,
+    /// Upload and download rate limits in effect while throttled. They only ever tighten the
+    /// regular limits of the node.
+    pub bandwidth_limits: BandwidthLimits,
+}
+
+impl ThrottlePolicy {
+    /// Returns true if the node may ever get throttled under this policy.
+    pub fn is_enabled(&self) -> bool {
+        !self.quiet_hours.is_empty()
+            || self.max_cpu_usage.is_some()
+            || self.max_memory_usage.is_some()
+    }
+
+    /// Returns why the node should be throttled at the given time of day, if at all.
+    ///
+    /// `currently_throttled` is the outcome of the previous evaluation, used to apply some
+    /// hysteresis to the resource pressure thresholds.
+    pub(crate) fn evaluate(
+        &self,
+        time: NaiveTime,
+        usage: Option<ResourceUsage>,
+        currently_throttled: bool,
+    ) -> Option<ThrottleReason> {
+        if self.quiet_hours.iter().any(|window| window.contains(time)) {
+            return Some(ThrottleReason::QuietHours);
+        }
+
+        let usage = usage?;
+        let exceeds = |usage: f32, max: Option<f32>| {
+            max.is_some_and(|max| {
+                if currently_throttled {
+                    usage > max - PRESSURE_HYSTERESIS_PERCENT
+                } else {
+                    usage > max
+                }
+            })
+        };
+        if exceeds(usage.cpu, self.max_cpu_usage) {
+            return Some(ThrottleReason::CpuPressure(usage.cpu));
+        }
+        if exceeds(usage.memory, self.max_memory_usage) {
+            return Some(ThrottleReason::MemoryPressure(usage.memory));
+        }
+        None
+    }
+}
+
+/// Why a node is throttled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ThrottleReason {
+    QuietHours,
+    CpuPressure(f32),
+    MemoryPressure(f32),
+}
+
+impl fmt::Display for ThrottleReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThrottleReason::QuietHours => write!(f, "quiet hours"),
+            ThrottleReason::CpuPressure(usage) => write!(f, "CPU usage at {usage:.1}%"),
+            ThrottleReason::MemoryPressure(usage) => write!(f, "memory usage at {usage:.1}%"),
+        }
+    }
+}
+
+/// System-wide resource usage, in percent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ResourceUsage {
+    pub(crate) cpu: f32,
+    pub(crate) memory: f32,
+}
+
+/// Samples the resource usage of the host.
+///
+/// Sampling relies on `sysinfo`, which is only available with the `open-metrics` feature.
+/// Without it, resource pressure thresholds are ignored.
+pub(crate) struct ResourceMonitor {
+    #[cfg(feature = "open-metrics")]
+    system: sysinfo::System,
+}
+
+impl ResourceMonitor {
+    pub(crate) fn new() -> Self {
+        #[cfg(not(feature = "open-metrics"))]
+        warn!("Resource usage cannot be sampled without the open-metrics feature");
+        Self {
+            #[cfg(feature = "open-metrics")]
+            system: sysinfo::System::new(),
+        }
+    }
+
+    /// Returns the usage since the previous sample. The CPU usage of the first sample is
+    /// meaningless, hence the monitor shall be sampled at a regular interval.
+    #[cfg(feature = "open-metrics")]
+    pub(crate) fn sample(&mut self) -> Option<ResourceUsage> {
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        let total_memory = self.system.total_memory();
+        if total_memory == 0 {
+            return None;
+        }
+        Some(ResourceUsage {
+            cpu: self.system.global_cpu_info().cpu_usage(),
+            memory: (self.system.used_memory() as f64 / total_memory as f64 * 100.0) as f32,
+        })
+    }
+
+    #[cfg(not(feature = "open-metrics"))]
+    pub(crate) fn sample(&mut self) -> Option<ResourceUsage> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").expect("valid time")
+    }
+
+    #[test]
+    fn quiet_window_parses_and_wraps_around_midnight() {
+        let window: QuietWindow = "22:00-07:00".parse().expect("valid window");
+        assert_eq!(window.to_string(), "22:00-07:00");
+        assert!(window.contains(time("22:00")));
+        assert!(window.contains(time("03:30")));
+        assert!(!window.contains(time("07:00")));
+        assert!(!window.contains(time("12:00")));
+
+        let window: QuietWindow = "09:00-17:30".parse().expect("valid window");
+        assert!(window.contains(time("12:00")));
+        assert!(!window.contains(time("17:30")));
+        assert!(!window.contains(time("22:00")));
+
+        assert!("09:00".parse::<QuietWindow>().is_err());
+        assert!("9am-5pm".parse::<QuietWindow>().is_err());
+        assert!("09:00-09:00".parse::<QuietWindow>().is_err());
+    }
+
+    #[test]
+    fn policy_throttles_on_quiet_hours_and_resource_pressure() {
+        let policy = ThrottlePolicy {
+            quiet_hours: vec!["09:00-17:00".parse().expect("valid window")],
+            max_cpu_usage: Some(80.0),
+            max_memory_usage: None,
+            bandwidth_limits: BandwidthLimits::default(),
+        };
+        let usage = |cpu| Some(ResourceUsage { cpu, memory: 99.0 });
+
+        assert_eq!(
+            policy.evaluate(time("10:00"), None, false),
+            Some(ThrottleReason::QuietHours)
+        );
+        assert_eq!(policy.evaluate(time("18:00"), None, false), None);
+        assert_eq!(policy.evaluate(time("18:00"), usage(50.0), false), None);
+        assert_eq!(
+            policy.evaluate(time("18:00"), usage(90.0), false),
+            Some(ThrottleReason::CpuPressure(90.0))
+        );
+        // Once throttled, the usage has to drop well below the threshold.
+        assert_eq!(
+            policy.evaluate(time("18:00"), usage(75.0), true),
+            Some(ThrottleReason::CpuPressure(75.0))
+        );
+        assert_eq!(policy.evaluate(time("18:00"), usage(65.0), true), None);
+    }
+}
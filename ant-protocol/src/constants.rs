@@ -29,3 +29,18 @@ pub const MAX_RECORD_SIZE: usize = 1024 * 1024 * 4;
 /// Is defined as CLOSE_GROUP_SIZE + 2
 pub const REPLICATION_FACTOR: NonZeroUsize =
     NonZeroUsize::new(7).expect("REPLICATION_FACTOR must be 7");
+
+/// A quote may report up to this many times the close records stored, plus
+/// `CLOSE_RECORDS_MARGIN`. A node may thus report itself as fuller than it is, e.g. to raise its
+/// prices, while clients deem a quote reporting more than that, next to the other quotes for the
+/// same address, implausible.
+const CLOSE_RECORDS_OUTLIER_FACTOR: usize = 4;
+const CLOSE_RECORDS_MARGIN: usize = 256;
+
+/// The most close records a quote may plausibly report, given the close records actually stored
+/// by its node or, as seen by a client, the median of the other quotes for the same address.
+pub fn max_plausible_close_records(close_records_stored: usize) -> usize {
+    close_records_stored
+        .saturating_mul(CLOSE_RECORDS_OUTLIER_FACTOR)
+        .saturating_add(CLOSE_RECORDS_MARGIN)
+}
@@ -73,6 +73,20 @@ pub enum Error {
     #[error("Outdated record: with counter {counter}, expected any above {expected}")]
     OutdatedRecordCounter { counter: u64, expected: u64 },
 
+    // ---------- Quote policy errors
+    /// The node declined to quote because its storage is above the limit set by its operator
+    #[error("The node declined to quote: its storage is above the limit set by its operator")]
+    QuoteDeclinedStorageFull,
+    /// The node declined to quote because its operator does not accept the data type
+    #[error("The node declined to quote: its operator does not accept this data type")]
+    QuoteDeclinedDataType,
+    /// The node declined to quote because the record is larger than its operator accepts
+    #[error("The node declined to quote: the record is larger than its operator accepts")]
+    QuoteDeclinedRecordSize,
+    /// The node declined to quote for another reason set by its operator
+    #[error("The node declined to quote")]
+    QuoteDeclined,
+
     // Dev Note: add new variants above this one for backward compatibility with older protocol versions
     // ---------- Unknown/fallback variant for retro compatibility
     /// Unknown error variant (for backward compatibility with newer protocol versions)
@@ -81,6 +95,54 @@ pub enum Error {
     Unknown,
 }
 
+/// Why a node declined to quote for storing a record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QuoteDeclineReason {
+    /// The node's storage is above the limit set by its operator
+    StorageFull,
+    /// The node's operator does not accept the data type
+    DataTypeNotAccepted,
+    /// The record is larger than the node's operator accepts
+    RecordTooLarge,
+    /// Any other reason set by the node's operator
+    Other,
+}
+
+impl std::fmt::Display for QuoteDeclineReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StorageFull => write!(f, "storage full"),
+            Self::DataTypeNotAccepted => write!(f, "data type not accepted"),
+            Self::RecordTooLarge => write!(f, "record too large"),
+            Self::Other => write!(f, "declined by operator"),
+        }
+    }
+}
+
+impl From<QuoteDeclineReason> for Error {
+    fn from(reason: QuoteDeclineReason) -> Self {
+        match reason {
+            QuoteDeclineReason::StorageFull => Error::QuoteDeclinedStorageFull,
+            QuoteDeclineReason::DataTypeNotAccepted => Error::QuoteDeclinedDataType,
+            QuoteDeclineReason::RecordTooLarge => Error::QuoteDeclinedRecordSize,
+            QuoteDeclineReason::Other => Error::QuoteDeclined,
+        }
+    }
+}
+
+impl Error {
+    /// Returns why the node declined to quote, if this error is such a decline.
+    pub fn quote_decline_reason(&self) -> Option<QuoteDeclineReason> {
+        match self {
+            Error::QuoteDeclinedStorageFull => Some(QuoteDeclineReason::StorageFull),
+            Error::QuoteDeclinedDataType => Some(QuoteDeclineReason::DataTypeNotAccepted),
+            Error::QuoteDeclinedRecordSize => Some(QuoteDeclineReason::RecordTooLarge),
+            Error::QuoteDeclined => Some(QuoteDeclineReason::Other),
+            _ => None,
+        }
+    }
+}
+
 impl From<Error> for store::Error {
     fn from(_err: Error) -> Self {
         store::Error::ValueTooLarge
@@ -376,4 +438,19 @@ mod tests {
             ComplexTypesRemoved::ChunkDoesNotExist(addr)
         );
     }
+
+    #[test]
+    fn test_quote_decline_reason_round_trip() {
+        for reason in [
+            QuoteDeclineReason::StorageFull,
+            QuoteDeclineReason::DataTypeNotAccepted,
+            QuoteDeclineReason::RecordTooLarge,
+            QuoteDeclineReason::Other,
+        ] {
+            let serialized = rmp_serde::to_vec(&Error::from(reason)).unwrap();
+            let deserialized: Error = rmp_serde::from_slice(&serialized).unwrap();
+            assert_eq!(deserialized.quote_decline_reason(), Some(reason));
+        }
+        assert_eq!(Error::GetStoreQuoteFailed.quote_decline_reason(), None);
+    }
 }
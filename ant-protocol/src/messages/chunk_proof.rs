@@ -8,11 +8,13 @@
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use xor_name::XorName;
 
 /// The nonce provided by the verifier
 pub type Nonce = u64;
 
-/// The hash(record_value + nonce) that is used to prove the existence of a chunk
+/// The hash(record_value + nonce) that is used to prove the existence of a chunk,
+/// or hash(content_hash + counter + nonce) for the mutable record types.
 #[derive(Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct ChunkProof([u8; 32]);
 
@@ -24,6 +26,16 @@ impl ChunkProof {
         ChunkProof(hash)
     }
 
+    /// Proof for a mutable record (pointer, scratchpad or graph entry), computed over the
+    /// hash and counter of the version being held, so that a stale copy gives a different proof.
+    pub fn new_versioned(content_hash: &XorName, counter: u64, nonce: Nonce) -> Self {
+        let counter_bytes = counter.to_be_bytes();
+        let nonce_bytes = nonce.to_be_bytes();
+        let combined = [&content_hash.0[..], &counter_bytes, &nonce_bytes].concat();
+        let hash = sha3_256(&combined);
+        ChunkProof(hash)
+    }
+
     pub fn verify(&self, other_proof: &ChunkProof) -> bool {
         self.0 == other_proof.0
     }
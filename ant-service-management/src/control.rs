@@ -7,16 +7,21 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::error::{Error, Result};
+use crate::limits::ResourceLimits;
 use service_manager::{
-    ServiceInstallCtx, ServiceLabel, ServiceLevel, ServiceManager, ServiceStartCtx, ServiceStopCtx,
-    ServiceUninstallCtx,
+    ServiceInstallCtx, ServiceLabel, ServiceLevel, ServiceManager, ServiceManagerKind,
+    ServiceStartCtx, ServiceStopCtx, ServiceUninstallCtx,
 };
 use std::{
     net::{SocketAddr, TcpListener},
-    path::Path,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 use sysinfo::System;
 
+/// The systemd drop-in the resource limits of a service are written to.
+const RESOURCE_LIMITS_DROP_IN_FILE_NAME: &str = "resource-limits.conf";
+
 /// A thin wrapper around the `service_manager::ServiceManager`, which makes our own testing
 /// easier.
 ///
@@ -26,8 +31,19 @@ use sysinfo::System;
 /// to clean up, especially if the tests fail.
 pub trait ServiceControl: Sync {
     fn create_service_user(&self, username: &str) -> Result<()>;
+    /// Whether the services run as the user managing them, rather than as a service user.
+    fn runs_as_current_user(&self) -> bool {
+        false
+    }
     fn get_available_port(&self) -> Result<u16>;
     fn install(&self, install_ctx: ServiceInstallCtx, user_mode: bool) -> Result<()>;
+    /// Limit the resources of an installed service. The limits are removed when it is uninstalled.
+    fn set_resource_limits(
+        &self,
+        service_name: &str,
+        limits: &ResourceLimits,
+        user_mode: bool,
+    ) -> Result<()>;
     fn get_process_pid(&self, path: &Path) -> Result<u32>;
     fn start(&self, service_name: &str, user_mode: bool) -> Result<()>;
     fn stop(&self, service_name: &str, user_mode: bool) -> Result<()>;
@@ -35,6 +51,52 @@ pub trait ServiceControl: Sync {
     fn wait(&self, delay: u64);
 }
 
+/// A controller shared by the services it manages, for the backend chosen to manage them.
+pub type SharedServiceControl = Arc<dyn ServiceControl + Send>;
+
+impl<T: ServiceControl + Send + ?Sized> ServiceControl for Arc<T> {
+    fn create_service_user(&self, username: &str) -> Result<()> {
+        (**self).create_service_user(username)
+    }
+
+    fn get_available_port(&self) -> Result<u16> {
+        (**self).get_available_port()
+    }
+
+    fn install(&self, install_ctx: ServiceInstallCtx, user_mode: bool) -> Result<()> {
+        (**self).install(install_ctx, user_mode)
+    }
+
+    fn set_resource_limits(
+        &self,
+        service_name: &str,
+        limits: &ResourceLimits,
+        user_mode: bool,
+    ) -> Result<()> {
+        (**self).set_resource_limits(service_name, limits, user_mode)
+    }
+
+    fn get_process_pid(&self, path: &Path) -> Result<u32> {
+        (**self).get_process_pid(path)
+    }
+
+    fn start(&self, service_name: &str, user_mode: bool) -> Result<()> {
+        (**self).start(service_name, user_mode)
+    }
+
+    fn stop(&self, service_name: &str, user_mode: bool) -> Result<()> {
+        (**self).stop(service_name, user_mode)
+    }
+
+    fn uninstall(&self, service_name: &str, user_mode: bool) -> Result<()> {
+        (**self).uninstall(service_name, user_mode)
+    }
+
+    fn wait(&self, delay: u64) {
+        (**self).wait(delay)
+    }
+}
+
 pub struct ServiceController {}
 
 impl ServiceControl for ServiceController {
@@ -178,28 +240,7 @@ impl ServiceControl for ServiceController {
     }
 
     fn get_process_pid(&self, bin_path: &Path) -> Result<u32> {
-        debug!(
-            "Searching for process with binary at {}",
-            bin_path.to_string_lossy()
-        );
-        let system = System::new_all();
-        for (pid, process) in system.processes() {
-            if let Some(path) = process.exe()
-                && bin_path == path
-            {
-                // There does not seem to be any easy way to get the process ID from the `Pid`
-                // type. Probably something to do with representing it in a cross-platform way.
-                trace!("Found process {bin_path:?} with PID: {pid}");
-                return Ok(pid.to_string().parse::<u32>()?);
-            }
-        }
-        error!(
-            "No process was located with a path at {}",
-            bin_path.to_string_lossy()
-        );
-        Err(Error::ServiceProcessNotFound(
-            bin_path.to_string_lossy().to_string(),
-        ))
+        find_process_pid(bin_path)
     }
 
     fn install(&self, install_ctx: ServiceInstallCtx, user_mode: bool) -> Result<()> {
@@ -217,6 +258,28 @@ impl ServiceControl for ServiceController {
         Ok(())
     }
 
+    fn set_resource_limits(
+        &self,
+        service_name: &str,
+        limits: &ResourceLimits,
+        user_mode: bool,
+    ) -> Result<()> {
+        debug!("Setting the resource limits of {service_name}: {limits:?}");
+        let kind = ServiceManagerKind::native()?;
+        if kind != ServiceManagerKind::Systemd {
+            error!("Resource limits are not supported by the {kind:?} service manager");
+            return Err(Error::ResourceLimitsNotSupported(format!("{kind:?}")));
+        }
+
+        let drop_in_dir_path = systemd_drop_in_dir_path(service_name, user_mode)?;
+        std::fs::create_dir_all(&drop_in_dir_path)?;
+        std::fs::write(
+            drop_in_dir_path.join(RESOURCE_LIMITS_DROP_IN_FILE_NAME),
+            limits.to_systemd_drop_in(),
+        )?;
+        systemd_daemon_reload(user_mode)
+    }
+
     fn start(&self, service_name: &str, user_mode: bool) -> Result<()> {
         debug!("Starting service: {service_name}");
         let label: ServiceLabel = service_name.parse()?;
@@ -261,6 +324,19 @@ impl ServiceControl for ServiceController {
                 .set_level(ServiceLevel::User)
                 .inspect_err(|err| error!("Could not set service to user mode: {err:?}"))?;
         }
+        if ServiceManagerKind::native().is_ok_and(|kind| kind == ServiceManagerKind::Systemd) {
+            // The drop-in would otherwise apply to any later service with the same name.
+            let drop_in_path = systemd_drop_in_dir_path(service_name, user_mode)?
+                .join(RESOURCE_LIMITS_DROP_IN_FILE_NAME);
+            if drop_in_path.exists() {
+                debug!("Removing the resource limits drop-in at {drop_in_path:?}");
+                std::fs::remove_file(&drop_in_path)?;
+                if let Some(drop_in_dir_path) = drop_in_path.parent() {
+                    // The directory is only removed if nothing else was put in it.
+                    let _ = std::fs::remove_dir(drop_in_dir_path);
+                }
+            }
+        }
         match manager.uninstall(ServiceUninstallCtx { label }) {
             Ok(()) => Ok(()),
             Err(err) => {
@@ -296,3 +372,59 @@ impl ServiceControl for ServiceController {
         std::thread::sleep(std::time::Duration::from_millis(delay));
     }
 }
+
+/// The directory of the drop-ins of the systemd unit of a service.
+fn systemd_drop_in_dir_path(service_name: &str, user_mode: bool) -> Result<PathBuf> {
+    let label: ServiceLabel = service_name.parse()?;
+    let dir_path = if user_mode {
+        service_manager::systemd_user_dir_path()?
+    } else {
+        service_manager::systemd_global_dir_path()
+    };
+    Ok(dir_path.join(format!("{}.service.d", label.to_script_name())))
+}
+
+fn systemd_daemon_reload(user_mode: bool) -> Result<()> {
+    let mut command = std::process::Command::new("systemctl");
+    if user_mode {
+        let _ = command.arg("--user");
+    }
+    let output = command.arg("daemon-reload").output()?;
+    if !output.status.success() {
+        error!("Failed to reload the systemd configuration: {output:?}");
+        return Err(std::io::Error::other(format!(
+            "systemctl daemon-reload failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Find the process running the binary at the given path.
+///
+/// Each service has its own binary, so the path identifies the process of the service.
+pub fn find_process_pid(bin_path: &Path) -> Result<u32> {
+    debug!(
+        "Searching for process with binary at {}",
+        bin_path.to_string_lossy()
+    );
+    let system = System::new_all();
+    for (pid, process) in system.processes() {
+        if let Some(path) = process.exe()
+            && bin_path == path
+        {
+            // There does not seem to be any easy way to get the process ID from the `Pid`
+            // type. Probably something to do with representing it in a cross-platform way.
+            trace!("Found process {bin_path:?} with PID: {pid}");
+            return Ok(pid.to_string().parse::<u32>()?);
+        }
+    }
+    error!(
+        "No process was located with a path at {}",
+        bin_path.to_string_lossy()
+    );
+    Err(Error::ServiceProcessNotFound(
+        bin_path.to_string_lossy().to_string(),
+    ))
+}
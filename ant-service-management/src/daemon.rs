@@ -7,15 +7,59 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::{
-    ServiceStateActions, ServiceStatus, UpgradeOptions,
+    ResourceLimits, ServiceStateActions, ServiceStatus, UpgradeOptions,
     control::ServiceControl,
     error::{Error, Result},
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use service_manager::ServiceInstallCtx;
-use std::{ffi::OsString, net::SocketAddr, path::PathBuf, sync::Arc};
+use std::{
+    ffi::OsString,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use subtle::ConstantTimeEq;
 use tokio::sync::RwLock;
+use tonic::{Request, metadata::MetadataValue};
+
+/// The metadata key carrying the token that authenticates requests to the daemon.
+const AUTHORIZATION_KEY: &str = "authorization";
+
+/// The token is generated by the daemon when it starts and is only readable by the user the daemon
+/// runs as.
+pub fn get_daemon_token_path() -> Result<PathBuf> {
+    let path = dirs_next::data_dir()
+        .ok_or(Error::UserDataDirectoryNotObtainable)?
+        .join("autonomi")
+        .join("antctld")
+        .join("token");
+    Ok(path)
+}
+
+pub fn read_daemon_token(path: &Path) -> Result<String> {
+    let token = std::fs::read_to_string(path)?;
+    Ok(token.trim().to_string())
+}
+
+/// Attach the token to a request, so that the daemon will accept it.
+pub fn authenticate_request<T>(request: &mut Request<T>, token: &str) -> Result<()> {
+    let value = MetadataValue::from_str(&format!("Bearer {token}"))
+        .map_err(|_| Error::DaemonTokenInvalid)?;
+    request.metadata_mut().insert(AUTHORIZATION_KEY, value);
+    Ok(())
+}
+
+pub fn is_request_authenticated<T>(request: &Request<T>, token: &str) -> bool {
+    request
+        .metadata()
+        .get(AUTHORIZATION_KEY)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        // Compared in constant time, so the time taken does not tell how much of the token matched
+        .is_some_and(|provided| provided.as_bytes().ct_eq(token.as_bytes()).into())
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DaemonServiceData {
@@ -121,6 +165,10 @@ impl ServiceStateActions for DaemonService {
         Ok(())
     }
 
+    async fn resource_limits(&self) -> ResourceLimits {
+        ResourceLimits::default()
+    }
+
     async fn set_version(&self, version: &str) {
         self.service_data.write().await.version = version.to_string();
     }
@@ -133,3 +181,24 @@ impl ServiceStateActions for DaemonService {
         self.service_data.read().await.version.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_with_the_token_should_be_authenticated() {
+        let mut request = Request::new(());
+        authenticate_request(&mut request, "abc123").unwrap();
+        assert!(is_request_authenticated(&request, "abc123"));
+    }
+
+    #[test]
+    fn request_with_another_token_or_none_should_not_be_authenticated() {
+        let mut request = Request::new(());
+        assert!(!is_request_authenticated(&request, "abc123"));
+
+        authenticate_request(&mut request, "def456").unwrap();
+        assert!(!is_request_authenticated(&request, "abc123"));
+    }
+}
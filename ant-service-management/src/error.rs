@@ -19,6 +19,8 @@ pub enum Error {
     AddrParseError(#[from] std::net::AddrParseError),
     #[error("The endpoint for the daemon has not been set")]
     DaemonEndpointNotSet,
+    #[error("The daemon token is not a valid header value")]
+    DaemonTokenInvalid,
     #[error(transparent)]
     Io(#[from] std::io::Error),
     #[error(transparent)]
@@ -27,14 +29,28 @@ pub enum Error {
     MultiAddrParseError(#[from] libp2p::multiaddr::Error),
     #[error("The registry does not contain a service named '{0}'")]
     NodeNotFound(String),
+    #[error("The '{0}' service was added to the registry by another writer")]
+    NodeRegistryConflict(String),
     #[error(transparent)]
     ParseIntError(#[from] std::num::ParseIntError),
     #[error(transparent)]
     PeerIdParseError(#[from] libp2p_identity::ParseError),
+    #[error(
+        "The {resource} limit of {limit} is above the hard limit of {hard_limit}, which only a privileged user can raise"
+    )]
+    ResourceLimitAboveHardLimit {
+        resource: String,
+        limit: u64,
+        hard_limit: u64,
+    },
+    #[error("Resource limits cannot be applied by the {0} service manager")]
+    ResourceLimitsNotSupported(String),
     #[error("Could not connect to RPC endpoint '{0}'")]
     RpcConnectionError(String),
     #[error("Could not obtain node info through RPC: {0}")]
     RpcNodeInfoError(String),
+    #[error("Could not obtain earnings through RPC: {0}")]
+    RpcEarningsError(String),
     #[error("Could not obtain network info through RPC: {0}")]
     RpcNetworkInfoError(String),
     #[error("Could not restart node through RPC: {0}")]
@@ -45,8 +61,18 @@ pub enum Error {
     RpcNodeUpdateError(String),
     #[error("Could not obtain record addresses through RPC: {0}")]
     RpcRecordAddressError(String),
+    #[error("Could not obtain record info through RPC: {0}")]
+    RpcRecordInfoError(String),
+    #[error("Could not run storage challenge through RPC: {0}")]
+    RpcStorageChallengeError(String),
+    #[error("Could not trigger cleanup through RPC: {0}")]
+    RpcTriggerCleanupError(String),
+    #[error("Could not trigger replication through RPC: {0}")]
+    RpcTriggerReplicationError(String),
     #[error("Could not find process at '{0}'")]
     ServiceProcessNotFound(String),
+    #[error("The service '{0}' is not installed")]
+    ServiceNotInstalled(String),
     #[error("The service '{0}' does not exists and cannot be removed.")]
     ServiceDoesNotExists(String),
     #[error("The user may have removed the '{0}' service outwith the node manager")]
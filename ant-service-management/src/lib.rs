@@ -13,9 +13,11 @@
 pub mod control;
 pub mod daemon;
 pub mod error;
+pub mod limits;
 pub mod node;
 pub mod registry;
 pub mod rpc;
+pub mod supervisor;
 
 #[macro_use]
 extern crate tracing;
@@ -34,6 +36,7 @@ use service_manager::ServiceInstallCtx;
 
 pub use daemon::{DaemonService, DaemonServiceData};
 pub use error::{Error, Result};
+pub use limits::ResourceLimits;
 pub use node::{NodeService, NodeServiceData};
 pub use registry::{NodeRegistryManager, StatusSummary, get_local_node_registry_path};
 
@@ -62,6 +65,11 @@ pub enum UpgradeResult {
     NotRequired,
     Upgraded(String, String),
     UpgradedButNotStarted(String, String, String),
+    /// The service did not become healthy after the upgrade, so its previous version was restored.
+    /// Holds the previous version, the version that was rolled back and the reason.
+    RolledBack(String, String, String),
+    /// The service was not upgraded because the rollout was stopped at an earlier service.
+    Skipped,
     Error(String),
 }
 
@@ -90,6 +98,8 @@ pub trait ServiceStateActions {
     async fn on_remove(&self);
     async fn on_start(&self, pid: Option<u32>, full_refresh: bool) -> Result<()>;
     async fn on_stop(&self) -> Result<()>;
+    /// The limits to apply to the resources of the service when it is installed.
+    async fn resource_limits(&self) -> ResourceLimits;
     async fn set_version(&self, version: &str);
     async fn status(&self) -> ServiceStatus;
     async fn version(&self) -> String;
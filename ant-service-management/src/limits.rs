@@ -0,0 +1,249 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Limits on the resources a service can use.
+//!
+//! With systemd, the limits are written to a drop-in for the unit of the service. The process
+//! supervisor applies them as resource limits of the process, which cannot express a CPU quota.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const KIB: u64 = 1024;
+const MIB: u64 = 1024 * KIB;
+const GIB: u64 = 1024 * MIB;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// The share of a CPU the service can use, in percent. Above 100 allows more than one CPU.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_quota: Option<u16>,
+    /// The most memory the service can use, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory: Option<u64>,
+    /// The most files the service can have open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nofile: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// These limits, with any that are not set taken from `other`.
+    pub fn or(&self, other: &ResourceLimits) -> ResourceLimits {
+        ResourceLimits {
+            cpu_quota: self.cpu_quota.or(other.cpu_quota),
+            max_memory: self.max_memory.or(other.max_memory),
+            nofile: self.nofile.or(other.nofile),
+        }
+    }
+
+    /// The contents of a systemd drop-in that applies the limits to a unit.
+    pub fn to_systemd_drop_in(&self) -> String {
+        let mut contents = String::from("[Service]\n");
+        if let Some(max_memory) = self.max_memory {
+            contents.push_str(&format!("MemoryMax={max_memory}\n"));
+        }
+        if let Some(cpu_quota) = self.cpu_quota {
+            contents.push_str(&format!("CPUQuota={cpu_quota}%\n"));
+        }
+        if let Some(nofile) = self.nofile {
+            contents.push_str(&format!("LimitNOFILE={nofile}\n"));
+        }
+        contents
+    }
+
+    /// Check the limits can be applied to the current process, so a service is not given limits
+    /// its supervisor fails to apply. Without privileges, a limit cannot be raised above the hard
+    /// limit of the process.
+    #[cfg(unix)]
+    pub fn check_applicable_to_current_process(&self) -> Result<()> {
+        use nix::sys::resource::{Resource, getrlimit};
+
+        if nix::unistd::geteuid().is_root() {
+            return Ok(());
+        }
+        for (resource, name, limit) in [
+            (Resource::RLIMIT_DATA, "memory", self.max_memory),
+            (Resource::RLIMIT_NOFILE, "open files", self.nofile),
+        ] {
+            if let Some(limit) = limit {
+                let (_, hard_limit) = getrlimit(resource).map_err(std::io::Error::from)?;
+                check_within_hard_limit(name, limit, hard_limit)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    pub fn check_applicable_to_current_process(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Apply the limits to the current process, from which the processes it spawns inherit them.
+    ///
+    /// The memory limit applies to the data segment of the process, which is the closest resource
+    /// limit to the memory a service uses. A CPU quota cannot be applied as a resource limit.
+    #[cfg(unix)]
+    pub fn apply_to_current_process(&self) -> Result<()> {
+        use nix::sys::resource::{Resource, setrlimit};
+
+        if let Some(max_memory) = self.max_memory {
+            debug!("Limiting the data segment to {max_memory} bytes");
+            setrlimit(Resource::RLIMIT_DATA, max_memory, max_memory)
+                .map_err(std::io::Error::from)?;
+        }
+        if let Some(nofile) = self.nofile {
+            // As with systemd, this sets the hard limit too, which requires privileges to raise.
+            debug!("Limiting the open files to {nofile}");
+            setrlimit(Resource::RLIMIT_NOFILE, nofile, nofile).map_err(std::io::Error::from)?;
+        }
+        if self.cpu_quota.is_some() {
+            warn!("A CPU quota cannot be applied without systemd, so it is ignored");
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    pub fn apply_to_current_process(&self) -> Result<()> {
+        if !self.is_empty() {
+            warn!("Resource limits cannot be applied on Windows, so they are ignored");
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ResourceLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut limits = Vec::new();
+        if let Some(max_memory) = self.max_memory {
+            limits.push(format!("memory {}", format_memory_size(max_memory)));
+        }
+        if let Some(cpu_quota) = self.cpu_quota {
+            limits.push(format!("CPU {cpu_quota}%"));
+        }
+        if let Some(nofile) = self.nofile {
+            limits.push(format!("{nofile} open files"));
+        }
+        if limits.is_empty() {
+            write!(f, "-")
+        } else {
+            write!(f, "{}", limits.join(", "))
+        }
+    }
+}
+
+#[cfg(unix)]
+fn check_within_hard_limit(resource: &str, limit: u64, hard_limit: u64) -> Result<()> {
+    if limit > hard_limit {
+        error!("The {resource} limit of {limit} is above the hard limit of {hard_limit}");
+        return Err(Error::ResourceLimitAboveHardLimit {
+            resource: resource.to_string(),
+            limit,
+            hard_limit,
+        });
+    }
+    Ok(())
+}
+
+/// Parse an amount of memory, in bytes or with a K, M or G suffix, such as '512M'.
+pub fn parse_memory_size(s: &str) -> std::result::Result<u64, String> {
+    let s = s.trim();
+    let (number, multiplier) = match s.char_indices().last() {
+        Some((index, 'K' | 'k')) => (&s[..index], KIB),
+        Some((index, 'M' | 'm')) => (&s[..index], MIB),
+        Some((index, 'G' | 'g')) => (&s[..index], GIB),
+        _ => (s, 1),
+    };
+    let bytes = number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .ok_or_else(|| format!("'{s}' is not an amount of memory, such as 512M or 2G"))?;
+    if bytes == 0 {
+        return Err("The amount of memory must be greater than zero".to_string());
+    }
+    Ok(bytes)
+}
+
+/// Format an amount of memory in the largest unit that represents it exactly.
+pub fn format_memory_size(bytes: u64) -> String {
+    match bytes {
+        0 => "0".to_string(),
+        bytes if bytes % GIB == 0 => format!("{}G", bytes / GIB),
+        bytes if bytes % MIB == 0 => format!("{}M", bytes / MIB),
+        bytes if bytes % KIB == 0 => format!("{}K", bytes / KIB),
+        bytes => bytes.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_memory_size_should_accept_units() {
+        assert_eq!(parse_memory_size("4096"), Ok(4096));
+        assert_eq!(parse_memory_size("64K"), Ok(64 * KIB));
+        assert_eq!(parse_memory_size("512M"), Ok(512 * MIB));
+        assert_eq!(parse_memory_size("2g"), Ok(2 * GIB));
+        assert!(parse_memory_size("0").is_err());
+        assert!(parse_memory_size("2T").is_err());
+        assert!(parse_memory_size("M").is_err());
+        assert!(parse_memory_size("99999999999999999G").is_err());
+    }
+
+    #[test]
+    fn format_memory_size_should_use_the_largest_exact_unit() {
+        assert_eq!(format_memory_size(2 * GIB), "2G");
+        assert_eq!(format_memory_size(1536 * MIB), "1536M");
+        assert_eq!(format_memory_size(1000), "1000");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_within_hard_limit_should_reject_limits_above_it() {
+        assert!(check_within_hard_limit("open files", 4096, 4096).is_ok());
+        assert!(check_within_hard_limit("open files", 4096, u64::MAX).is_ok());
+        assert!(matches!(
+            check_within_hard_limit("open files", 65536, 4096),
+            Err(Error::ResourceLimitAboveHardLimit {
+                limit: 65536,
+                hard_limit: 4096,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn to_systemd_drop_in_should_contain_the_limits_that_are_set() {
+        let limits = ResourceLimits {
+            cpu_quota: Some(150),
+            max_memory: Some(512 * MIB),
+            nofile: Some(4096),
+        };
+
+        assert_eq!(
+            limits.to_systemd_drop_in(),
+            "[Service]\nMemoryMax=536870912\nCPUQuota=150%\nLimitNOFILE=4096\n"
+        );
+        assert_eq!(limits.to_string(), "memory 512M, CPU 150%, 4096 open files");
+        assert_eq!(
+            ResourceLimits {
+                nofile: Some(1024),
+                ..Default::default()
+            }
+            .to_systemd_drop_in(),
+            "[Service]\nLimitNOFILE=1024\n"
+        );
+    }
+}
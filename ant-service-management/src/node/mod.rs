@@ -14,7 +14,10 @@ mod node_service_data_v2;
 // Re-export types
 pub use node_service_data::{NODE_SERVICE_DATA_SCHEMA_LATEST, NodeServiceData};
 
-use crate::{ServiceStateActions, ServiceStatus, UpgradeOptions, error::Result, rpc::RpcActions};
+use crate::{
+    ResourceLimits, ServiceStateActions, ServiceStatus, UpgradeOptions, error::Result,
+    rpc::RpcActions,
+};
 use ant_bootstrap::InitialPeersConfig;
 use ant_evm::EvmNetwork;
 use ant_protocol::get_port_from_multiaddr;
@@ -101,6 +104,14 @@ impl ServiceStateActions for NodeService {
             args.push(OsString::from("--port"));
             args.push(OsString::from(node_port.to_string()));
         }
+        if let Some(tcp_port) = service_data.tcp_port {
+            args.push(OsString::from("--tcp-port"));
+            args.push(OsString::from(tcp_port.to_string()));
+        }
+        if let Some(ws_port) = service_data.ws_port {
+            args.push(OsString::from("--ws-port"));
+            args.push(OsString::from(ws_port.to_string()));
+        }
         if let Some(metrics_port) = service_data.metrics_port {
             args.push(OsString::from("--metrics-server-port"));
             args.push(OsString::from(metrics_port.to_string()));
@@ -113,6 +124,46 @@ impl ServiceStateActions for NodeService {
             args.push(OsString::from("--max-log-files"));
             args.push(OsString::from(max_log_files.to_string()));
         }
+        if let Some(rate) = service_data.max_upload_rate {
+            args.push(OsString::from("--max-upload-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(rate) = service_data.max_download_rate {
+            args.push(OsString::from("--max-download-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(rate) = service_data.max_peer_upload_rate {
+            args.push(OsString::from("--max-peer-upload-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(rate) = service_data.max_peer_download_rate {
+            args.push(OsString::from("--max-peer-download-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(quiet_hours) = &service_data.quiet_hours {
+            args.push(OsString::from("--quiet-hours"));
+            args.push(OsString::from(quiet_hours));
+        }
+        if let Some(rate) = service_data.throttled_upload_rate {
+            args.push(OsString::from("--throttled-upload-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(rate) = service_data.throttled_download_rate {
+            args.push(OsString::from("--throttled-download-rate"));
+            args.push(OsString::from(rate.to_string()));
+        }
+        if let Some(percent) = service_data.throttle_cpu_usage {
+            args.push(OsString::from("--throttle-cpu-usage"));
+            args.push(OsString::from(percent.to_string()));
+        }
+        if let Some(percent) = service_data.throttle_memory_usage {
+            args.push(OsString::from("--throttle-memory-usage"));
+            args.push(OsString::from(percent.to_string()));
+        }
+        if let Some(evm_config) = &service_data.evm_config {
+            args.push(OsString::from("--evm-config"));
+            args.push(OsString::from(evm_config));
+        }
 
         args.push(OsString::from("--rewards-address"));
         args.push(OsString::from(service_data.rewards_address.to_string()));
@@ -255,6 +306,10 @@ impl ServiceStateActions for NodeService {
         Ok(())
     }
 
+    async fn resource_limits(&self) -> ResourceLimits {
+        self.service_data.read().await.resource_limits.clone()
+    }
+
     async fn set_version(&self, version: &str) {
         self.service_data.write().await.version = version.to_string();
     }
@@ -9,6 +9,7 @@
 use super::NodeServiceData;
 use crate::{
     ServiceStatus,
+    limits::ResourceLimits,
     node::node_service_data_v2::{NODE_SERVICE_DATA_SCHEMA_V2, NodeServiceDataV2},
 };
 use ant_bootstrap::InitialPeersConfig;
@@ -93,7 +94,11 @@ impl From<NodeServiceDataV1> for NodeServiceDataV2 {
             log_dir_path: v1.log_dir_path,
             log_format: v1.log_format,
             max_archived_log_files: v1.max_archived_log_files,
+            max_download_rate: None,
             max_log_files: v1.max_log_files,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
             metrics_port: v1.metrics_port,
             network_id: v1.network_id,
             node_ip: v1.node_ip,
@@ -103,6 +108,7 @@ impl From<NodeServiceDataV1> for NodeServiceDataV2 {
             peer_id: v1.peer_id,
             pid: v1.pid,
             relay: v1.relay,
+            resource_limits: ResourceLimits::default(),
             rewards_address: v1.rewards_address,
             reward_balance: v1.reward_balance,
             rpc_socket_addr: v1.rpc_socket_addr,
@@ -113,6 +119,14 @@ impl From<NodeServiceDataV1> for NodeServiceDataV2 {
             user_mode: v1.user_mode,
             version: v1.version,
             write_older_cache_files: false, // Default value for upgraded instances
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         }
     }
 }
@@ -7,7 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use super::NodeServiceData;
-use crate::{ServiceStatus, error::Result};
+use crate::{ServiceStatus, error::Result, limits::ResourceLimits};
 use ant_bootstrap::InitialPeersConfig;
 use ant_evm::{AttoTokens, EvmNetwork, RewardsAddress};
 use ant_logging::LogFormat;
@@ -37,6 +37,9 @@ pub struct NodeServiceDataV2 {
     #[serde(serialize_with = "NodeServiceData::serialize_connected_peers")]
     pub connected_peers: Option<Vec<PeerId>>,
     pub data_dir_path: PathBuf,
+    /// The EVM network config file the node reads its RPC endpoints from.
+    #[serde(default)]
+    pub evm_config: Option<PathBuf>,
     #[serde(default)]
     pub evm_network: EvmNetwork,
     pub initial_peers_config: InitialPeersConfig,
@@ -44,8 +47,16 @@ pub struct NodeServiceDataV2 {
     pub log_dir_path: PathBuf,
     pub log_format: Option<LogFormat>,
     pub max_archived_log_files: Option<usize>,
+    #[serde(default)]
+    pub max_download_rate: Option<u64>,
     pub max_log_files: Option<usize>,
     #[serde(default)]
+    pub max_peer_download_rate: Option<u64>,
+    #[serde(default)]
+    pub max_peer_upload_rate: Option<u64>,
+    #[serde(default)]
+    pub max_upload_rate: Option<u64>,
+    #[serde(default)]
     pub metrics_port: Option<u16>,
     pub network_id: Option<u8>,
     #[serde(default)]
@@ -57,13 +68,30 @@ pub struct NodeServiceDataV2 {
     #[serde(serialize_with = "NodeServiceData::serialize_peer_id")]
     pub peer_id: Option<PeerId>,
     pub pid: Option<u32>,
+    /// Daily windows of local time during which the node is throttled, e.g. "22:00-07:00".
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
     pub relay: bool,
+    /// The limits the service manager applies to the memory, CPU and open files of the node.
+    #[serde(default)]
+    pub resource_limits: ResourceLimits,
     #[serde(default)]
     pub rewards_address: RewardsAddress,
     pub reward_balance: Option<AttoTokens>,
     pub rpc_socket_addr: SocketAddr,
     pub service_name: String,
     pub status: ServiceStatus,
+    /// The port for the node to listen on with TCP, in addition to QUIC.
+    #[serde(default)]
+    pub tcp_port: Option<u16>,
+    #[serde(default)]
+    pub throttle_cpu_usage: Option<u8>,
+    #[serde(default)]
+    pub throttle_memory_usage: Option<u8>,
+    #[serde(default)]
+    pub throttled_download_rate: Option<u64>,
+    #[serde(default)]
+    pub throttled_upload_rate: Option<u64>,
     pub user: Option<String>,
     pub user_mode: bool,
     pub version: String,
@@ -71,6 +99,9 @@ pub struct NodeServiceDataV2 {
     /// Serde::default is used here for backward compatibility
     #[serde(default)]
     pub write_older_cache_files: bool,
+    /// The port for the node to listen on with WebSocket, in addition to QUIC.
+    #[serde(default)]
+    pub ws_port: Option<u16>,
 }
 
 // Helper method for direct V2 deserialization
@@ -91,14 +122,24 @@ impl NodeServiceDataV2 {
             connected_peers: Option<Vec<PeerId>>,
             data_dir_path: PathBuf,
             #[serde(default)]
+            evm_config: Option<PathBuf>,
+            #[serde(default)]
             evm_network: EvmNetwork,
             initial_peers_config: InitialPeersConfig,
             listen_addr: Option<Vec<Multiaddr>>,
             log_dir_path: PathBuf,
             log_format: Option<LogFormat>,
             max_archived_log_files: Option<usize>,
+            #[serde(default)]
+            max_download_rate: Option<u64>,
             max_log_files: Option<usize>,
             #[serde(default)]
+            max_peer_download_rate: Option<u64>,
+            #[serde(default)]
+            max_peer_upload_rate: Option<u64>,
+            #[serde(default)]
+            max_upload_rate: Option<u64>,
+            #[serde(default)]
             metrics_port: Option<u16>,
             network_id: Option<u8>,
             #[serde(default)]
@@ -110,13 +151,27 @@ impl NodeServiceDataV2 {
             #[serde(deserialize_with = "NodeServiceData::deserialize_peer_id")]
             peer_id: Option<PeerId>,
             pid: Option<u32>,
+            #[serde(default)]
+            quiet_hours: Option<String>,
             relay: bool,
             #[serde(default)]
+            resource_limits: ResourceLimits,
+            #[serde(default)]
             rewards_address: RewardsAddress,
             reward_balance: Option<AttoTokens>,
             rpc_socket_addr: SocketAddr,
             service_name: String,
             status: ServiceStatus,
+            #[serde(default)]
+            tcp_port: Option<u16>,
+            #[serde(default)]
+            throttle_cpu_usage: Option<u8>,
+            #[serde(default)]
+            throttle_memory_usage: Option<u8>,
+            #[serde(default)]
+            throttled_download_rate: Option<u64>,
+            #[serde(default)]
+            throttled_upload_rate: Option<u64>,
             user: Option<String>,
             user_mode: bool,
             version: String,
@@ -124,6 +179,8 @@ impl NodeServiceDataV2 {
             alpha: bool,
             #[serde(default)]
             write_older_cache_files: bool,
+            #[serde(default)]
+            ws_port: Option<u16>,
         }
 
         let helper = NodeServiceDataV2Helper::deserialize(deserializer)?;
@@ -134,13 +191,18 @@ impl NodeServiceDataV2 {
             auto_restart: helper.auto_restart,
             connected_peers: helper.connected_peers,
             data_dir_path: helper.data_dir_path,
+            evm_config: helper.evm_config,
             evm_network: helper.evm_network,
             initial_peers_config: helper.initial_peers_config,
             listen_addr: helper.listen_addr,
             log_dir_path: helper.log_dir_path,
             log_format: helper.log_format,
             max_archived_log_files: helper.max_archived_log_files,
+            max_download_rate: helper.max_download_rate,
             max_log_files: helper.max_log_files,
+            max_peer_download_rate: helper.max_peer_download_rate,
+            max_peer_upload_rate: helper.max_peer_upload_rate,
+            max_upload_rate: helper.max_upload_rate,
             metrics_port: helper.metrics_port,
             network_id: helper.network_id,
             node_ip: helper.node_ip,
@@ -149,17 +211,25 @@ impl NodeServiceDataV2 {
             number: helper.number,
             peer_id: helper.peer_id,
             pid: helper.pid,
+            quiet_hours: helper.quiet_hours,
             relay: helper.relay,
+            resource_limits: helper.resource_limits,
             rewards_address: helper.rewards_address,
             reward_balance: helper.reward_balance,
             rpc_socket_addr: helper.rpc_socket_addr,
             service_name: helper.service_name,
             status: helper.status,
+            tcp_port: helper.tcp_port,
+            throttle_cpu_usage: helper.throttle_cpu_usage,
+            throttle_memory_usage: helper.throttle_memory_usage,
+            throttled_download_rate: helper.throttled_download_rate,
+            throttled_upload_rate: helper.throttled_upload_rate,
             user: helper.user,
             user_mode: helper.user_mode,
             version: helper.version,
             alpha: helper.alpha,
             write_older_cache_files: helper.write_older_cache_files,
+            ws_port: helper.ws_port,
         })
     }
 }
@@ -169,6 +239,7 @@ mod tests {
     use super::super::node_service_data::NodeServiceData;
     use crate::{
         ServiceStatus,
+        limits::ResourceLimits,
         node::{
             NODE_SERVICE_DATA_SCHEMA_LATEST,
             node_service_data_v2::{NODE_SERVICE_DATA_SCHEMA_V2, NodeServiceDataV2},
@@ -197,6 +268,7 @@ mod tests {
             version: "0.1.0".to_string(),
             no_upnp: false,
             relay: true,
+            resource_limits: ResourceLimits::default(),
             auto_restart: false,
             connected_peers: None,
             evm_network: EvmNetwork::ArbitrumSepoliaTest,
@@ -222,6 +294,18 @@ mod tests {
             reward_balance: None,
             user: None,
             write_older_cache_files: false,
+            quiet_hours: None,
+            evm_config: None,
+            throttle_cpu_usage: None,
+            throttle_memory_usage: None,
+            throttled_download_rate: None,
+            throttled_upload_rate: None,
+            max_download_rate: None,
+            max_peer_download_rate: None,
+            max_peer_upload_rate: None,
+            max_upload_rate: None,
+            tcp_port: None,
+            ws_port: None,
         };
 
         let v2_json = serde_json::to_value(&v2_data).unwrap();
@@ -11,6 +11,8 @@ use crate::{DaemonServiceData, NatDetectionStatus, NodeServiceData};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::{
+    ffi::OsString,
+    fs::{File, OpenOptions},
     io::{Read, Write},
     path::{Path, PathBuf},
 };
@@ -27,11 +29,14 @@ pub struct NodeRegistryManager {
     pub nat_status: Arc<RwLock<Option<NatDetectionStatus>>>,
     pub nodes: Arc<RwLock<Vec<Arc<RwLock<NodeServiceData>>>>>,
     pub save_path: PathBuf,
+    /// The registry as it was last loaded or saved, which tells a save what has changed since.
+    base: Arc<RwLock<NodeRegistry>>,
 }
 
 impl From<NodeRegistry> for NodeRegistryManager {
     fn from(registry: NodeRegistry) -> Self {
         NodeRegistryManager {
+            base: Arc::new(RwLock::new(registry.clone())),
             daemon: Arc::new(RwLock::new(
                 registry.daemon.map(|daemon| Arc::new(RwLock::new(daemon))),
             )),
@@ -55,6 +60,7 @@ impl NodeRegistryManager {
     /// This is primarily used for testing purposes.
     pub fn empty(save_path: PathBuf) -> Self {
         NodeRegistryManager {
+            base: Arc::new(RwLock::new(NodeRegistry::empty(&save_path))),
             daemon: Arc::new(RwLock::new(None)),
             environment_variables: Arc::new(RwLock::new(None)),
             nat_status: Arc::new(RwLock::new(None)),
@@ -74,14 +80,74 @@ impl NodeRegistryManager {
     }
 
     /// Saves the current state of the node registry to the specified path.
+    ///
+    /// If another process saved the registry since it was loaded, the changes made here are
+    /// applied on top of theirs rather than overwriting them, and this manager is updated with the
+    /// result.
+    ///
+    /// If the other process added a service with the same name as one added here, nothing is
+    /// saved and [`Error::NodeRegistryConflict`] is returned. This manager is then updated with the
+    /// registry the other process saved, so the operation that added the service can run again.
     pub async fn save(&self) -> Result<()> {
-        let registry = self.to_registry().await;
-        registry.save()?;
+        let mut registry = self.to_registry().await;
+        let mut base = self.base.write().await;
+        match registry.save(&base) {
+            Ok(true) => self.update_from(&registry).await,
+            Ok(false) => {}
+            Err(err @ Error::NodeRegistryConflict(_)) => {
+                let latest = NodeRegistry::load(&self.save_path)?;
+                self.update_from(&latest).await;
+                *base = latest;
+                return Err(err);
+            }
+            Err(err) => return Err(err),
+        }
+        *base = registry;
         Ok(())
     }
 
+    /// Replace the state with that of the given registry. Nodes that remain keep their existing
+    /// handles, so services referring to them stay attached to the registry.
+    async fn update_from(&self, registry: &NodeRegistry) {
+        {
+            let mut daemon = self.daemon.write().await;
+            match (daemon.as_ref(), registry.daemon.as_ref()) {
+                (Some(current), Some(updated)) => *current.write().await = updated.clone(),
+                _ => {
+                    *daemon = registry
+                        .daemon
+                        .clone()
+                        .map(|daemon| Arc::new(RwLock::new(daemon)))
+                }
+            }
+        }
+        *self.environment_variables.write().await = registry.environment_variables.clone();
+        *self.nat_status.write().await = registry.nat_status.clone();
+
+        let mut nodes = self.nodes.write().await;
+        let mut updated_nodes = Vec::with_capacity(registry.nodes.len());
+        for updated in registry.nodes.iter() {
+            let mut existing = None;
+            for node in nodes.iter() {
+                if node.read().await.service_name == updated.service_name {
+                    existing = Some(Arc::clone(node));
+                    break;
+                }
+            }
+            match existing {
+                Some(node) => {
+                    *node.write().await = updated.clone();
+                    updated_nodes.push(node);
+                }
+                None => updated_nodes.push(Arc::new(RwLock::new(updated.clone()))),
+            }
+        }
+        *nodes = updated_nodes;
+    }
+
     /// Converts the current state of the `NodeRegistryManager` to a `NodeRegistry`.
     async fn to_registry(&self) -> NodeRegistry {
+        let generation = self.base.read().await.generation;
         let nodes = self.get_node_service_data().await;
         let mut daemon = None;
         {
@@ -92,6 +158,7 @@ impl NodeRegistryManager {
         NodeRegistry {
             daemon,
             environment_variables: self.environment_variables.read().await.clone(),
+            generation,
             nat_status: self.nat_status.read().await.clone(),
             nodes,
             save_path: self.save_path.clone(),
@@ -131,6 +198,10 @@ impl NodeRegistryManager {
 struct NodeRegistry {
     daemon: Option<DaemonServiceData>,
     environment_variables: Option<Vec<(String, String)>>,
+    /// Incremented on every save, so a writer can tell whether the registry changed after it was
+    /// loaded.
+    #[serde(default)]
+    generation: u64,
     nat_status: Option<NatDetectionStatus>,
     nodes: Vec<NodeServiceData>,
     save_path: PathBuf,
@@ -143,37 +214,145 @@ pub struct StatusSummary {
 }
 
 impl NodeRegistry {
-    fn save(&self) -> Result<()> {
+    fn empty(path: &Path) -> Self {
+        NodeRegistry {
+            daemon: None,
+            environment_variables: None,
+            generation: 0,
+            nat_status: None,
+            nodes: vec![],
+            save_path: path.to_path_buf(),
+        }
+    }
+
+    /// Write the registry, given the registry it was loaded as.
+    ///
+    /// The write holds an exclusive lock on a file next to the registry, so it cannot interleave
+    /// with a write from another process, and replaces the registry with a rename, so readers
+    /// never see a partial file. If the registry was saved by someone else after `base`, this
+    /// writer is stale: the changes it made since `base` are applied to the latest registry and
+    /// that is written instead. Returns whether that happened.
+    fn save(&mut self, base: &NodeRegistry) -> Result<bool> {
         debug!(
             "Saving node registry to {}",
             self.save_path.to_string_lossy()
         );
-        let path = Path::new(&self.save_path);
+        let path = self.save_path.clone();
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).inspect_err(|err| {
                 error!("Error creating node registry parent {parent:?}: {err:?}")
             })?;
         }
 
+        // The lock file may have been created by another user, but a read-only handle can be
+        // locked too.
+        let lock_path = with_extension_suffix(&path, "lock");
+        let lock_file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .or_else(|_| File::open(&lock_path))
+            .inspect_err(|err| error!("Error opening node registry lock file: {err:?}"))?;
+        lock_file
+            .lock()
+            .inspect_err(|err| error!("Error locking node registry: {err:?}"))?;
+
+        let latest = Self::load(&path)?;
+        let latest_generation = latest.generation;
+        let rebased = latest_generation != base.generation;
+        if rebased {
+            info!(
+                "The node registry was modified from generation {} to {} by another writer; applying our changes to it",
+                base.generation, latest_generation
+            );
+            self.rebase(base, latest)?;
+        }
+        self.generation = latest_generation + 1;
+
         let json = serde_json::to_string(self)?;
-        let mut file = std::fs::File::create(self.save_path.clone())
+        let temp_path = with_extension_suffix(&path, "tmp");
+        let mut file = File::create(&temp_path)
             .inspect_err(|err| error!("Error creating node registry file: {err:?}"))?;
         file.write_all(json.as_bytes())
             .inspect_err(|err| error!("Error writing to node registry: {err:?}"))?;
+        file.sync_all()
+            .inspect_err(|err| error!("Error writing to node registry: {err:?}"))?;
+        // The system-wide registry is writable by all users, which the replacement must keep.
+        if let Ok(metadata) = std::fs::metadata(&path) {
+            std::fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        std::fs::rename(&temp_path, &path)
+            .inspect_err(|err| error!("Error replacing node registry: {err:?}"))?;
+
+        // The lock is released when the file is closed.
+        Ok(rebased)
+    }
+
+    /// Apply the changes made between `base` and this registry to `latest`.
+    ///
+    /// Nodes are matched by service name. A node this writer changed takes its version, and any
+    /// other node takes the version in `latest`. A node removed on either side stays removed. A
+    /// node added on both sides is a conflict, as neither version can be kept without losing the
+    /// other.
+    fn rebase(&mut self, base: &NodeRegistry, latest: NodeRegistry) -> Result<()> {
+        let mut nodes = Vec::new();
+        for theirs in latest.nodes {
+            let base_node = base
+                .nodes
+                .iter()
+                .find(|node| node.service_name == theirs.service_name);
+            let our_node = self
+                .nodes
+                .iter()
+                .find(|node| node.service_name == theirs.service_name);
+            match (base_node, our_node) {
+                (Some(base_node), Some(our_node)) => {
+                    if is_changed(base_node, our_node)? {
+                        nodes.push(our_node.clone());
+                    } else {
+                        nodes.push(theirs);
+                    }
+                }
+                // Removed by this writer.
+                (Some(_), None) => {}
+                (None, Some(our_node)) => {
+                    warn!("{} was added by another writer too", our_node.service_name);
+                    return Err(Error::NodeRegistryConflict(our_node.service_name.clone()));
+                }
+                (None, None) => nodes.push(theirs),
+            }
+        }
+        for our_node in self.nodes.iter() {
+            let is_new = !base
+                .nodes
+                .iter()
+                .any(|node| node.service_name == our_node.service_name);
+            let is_present = nodes
+                .iter()
+                .any(|node| node.service_name == our_node.service_name);
+            if is_new && !is_present {
+                nodes.push(our_node.clone());
+            }
+        }
+        self.nodes = nodes;
 
+        if !is_changed(&base.daemon, &self.daemon)? {
+            self.daemon = latest.daemon;
+        }
+        if !is_changed(&base.environment_variables, &self.environment_variables)? {
+            self.environment_variables = latest.environment_variables;
+        }
+        if !is_changed(&base.nat_status, &self.nat_status)? {
+            self.nat_status = latest.nat_status;
+        }
         Ok(())
     }
 
     fn load(path: &Path) -> Result<Self> {
         if !path.exists() {
             debug!("Loading default node registry as {path:?} does not exist");
-            return Ok(NodeRegistry {
-                daemon: None,
-                environment_variables: None,
-                nat_status: None,
-                nodes: vec![],
-                save_path: path.to_path_buf(),
-            });
+            return Ok(NodeRegistry::empty(path));
         }
         debug!("Loading node registry from {}", path.to_string_lossy());
 
@@ -187,13 +366,7 @@ impl NodeRegistry {
         // It's possible for the file to be empty if the user runs a `status` command before any
         // services were added.
         if contents.is_empty() {
-            return Ok(NodeRegistry {
-                daemon: None,
-                environment_variables: None,
-                nat_status: None,
-                nodes: vec![],
-                save_path: path.to_path_buf(),
-            });
+            return Ok(NodeRegistry::empty(path));
         }
 
         Self::from_json(&contents)
@@ -213,6 +386,19 @@ impl NodeRegistry {
     }
 }
 
+/// The types in the registry do not implement `PartialEq`, so they are compared through their
+/// serialized form.
+fn is_changed<T: Serialize>(before: &T, after: &T) -> Result<bool> {
+    Ok(serde_json::to_value(before)? != serde_json::to_value(after)?)
+}
+
+fn with_extension_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(".");
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
 pub fn get_local_node_registry_path() -> Result<PathBuf> {
     let path = dirs_next::data_dir()
         .ok_or_else(|| {
@@ -227,3 +413,182 @@ pub fn get_local_node_registry_path() -> Result<PathBuf> {
     }
     Ok(path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ServiceStatus;
+    use std::sync::Barrier;
+
+    fn node(number: u16) -> NodeServiceData {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": 1,
+            "antnode_path": "/usr/bin/antnode",
+            "auto_restart": false,
+            "connected_peers": null,
+            "data_dir_path": format!("/var/antctl/services/antnode{number}"),
+            "evm_network": "ArbitrumOne",
+            "initial_peers_config": {
+                "first": false,
+                "local": false,
+                "addrs": [],
+                "network_contacts_url": [],
+                "ignore_cache": false,
+                "bootstrap_cache_dir": null
+            },
+            "listen_addr": null,
+            "log_dir_path": format!("/var/log/antnode/antnode{number}"),
+            "log_format": null,
+            "max_archived_log_files": null,
+            "max_log_files": null,
+            "metrics_port": null,
+            "network_id": null,
+            "node_ip": null,
+            "node_port": null,
+            "no_upnp": false,
+            "number": number,
+            "peer_id": null,
+            "pid": null,
+            "relay": false,
+            "rewards_address": "0x1234567890123456789012345678901234567890",
+            "reward_balance": null,
+            "rpc_socket_addr": format!("127.0.0.1:{}", 13000 + number),
+            "service_name": format!("antnode{number}"),
+            "status": "Added",
+            "user": "ant",
+            "user_mode": false,
+            "version": "0.1.0"
+        }))
+        .unwrap()
+    }
+
+    async fn set_status(registry: &NodeRegistryManager, service_name: &str, status: ServiceStatus) {
+        for node in registry.nodes.read().await.iter() {
+            if node.read().await.service_name == service_name {
+                node.write().await.status = status;
+                return;
+            }
+        }
+        panic!("{service_name} is not in the registry");
+    }
+
+    async fn get_status(registry: &NodeRegistryManager, service_name: &str) -> ServiceStatus {
+        for node in registry.nodes.read().await.iter() {
+            let node = node.read().await;
+            if node.service_name == service_name {
+                return node.status.clone();
+            }
+        }
+        panic!("{service_name} is not in the registry");
+    }
+
+    #[test]
+    fn concurrent_writers_should_not_lose_each_others_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node_registry.json");
+        const WRITERS: u16 = 8;
+
+        // Every writer loads the registry before any of them saves, so all but the first to save
+        // are stale.
+        let barrier = Arc::new(Barrier::new(WRITERS as usize));
+        let handles = (1..=WRITERS)
+            .map(|number| {
+                let barrier = Arc::clone(&barrier);
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let runtime = tokio::runtime::Builder::new_current_thread()
+                        .build()
+                        .unwrap();
+                    runtime.block_on(async {
+                        let registry = NodeRegistryManager::load(&path).await.unwrap();
+                        let _ = barrier.wait();
+                        registry.push_node(node(number)).await;
+                        registry.save().await.unwrap();
+                    });
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let registry = NodeRegistry::load(&path).unwrap();
+        assert_eq!(registry.generation, WRITERS as u64);
+        let mut numbers = registry
+            .nodes
+            .iter()
+            .map(|node| node.number)
+            .collect::<Vec<_>>();
+        numbers.sort();
+        assert_eq!(numbers, (1..=WRITERS).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn stale_writer_should_keep_changes_made_by_others() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node_registry.json");
+        let initial = NodeRegistryManager::empty(path.clone());
+        for number in 1..=3 {
+            initial.push_node(node(number)).await;
+        }
+        initial.save().await.unwrap();
+
+        let first = NodeRegistryManager::load(&path).await.unwrap();
+        let second = NodeRegistryManager::load(&path).await.unwrap();
+
+        set_status(&first, "antnode1", ServiceStatus::Running).await;
+        let _ = first.nodes.write().await.pop();
+        first.save().await.unwrap();
+
+        set_status(&second, "antnode2", ServiceStatus::Stopped).await;
+        second.save().await.unwrap();
+
+        // The stale writer was updated with the changes it missed.
+        assert_eq!(second.nodes.read().await.len(), 2);
+        assert_eq!(
+            get_status(&second, "antnode1").await,
+            ServiceStatus::Running
+        );
+
+        let saved = NodeRegistryManager::load(&path).await.unwrap();
+        assert_eq!(saved.nodes.read().await.len(), 2);
+        assert_eq!(get_status(&saved, "antnode1").await, ServiceStatus::Running);
+        assert_eq!(get_status(&saved, "antnode2").await, ServiceStatus::Stopped);
+        assert_eq!(saved.base.read().await.generation, 3);
+    }
+
+    #[tokio::test]
+    async fn stale_writer_should_report_a_conflict_for_a_node_added_by_both() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node_registry.json");
+        let first = NodeRegistryManager::load(&path).await.unwrap();
+        let second = NodeRegistryManager::load(&path).await.unwrap();
+
+        first.push_node(node(1)).await;
+        set_status(&first, "antnode1", ServiceStatus::Running).await;
+        first.save().await.unwrap();
+
+        second.push_node(node(1)).await;
+        second.push_node(node(2)).await;
+        assert!(matches!(
+            second.save().await,
+            Err(Error::NodeRegistryConflict(service_name)) if service_name == "antnode1"
+        ));
+
+        // Nothing was saved, and the stale writer now has the registry of the other writer, so it
+        // can run its operation again.
+        let saved = NodeRegistryManager::load(&path).await.unwrap();
+        assert_eq!(saved.nodes.read().await.len(), 1);
+        assert_eq!(get_status(&saved, "antnode1").await, ServiceStatus::Running);
+        assert_eq!(second.nodes.read().await.len(), 1);
+        assert_eq!(
+            get_status(&second, "antnode1").await,
+            ServiceStatus::Running
+        );
+
+        second.push_node(node(2)).await;
+        second.save().await.unwrap();
+        let saved = NodeRegistryManager::load(&path).await.unwrap();
+        assert_eq!(saved.nodes.read().await.len(), 2);
+    }
+}
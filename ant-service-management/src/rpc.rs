@@ -7,16 +7,24 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::error::{Error, Result};
+use ant_evm::{AttoTokens, QuoteHash, U256};
 use ant_protocol::{
     CLOSE_GROUP_SIZE,
     antnode_proto::{
-        NetworkInfoRequest, NodeInfoRequest, RecordAddressesRequest, RestartRequest, StopRequest,
-        UpdateLogLevelRequest, UpdateRequest, ant_node_client::AntNodeClient,
+        EarningsRequest, GetRecordInfoRequest, NetworkInfoRequest, NodeInfoRequest,
+        RecordAddressesRequest, RestartRequest, RunStorageChallengeRequest, StopRequest,
+        TriggerCleanupRequest, TriggerReplicationRequest, UpdateLogLevelRequest, UpdateRequest,
+        ant_node_client::AntNodeClient,
     },
 };
 use async_trait::async_trait;
 use libp2p::{Multiaddr, PeerId, kad::RecordKey};
-use std::{net::SocketAddr, path::PathBuf, str::FromStr};
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 use tokio::time::Duration;
 use tonic::Request;
 use tracing::error;
@@ -43,11 +51,66 @@ pub struct RecordAddress {
     pub key: RecordKey,
 }
 
+#[derive(Debug, Clone)]
+pub struct RecordInfo {
+    pub key: RecordKey,
+    pub record_type: String,
+    pub data_type: String,
+    /// Size of the encrypted record file, or `None` if the file is missing from disk.
+    pub size: Option<u64>,
+    pub stored_at: Option<SystemTime>,
+    /// The closest peers to the record known by the node, which are expected to hold it as well.
+    pub holders: Vec<PeerId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageChallengeResult {
+    pub peer_id: PeerId,
+    pub healthy: bool,
+}
+
+/// A payment received by the node, as recorded in its earnings ledger.
+#[derive(Debug, Clone)]
+pub struct EarningsEntry {
+    pub quote_hashes: Vec<QuoteHash>,
+    /// Key of the record the payment was made for.
+    pub key: RecordKey,
+    pub data_type: String,
+    pub amount: AttoTokens,
+    pub received_at: SystemTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct Earnings {
+    /// Total received over the lifetime of the node, which is kept across restarts.
+    pub lifetime_total: AttoTokens,
+    pub entries: Vec<EarningsEntry>,
+}
+
+impl Earnings {
+    /// Returns the total of the entries received at or after the given time.
+    pub fn total_since(&self, since: SystemTime) -> AttoTokens {
+        self.entries
+            .iter()
+            .filter(|entry| entry.received_at >= since)
+            .fold(AttoTokens::zero(), |total, entry| {
+                total
+                    .checked_add(entry.amount)
+                    .unwrap_or(AttoTokens::from_atto(U256::MAX))
+            })
+    }
+}
+
 #[async_trait]
 pub trait RpcActions: Sync {
     async fn node_info(&self) -> Result<NodeInfo>;
     async fn network_info(&self) -> Result<NetworkInfo>;
     async fn record_addresses(&self) -> Result<Vec<RecordAddress>>;
+    async fn record_info(&self, key: RecordKey) -> Result<RecordInfo>;
+    async fn trigger_replication(&self) -> Result<()>;
+    async fn trigger_cleanup(&self) -> Result<()>;
+    async fn run_storage_challenge(&self) -> Result<Vec<StorageChallengeResult>>;
+    async fn earnings(&self, since: SystemTime) -> Result<Earnings>;
     async fn node_restart(&self, delay_millis: u64, retain_peer_id: bool) -> Result<()>;
     async fn node_stop(&self, delay_millis: u64) -> Result<()>;
     async fn node_update(&self, delay_millis: u64) -> Result<()>;
@@ -188,6 +251,120 @@ impl RpcActions for RpcClient {
         Ok(record_addresses)
     }
 
+    async fn record_info(&self, key: RecordKey) -> Result<RecordInfo> {
+        let mut client = self.connect_with_retry().await?;
+        let response = client
+            .get_record_info(Request::new(GetRecordInfoRequest { key: key.to_vec() }))
+            .await
+            .map_err(|e| {
+                error!("Could not obtain record info through RPC: {e:?}");
+                Error::RpcRecordInfoError(e.to_string())
+            })?;
+        let record_info_resp = response.get_ref();
+        let holders = record_info_resp
+            .holders
+            .iter()
+            .map(|bytes| PeerId::from_bytes(bytes))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(RecordInfo {
+            key,
+            record_type: record_info_resp.record_type.clone(),
+            data_type: record_info_resp.data_type.clone(),
+            size: record_info_resp.size,
+            stored_at: record_info_resp
+                .stored_at_secs
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+            holders,
+        })
+    }
+
+    async fn trigger_replication(&self) -> Result<()> {
+        let mut client = self.connect_with_retry().await?;
+        let _response = client
+            .trigger_replication(Request::new(TriggerReplicationRequest {}))
+            .await
+            .map_err(|e| {
+                error!("Could not trigger replication through RPC: {e:?}");
+                Error::RpcTriggerReplicationError(e.to_string())
+            })?;
+        Ok(())
+    }
+
+    async fn trigger_cleanup(&self) -> Result<()> {
+        let mut client = self.connect_with_retry().await?;
+        let _response = client
+            .trigger_cleanup(Request::new(TriggerCleanupRequest {}))
+            .await
+            .map_err(|e| {
+                error!("Could not trigger cleanup through RPC: {e:?}");
+                Error::RpcTriggerCleanupError(e.to_string())
+            })?;
+        Ok(())
+    }
+
+    async fn run_storage_challenge(&self) -> Result<Vec<StorageChallengeResult>> {
+        let mut client = self.connect_with_retry().await?;
+        let response = client
+            .run_storage_challenge(Request::new(RunStorageChallengeRequest {}))
+            .await
+            .map_err(|e| {
+                error!("Could not run storage challenge through RPC: {e:?}");
+                Error::RpcStorageChallengeError(e.to_string())
+            })?;
+        let mut results = vec![];
+        for result in response.get_ref().results.iter() {
+            results.push(StorageChallengeResult {
+                peer_id: PeerId::from_bytes(&result.peer_id)?,
+                healthy: result.healthy,
+            });
+        }
+        Ok(results)
+    }
+
+    async fn earnings(&self, since: SystemTime) -> Result<Earnings> {
+        let mut client = self.connect_with_retry().await?;
+        let since_secs = since
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or_default();
+        let response = client
+            .earnings(Request::new(EarningsRequest { since_secs }))
+            .await
+            .map_err(|e| {
+                error!("Could not obtain earnings through RPC: {e:?}");
+                Error::RpcEarningsError(e.to_string())
+            })?;
+        let parse_amount = |amount: &str| {
+            U256::from_str(amount)
+                .map(AttoTokens::from_atto)
+                .map_err(|e| Error::RpcEarningsError(format!("Invalid amount '{amount}': {e}")))
+        };
+
+        let earnings_resp = response.get_ref();
+        let mut entries = vec![];
+        for entry in earnings_resp.entries.iter() {
+            let quote_hashes = entry
+                .quote_hashes
+                .iter()
+                .map(|hash| {
+                    QuoteHash::try_from(hash.as_slice())
+                        .map_err(|e| Error::RpcEarningsError(format!("Invalid quote hash: {e}")))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            entries.push(EarningsEntry {
+                quote_hashes,
+                key: RecordKey::new(&entry.address),
+                data_type: entry.data_type.clone(),
+                amount: parse_amount(&entry.amount)?,
+                received_at: UNIX_EPOCH + Duration::from_secs(entry.timestamp_secs),
+            });
+        }
+        Ok(Earnings {
+            lifetime_total: parse_amount(&earnings_resp.lifetime_total)?,
+            entries,
+        })
+    }
+
     async fn node_restart(&self, delay_millis: u64, retain_peer_id: bool) -> Result<()> {
         let mut client = self.connect_with_retry().await?;
         let _response = client
@@ -0,0 +1,514 @@
+// Copyright (C) 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! A lightweight process supervisor, for machines without a service manager, such as containers,
+//! or for users without the privileges to use it.
+//!
+//! Each service has a directory holding its definition, the PID files of its supervisor and its
+//! process, and the captured output of the process. Starting a service spawns a detached
+//! supervisor, which runs the process and restarts it with a backoff whenever it exits with a
+//! failure. Services run as the user that started them, and are not started when the machine
+//! boots.
+
+use crate::control::{ServiceControl, ServiceController, find_process_pid};
+use crate::error::{Error, Result};
+use crate::limits::ResourceLimits;
+use serde::{Deserialize, Serialize};
+use service_manager::ServiceInstallCtx;
+use std::{
+    ffi::OsString,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+use sysinfo::{Pid, Signal, System};
+
+const DEFINITION_FILE_NAME: &str = "service.json";
+const SUPERVISOR_PID_FILE_NAME: &str = "supervisor.pid";
+const PROCESS_PID_FILE_NAME: &str = "process.pid";
+/// The file the stdout and stderr of a supervised process are appended to.
+pub const OUTPUT_FILE_NAME: &str = "output.log";
+const INITIAL_RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+/// A process that ran at least this long before it failed is restarted without delay build-up.
+const STABLE_RUN_TIME: Duration = Duration::from_secs(60);
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What the supervisor of a service runs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct ServiceDefinition {
+    program: PathBuf,
+    args: Vec<String>,
+    environment: Vec<(String, String)>,
+    working_directory: Option<PathBuf>,
+    #[serde(default)]
+    limits: ResourceLimits,
+}
+
+/// Manages services with supervisor processes rather than the service manager of the OS.
+pub struct SupervisorController {
+    dir: PathBuf,
+    program: PathBuf,
+    args: Vec<OsString>,
+}
+
+impl SupervisorController {
+    /// Creates a controller keeping the services in the given directory.
+    ///
+    /// The supervisor of a service is run as the given program, with the given arguments followed
+    /// by the name of the service. The program is expected to call [`supervise`] for it.
+    pub fn new(dir: PathBuf, program: PathBuf, args: Vec<OsString>) -> Self {
+        Self { dir, program, args }
+    }
+
+    fn service_dir(&self, service_name: &str) -> PathBuf {
+        self.dir.join(service_name)
+    }
+
+    /// Terminates the supervisor of the service, if it runs, so the process is not restarted.
+    fn stop_supervisor(&self, service_dir: &Path) -> Result<()> {
+        if let Some(pid) = running_supervisor_pid(&service_dir.join(SUPERVISOR_PID_FILE_NAME)) {
+            debug!("Terminating supervisor with PID {pid}");
+            terminate(pid)?;
+        }
+        let _ = std::fs::remove_file(service_dir.join(SUPERVISOR_PID_FILE_NAME));
+        Ok(())
+    }
+}
+
+impl ServiceControl for SupervisorController {
+    fn create_service_user(&self, username: &str) -> Result<()> {
+        debug!("Supervised services run as the current user rather than {username}");
+        Ok(())
+    }
+
+    fn runs_as_current_user(&self) -> bool {
+        true
+    }
+
+    fn get_available_port(&self) -> Result<u16> {
+        ServiceController {}.get_available_port()
+    }
+
+    fn get_process_pid(&self, bin_path: &Path) -> Result<u32> {
+        find_process_pid(bin_path)
+    }
+
+    fn install(&self, install_ctx: ServiceInstallCtx, _user_mode: bool) -> Result<()> {
+        debug!("Installing supervised service: {install_ctx:?}");
+        let service_dir = self.service_dir(&install_ctx.label.to_string());
+        std::fs::create_dir_all(&service_dir)?;
+        let definition = ServiceDefinition {
+            program: install_ctx.program,
+            args: install_ctx
+                .args
+                .iter()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            environment: install_ctx.environment.unwrap_or_default(),
+            working_directory: install_ctx.working_directory,
+            limits: ResourceLimits::default(),
+        };
+        write_definition(&service_dir, &definition)
+    }
+
+    fn set_resource_limits(
+        &self,
+        service_name: &str,
+        limits: &ResourceLimits,
+        _user_mode: bool,
+    ) -> Result<()> {
+        debug!("Setting the resource limits of supervised service {service_name}: {limits:?}");
+        let service_dir = self.service_dir(service_name);
+        if !service_dir.join(DEFINITION_FILE_NAME).exists() {
+            error!("The {service_name} service is not installed");
+            return Err(Error::ServiceNotInstalled(service_name.to_string()));
+        }
+        limits.check_applicable_to_current_process()?;
+        let mut definition = read_definition(&service_dir)?;
+        definition.limits = limits.clone();
+        write_definition(&service_dir, &definition)
+    }
+
+    fn start(&self, service_name: &str, _user_mode: bool) -> Result<()> {
+        debug!("Starting supervised service: {service_name}");
+        let service_dir = self.service_dir(service_name);
+        if !service_dir.join(DEFINITION_FILE_NAME).exists() {
+            error!("The {service_name} service is not installed");
+            return Err(Error::ServiceNotInstalled(service_name.to_string()));
+        }
+        // A supervisor waiting to restart a failed process is replaced, so it starts right away.
+        self.stop_supervisor(&service_dir)?;
+
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .arg(service_name)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        detach(&mut command);
+        let child = command
+            .spawn()
+            .inspect_err(|err| error!("Error while spawning the supervisor: {err:?}"))?;
+        debug!(
+            "Spawned the supervisor of {service_name} with PID {}",
+            child.id()
+        );
+        Ok(())
+    }
+
+    fn stop(&self, service_name: &str, _user_mode: bool) -> Result<()> {
+        debug!("Stopping supervised service: {service_name}");
+        let service_dir = self.service_dir(service_name);
+        self.stop_supervisor(&service_dir)?;
+
+        let definition = read_definition(&service_dir)?;
+        let pid_path = service_dir.join(PROCESS_PID_FILE_NAME);
+        if let Some(pid) = running_pid(&pid_path, &definition.program) {
+            debug!("Terminating the process of {service_name} with PID {pid}");
+            terminate(pid)?;
+        }
+        let _ = std::fs::remove_file(pid_path);
+        Ok(())
+    }
+
+    fn uninstall(&self, service_name: &str, user_mode: bool) -> Result<()> {
+        debug!("Uninstalling supervised service: {service_name}");
+        let service_dir = self.service_dir(service_name);
+        if !service_dir.join(DEFINITION_FILE_NAME).exists() {
+            error!("The supervised service {service_name} does not exist");
+            return Err(Error::ServiceDoesNotExists(service_name.to_string()));
+        }
+        self.stop(service_name, user_mode)?;
+        // The captured output is kept, as it may explain why the service was removed.
+        std::fs::remove_file(service_dir.join(DEFINITION_FILE_NAME))?;
+        Ok(())
+    }
+
+    fn wait(&self, delay: u64) {
+        ServiceController {}.wait(delay);
+    }
+}
+
+/// Run the process of a service until it exits successfully, restarting it whenever it fails.
+///
+/// This blocks, so it is meant to be called by the supervisor process that is spawned when the
+/// service is started.
+pub fn supervise(dir: &Path, service_name: &str) -> Result<()> {
+    let service_dir = dir.join(service_name);
+    let result = run_supervised(&service_dir, service_name);
+    if let Err(err) = &result {
+        error!("Failed to supervise the {service_name} service: {err}");
+        // The output of the supervisor goes nowhere, so its failure is recorded with the output
+        // of the service, where it can be found.
+        record_in_output(
+            &service_dir,
+            &format!("Failed to supervise the service: {err}"),
+        );
+        let _ = std::fs::remove_file(service_dir.join(SUPERVISOR_PID_FILE_NAME));
+    }
+    result
+}
+
+fn run_supervised(service_dir: &Path, service_name: &str) -> Result<()> {
+    let definition = read_definition(service_dir)?;
+    // The program is recorded so the supervisor can be stopped by a controller that runs its
+    // supervisors as another program, e.g. antctl stopping a service that antctld started.
+    std::fs::write(
+        service_dir.join(SUPERVISOR_PID_FILE_NAME),
+        format!(
+            "{}\n{}",
+            std::process::id(),
+            std::env::current_exe()?.to_string_lossy()
+        ),
+    )?;
+    info!("Supervising the {service_name} service: {definition:?}");
+    // The process inherits the limits of its supervisor.
+    definition.limits.apply_to_current_process()?;
+
+    let pid_path = service_dir.join(PROCESS_PID_FILE_NAME);
+    let mut restart_delay = INITIAL_RESTART_DELAY;
+    loop {
+        let output = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(service_dir.join(OUTPUT_FILE_NAME))?;
+        let mut command = Command::new(&definition.program);
+        command
+            .args(&definition.args)
+            .envs(definition.environment.iter().cloned())
+            .stdin(Stdio::null())
+            .stdout(output.try_clone()?)
+            .stderr(output);
+        if let Some(working_directory) = &definition.working_directory {
+            let _ = command.current_dir(working_directory);
+        }
+
+        let started = Instant::now();
+        let mut child = command
+            .spawn()
+            .inspect_err(|err| error!("Error while spawning {service_name}: {err:?}"))?;
+        std::fs::write(&pid_path, child.id().to_string())?;
+        let status = child.wait()?;
+        let _ = std::fs::remove_file(&pid_path);
+
+        if status.success() {
+            info!("The {service_name} process exited successfully");
+            break;
+        }
+        if started.elapsed() >= STABLE_RUN_TIME {
+            restart_delay = INITIAL_RESTART_DELAY;
+        }
+        warn!("The {service_name} process exited with {status}, restarting in {restart_delay:?}");
+        record_in_output(
+            service_dir,
+            &format!("The process exited with {status}, restarting in {restart_delay:?}"),
+        );
+        std::thread::sleep(restart_delay);
+        restart_delay = (restart_delay * 2).min(MAX_RESTART_DELAY);
+    }
+
+    let _ = std::fs::remove_file(service_dir.join(SUPERVISOR_PID_FILE_NAME));
+    Ok(())
+}
+
+/// Append a line from the supervisor to the captured output of the process.
+fn record_in_output(service_dir: &Path, message: &str) {
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(service_dir.join(OUTPUT_FILE_NAME))
+        .and_then(|mut output| writeln!(output, "[supervisor] {message}"));
+    if let Err(err) = result {
+        error!("Failed to record '{message}' in the output of the service: {err}");
+    }
+}
+
+fn read_definition(service_dir: &Path) -> Result<ServiceDefinition> {
+    let contents = std::fs::read_to_string(service_dir.join(DEFINITION_FILE_NAME))
+        .inspect_err(|err| error!("Error reading the service definition: {err:?}"))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+fn write_definition(service_dir: &Path, definition: &ServiceDefinition) -> Result<()> {
+    std::fs::write(
+        service_dir.join(DEFINITION_FILE_NAME),
+        serde_json::to_string_pretty(definition)?,
+    )?;
+    Ok(())
+}
+
+/// The PID in the given file, if a process running the given program has it.
+///
+/// The program is checked so a PID reused by another process is never signalled.
+fn running_pid(pid_path: &Path, program: &Path) -> Option<Pid> {
+    let pid: usize = std::fs::read_to_string(pid_path)
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    is_running(Pid::from(pid), program)
+}
+
+/// The PID of the supervisor of a service, if it runs, which is recorded along with the program
+/// the supervisor runs as.
+fn running_supervisor_pid(pid_path: &Path) -> Option<Pid> {
+    let contents = std::fs::read_to_string(pid_path).ok()?;
+    let mut lines = contents.lines();
+    let pid: usize = lines.next()?.trim().parse().ok()?;
+    let program = PathBuf::from(lines.next()?);
+    is_running(Pid::from(pid), &program)
+}
+
+fn is_running(pid: Pid, program: &Path) -> Option<Pid> {
+    let mut system = System::new();
+    if !system.refresh_process(pid) {
+        return None;
+    }
+    let process = system.process(pid)?;
+    (process.exe() == Some(program)).then_some(pid)
+}
+
+/// Ask the process to exit, killing it if it has not within the timeout.
+fn terminate(pid: Pid) -> Result<()> {
+    let mut system = System::new();
+    if !system.refresh_process(pid) {
+        return Ok(());
+    }
+    if let Some(process) = system.process(pid)
+        && process.kill_with(Signal::Term).is_none()
+    {
+        let _ = process.kill();
+    }
+
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    while Instant::now() < deadline {
+        // A child that exited lingers until its parent reaps it, which is not us.
+        if !system.refresh_process(pid)
+            || system
+                .process(pid)
+                .is_some_and(|process| process.status() == sysinfo::ProcessStatus::Zombie)
+        {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    warn!("The process with PID {pid} did not exit in time, so it is killed");
+    if let Some(process) = system.process(pid) {
+        let _ = process.kill();
+    }
+    Ok(())
+}
+
+/// Keep the supervisor running when the terminal that started it is interrupted or closed.
+#[cfg(unix)]
+fn detach(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    let _ = command.process_group(0);
+}
+
+#[cfg(windows)]
+fn detach(command: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const DETACHED_PROCESS: u32 = 0x0000_0008;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+    let _ = command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use service_manager::ServiceLabel;
+
+    fn install(controller: &SupervisorController, script: &str) {
+        controller
+            .install(
+                ServiceInstallCtx {
+                    label: "antnode1".parse::<ServiceLabel>().unwrap(),
+                    program: PathBuf::from("/bin/sh"),
+                    args: vec![OsString::from("-c"), OsString::from(script)],
+                    contents: None,
+                    username: None,
+                    working_directory: None,
+                    environment: Some(vec![("ANT_LOG".to_string(), "all".to_string())]),
+                    autostart: false,
+                    disable_restart_on_failure: true,
+                },
+                true,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn supervise_should_run_the_process_and_capture_its_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let controller =
+            SupervisorController::new(dir.path().to_path_buf(), PathBuf::from("antctl"), vec![]);
+        install(&controller, "echo \"log level $ANT_LOG\"; echo failure >&2");
+
+        supervise(dir.path(), "antnode1").unwrap();
+
+        let output = std::fs::read_to_string(dir.path().join("antnode1/output.log")).unwrap();
+        assert_eq!(output, "log level all\nfailure\n");
+        assert!(!dir.path().join("antnode1/supervisor.pid").exists());
+        assert!(!dir.path().join("antnode1/process.pid").exists());
+    }
+
+    #[test]
+    fn supervise_should_restart_a_process_that_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let controller =
+            SupervisorController::new(dir.path().to_path_buf(), PathBuf::from("antctl"), vec![]);
+        // The process fails on its first run, leaving a marker, and succeeds on the next.
+        let marker = dir.path().join("marker");
+        install(
+            &controller,
+            &format!(
+                "if [ -f {0} ]; then echo second; else touch {0}; echo first; exit 1; fi",
+                marker.display()
+            ),
+        );
+
+        supervise(dir.path(), "antnode1").unwrap();
+
+        let output = std::fs::read_to_string(dir.path().join("antnode1/output.log")).unwrap();
+        assert_eq!(
+            output,
+            "first\n[supervisor] The process exited with exit status: 1, restarting in 1s\nsecond\n"
+        );
+    }
+
+    #[test]
+    fn supervise_should_record_its_failure_in_the_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let controller =
+            SupervisorController::new(dir.path().to_path_buf(), PathBuf::from("antctl"), vec![]);
+        install(&controller, "echo output");
+        let service_dir = dir.path().join("antnode1");
+        let mut definition = read_definition(&service_dir).unwrap();
+        definition.program = dir.path().join("missing");
+        write_definition(&service_dir, &definition).unwrap();
+
+        assert!(supervise(dir.path(), "antnode1").is_err());
+
+        let output = std::fs::read_to_string(service_dir.join("output.log")).unwrap();
+        assert!(output.starts_with("[supervisor] Failed to supervise the service: "));
+        assert!(!service_dir.join("supervisor.pid").exists());
+    }
+
+    #[test]
+    fn set_resource_limits_should_store_the_limits_in_the_definition() {
+        let dir = tempfile::tempdir().unwrap();
+        let controller =
+            SupervisorController::new(dir.path().to_path_buf(), PathBuf::from("antctl"), vec![]);
+        install(&controller, "echo output");
+        let limits = ResourceLimits {
+            cpu_quota: None,
+            max_memory: Some(512 * 1024 * 1024),
+            nofile: Some(4096),
+        };
+
+        controller
+            .set_resource_limits("antnode1", &limits, true)
+            .unwrap();
+
+        let definition = read_definition(&dir.path().join("antnode1")).unwrap();
+        assert_eq!(definition.limits, limits);
+        assert_eq!(definition.args, vec!["-c", "echo output"]);
+        assert!(matches!(
+            controller.set_resource_limits("antnode2", &limits, true),
+            Err(Error::ServiceNotInstalled(_))
+        ));
+    }
+
+    #[test]
+    fn uninstall_should_keep_the_output_and_fail_for_an_unknown_service() {
+        let dir = tempfile::tempdir().unwrap();
+        let controller =
+            SupervisorController::new(dir.path().to_path_buf(), PathBuf::from("antctl"), vec![]);
+        install(&controller, "echo output");
+        supervise(dir.path(), "antnode1").unwrap();
+
+        controller.uninstall("antnode1", true).unwrap();
+
+        assert!(!dir.path().join("antnode1/service.json").exists());
+        assert!(dir.path().join("antnode1/output.log").exists());
+        assert!(matches!(
+            controller.uninstall("antnode1", true),
+            Err(Error::ServiceDoesNotExists(_))
+        ));
+        assert!(matches!(
+            controller.start("antnode1", true),
+            Err(Error::ServiceNotInstalled(_))
+        ));
+    }
+}
@@ -259,7 +259,7 @@ impl Client {
         // pay for the chunk storage
         let xor_name = *chunk.name();
         debug!("Paying for chunk at address: {address:?}");
-        let (payment_proofs, _skipped_payments) = self
+        let (payment_proofs, _skipped_payments, _quotes_excluded) = self
             .pay_for_content_addrs(
                 DataTypes::Chunk,
                 std::iter::once((xor_name, chunk.size())),
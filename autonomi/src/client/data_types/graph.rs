@@ -116,7 +116,7 @@ impl Client {
         // pay for the graph entry
         let xor_name = address.xorname();
         debug!("Paying for graph entry at address: {address:?}");
-        let (payment_proofs, skipped_payments) = self
+        let (payment_proofs, skipped_payments, quotes_excluded) = self
             .pay_for_content_addrs(
                 DataTypes::GraphEntry,
                 std::iter::once((xor_name, entry.size())),
@@ -182,6 +182,7 @@ impl Client {
                 records_paid: 1usize.saturating_sub(skipped_payments),
                 records_already_paid: skipped_payments,
                 tokens_spent: price.as_atto(),
+                quotes_excluded,
             };
             if let Err(err) = channel.send(ClientEvent::UploadComplete(summary)).await {
                 error!("Failed to send client event: {err}");
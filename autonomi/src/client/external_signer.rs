@@ -1,11 +1,15 @@
 use crate::Client;
 use crate::client::PutError;
-use crate::client::quote::DataTypes;
+use crate::client::payment::{Receipt, receipt_from_store_quotes};
+use crate::client::quote::{DataTypes, StoreQuote};
 use crate::self_encryption::encrypt;
-use ant_evm::QuotePayment;
+use crate::{Amount, AttoTokens};
+use ant_evm::{ClientProofOfPayment, EvmNetwork, QuoteHash, QuotePayment, TxHash};
 use ant_protocol::storage::Chunk;
 use bytes::Bytes;
-use std::collections::HashMap;
+use evmlib::common::{Address, Calldata};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::time::Instant;
 use xor_name::XorName;
 
@@ -14,6 +18,154 @@ pub use ant_evm::external_signer::*;
 
 use super::quote::QuoteForAddress;
 
+/// Errors that can occur while building or completing a [`PaymentIntent`].
+#[derive(Debug, thiserror::Error)]
+pub enum PaymentIntentError {
+    #[error("Failed to quote for the records: {0}")]
+    Put(#[from] PutError),
+    #[error("Failed to build the payment calldata: {0}")]
+    Calldata(#[from] Error),
+    #[error("No transaction hash was provided for payment batch {0}")]
+    MissingTransaction(usize),
+    #[error("A transaction hash was provided for unknown payment batch {0}")]
+    UnknownBatch(usize),
+    #[error("The transaction of payment batch {0} did not pay for it: {1}")]
+    Unpaid(usize, Error),
+}
+
+/// A transaction to be signed and sent by an external signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsignedTransaction {
+    pub to: Address,
+    pub calldata: Calldata,
+}
+
+/// A transaction paying for a batch of quotes, of at most `MAX_TRANSFERS_PER_TRANSACTION`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentBatch {
+    pub transaction: UnsignedTransaction,
+    pub quote_hashes: Vec<QuoteHash>,
+}
+
+/// Everything needed to pay for a set of records offline, and to upload them once paid.
+///
+/// The intent is serializable, so it can be handed over to a hardware wallet or a multisig and
+/// resumed later on. Once the `approve` transaction and every batch transaction are signed and
+/// mined, the hashes of the batch transactions turn the intent into a [`Receipt`] with
+/// [`PaymentIntent::into_receipt`], which is then used as the payment option of the upload, e.g.
+/// `client.data_put(data, receipt.into())`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaymentIntent {
+    pub data_type: DataTypes,
+    /// Allows the payment vault to spend the total amount of the payment.
+    pub approve: UnsignedTransaction,
+    /// Total amount of the payment, in atto tokens.
+    pub total_amount: Amount,
+    /// The payment transactions, to be sent after `approve` in any order.
+    pub batches: Vec<PaymentBatch>,
+    /// Addresses which do not need paying for, e.g. already stored on the network.
+    pub free_addresses: Vec<XorName>,
+    /// The proofs of payment, which become valid once the batches are paid.
+    proofs: Vec<(XorName, ClientProofOfPayment, AttoTokens)>,
+}
+
+impl PaymentIntent {
+    /// Builds the intent of paying for the given quotes on the given network.
+    pub fn new(
+        evm_network: &EvmNetwork,
+        data_type: DataTypes,
+        quote: StoreQuote,
+        free_addresses: Vec<XorName>,
+    ) -> Result<Self, PaymentIntentError> {
+        // Quotes of no amount need no transfer, as when paying with a wallet.
+        let payments: Vec<QuotePayment> = quote
+            .payments()
+            .into_iter()
+            .filter(|(_, _, amount)| *amount > Amount::ZERO)
+            .collect();
+        let calldata = pay_for_quotes_calldata(evm_network, payments)?;
+        let (approve_calldata, approve_to) = approve_to_spend_tokens_calldata(
+            evm_network,
+            calldata.approve_spender,
+            calldata.approve_amount,
+        );
+        let batches = calldata
+            .batched_calldata_map
+            .into_iter()
+            .map(|(calldata_bytes, quote_hashes)| PaymentBatch {
+                transaction: UnsignedTransaction {
+                    to: calldata.to,
+                    calldata: calldata_bytes,
+                },
+                quote_hashes,
+            })
+            .collect();
+        let proofs = receipt_from_store_quotes(quote)
+            .into_iter()
+            .map(|(addr, (proof, price))| (addr, proof, price))
+            .collect();
+
+        Ok(Self {
+            data_type,
+            approve: UnsignedTransaction {
+                to: approve_to,
+                calldata: approve_calldata,
+            },
+            total_amount: calldata.approve_amount,
+            batches,
+            free_addresses,
+            proofs,
+        })
+    }
+
+    /// Turns the intent into a receipt for the upload, given the hash of the transaction sent for
+    /// each batch, keyed by the index of the batch in `batches`.
+    /// Fails unless every batch was paid by its transaction, as checked on chain.
+    pub async fn into_receipt(
+        self,
+        evm_network: &EvmNetwork,
+        batch_tx_hashes: &BTreeMap<usize, TxHash>,
+    ) -> Result<Receipt, PaymentIntentError> {
+        self.check_batch_tx_hashes(batch_tx_hashes)?;
+        for (i, tx_hash) in batch_tx_hashes {
+            let transaction = &self.batches[*i].transaction;
+            verify_transaction(evm_network, *tx_hash, transaction.to, &transaction.calldata)
+                .await
+                .map_err(|err| PaymentIntentError::Unpaid(*i, err))?;
+            debug!(
+                "Payment batch {i} of {} quotes was paid in transaction {tx_hash}",
+                self.batches[*i].quote_hashes.len()
+            );
+        }
+        Ok(self.receipt())
+    }
+
+    /// Fails unless a transaction hash was given for every batch, and for batches only.
+    fn check_batch_tx_hashes(
+        &self,
+        batch_tx_hashes: &BTreeMap<usize, TxHash>,
+    ) -> Result<(), PaymentIntentError> {
+        if let Some(batch) = batch_tx_hashes
+            .keys()
+            .find(|batch| **batch >= self.batches.len())
+        {
+            return Err(PaymentIntentError::UnknownBatch(*batch));
+        }
+        if let Some(batch) = (0..self.batches.len()).find(|i| !batch_tx_hashes.contains_key(i)) {
+            return Err(PaymentIntentError::MissingTransaction(batch));
+        }
+        Ok(())
+    }
+
+    /// The proofs of payment of the records, valid once every batch is paid.
+    fn receipt(self) -> Receipt {
+        self.proofs
+            .into_iter()
+            .map(|(addr, proof, price)| (addr, (proof, price)))
+            .collect()
+    }
+}
+
 impl Client {
     /// Get quotes for data.
     /// Returns a cost map, data payments to be executed and a list of free (already paid for) chunks.
@@ -44,6 +196,39 @@ impl Client {
             free_chunks.iter().map(|(addr, _)| *addr).collect(),
         ))
     }
+
+    /// Get quotes for the content addresses and build the intent of paying for them, to be
+    /// signed by an external signer.
+    pub async fn payment_intent_for_content_addresses(
+        &self,
+        data_type: DataTypes,
+        content_addrs: impl Iterator<Item = (XorName, usize)> + Clone,
+    ) -> Result<PaymentIntent, PaymentIntentError> {
+        let quote = self
+            .get_store_quotes(data_type, content_addrs.clone())
+            .await
+            .map_err(PutError::from)?;
+        let free_addresses = content_addrs
+            .filter(|(addr, _)| !quote.0.contains_key(addr))
+            .map(|(addr, _)| addr)
+            .collect();
+        PaymentIntent::new(self.evm_network(), data_type, quote, free_addresses)
+    }
+
+    /// Encrypt the data and build the intent of paying for all its chunks, data map included.
+    /// The receipt of the intent can be used to upload the data, publicly or privately.
+    pub async fn data_payment_intent(
+        &self,
+        data: Bytes,
+    ) -> Result<PaymentIntent, PaymentIntentError> {
+        let (data_map_chunk, chunks) = encrypt_data(data).map_err(PutError::from)?;
+        let content_addrs: Vec<_> = std::iter::once(&data_map_chunk)
+            .chain(chunks.iter())
+            .map(|chunk| (*chunk.name(), chunk.size()))
+            .collect();
+        self.payment_intent_for_content_addresses(DataTypes::Chunk, content_addrs.into_iter())
+            .await
+    }
 }
 
 /// Encrypts data as chunks.
@@ -57,3 +242,113 @@ pub fn encrypt_data(data: Bytes) -> Result<(Chunk, Vec<Chunk>), crate::self_encr
 
     Ok((result.0, result.1))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::networking::common::Addresses;
+    use ant_evm::{PaymentQuote, QuotingMetrics, RewardsAddress};
+    use evmlib::contract::payment_vault::MAX_TRANSFERS_PER_TRANSACTION;
+    use libp2p::PeerId;
+    use std::time::SystemTime;
+
+    fn quote(content: XorName, price: u64) -> (PeerId, Addresses, PaymentQuote, Amount) {
+        let quote = PaymentQuote {
+            content,
+            timestamp: SystemTime::now(),
+            quoting_metrics: QuotingMetrics {
+                data_type: DataTypes::Chunk.get_index(),
+                data_size: 1024,
+                close_records_stored: 0,
+                records_per_type: vec![],
+                max_records: 0,
+                received_payment_count: 0,
+                live_time: 0,
+                network_density: None,
+                network_size: None,
+            },
+            rewards_address: RewardsAddress::repeat_byte(1),
+            pub_key: vec![],
+            signature: vec![],
+        };
+        (
+            PeerId::random(),
+            Addresses(vec![]),
+            quote,
+            Amount::from(price),
+        )
+    }
+
+    fn store_quote(addresses: usize) -> StoreQuote {
+        StoreQuote(
+            (0..addresses)
+                .map(|_| {
+                    let content = XorName::random(&mut rand::thread_rng());
+                    // As in single node payments, only one of the quotes is paid.
+                    let quotes = vec![quote(content, 3), quote(content, 0)];
+                    (content, QuoteForAddress(quotes))
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn intent_batches_paid_quotes_and_survives_serialization() -> eyre::Result<()> {
+        let addresses = MAX_TRANSFERS_PER_TRANSACTION + 10;
+        let intent = PaymentIntent::new(
+            &EvmNetwork::ArbitrumOne,
+            DataTypes::Chunk,
+            store_quote(addresses),
+            vec![],
+        )?;
+
+        assert_eq!(intent.batches.len(), 2);
+        let paid_quotes: usize = intent
+            .batches
+            .iter()
+            .map(|batch| batch.quote_hashes.len())
+            .sum();
+        assert_eq!(paid_quotes, addresses);
+        assert_eq!(intent.total_amount, Amount::from(3 * addresses as u64));
+
+        let serialized = serde_json::to_string(&intent)?;
+        let resumed: PaymentIntent = serde_json::from_str(&serialized)?;
+        assert_eq!(resumed.batches, intent.batches);
+        assert_eq!(resumed.approve, intent.approve);
+        Ok(())
+    }
+
+    #[test]
+    fn receipt_requires_a_transaction_for_every_batch() -> eyre::Result<()> {
+        let intent = PaymentIntent::new(
+            &EvmNetwork::ArbitrumOne,
+            DataTypes::Chunk,
+            store_quote(MAX_TRANSFERS_PER_TRANSACTION + 1),
+            vec![],
+        )?;
+
+        let mut tx_hashes = BTreeMap::from([(0, TxHash::repeat_byte(1))]);
+        assert!(matches!(
+            intent.check_batch_tx_hashes(&tx_hashes),
+            Err(PaymentIntentError::MissingTransaction(1))
+        ));
+        let _ = tx_hashes.insert(2, TxHash::repeat_byte(3));
+        assert!(matches!(
+            intent.check_batch_tx_hashes(&tx_hashes),
+            Err(PaymentIntentError::UnknownBatch(2))
+        ));
+
+        let _ = tx_hashes.remove(&2);
+        let _ = tx_hashes.insert(1, TxHash::repeat_byte(2));
+        intent.check_batch_tx_hashes(&tx_hashes)?;
+        let receipt = intent.receipt();
+        assert_eq!(receipt.len(), MAX_TRANSFERS_PER_TRANSACTION + 1);
+        assert!(
+            receipt
+                .values()
+                .all(|(proof, price)| proof.peer_quotes.len() == 2
+                    && *price == AttoTokens::from_atto(Amount::from(3)))
+        );
+        Ok(())
+    }
+}
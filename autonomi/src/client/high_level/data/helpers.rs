@@ -29,6 +29,7 @@ impl Client {
         total_chunks: usize,
         payment_receipts: Vec<Receipt>,
         total_free_chunks: usize,
+        quotes_excluded: usize,
     ) -> AttoTokens {
         // Calculate total tokens spent across all receipts
         let total_tokens: Amount = payment_receipts
@@ -42,6 +43,7 @@ impl Client {
                 records_paid: total_chunks.saturating_sub(total_free_chunks),
                 records_already_paid: total_free_chunks,
                 tokens_spent: total_tokens,
+                quotes_excluded,
             };
 
             if let Err(err) = sender.send(ClientEvent::UploadComplete(summary)).await {
@@ -64,6 +66,7 @@ impl Client {
         let total_files = encryption_streams.len();
         let mut receipts = Vec::new();
         let mut total_free_chunks = 0;
+        let mut total_quotes_excluded = 0;
         let mut total_chunks = 0;
 
         // Estimate total chunks to be processed
@@ -87,11 +90,12 @@ impl Client {
                 #[cfg(feature = "loud")]
                 println!("Uploading file: {}", stream.file_path);
             }
-            let (processed_chunks, free_chunks, receipt) = self
+            let (processed_chunks, free_chunks, quotes_excluded, receipt) = self
                 .pay_and_upload_file(payment_option.clone(), stream)
                 .await?;
             total_chunks += processed_chunks;
             total_free_chunks += free_chunks;
+            total_quotes_excluded += quotes_excluded;
             receipts.extend(receipt);
 
             // Report upload completion
@@ -117,19 +121,25 @@ impl Client {
         println!("Upload{maybe_file} completed in {total_elapsed:?}");
 
         Ok(self
-            .calculate_total_cost(total_chunks, receipts, total_free_chunks)
+            .calculate_total_cost(
+                total_chunks,
+                receipts,
+                total_free_chunks,
+                total_quotes_excluded,
+            )
             .await)
     }
 
-    /// Returns: (processed_chunks, total_free_chunks, receipt)
+    /// Returns: (processed_chunks, total_free_chunks, total_quotes_excluded, receipt)
     pub(crate) async fn pay_and_upload_file(
         &self,
         payment_option: PaymentOption,
         file: &mut EncryptionStream,
-    ) -> Result<(usize, usize, Vec<Receipt>), PutError> {
+    ) -> Result<(usize, usize, usize, Vec<Receipt>), PutError> {
         let est_total_todo = file.total_chunks();
         let mut processed_chunks = 0;
         let mut total_free_chunks = 0;
+        let mut total_quotes_excluded = 0;
         let mut receipts = vec![];
 
         // Allow up to `retry_failed` * est_total_chunks total uploads to be attempted
@@ -166,12 +176,13 @@ impl Client {
 
             attempted_uploads += current_batch.len();
 
-            let (retry_chunks, receipt, free_chunks_count, put_error) = self
+            let (retry_chunks, receipt, free_chunks_count, quotes_excluded, put_error) = self
                 .process_chunk_batch(current_batch, payment_option.clone(), retry_on_failure)
                 .await;
 
             receipts.extend(receipt);
             total_free_chunks += free_chunks_count;
+            total_quotes_excluded += quotes_excluded;
 
             if let Some(err) = put_error {
                 return Err(err);
@@ -199,18 +210,29 @@ impl Client {
             current_batch = retry_chunks;
         }
 
-        Ok((processed_chunks, total_free_chunks, receipts))
+        Ok((
+            processed_chunks,
+            total_free_chunks,
+            total_quotes_excluded,
+            receipts,
+        ))
     }
 
     /// Processes a single batch of chunks (quote -> pay -> upload)
-    /// Returns: (failed_chunks_for_retry, receipt, free_chunks_counts, error_if_retry_on_failure_not_enabled)
+    /// Returns: (failed_chunks_for_retry, receipt, free_chunks_counts, quotes_excluded, error_if_retry_on_failure_not_enabled)
     #[allow(clippy::too_many_arguments)]
     async fn process_chunk_batch(
         &self,
         mut batch: AggregatedChunks,
         payment_option: PaymentOption,
         retry_on_failure: bool,
-    ) -> (AggregatedChunks, Vec<Receipt>, usize, Option<PutError>) {
+    ) -> (
+        AggregatedChunks,
+        Vec<Receipt>,
+        usize,
+        usize,
+        Option<PutError>,
+    ) {
         // Prepare payment info for batch
         let payment_info: Vec<_> = batch
             .iter()
@@ -242,24 +264,24 @@ impl Client {
         }
 
         // Process payment for this batch
-        let (receipt, free_chunks) = match self
+        let (receipt, free_chunks, quotes_excluded) = match self
             .pay_for_content_addrs(DataTypes::Chunk, payment_info.into_iter(), payment_option)
             .await
         {
-            Ok((receipt, free_chunks)) => (receipt, free_chunks),
+            Ok(payment) => payment,
             Err(err) if matches!(err, EvmWalletError(InsufficientTokensForQuotes(_, _))) => {
                 error!("Insufficient tokens: {err:?}. Returning immediately.");
-                return (vec![], vec![], 0, Some(PutError::from(err)));
+                return (vec![], vec![], 0, 0, Some(PutError::from(err)));
             }
             Err(err) => {
                 return if retry_on_failure {
                     error!("Quoting or payment error encountered, retry scheduled {err}");
                     #[cfg(feature = "loud")]
                     println!("Quoting or payment error encountered, retry scheduled: {err}.");
-                    (batch, vec![], 0, None)
+                    (batch, vec![], 0, 0, None)
                 } else {
                     error!("Quoting or payment error encountered, no retry scheduled {err}");
-                    (vec![], vec![], 0, Some(PutError::from(err)))
+                    (vec![], vec![], 0, 0, Some(PutError::from(err)))
                 };
             }
         };
@@ -306,6 +328,12 @@ impl Client {
             Err(err) => put_error = Some(err),
         }
 
-        (retry_chunks, vec![receipt], free_chunks, put_error)
+        (
+            retry_chunks,
+            vec![receipt],
+            free_chunks,
+            quotes_excluded,
+            put_error,
+        )
     }
 }
@@ -134,10 +134,10 @@ impl Client {
         path: PathBuf,
         payment_option: PaymentOption,
     ) -> Result<(AttoTokens, DataMapChunk), UploadError> {
-        let (data_map_chunk, processed_chunks, free_chunks, receipts) =
+        let (data_map_chunk, processed_chunks, free_chunks, quotes_excluded, receipts) =
             self.stream_upload_file(path, payment_option, false).await?;
         let total_cost = self
-            .calculate_total_cost(processed_chunks, receipts, free_chunks)
+            .calculate_total_cost(processed_chunks, receipts, free_chunks, quotes_excluded)
             .await;
         Ok((total_cost, data_map_chunk))
     }
@@ -13,9 +13,12 @@ use crate::client::Client;
 use crate::client::data_types::chunk::{ChunkAddress, DataMapChunk};
 use crate::client::high_level::data::DataAddress;
 use crate::client::payment::PaymentOption;
+use crate::client::quote::DataTypes;
+use ant_evm::{EvmAddress, EvmWalletError, PaymentFeeEstimate};
 use bytes::Bytes;
 use std::path::PathBuf;
 use std::time::{Duration, SystemTime};
+use xor_name::XorName;
 
 impl Client {
     /// Download file from network to local file system
@@ -145,12 +148,12 @@ impl Client {
         path: PathBuf,
         payment_option: PaymentOption,
     ) -> Result<(AttoTokens, DataAddress), UploadError> {
-        let (data_map_chunk, processed_chunks, free_chunks, receipts) = self
+        let (data_map_chunk, processed_chunks, free_chunks, quotes_excluded, receipts) = self
             .stream_upload_file(path.clone(), payment_option, true)
             .await?;
         let addr = DataAddress::new(*data_map_chunk.0.name());
         let total_cost = self
-            .calculate_total_cost(processed_chunks, receipts, free_chunks)
+            .calculate_total_cost(processed_chunks, receipts, free_chunks, quotes_excluded)
             .await;
 
         debug!("File {path:?} uploaded to the network at {addr:?}");
@@ -160,6 +163,40 @@ impl Client {
     /// Get the cost to upload a file/dir to the network.
     /// quick and dirty implementation, please refactor once files are cleanly implemented
     pub async fn file_cost(&self, path: &PathBuf) -> Result<AttoTokens, FileCostError> {
+        let content_addrs = self.file_content_addrs(path).await?;
+        let total_cost = self.get_cost_estimation(content_addrs).await?;
+        debug!("Total cost for the directory: {total_cost:?}");
+        Ok(total_cost)
+    }
+
+    /// Get the cost to upload a file/dir to the network, along with an estimate of the
+    /// transactions and gas it would take `payer` to pay for it.
+    ///
+    /// The cost is returned even if the gas cannot be estimated.
+    pub async fn file_cost_with_fees(
+        &self,
+        path: &PathBuf,
+        payer: EvmAddress,
+    ) -> Result<(AttoTokens, Result<PaymentFeeEstimate, EvmWalletError>), FileCostError> {
+        let content_addrs = self.file_content_addrs(path).await?;
+        let store_quote = self
+            .get_store_quotes(DataTypes::Chunk, content_addrs.into_iter())
+            .await
+            .inspect_err(|err| error!("Error getting store quotes: {err:?}"))?;
+        let total_cost = AttoTokens::from_atto(store_quote.price());
+        let fees = self
+            .estimate_payment_fees(payer, &store_quote)
+            .await
+            .inspect_err(|err| warn!("Error estimating the payment fees: {err:?}"));
+        debug!("Total cost for the directory: {total_cost:?}, fees: {fees:?}");
+        Ok((total_cost, fees))
+    }
+
+    /// Returns the content addresses of a file/dir, along with the archive of it.
+    async fn file_content_addrs(
+        &self,
+        path: &PathBuf,
+    ) -> Result<Vec<(XorName, usize)>, FileCostError> {
         let mut archive = PublicArchive::new();
         let mut content_addrs = vec![];
 
@@ -190,10 +227,7 @@ impl Client {
 
         let serialized = archive.to_bytes()?;
         content_addrs.extend(self.get_content_addrs(serialized)?);
-
-        let total_cost = self.get_cost_estimation(content_addrs).await?;
-        debug!("Total cost for the directory: {total_cost:?}");
-        Ok(total_cost)
+        Ok(content_addrs)
     }
 }
 
@@ -274,7 +274,7 @@ impl Client {
         path: PathBuf,
         payment_option: PaymentOption,
         is_public: bool,
-    ) -> Result<(DataMapChunk, usize, usize, Vec<Receipt>), UploadError> {
+    ) -> Result<(DataMapChunk, usize, usize, usize, Vec<Receipt>), UploadError> {
         info!("Uploading file: {path:?}");
         #[cfg(feature = "loud")]
         println!("Uploading file: {path:?}");
@@ -301,7 +301,7 @@ impl Client {
         };
 
         // pay and upload
-        let (processed_chunks, free_chunks, receipts) = self
+        let (processed_chunks, free_chunks, quotes_excluded, receipts) = self
             .pay_and_upload_file(payment_option, &mut encryption_stream)
             .await?;
 
@@ -316,7 +316,13 @@ impl Client {
             }
         };
 
-        Ok((data_map_chunk, processed_chunks, free_chunks, receipts))
+        Ok((
+            data_map_chunk,
+            processed_chunks,
+            free_chunks,
+            quotes_excluded,
+            receipts,
+        ))
     }
 }
 
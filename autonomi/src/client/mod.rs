@@ -47,7 +47,7 @@ mod put_error_state;
 
 use payment::Receipt;
 pub use put_error_state::ChunkBatchUploadState;
-use quote::PaymentMode;
+use quote::{PaymentMode, QuoteRecorder, QuoteScreener};
 
 use ant_bootstrap::{bootstrap::Bootstrap, contacts_fetcher::ALPHANET_CONTACTS};
 pub use ant_evm::Amount;
@@ -57,6 +57,7 @@ use payment::PayError;
 use quote::CostError;
 use self_encryption::DataMap;
 use std::collections::HashSet;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 
 /// Time before considering the connection timed out.
@@ -99,6 +100,10 @@ pub struct Client {
     retry_failed: u64,
     /// Payment mode to use for uploads
     payment_mode: PaymentMode,
+    /// Records the quotes received, see [`Client::with_quote_recorder`].
+    quote_recorder: Option<Arc<QuoteRecorder>>,
+    /// Sanity checks of the quotes received, shared by all clones of the client.
+    quote_screener: Arc<QuoteScreener>,
 }
 
 /// Error returned by [`Client::init`].
@@ -308,6 +313,8 @@ impl Client {
                 config: config.strategy,
                 retry_failed: 0,
                 payment_mode: PaymentMode::Standard,
+                quote_recorder: None,
+                quote_screener: Arc::default(),
             });
         }
 
@@ -321,6 +328,8 @@ impl Client {
             config: config.strategy,
             retry_failed: 0,
             payment_mode: PaymentMode::default(),
+            quote_recorder: None,
+            quote_screener: Arc::default(),
         })
     }
 
@@ -342,6 +351,13 @@ impl Client {
         self
     }
 
+    /// Record every priced quote received from now on, whether it is paid or not.
+    /// See [`quote::recorder`] for querying the recorded history.
+    pub fn with_quote_recorder(mut self, quote_recorder: QuoteRecorder) -> Self {
+        self.quote_recorder = Some(Arc::new(quote_recorder));
+        self
+    }
+
     /// Receive events from the client.
     pub fn enable_client_events(&mut self) -> mpsc::Receiver<ClientEvent> {
         let (client_event_sender, client_event_receiver) =
@@ -365,7 +381,7 @@ pub enum ClientEvent {
 }
 
 /// Summary of an upload operation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct UploadSummary {
     /// Records that were uploaded to the network
     pub records_paid: usize,
@@ -373,6 +389,8 @@ pub struct UploadSummary {
     pub records_already_paid: usize,
     /// Total cost of the upload
     pub tokens_spent: Amount,
+    /// Quotes excluded before payment as suspicious, e.g. forged or with implausible metrics
+    pub quotes_excluded: usize,
 }
 
 #[cfg(test)]
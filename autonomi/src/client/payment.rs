@@ -8,7 +8,9 @@
 
 use crate::Client;
 use crate::client::quote::{DataTypes, StoreQuote};
-use ant_evm::{ClientProofOfPayment, EncodedPeerId, EvmWallet, EvmWalletError};
+use ant_evm::{
+    ClientProofOfPayment, EncodedPeerId, EvmAddress, EvmWallet, EvmWalletError, PaymentFeeEstimate,
+};
 use std::collections::HashMap;
 use xor_name::XorName;
 
@@ -21,6 +23,9 @@ pub type Receipt = HashMap<XorName, (ClientProofOfPayment, AttoTokens)>;
 
 pub type AlreadyPaidAddressesCount = usize;
 
+/// Number of quotes excluded as suspicious before paying
+pub type ExcludedQuotesCount = usize;
+
 /// Errors that can occur during the pay operation.
 #[derive(Debug, thiserror::Error)]
 pub enum PayError {
@@ -91,18 +96,25 @@ impl From<Receipt> for PaymentOption {
 }
 
 impl Client {
+    /// Estimates the transactions and gas it would take `payer` to pay for the quotes, at the
+    /// current base fee. Nothing is paid.
+    pub async fn estimate_payment_fees(
+        &self,
+        payer: EvmAddress,
+        quotes: &StoreQuote,
+    ) -> Result<PaymentFeeEstimate, EvmWalletError> {
+        ant_evm::estimate_pay_for_quotes(payer, self.evm_network(), quotes.payments()).await
+    }
+
     pub(crate) async fn pay_for_content_addrs(
         &self,
         data_type: DataTypes,
         content_addrs: impl Iterator<Item = (XorName, usize)> + Clone,
         payment_option: PaymentOption,
-    ) -> Result<(Receipt, AlreadyPaidAddressesCount), PayError> {
+    ) -> Result<(Receipt, AlreadyPaidAddressesCount, ExcludedQuotesCount), PayError> {
         match payment_option {
-            PaymentOption::Wallet(wallet) => {
-                let (receipt, skipped) = self.pay(data_type, content_addrs, &wallet).await?;
-                Ok((receipt, skipped))
-            }
-            PaymentOption::Receipt(receipt) => Ok((receipt, 0)),
+            PaymentOption::Wallet(wallet) => self.pay(data_type, content_addrs, &wallet).await,
+            PaymentOption::Receipt(receipt) => Ok((receipt, 0, 0)),
         }
     }
 
@@ -112,24 +124,32 @@ impl Client {
         data_type: DataTypes,
         content_addrs: impl Iterator<Item = (XorName, usize)> + Clone,
         wallet: &EvmWallet,
-    ) -> Result<(Receipt, AlreadyPaidAddressesCount), PayError> {
+    ) -> Result<(Receipt, AlreadyPaidAddressesCount, ExcludedQuotesCount), PayError> {
         // Check if the wallet uses the same network as the client
         if wallet.network() != self.evm_network() {
             return Err(PayError::EvmWalletNetworkMismatch);
         }
 
         let number_of_content_addrs = content_addrs.clone().count();
-        let quotes = self.get_store_quotes(data_type, content_addrs).await?;
+        let (quotes, quotes_excluded) = self
+            .get_screened_store_quotes(data_type, content_addrs)
+            .await?;
 
         info!("Paying for {} addresses..", quotes.len());
         #[cfg(feature = "loud")]
         println!("Paying for {} addresses..", quotes.len());
 
         if !quotes.is_empty() {
-            // Make sure nobody else can use the wallet while we are paying
-            debug!("Waiting for wallet lock");
-            let lock_guard = wallet.lock().await;
-            debug!("Locked wallet");
+            // Make sure nobody else can use the wallet while we are paying, unless the wallet
+            // assigns its nonces locally, letting concurrent payments go through.
+            let lock_guard = if wallet.nonce_manager().is_some() {
+                None
+            } else {
+                debug!("Waiting for wallet lock");
+                let lock_guard = wallet.lock().await;
+                debug!("Locked wallet");
+                Some(lock_guard)
+            };
 
             // TODO: the error might contain some succeeded quote payments as well. These should be returned on err, so that they can be skipped when retrying.
             // TODO: retry when it fails?
@@ -159,6 +179,6 @@ impl Client {
 
         let receipt = receipt_from_store_quotes(quotes);
 
-        Ok((receipt, skipped_chunks))
+        Ok((receipt, skipped_chunks, quotes_excluded))
     }
 }
@@ -20,6 +20,12 @@ use libp2p::PeerId;
 use std::collections::HashMap;
 use xor_name::XorName;
 
+pub mod recorder;
+mod screening;
+
+pub use recorder::{QuoteRecord, QuoteRecorder};
+pub(crate) use screening::QuoteScreener;
+
 /// Payment strategy for uploads
 #[derive(Debug, Clone, Copy, Default)]
 pub enum PaymentMode {
@@ -171,11 +177,28 @@ impl Client {
         data_type: DataTypes,
         content_addrs: impl Iterator<Item = (XorName, usize)>,
     ) -> Result<StoreQuote, CostError> {
-        let raw_quotes_per_addr = self.get_raw_quotes(data_type, content_addrs).await;
+        let (store_quote, _excluded) = self
+            .get_screened_store_quotes(data_type, content_addrs)
+            .await?;
+        Ok(store_quote)
+    }
+
+    /// Same as [`Client::get_store_quotes`], but also returns the number of quotes excluded as
+    /// suspicious before picking the ones to pay.
+    pub(crate) async fn get_screened_store_quotes(
+        &self,
+        data_type: DataTypes,
+        content_addrs: impl Iterator<Item = (XorName, usize)>,
+    ) -> Result<(StoreQuote, usize), CostError> {
+        let content_addrs: HashMap<XorName, usize> = content_addrs.collect();
+        let raw_quotes_per_addr = self
+            .get_raw_quotes(data_type, content_addrs.clone().into_iter())
+            .await;
         let mut all_quotes = Vec::new();
+        let mut quotes_excluded = 0;
 
         for result in raw_quotes_per_addr {
-            let (content_addr, mut raw_quotes) = result?;
+            let (content_addr, raw_quotes) = result?;
             debug!(
                 "fetched raw quotes for content_addr: {content_addr}, with {} quotes.",
                 raw_quotes.len()
@@ -188,6 +211,16 @@ impl Client {
                 continue;
             }
 
+            // Exclude suspicious quotes before picking the ones to pay
+            let data_size = content_addrs
+                .get(&content_addr)
+                .copied()
+                .unwrap_or_default();
+            let (mut raw_quotes, excluded) =
+                self.quote_screener
+                    .screen(content_addr, data_type, data_size, raw_quotes);
+            quotes_excluded += excluded;
+
             let target_addr = NetworkAddress::from(ChunkAddress::new(content_addr));
 
             // Only keep the quotes of the 5 closest nodes
@@ -228,6 +261,8 @@ impl Client {
                 })
                 .collect();
 
+        self.record_quotes(data_type, &quotes_with_prices).await;
+
         let mut quotes_per_addr: HashMap<XorName, Vec<(PeerId, Addresses, PaymentQuote, Amount)>> =
             HashMap::new();
 
@@ -239,7 +274,45 @@ impl Client {
 
         let quotes_to_pay_per_addr = self.process_quotes_by_payment_mode(quotes_per_addr)?;
 
-        Ok(StoreQuote(quotes_to_pay_per_addr))
+        Ok((StoreQuote(quotes_to_pay_per_addr), quotes_excluded))
+    }
+
+    /// Record the priced quotes, if a quote recorder is set.
+    /// Failing to record is logged, but never fails the quoting.
+    async fn record_quotes(
+        &self,
+        data_type: DataTypes,
+        quotes_with_prices: &[(XorName, PeerId, Addresses, PaymentQuote, Amount)],
+    ) {
+        let Some(recorder) = self.quote_recorder.clone() else {
+            return;
+        };
+        let records: Vec<QuoteRecord> = quotes_with_prices
+            .iter()
+            .map(|(content_addr, peer_id, _, quote, price)| {
+                QuoteRecord::new(
+                    peer_id.to_string(),
+                    *content_addr,
+                    data_type,
+                    quote.quoting_metrics.clone(),
+                    *price,
+                )
+            })
+            .collect();
+        // The file is written off the async runtime.
+        let recorded = tokio::task::spawn_blocking(move || {
+            if let Err(err) = recorder.record(&records) {
+                warn!(
+                    "Failed to record {} quotes to {}: {err}",
+                    records.len(),
+                    recorder.path().display()
+                );
+            }
+        })
+        .await;
+        if let Err(err) = recorded {
+            warn!("Failed to record the quotes: {err}");
+        }
     }
 
     /// Process quotes according to the payment mode
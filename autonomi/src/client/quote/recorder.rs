@@ -0,0 +1,302 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Local history of the quotes received by the client, paid or not.
+//!
+//! When a [`QuoteRecorder`] is set with [`crate::Client::with_quote_recorder`], every priced
+//! quote fetched by [`crate::Client::get_store_quotes`] is appended as a JSON line to the
+//! recorder's file. The helpers of this module turn that history into price trends.
+
+use super::DataTypes;
+use ant_bootstrap::jsonl::JsonLinesFile;
+use ant_evm::{Amount, QuotingMetrics};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use xor_name::XorName;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+/// Size at which the history is rotated. Up to twice this size is kept, i.e. a few hundred
+/// thousand quotes.
+const MAX_HISTORY_SIZE: u64 = 64 * BYTES_PER_MB;
+
+/// A quote received from a node, along with the market price of it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteRecord {
+    /// Seconds since the UNIX epoch at which the quote was priced.
+    pub timestamp: u64,
+    /// The node which issued the quote.
+    pub peer_id: String,
+    /// The address the quote was requested for.
+    pub content_addr: XorName,
+    pub data_type: DataTypes,
+    /// Size of the data the quote was requested for, in bytes.
+    pub data_size: usize,
+    pub quoting_metrics: QuotingMetrics,
+    /// The price of the quote, as set by the payment vault.
+    pub market_price: Amount,
+}
+
+impl QuoteRecord {
+    /// Creates a record for a quote priced now.
+    pub(crate) fn new(
+        peer_id: String,
+        content_addr: XorName,
+        data_type: DataTypes,
+        quoting_metrics: QuotingMetrics,
+        market_price: Amount,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or_default();
+        Self {
+            timestamp,
+            peer_id,
+            content_addr,
+            data_type,
+            data_size: quoting_metrics.data_size,
+            quoting_metrics,
+            market_price,
+        }
+    }
+
+    /// The market price of the quote scaled to a MB of data.
+    /// Returns `None` for quotes of empty data, which cannot be scaled.
+    pub fn price_per_mb(&self) -> Option<Amount> {
+        if self.data_size == 0 {
+            return None;
+        }
+        Some(
+            self.market_price.saturating_mul(Amount::from(BYTES_PER_MB))
+                / Amount::from(self.data_size),
+        )
+    }
+}
+
+/// Appends the quotes received by the client to a JSON lines file.
+#[derive(Debug)]
+pub struct QuoteRecorder {
+    file: JsonLinesFile,
+}
+
+impl QuoteRecorder {
+    /// Creates a recorder writing to the given file. The file is created on the first append,
+    /// and rotated once it reaches `MAX_HISTORY_SIZE`, dropping the oldest quotes.
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file: JsonLinesFile::new(file_path).with_max_size(MAX_HISTORY_SIZE),
+        }
+    }
+
+    /// The file the quotes are recorded to.
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Appends the records to the file.
+    pub fn record(&self, records: &[QuoteRecord]) -> io::Result<()> {
+        self.file.append(records)
+    }
+
+    /// Returns the records priced at or after the given UNIX timestamp, oldest first.
+    pub fn records_since(&self, since_secs: u64) -> io::Result<Vec<QuoteRecord>> {
+        self.file
+            .read(|record: &QuoteRecord| record.timestamp >= since_secs)
+    }
+}
+
+/// The median price per MB of the quotes received during a period.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PricePoint {
+    /// Seconds since the UNIX epoch at which the period starts.
+    pub period_start: u64,
+    pub median_price_per_mb: Amount,
+    /// Number of quotes received during the period.
+    pub quote_count: usize,
+}
+
+/// Buckets the records into consecutive periods of the given length and returns the median price
+/// per MB of each period which received quotes, oldest first.
+pub fn median_price_per_mb_over_time(records: &[QuoteRecord], period: Duration) -> Vec<PricePoint> {
+    let period_secs = period.as_secs().max(1);
+    let mut prices_per_period: BTreeMap<u64, Vec<Amount>> = BTreeMap::new();
+    for record in records {
+        if let Some(price) = record.price_per_mb() {
+            let period_start = record.timestamp - record.timestamp % period_secs;
+            prices_per_period
+                .entry(period_start)
+                .or_default()
+                .push(price);
+        }
+    }
+
+    prices_per_period
+        .into_iter()
+        .filter_map(|(period_start, mut prices)| {
+            Some(PricePoint {
+                period_start,
+                quote_count: prices.len(),
+                median_price_per_mb: median(&mut prices)?,
+            })
+        })
+        .collect()
+}
+
+/// A node whose quotes are priced far from the rest of the network.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerOutlier {
+    pub peer_id: String,
+    /// Number of quotes received from the node.
+    pub quote_count: usize,
+    pub median_price_per_mb: Amount,
+    /// Median price per MB of the quotes of all nodes.
+    pub network_median_price_per_mb: Amount,
+}
+
+/// Returns the nodes whose median price per MB is at least `factor` times above or below the
+/// median of all the records, the furthest from the network median first.
+pub fn peer_outliers(records: &[QuoteRecord], factor: u64) -> Vec<PeerOutlier> {
+    let factor = Amount::from(factor.max(1));
+    let mut all_prices = vec![];
+    let mut prices_per_peer: HashMap<&str, Vec<Amount>> = HashMap::new();
+    for record in records {
+        if let Some(price) = record.price_per_mb() {
+            all_prices.push(price);
+            prices_per_peer
+                .entry(record.peer_id.as_str())
+                .or_default()
+                .push(price);
+        }
+    }
+    let Some(network_median) = median(&mut all_prices) else {
+        return vec![];
+    };
+
+    let mut outliers: Vec<_> = prices_per_peer
+        .into_iter()
+        .filter_map(|(peer_id, mut prices)| {
+            let quote_count = prices.len();
+            let peer_median = median(&mut prices)?;
+            let is_outlier = peer_median >= network_median.saturating_mul(factor)
+                || peer_median.saturating_mul(factor) <= network_median;
+            is_outlier.then(|| PeerOutlier {
+                peer_id: peer_id.to_string(),
+                quote_count,
+                median_price_per_mb: peer_median,
+                network_median_price_per_mb: network_median,
+            })
+        })
+        .collect();
+    outliers.sort_by_key(|outlier| {
+        std::cmp::Reverse(outlier.median_price_per_mb.abs_diff(network_median))
+    });
+    outliers
+}
+
+fn median(prices: &mut [Amount]) -> Option<Amount> {
+    if prices.is_empty() {
+        return None;
+    }
+    prices.sort();
+    let middle = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        Some(prices[middle])
+    } else {
+        Some(prices[middle - 1].saturating_add(prices[middle]) / Amount::from(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(peer_id: &str, timestamp: u64, data_size: usize, market_price: u64) -> QuoteRecord {
+        QuoteRecord {
+            timestamp,
+            peer_id: peer_id.to_string(),
+            content_addr: XorName::random(&mut rand::thread_rng()),
+            data_type: DataTypes::Chunk,
+            data_size,
+            quoting_metrics: QuotingMetrics {
+                data_type: DataTypes::Chunk.get_index(),
+                data_size,
+                close_records_stored: 0,
+                records_per_type: vec![],
+                max_records: 0,
+                received_payment_count: 0,
+                live_time: 0,
+                network_density: None,
+                network_size: None,
+            },
+            market_price: Amount::from(market_price),
+        }
+    }
+
+    #[test]
+    fn recorder_appends_and_filters_by_time() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let recorder = QuoteRecorder::new(temp_dir.path().join("history").join("quotes"));
+        assert!(recorder.records_since(0)?.is_empty());
+
+        let first = record("a", 100, 1024, 10);
+        let second = record("b", 200, 1024, 20);
+        recorder.record(std::slice::from_ref(&first))?;
+        recorder.record(std::slice::from_ref(&second))?;
+
+        assert_eq!(recorder.records_since(0)?, vec![first, second.clone()]);
+        assert_eq!(recorder.records_since(150)?, vec![second]);
+        Ok(())
+    }
+
+    #[test]
+    fn median_price_is_scaled_per_mb_and_bucketed() {
+        let mb = BYTES_PER_MB as usize;
+        let records = vec![
+            record("a", 10, mb / 2, 5),
+            record("b", 20, mb, 20),
+            record("c", 30, mb, 30),
+            record("a", 110, mb, 40),
+            record("b", 120, 0, 1000),
+        ];
+
+        assert_eq!(
+            median_price_per_mb_over_time(&records, Duration::from_secs(100)),
+            vec![
+                PricePoint {
+                    period_start: 0,
+                    median_price_per_mb: Amount::from(20),
+                    quote_count: 3,
+                },
+                PricePoint {
+                    period_start: 100,
+                    median_price_per_mb: Amount::from(40),
+                    quote_count: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn outliers_deviate_from_the_network_median_by_the_factor() {
+        let mb = BYTES_PER_MB as usize;
+        let mut records: Vec<_> = (0..5).map(|i| record("normal", i, mb, 100)).collect();
+        records.push(record("pricey", 10, mb, 1000));
+        records.push(record("cheap", 11, mb, 10));
+        records.push(record("close", 12, mb, 200));
+
+        let outliers = peer_outliers(&records, 3);
+        let peers: Vec<_> = outliers.iter().map(|o| o.peer_id.as_str()).collect();
+        assert_eq!(peers, vec!["pricey", "cheap"]);
+        assert_eq!(outliers[0].network_median_price_per_mb, Amount::from(100));
+    }
+}
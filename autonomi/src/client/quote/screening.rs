@@ -0,0 +1,377 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Client side sanity checks of the quotes received, run before any of them is paid.
+//!
+//! Quotes which are not genuine (bad signature, wrong content, timestamp far off, metrics not
+//! matching the request) are always excluded. Quotes which are genuine but look abusive (metrics
+//! implausible next to the other quotes for the same address, or inconsistent with the previous
+//! quotes of the same peer) are excluded as long as enough quotes are left to pay for the address.
+
+use super::DataTypes;
+use crate::networking::common::Addresses;
+use ant_evm::PaymentQuote;
+use ant_protocol::{CLOSE_GROUP_SIZE, constants::max_plausible_close_records};
+use libp2p::PeerId;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, PoisonError},
+    time::{Duration, SystemTime},
+};
+use xor_name::XorName;
+
+/// How far a quote timestamp may be from the client clock, in either direction.
+const MAX_QUOTE_CLOCK_SKEW: Duration = Duration::from_secs(10 * 60);
+
+/// Upper bound on the number of peers whose last quote is remembered.
+const MAX_REMEMBERED_PEERS: usize = 10_000;
+
+type RawQuote = (PeerId, Addresses, PaymentQuote);
+
+/// Why a quote was excluded before payment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SuspiciousQuoteReason {
+    /// The quote is not signed by the peer which sent it.
+    InvalidSignature,
+    /// The quote is for another address than the one requested.
+    ContentMismatch,
+    /// The quote timestamp is too far from the client clock.
+    TimestampOutOfRange,
+    /// The quote is for another data type or size than the one requested.
+    MetricsMismatch,
+    /// The quoting metrics are implausible next to the other quotes for the same address.
+    ImplausibleMetrics,
+    /// The quoting metrics contradict a previous quote of the same peer.
+    InconsistentWithHistory,
+}
+
+impl fmt::Display for SuspiciousQuoteReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            Self::InvalidSignature => "invalid signature",
+            Self::ContentMismatch => "quote for another address",
+            Self::TimestampOutOfRange => "timestamp out of range",
+            Self::MetricsMismatch => "quote for another data type or size",
+            Self::ImplausibleMetrics => "implausible quoting metrics",
+            Self::InconsistentWithHistory => "inconsistent with previous quotes of the peer",
+        };
+        write!(f, "{reason}")
+    }
+}
+
+/// Screens the quotes received by a client, remembering the last quote of each peer.
+#[derive(Debug, Default)]
+pub(crate) struct QuoteScreener {
+    last_quotes: Mutex<HashMap<PeerId, PaymentQuote>>,
+}
+
+impl QuoteScreener {
+    /// Returns the quotes for `content_addr` which pass the checks, along with the number of
+    /// quotes excluded.
+    pub(crate) fn screen(
+        &self,
+        content_addr: XorName,
+        data_type: DataTypes,
+        data_size: usize,
+        quotes: Vec<RawQuote>,
+    ) -> (Vec<RawQuote>, usize) {
+        let now = SystemTime::now();
+        let mut last_quotes = self
+            .last_quotes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let mut excluded = 0;
+        let mut genuine = vec![];
+        for (peer_id, addrs, quote) in quotes {
+            match check_genuine(&quote, peer_id, content_addr, data_type, data_size, now) {
+                Ok(()) => genuine.push((peer_id, addrs, quote)),
+                Err(reason) => {
+                    exclude(content_addr, peer_id, reason);
+                    excluded += 1;
+                }
+            }
+        }
+
+        let mut suspicious = vec![];
+        let mut kept = vec![];
+        for (index, (peer_id, addrs, quote)) in genuine.iter().enumerate() {
+            let previous = last_quotes.get(peer_id);
+            match check_plausible(quote, index, &genuine, previous) {
+                Ok(()) => kept.push((*peer_id, addrs.clone(), quote.clone())),
+                Err(reason) => suspicious.push((*peer_id, addrs.clone(), quote.clone(), reason)),
+            }
+        }
+
+        if last_quotes.len() >= MAX_REMEMBERED_PEERS {
+            last_quotes.clear();
+        }
+        for (peer_id, _, quote) in &genuine {
+            let is_newer = last_quotes
+                .get(peer_id)
+                .is_none_or(|previous| quote.is_newer_than(previous));
+            if is_newer {
+                let _ = last_quotes.insert(*peer_id, quote.clone());
+            }
+        }
+        drop(last_quotes);
+
+        for (peer_id, addrs, quote, reason) in suspicious {
+            if kept.len() + 1 > CLOSE_GROUP_SIZE {
+                exclude(content_addr, peer_id, reason);
+                excluded += 1;
+            } else {
+                warn!(
+                    "Keeping the quote of {peer_id:?} for {content_addr} despite {reason}, as too few quotes would be left"
+                );
+                kept.push((peer_id, addrs, quote));
+            }
+        }
+        (kept, excluded)
+    }
+}
+
+fn exclude(content_addr: XorName, peer_id: PeerId, reason: SuspiciousQuoteReason) {
+    warn!("Excluding the quote of {peer_id:?} for {content_addr}: {reason}");
+}
+
+fn check_genuine(
+    quote: &PaymentQuote,
+    peer_id: PeerId,
+    content_addr: XorName,
+    data_type: DataTypes,
+    data_size: usize,
+    now: SystemTime,
+) -> Result<(), SuspiciousQuoteReason> {
+    if quote.content != content_addr {
+        return Err(SuspiciousQuoteReason::ContentMismatch);
+    }
+    let skew = match now.duration_since(quote.timestamp) {
+        Ok(age) => age,
+        Err(err) => err.duration(),
+    };
+    if skew > MAX_QUOTE_CLOCK_SKEW {
+        return Err(SuspiciousQuoteReason::TimestampOutOfRange);
+    }
+    if quote.quoting_metrics.data_type != data_type.get_index()
+        || quote.quoting_metrics.data_size != data_size
+    {
+        return Err(SuspiciousQuoteReason::MetricsMismatch);
+    }
+    if !quote.check_is_signed_by_claimed_peer(peer_id) {
+        return Err(SuspiciousQuoteReason::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// Checks the quote at `index` of `quotes` against the other quotes and the previous quote of
+/// the same peer, if any.
+fn check_plausible(
+    quote: &PaymentQuote,
+    index: usize,
+    quotes: &[RawQuote],
+    previous: Option<&PaymentQuote>,
+) -> Result<(), SuspiciousQuoteReason> {
+    let metrics = &quote.quoting_metrics;
+    if metrics.close_records_stored > metrics.max_records {
+        return Err(SuspiciousQuoteReason::ImplausibleMetrics);
+    }
+
+    let mut others: Vec<usize> = quotes
+        .iter()
+        .enumerate()
+        .filter(|(other_index, _)| *other_index != index)
+        .map(|(_, (_, _, other))| other.quoting_metrics.close_records_stored)
+        .collect();
+    others.sort_unstable();
+    // Nodes may report themselves as fuller than they are, up to the same bound.
+    if let Some(median) = others.get(others.len() / 2)
+        && metrics.close_records_stored > max_plausible_close_records(*median)
+    {
+        return Err(SuspiciousQuoteReason::ImplausibleMetrics);
+    }
+
+    if let Some(previous) = previous
+        && previous.hash() != quote.hash()
+        && !previous.historical_verify(quote)
+    {
+        return Err(SuspiciousQuoteReason::InconsistentWithHistory);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_evm::{QuotingMetrics, RewardsAddress};
+    use libp2p::identity::Keypair;
+
+    const DATA_SIZE: usize = 1024;
+
+    fn quote(
+        keypair: &Keypair,
+        content: XorName,
+        timestamp: SystemTime,
+        close_records_stored: usize,
+        live_time: u64,
+    ) -> RawQuote {
+        let quoting_metrics = QuotingMetrics {
+            data_type: DataTypes::Chunk.get_index(),
+            data_size: DATA_SIZE,
+            close_records_stored,
+            records_per_type: vec![],
+            max_records: 16 * 1024,
+            received_payment_count: 0,
+            live_time,
+            network_density: None,
+            network_size: None,
+        };
+        let rewards_address = RewardsAddress::ZERO;
+        let bytes =
+            PaymentQuote::bytes_for_signing(content, timestamp, &quoting_metrics, &rewards_address);
+        let quote = PaymentQuote {
+            content,
+            timestamp,
+            quoting_metrics,
+            rewards_address,
+            pub_key: keypair.public().encode_protobuf(),
+            signature: keypair.sign(&bytes).expect("Signing to succeed"),
+        };
+        (keypair.public().to_peer_id(), Addresses(vec![]), quote)
+    }
+
+    fn honest_quotes(content: XorName, count: usize) -> Vec<RawQuote> {
+        (0..count)
+            .map(|i| {
+                quote(
+                    &Keypair::generate_ed25519(),
+                    content,
+                    SystemTime::now(),
+                    100 + i,
+                    1000,
+                )
+            })
+            .collect()
+    }
+
+    fn peers(quotes: &[RawQuote]) -> Vec<PeerId> {
+        quotes.iter().map(|(peer_id, _, _)| *peer_id).collect()
+    }
+
+    #[test]
+    fn forged_quotes_are_always_excluded() {
+        let content = XorName::random(&mut rand::thread_rng());
+        let mut quotes = honest_quotes(content, 3);
+        let expected = peers(&quotes);
+
+        let other = XorName::random(&mut rand::thread_rng());
+        quotes.push(quote(
+            &Keypair::generate_ed25519(),
+            other,
+            SystemTime::now(),
+            100,
+            1000,
+        ));
+        let stale = SystemTime::now() - 2 * MAX_QUOTE_CLOCK_SKEW;
+        quotes.push(quote(
+            &Keypair::generate_ed25519(),
+            content,
+            stale,
+            100,
+            1000,
+        ));
+        let mut forged = quote(
+            &Keypair::generate_ed25519(),
+            content,
+            SystemTime::now(),
+            100,
+            1000,
+        );
+        forged.2.quoting_metrics.close_records_stored = 0;
+        quotes.push(forged);
+
+        let screener = QuoteScreener::default();
+        let (kept, excluded) = screener.screen(content, DataTypes::Chunk, DATA_SIZE, quotes);
+        assert_eq!(peers(&kept), expected);
+        assert_eq!(excluded, 3);
+    }
+
+    #[test]
+    fn implausible_quotes_are_excluded_while_enough_are_left() {
+        let content = XorName::random(&mut rand::thread_rng());
+        let screener = QuoteScreener::default();
+
+        let mut quotes = honest_quotes(content, CLOSE_GROUP_SIZE);
+        let expected = peers(&quotes);
+        quotes.push(quote(
+            &Keypair::generate_ed25519(),
+            content,
+            SystemTime::now(),
+            10_000,
+            1000,
+        ));
+        let (kept, excluded) = screener.screen(content, DataTypes::Chunk, DATA_SIZE, quotes);
+        assert_eq!(peers(&kept), expected);
+        assert_eq!(excluded, 1);
+
+        // With too few quotes left, the suspicious one is kept rather than failing the upload.
+        let mut quotes = honest_quotes(content, CLOSE_GROUP_SIZE - 1);
+        quotes.push(quote(
+            &Keypair::generate_ed25519(),
+            content,
+            SystemTime::now(),
+            10_000,
+            1000,
+        ));
+        let (kept, excluded) = screener.screen(content, DataTypes::Chunk, DATA_SIZE, quotes);
+        assert_eq!(kept.len(), CLOSE_GROUP_SIZE);
+        assert_eq!(excluded, 0);
+    }
+
+    #[test]
+    fn quotes_of_nodes_reporting_themselves_fuller_within_bounds_are_kept() {
+        let content = XorName::random(&mut rand::thread_rng());
+        let screener = QuoteScreener::default();
+
+        // A node storing as many records as its peers, reporting as many as it may.
+        let mut quotes = honest_quotes(content, CLOSE_GROUP_SIZE);
+        quotes.push(quote(
+            &Keypair::generate_ed25519(),
+            content,
+            SystemTime::now(),
+            max_plausible_close_records(100),
+            1000,
+        ));
+        let expected = peers(&quotes);
+        let (kept, excluded) = screener.screen(content, DataTypes::Chunk, DATA_SIZE, quotes);
+        assert_eq!(peers(&kept), expected);
+        assert_eq!(excluded, 0);
+    }
+
+    #[test]
+    fn quotes_contradicting_the_history_of_the_peer_are_excluded() {
+        let content = XorName::random(&mut rand::thread_rng());
+        let screener = QuoteScreener::default();
+        let keypair = Keypair::generate_ed25519();
+
+        let mut quotes = honest_quotes(content, CLOSE_GROUP_SIZE);
+        quotes.push(quote(&keypair, content, SystemTime::now(), 100, 1000));
+        let (kept, _) = screener.screen(content, DataTypes::Chunk, DATA_SIZE, quotes);
+        assert_eq!(kept.len(), CLOSE_GROUP_SIZE + 1);
+
+        // A later quote claiming a shorter live time than the previous one.
+        let later = SystemTime::now() + Duration::from_secs(1);
+        let mut quotes = honest_quotes(content, CLOSE_GROUP_SIZE);
+        let expected = peers(&quotes);
+        quotes.push(quote(&keypair, content, later, 100, 10));
+        let (kept, excluded) = screener.screen(content, DataTypes::Chunk, DATA_SIZE, quotes);
+        assert_eq!(peers(&kept), expected);
+        assert_eq!(excluded, 1);
+    }
+}
@@ -81,8 +81,10 @@ pub use ant_evm::EvmNetwork as Network;
 pub use ant_evm::EvmWallet as Wallet;
 pub use ant_evm::QuoteHash;
 pub use ant_evm::RewardsAddress;
+pub use ant_evm::evm_config::{Error as EvmConfigError, EvmConfig};
 pub use ant_evm::utils::{Error as EvmUtilError, get_evm_network};
 pub use ant_evm::{Amount, AttoTokens};
+pub use ant_evm::{EvmAddress, PaymentFeeEstimate};
 pub use ant_evm::{MaxFeePerGas, TransactionConfig};
 
 // Re-exports of address related types
@@ -127,7 +127,24 @@ impl NetworkDriver {
         
         let transport_gen = QuicTransport::new(quic_config);
         let trans = transport_gen.map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
-        let transport = trans.boxed();
+
+        // TCP and WebSocket transports, to be able to dial nodes that opted-in to listen on them.
+        let tcp_config = libp2p::tcp::Config::default().nodelay(true);
+        let stream_transport =
+            libp2p::websocket::Config::new(libp2p::tcp::tokio::Transport::new(tcp_config.clone()))
+                .or_transport(libp2p::tcp::tokio::Transport::new(tcp_config))
+                .upgrade(libp2p::core::upgrade::Version::V1Lazy)
+                .authenticate(
+                    libp2p::noise::Config::new(&keypair)
+                        .expect("Signing libp2p-noise static DH keypair failed."),
+                )
+                .multiplex(libp2p::yamux::Config::default())
+                .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
+
+        let transport = trans
+            .or_transport(stream_transport)
+            .map(|either_output, _| either_output.into_inner())
+            .boxed();
 
         let (relay_transport, relay_client_behaviour) = libp2p::relay::client::new(peer_id);
         let relay_transport = relay_transport
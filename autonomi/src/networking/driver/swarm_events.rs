@@ -331,7 +331,8 @@ fn endpoint_str(endpoint: &libp2p::core::ConnectedPoint) -> String {
     }
 }
 
-/// Craft valid multiaddr like /ip4/68.183.39.80/udp/31055/quic-v1
+/// Craft valid multiaddr like /ip4/68.183.39.80/udp/31055/quic-v1,
+/// or /ip4/68.183.39.80/tcp/31055(/ws) for a connection over TCP or WebSocket.
 /// RelayManager::craft_relay_address for relayed addr. This is for non-relayed addr.
 fn craft_valid_multiaddr_without_p2p(addr: &Multiaddr) -> Option<Multiaddr> {
     let mut new_multiaddr = Multiaddr::empty();
@@ -339,11 +340,21 @@ fn craft_valid_multiaddr_without_p2p(addr: &Multiaddr) -> Option<Multiaddr> {
         Protocol::Ip4(addr) => Some(addr),
         _ => None,
     })?;
-    let port = multiaddr_get_port(addr)?;
-
     new_multiaddr.push(Protocol::Ip4(ip));
-    new_multiaddr.push(Protocol::Udp(port));
-    new_multiaddr.push(Protocol::QuicV1);
+
+    if let Some(port) = multiaddr_get_port(addr) {
+        new_multiaddr.push(Protocol::Udp(port));
+        new_multiaddr.push(Protocol::QuicV1);
+    } else {
+        let port = addr.iter().find_map(|p| match p {
+            Protocol::Tcp(port) => Some(port),
+            _ => None,
+        })?;
+        new_multiaddr.push(Protocol::Tcp(port));
+        if let Some(ws) = addr.iter().find(|p| matches!(p, Protocol::Ws(_))) {
+            new_multiaddr.push(ws);
+        }
+    }
 
     Some(new_multiaddr)
 }
@@ -576,7 +576,12 @@ fn verify_quote(
     let quote = match quote_res {
         Ok(quote) => quote,
         Err(ant_protocol::error::Error::RecordExists(_)) => return Ok(None),
-        Err(e) => return Err(NetworkError::GetQuoteError(e.to_string())),
+        Err(e) => {
+            return Err(match e.quote_decline_reason() {
+                Some(reason) => NetworkError::QuoteDeclined(reason),
+                None => NetworkError::GetQuoteError(e.to_string()),
+            });
+        }
     };
 
     // Check the quote itself is valid
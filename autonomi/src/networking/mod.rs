@@ -23,6 +23,7 @@ pub(crate) use utils::multiaddr_is_global;
 // re-export the types our API exposes to avoid dependency version conflicts
 pub use ant_evm::PaymentQuote;
 pub use ant_protocol::NetworkAddress;
+pub use ant_protocol::error::QuoteDeclineReason;
 pub use config::{RetryStrategy, Strategy};
 pub use libp2p::kad::PeerInfo;
 pub use libp2p::{
@@ -103,6 +104,8 @@ pub enum NetworkError {
     GetQuoteError(String),
     #[error("Invalid quote: {0}")]
     InvalidQuote(String),
+    #[error("Peer declined to quote: {0}")]
+    QuoteDeclined(QuoteDeclineReason),
     #[error(
         "Failed to get enough quotes: {got_quotes}/{CLOSE_GROUP_SIZE} quotes, got {record_exists_responses} record exists responses, and {errors_len} errors: {errors:?}"
     )]
@@ -149,6 +152,18 @@ impl NetworkError {
     pub fn cannot_retry(&self) -> bool {
         matches!(self, NetworkError::OutdatedRecordRejected { .. }) || self.is_fatal()
     }
+
+    /// Returns why peers declined to quote, including the declines behind [`NetworkError::InsufficientQuotes`]
+    pub fn quote_decline_reasons(&self) -> Vec<QuoteDeclineReason> {
+        match self {
+            NetworkError::QuoteDeclined(reason) => vec![*reason],
+            NetworkError::InsufficientQuotes { errors, .. } => errors
+                .iter()
+                .flat_map(NetworkError::quote_decline_reasons)
+                .collect(),
+            _ => vec![],
+        }
+    }
 }
 
 /// The Client interface to the Autonomi Network
@@ -391,7 +406,13 @@ impl Network {
         peer: PeerInfo,
         nonce: u64,
         difficulty: usize,
-    ) -> Result<Vec<(NetworkAddress, Result<ant_protocol::messages::ChunkProof, ant_protocol::error::Error>)>, NetworkError> {
+    ) -> Result<
+        Vec<(
+            NetworkAddress,
+            Result<ant_protocol::messages::ChunkProof, ant_protocol::error::Error>,
+        )>,
+        NetworkError,
+    > {
         let (tx, rx) = oneshot::channel();
         let task = NetworkTask::GetStorageProofsFromPeer {
             addr,
@@ -3082,10 +3082,18 @@ impl PyUploadSummary {
         self.inner.tokens_spent.to_string()
     }
 
+    #[getter]
+    fn quotes_excluded(&self) -> usize {
+        self.inner.quotes_excluded
+    }
+
     fn __str__(&self) -> PyResult<String> {
         Ok(format!(
-            "UploadSummary {{ records_paid: {}, records_already_paid: {}, tokens_spent: {} }}",
-            self.inner.records_paid, self.inner.records_already_paid, self.inner.tokens_spent
+            "UploadSummary {{ records_paid: {}, records_already_paid: {}, tokens_spent: {}, quotes_excluded: {} }}",
+            self.inner.records_paid,
+            self.inner.records_already_paid,
+            self.inner.tokens_spent,
+            self.inner.quotes_excluded
         ))
     }
 }
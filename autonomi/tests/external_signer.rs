@@ -5,7 +5,7 @@ use alloy::providers::Provider;
 use ant_evm::{QuoteHash, TxHash};
 use ant_logging::LogBuilder;
 use ant_protocol::storage::DataTypes;
-use autonomi::client::external_signer::encrypt_data;
+use autonomi::client::external_signer::{PaymentIntent, encrypt_data};
 use autonomi::client::files::{Metadata, archive_private::PrivateArchive};
 use autonomi::client::payment::{Receipt, receipt_from_store_quotes};
 use autonomi::client::quote::StoreQuote;
@@ -100,6 +100,50 @@ async fn pay_for_content_addresses(
     Ok(receipt_from_store_quotes(StoreQuote(quotes)))
 }
 
+async fn pay_with_payment_intent(
+    client: &Client,
+    wallet: &Wallet,
+    data: Bytes,
+) -> eyre::Result<Receipt> {
+    // Form the intent of paying for the data, and hand it over to the signer, serialized
+    let intent = client.data_payment_intent(data).await?;
+    let intent: PaymentIntent = serde_json::from_str(&serde_json::to_string(&intent)?)?;
+
+    // Init an external wallet provider. In the webapp, this would be MetaMask for example
+    let provider = wallet.to_provider();
+
+    // Send approve to spend tokens transaction
+    let transaction_request = provider
+        .transaction_request()
+        .with_to(intent.approve.to)
+        .with_input(intent.approve.calldata.clone());
+    let _tx_hash = provider
+        .send_transaction(transaction_request)
+        .await?
+        .watch()
+        .await?;
+
+    // Execute all quote payment transactions in batches
+    let mut batch_tx_hashes: BTreeMap<usize, TxHash> = Default::default();
+    for (i, batch) in intent.batches.iter().enumerate() {
+        let transaction_request = provider
+            .transaction_request()
+            .with_to(batch.transaction.to)
+            .with_input(batch.transaction.calldata.clone());
+        let tx_hash = provider
+            .send_transaction(transaction_request)
+            .await?
+            .watch()
+            .await?;
+        batch_tx_hashes.insert(i, tx_hash);
+    }
+
+    // Payment proofs, once the batch transactions are checked on chain
+    Ok(intent
+        .into_receipt(wallet.network(), &batch_tx_hashes)
+        .await?)
+}
+
 // Example of how put would be done using external signers.
 #[tokio::test]
 async fn external_signer_put() -> eyre::Result<()> {
@@ -187,3 +231,26 @@ async fn external_signer_put() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn external_signer_put_with_payment_intent() -> eyre::Result<()> {
+    let _log_appender_guard = LogBuilder::init_single_threaded_tokio_test();
+
+    let client = Client::init_local().await?;
+    let wallet = get_funded_wallet();
+    let data = gen_random_data(1024 * 1024 * 10);
+
+    let receipt = pay_with_payment_intent(&client, &wallet, data.clone()).await?;
+
+    sleep(Duration::from_secs(5)).await;
+
+    let (_cost, private_data_access) = client.data_put(data.clone(), receipt.into()).await?;
+    let fetched_data = client.data_get(&private_data_access).await?;
+
+    assert_eq!(
+        fetched_data, data,
+        "Fetched data is not identical to the uploaded data"
+    );
+
+    Ok(())
+}
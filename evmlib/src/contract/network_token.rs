@@ -8,6 +8,7 @@
 
 use crate::common::{Address, Calldata, TxHash, U256};
 use crate::contract::network_token::NetworkTokenContract::NetworkTokenContractInstance;
+use crate::nonce_manager::NonceManager;
 use crate::retry;
 use crate::retry::{retry, send_transaction_with_retries};
 use crate::transaction_config::TransactionConfig;
@@ -39,6 +40,7 @@ pub enum Error {
 
 pub struct NetworkToken<P: Provider<N>, N: Network> {
     pub contract: NetworkTokenContractInstance<P, N>,
+    nonce_manager: Option<NonceManager>,
 }
 
 impl<P, N> NetworkToken<P, N>
@@ -49,7 +51,10 @@ where
     /// Create a new NetworkToken contract instance.
     pub fn new(contract_address: Address, provider: P) -> Self {
         let contract = NetworkTokenContract::new(contract_address, provider);
-        NetworkToken { contract }
+        NetworkToken {
+            contract,
+            nonce_manager: None,
+        }
     }
 
     /// Deploys the AutonomiNetworkToken smart contract to the network of the provider.
@@ -58,7 +63,10 @@ where
         let contract = NetworkTokenContract::deploy(provider)
             .await
             .expect("Could not deploy contract, update anvil by running `foundryup` and try again");
-        NetworkToken { contract }
+        NetworkToken {
+            contract,
+            nonce_manager: None,
+        }
     }
 
     pub fn set_provider(&mut self, provider: P) {
@@ -66,6 +74,12 @@ where
         self.contract = NetworkTokenContract::new(address, provider);
     }
 
+    /// Assign the nonces of the transactions sent with the given manager.
+    pub fn with_nonce_manager(mut self, nonce_manager: NonceManager) -> Self {
+        self.nonce_manager = Some(nonce_manager);
+        self
+    }
+
     /// Get the raw token balance of an address.
     pub async fn balance_of(&self, account: Address) -> Result<U256, Error> {
         debug!("Getting balance of account: {account:?}");
@@ -107,6 +121,7 @@ where
             to,
             "approve",
             transaction_config,
+            self.nonce_manager.as_ref(),
         )
         .await
         .map_err(Error::from)
@@ -134,6 +149,7 @@ where
             to,
             "transfer",
             transaction_config,
+            self.nonce_manager.as_ref(),
         )
         .await
         .map_err(Error::from)
@@ -1,4 +1,5 @@
 use crate::retry;
+use alloy::transports::layers::{RateLimitRetryPolicy, RetryPolicy};
 use alloy::transports::{RpcError, TransportErrorKind};
 
 #[derive(thiserror::Error, Debug)]
@@ -18,3 +19,17 @@ pub enum Error {
     #[error(transparent)]
     Transaction(#[from] retry::TransactionError),
 }
+
+impl Error {
+    /// Returns true if the RPC endpoint turned the request down because it is rate limiting us or
+    /// is temporarily unavailable, i.e. the request should only be retried after backing off.
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            Self::RpcError(err)
+            | Self::ContractError(alloy::contract::Error::TransportError(err)) => {
+                RateLimitRetryPolicy::default().should_retry(err)
+            }
+            _ => false,
+        }
+    }
+}
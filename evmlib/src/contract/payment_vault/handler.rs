@@ -2,13 +2,19 @@ use crate::common::{Address, Amount, Calldata, TxHash};
 use crate::contract::payment_vault::error::Error;
 use crate::contract::payment_vault::interface::IPaymentVault;
 use crate::contract::payment_vault::interface::IPaymentVault::IPaymentVaultInstance;
+use crate::nonce_manager::NonceManager;
 use crate::retry::{retry, send_transaction_with_retries};
 use crate::transaction_config::TransactionConfig;
+use alloy::eips::BlockId;
 use alloy::network::Network;
+use alloy::primitives::Bytes;
 use alloy::providers::Provider;
+use alloy::rpc::client::BatchRequest;
+use alloy::sol_types::SolCall;
 
 pub struct PaymentVaultHandler<P: Provider<N>, N: Network> {
     pub contract: IPaymentVaultInstance<P, N>,
+    nonce_manager: Option<NonceManager>,
 }
 
 impl<P, N> PaymentVaultHandler<P, N>
@@ -19,7 +25,10 @@ where
     /// Create a new PaymentVaultHandler instance from a (proxy) contract's address
     pub fn new(contract_address: Address, provider: P) -> Self {
         let contract = IPaymentVault::new(contract_address, provider);
-        Self { contract }
+        Self {
+            contract,
+            nonce_manager: None,
+        }
     }
 
     /// Set the provider
@@ -28,6 +37,12 @@ where
         self.contract = IPaymentVault::new(address, provider);
     }
 
+    /// Assign the nonces of the transactions sent with the given manager.
+    pub fn with_nonce_manager(mut self, nonce_manager: NonceManager) -> Self {
+        self.nonce_manager = Some(nonce_manager);
+        self
+    }
+
     /// Fetch a quote from the contract
     pub async fn get_quote<I: IntoIterator<Item: Into<IPaymentVault::QuotingMetrics>>>(
         &self,
@@ -69,6 +84,7 @@ where
             to,
             "pay for quotes",
             transaction_config,
+            self.nonce_manager.as_ref(),
         )
         .await
         .map_err(Error::from)
@@ -119,4 +135,49 @@ where
 
         Ok(results)
     }
+
+    /// Verify if the payments of several data payments are valid, sending one `verifyPayment`
+    /// call per data payment in a single JSON-RPC batch request.
+    /// Returns the verification results of each data payment, in the order they were given.
+    pub async fn verify_payments(
+        &self,
+        payments: Vec<Vec<IPaymentVault::PaymentVerification>>,
+    ) -> Result<Vec<Result<[IPaymentVault::PaymentVerificationResult; 3], Error>>, Error> {
+        let calls: Vec<_> = payments
+            .into_iter()
+            .map(|payment_verifications| {
+                (
+                    self.contract
+                        .verifyPayment(payment_verifications)
+                        .into_transaction_request(),
+                    BlockId::latest(),
+                )
+            })
+            .collect();
+
+        debug!("Verifying {} payments in one batch", calls.len());
+
+        retry(
+            || async {
+                let mut batch = BatchRequest::new(self.contract.provider().client());
+                let waiters = calls
+                    .iter()
+                    .map(|call| batch.add_call::<_, Bytes>("eth_call", call))
+                    .collect::<Result<Vec<_>, _>>()?;
+                batch.send().await?;
+
+                let mut results = Vec::with_capacity(waiters.len());
+                for waiter in waiters {
+                    results.push(waiter.await.map_err(Error::from).and_then(|output| {
+                        IPaymentVault::verifyPaymentCall::abi_decode_returns(&output)
+                            .map_err(|err| Error::ContractError(err.into()))
+                    }));
+                }
+                Ok::<_, Error>(results)
+            },
+            "verifyPayments",
+            None,
+        )
+        .await
+    }
 }
@@ -31,14 +31,47 @@ pub async fn verify_data_payment(
     let provider = http_provider(network.rpc_url().clone());
     let payment_vault = PaymentVaultHandler::new(*network.data_payments_address(), provider);
 
-    let mut amount = Amount::ZERO;
-
     let payment_verifications: Vec<_> = payment
         .into_iter()
         .map(interface::IPaymentVault::PaymentVerification::from)
         .collect();
 
     let payment_verification_results = payment_vault.verify_payment(payment_verifications).await?;
+    amount_paid_to(&owned_quote_hashes, &payment_verification_results)
+}
+
+/// Helper function to fetch the on-chain verification of several data payments in one batch request.
+/// Returns the verification results of each payment, in the order the payments were given.
+pub async fn fetch_batch_payment_verifications(
+    network: &Network,
+    payments: Vec<Vec<(QuoteHash, QuotingMetrics, Address)>>,
+) -> Result<
+    Vec<Result<[interface::IPaymentVault::PaymentVerificationResult; 3], error::Error>>,
+    error::Error,
+> {
+    let provider = http_provider(network.rpc_url().clone());
+    let payment_vault = PaymentVaultHandler::new(*network.data_payments_address(), provider);
+
+    let payments: Vec<Vec<_>> = payments
+        .into_iter()
+        .map(|payment| {
+            payment
+                .into_iter()
+                .map(interface::IPaymentVault::PaymentVerification::from)
+                .collect()
+        })
+        .collect();
+
+    payment_vault.verify_payments(payments).await
+}
+
+/// Returns the amount paid to the owned quote hashes, given the verification results of a data payment.
+/// Fails if any of the verified quotes was not validly paid.
+pub fn amount_paid_to(
+    owned_quote_hashes: &[QuoteHash],
+    payment_verification_results: &[interface::IPaymentVault::PaymentVerificationResult],
+) -> Result<Amount, error::Error> {
+    let mut amount = Amount::ZERO;
 
     for payment_verification_result in payment_verification_results {
         // TODO we currently fail on a single invalid payment, maybe we should deal with this in a different way
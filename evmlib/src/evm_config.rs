@@ -0,0 +1,246 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! EVM network configuration files, listing several RPC endpoints for a network.
+//!
+//! A configuration is a JSON file such as:
+//!
+//! ```json
+//! {
+//!   "network": "arbitrum-one",
+//!   "rpc_endpoints": [
+//!     { "url": "https://arb1.example.com/rpc", "priority": 0, "max_requests_per_sec": 20 },
+//!     { "url": "https://arb1.arbitrum.io/rpc", "priority": 1 }
+//!   ]
+//! }
+//! ```
+//!
+//! where `network` is `arbitrum-one`, `arbitrum-sepolia-test` or
+//! `{ "custom": { "payment_token_address": "0x…", "data_payments_address": "0x…" } }`.
+//!
+//! Once installed with [`EvmConfig::install`], every provider built for the network fails over
+//! between its endpoints, lower priorities first.
+
+use crate::failover::{FailoverTransport, RpcPool};
+use crate::utils::{ALPHANET_ID, MAINNET_ID, set_evm_network};
+use crate::{CustomNetwork, Network, common::Address};
+use alloy::rpc::client::{ClientBuilder, RpcClient};
+use alloy::transports::http::reqwest;
+use alloy::transports::utils::guess_local_url;
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// The endpoints of the installed configuration, along with the RPC URL of its network.
+static RPC_POOL: OnceLock<(reqwest::Url, Arc<RpcPool>)> = OnceLock::new();
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Failed to read the EVM config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse the EVM config file: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("The EVM config lists no RPC endpoint")]
+    NoRpcEndpoints,
+    #[error("Another EVM network was already selected for this process")]
+    AlreadyInstalled,
+    #[error(
+        "The EVM config is for {network:?}, which is not the EVM network of network ID {network_id}"
+    )]
+    NetworkMismatch {
+        network_id: u8,
+        network: EvmConfigNetwork,
+    },
+}
+
+/// The network an EVM config is for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EvmConfigNetwork {
+    ArbitrumOne,
+    ArbitrumSepoliaTest,
+    Custom {
+        payment_token_address: Address,
+        data_payments_address: Address,
+    },
+}
+
+/// An RPC endpoint of an EVM network.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcEndpointConfig {
+    #[serde_as(as = "DisplayFromStr")]
+    pub url: reqwest::Url,
+    /// Endpoints of lower priority are tried first. Endpoints of the same priority share the
+    /// requests round-robin.
+    #[serde(default)]
+    pub priority: u32,
+    /// Requests per second the endpoint accepts, unlimited if absent.
+    #[serde(default)]
+    pub max_requests_per_sec: Option<u32>,
+}
+
+/// An EVM network along with the RPC endpoints to reach it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvmConfig {
+    pub network: EvmConfigNetwork,
+    pub rpc_endpoints: Vec<RpcEndpointConfig>,
+}
+
+impl EvmConfig {
+    /// Reads the configuration from a JSON file.
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)?;
+        let config: Self = serde_json::from_str(&content)?;
+        if config.rpc_endpoints.is_empty() {
+            return Err(Error::NoRpcEndpoints);
+        }
+        Ok(config)
+    }
+
+    /// Returns the network of the configuration. The RPC URL of a custom network is the one of
+    /// its first endpoint of the lowest priority.
+    pub fn network(&self) -> Result<Network, Error> {
+        match &self.network {
+            EvmConfigNetwork::ArbitrumOne => Ok(Network::ArbitrumOne),
+            EvmConfigNetwork::ArbitrumSepoliaTest => Ok(Network::ArbitrumSepoliaTest),
+            EvmConfigNetwork::Custom {
+                payment_token_address,
+                data_payments_address,
+            } => {
+                let primary = self
+                    .rpc_endpoints
+                    .iter()
+                    .min_by_key(|endpoint| endpoint.priority)
+                    .ok_or(Error::NoRpcEndpoints)?;
+                Ok(Network::Custom(CustomNetwork {
+                    rpc_url_http: primary.url.clone(),
+                    payment_token_address: *payment_token_address,
+                    data_payments_address: *data_payments_address,
+                }))
+            }
+        }
+    }
+
+    /// Fails unless the configuration is for the EVM network of the given network ID. The mainnet
+    /// and the alpha network have their own EVM networks, whereas any other network, e.g. a local
+    /// or test network, may use any.
+    pub fn check_network_id(&self, network_id: u8) -> Result<(), Error> {
+        let expected = match network_id {
+            MAINNET_ID => EvmConfigNetwork::ArbitrumOne,
+            ALPHANET_ID => EvmConfigNetwork::ArbitrumSepoliaTest,
+            _ => return Ok(()),
+        };
+        if self.network != expected {
+            return Err(Error::NetworkMismatch {
+                network_id,
+                network: self.network.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Selects the network of the configuration for this process, as [`crate::utils::get_evm_network`]
+    /// would, and makes every provider built for it fail over between the configured endpoints.
+    /// Fails if the configuration is not for the EVM network of the given network ID.
+    /// Returns the network.
+    pub fn install(&self, network_id: u8) -> Result<Network, Error> {
+        self.check_network_id(network_id)?;
+        let network = self.network()?;
+        let pool = Arc::new(RpcPool::new(&self.rpc_endpoints));
+        if !set_evm_network(network.clone())
+            || RPC_POOL.set((network.rpc_url().clone(), pool)).is_err()
+        {
+            return Err(Error::AlreadyInstalled);
+        }
+        info!(
+            "Using EVM network {network} over {} RPC endpoints",
+            self.rpc_endpoints.len()
+        );
+        Ok(network)
+    }
+}
+
+/// Returns the RPC client to reach the network of the given RPC URL, failing over between the
+/// endpoints of the installed configuration if it is for that network.
+pub(crate) fn rpc_client(rpc_url: reqwest::Url) -> RpcClient {
+    match RPC_POOL.get() {
+        Some((pool_url, pool)) if *pool_url == rpc_url => RpcClient::new(
+            FailoverTransport::new(Arc::clone(pool)),
+            guess_local_url(&rpc_url),
+        ),
+        _ => ClientBuilder::default().http(rpc_url),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_custom_network_config() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("evm.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "network": { "custom": {
+                    "payment_token_address": "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+                    "data_payments_address": "0x8464135c8F25Da09e49BC8782676a84730C318bC"
+                } },
+                "rpc_endpoints": [
+                    { "url": "http://backup.example.com:8545", "priority": 1 },
+                    { "url": "http://primary.example.com:8545", "max_requests_per_sec": 5 }
+                ]
+            }"#,
+        )?;
+
+        let config = EvmConfig::from_file(&path)?;
+        assert_eq!(config.rpc_endpoints[0].priority, 1);
+        assert_eq!(config.rpc_endpoints[1].max_requests_per_sec, Some(5));
+        let network = config.network()?;
+        assert_eq!(
+            network.rpc_url().as_str(),
+            "http://primary.example.com:8545/"
+        );
+
+        std::fs::write(
+            &path,
+            r#"{ "network": "arbitrum-one", "rpc_endpoints": [] }"#,
+        )?;
+        assert!(matches!(
+            EvmConfig::from_file(&path),
+            Err(Error::NoRpcEndpoints)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn config_must_be_for_the_evm_network_of_the_network_id() -> Result<(), Error> {
+        let config: EvmConfig = serde_json::from_str(
+            r#"{
+                "network": "arbitrum-sepolia-test",
+                "rpc_endpoints": [{ "url": "https://sepolia-rollup.arbitrum.io/rpc" }]
+            }"#,
+        )?;
+
+        assert!(matches!(
+            config.check_network_id(1),
+            Err(Error::NetworkMismatch { network_id: 1, .. })
+        ));
+        // The mismatch is caught before the network is selected for the process.
+        assert!(matches!(
+            config.install(1),
+            Err(Error::NetworkMismatch { .. })
+        ));
+        assert!(config.check_network_id(2).is_ok());
+        assert!(config.check_network_id(7).is_ok());
+        Ok(())
+    }
+}
@@ -7,10 +7,13 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::Network;
-use crate::common::{Address, Amount, Calldata, QuoteHash, QuotePayment, U256};
+use crate::common::{Address, Amount, Calldata, QuoteHash, QuotePayment, TxHash, U256};
 use crate::contract::network_token::{self, NetworkToken};
 use crate::contract::payment_vault::MAX_TRANSFERS_PER_TRANSACTION;
 use crate::utils::http_provider;
+use alloy::consensus::Transaction;
+use alloy::providers::Provider;
+use alloy::transports::{RpcError, TransportErrorKind};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -20,6 +23,16 @@ pub enum Error {
     NetworkTokenContract(#[from] network_token::Error),
     #[error("Data payments contract error: {0}")]
     DataPaymentsContract(#[from] crate::contract::payment_vault::error::Error),
+    #[error(transparent)]
+    RpcError(#[from] RpcError<TransportErrorKind>),
+    #[error("Transaction {0} was not found on chain")]
+    TransactionNotFound(TxHash),
+    #[error("Transaction {0} has not been mined yet")]
+    TransactionNotMined(TxHash),
+    #[error("Transaction {0} was reverted")]
+    TransactionReverted(TxHash),
+    #[error("Transaction {0} does not send the expected calldata to the expected address")]
+    TransactionMismatch(TxHash),
 }
 
 /// Approve an address / smart contract to spend this wallet's payment tokens.
@@ -96,3 +109,29 @@ pub fn pay_for_quotes_calldata<T: IntoIterator<Item = QuotePayment>>(
         approve_amount,
     })
 }
+
+/// Checks that the transaction of the given hash was mined successfully, and that it sent the
+/// given calldata to the given address, e.g. one of the batches of `pay_for_quotes_calldata`.
+pub async fn verify_transaction(
+    network: &Network,
+    tx_hash: TxHash,
+    to: Address,
+    calldata: &Calldata,
+) -> Result<(), Error> {
+    let provider = http_provider(network.rpc_url().clone());
+    let transaction = provider
+        .get_transaction_by_hash(tx_hash)
+        .await?
+        .ok_or(Error::TransactionNotFound(tx_hash))?;
+    if transaction.to() != Some(to) || transaction.input() != calldata {
+        return Err(Error::TransactionMismatch(tx_hash));
+    }
+    let receipt = provider
+        .get_transaction_receipt(tx_hash)
+        .await?
+        .ok_or(Error::TransactionNotMined(tx_hash))?;
+    if !receipt.status() {
+        return Err(Error::TransactionReverted(tx_hash));
+    }
+    Ok(())
+}
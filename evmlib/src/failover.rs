@@ -0,0 +1,320 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! A transport spreading JSON-RPC requests over several endpoints.
+//!
+//! Endpoints are tried by priority, round-robin between the endpoints of the same priority. An
+//! endpoint failing at the transport level, e.g. unreachable or answering with an HTTP error, is
+//! failed over and set aside for an exponential backoff. JSON-RPC error responses are the
+//! endpoint's answer, so they are returned as they are.
+
+use crate::evm_config::RpcEndpointConfig;
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
+use alloy::transports::http::{Http, reqwest};
+use alloy::transports::{
+    BoxTransport, IntoBoxTransport, TransportError, TransportErrorKind, TransportFut,
+    TransportResult,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::Service;
+
+/// Backoff after the first failure of an endpoint, doubled on every consecutive failure.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+    window_start: Option<Instant>,
+    window_requests: u32,
+}
+
+struct Endpoint {
+    /// Identifies the endpoint in logs.
+    label: String,
+    priority: u32,
+    max_requests_per_sec: Option<u32>,
+    transport: BoxTransport,
+    health: Mutex<EndpointHealth>,
+}
+
+impl Endpoint {
+    fn new(
+        label: String,
+        priority: u32,
+        max_requests_per_sec: Option<u32>,
+        transport: BoxTransport,
+    ) -> Self {
+        Self {
+            label,
+            priority,
+            max_requests_per_sec,
+            transport,
+            health: Mutex::default(),
+        }
+    }
+
+    fn health(&self) -> std::sync::MutexGuard<'_, EndpointHealth> {
+        self.health.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.health()
+            .unhealthy_until
+            .is_none_or(|unhealthy_until| unhealthy_until <= now)
+    }
+
+    /// Takes a request slot of the current rate limit window.
+    /// Returns how long until a slot frees up if the window is full.
+    fn try_acquire(&self, now: Instant) -> Result<(), Duration> {
+        let Some(max_requests) = self.max_requests_per_sec else {
+            return Ok(());
+        };
+        let mut health = self.health();
+        match health.window_start {
+            Some(start) if now.duration_since(start) < RATE_LIMIT_WINDOW => {
+                if health.window_requests >= max_requests {
+                    return Err(RATE_LIMIT_WINDOW - now.duration_since(start));
+                }
+                health.window_requests += 1;
+            }
+            _ => {
+                health.window_start = Some(now);
+                health.window_requests = 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn record_success(&self) {
+        let mut health = self.health();
+        if health.consecutive_failures > 0 {
+            info!("EVM RPC endpoint {} is healthy again", self.label);
+        }
+        health.consecutive_failures = 0;
+        health.unhealthy_until = None;
+    }
+
+    fn record_failure(&self, now: Instant) {
+        let mut health = self.health();
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        let backoff = INITIAL_BACKOFF
+            .saturating_mul(2u32.saturating_pow(health.consecutive_failures - 1))
+            .min(MAX_BACKOFF);
+        health.unhealthy_until = Some(now + backoff);
+        warn!(
+            "EVM RPC endpoint {} failed {} times in a row, setting it aside for {backoff:?}",
+            self.label, health.consecutive_failures
+        );
+    }
+}
+
+/// The endpoints of an EVM network, along with their health.
+pub(crate) struct RpcPool {
+    endpoints: Vec<Endpoint>,
+    /// Rotates the endpoints of the same priority.
+    next: AtomicUsize,
+}
+
+impl RpcPool {
+    pub(crate) fn new(configs: &[RpcEndpointConfig]) -> Self {
+        Self::from_endpoints(
+            configs
+                .iter()
+                .map(|config| {
+                    Endpoint::new(
+                        config.url.to_string(),
+                        config.priority,
+                        config.max_requests_per_sec,
+                        Http::<reqwest::Client>::new(config.url.clone()).into_box_transport(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn from_endpoints(endpoints: Vec<Endpoint>) -> Self {
+        Self {
+            endpoints,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the endpoints in the order to try them: healthy ones first, by priority.
+    fn candidates(&self, now: Instant) -> Vec<&Endpoint> {
+        let count = self.endpoints.len().max(1);
+        let offset = self.next.fetch_add(1, Ordering::Relaxed) % count;
+        let mut candidates: Vec<_> = self.endpoints.iter().enumerate().collect();
+        candidates.sort_by_key(|(i, endpoint)| {
+            (
+                !endpoint.is_healthy(now),
+                endpoint.priority,
+                (i + count - offset) % count,
+            )
+        });
+        candidates
+            .into_iter()
+            .map(|(_, endpoint)| endpoint)
+            .collect()
+    }
+
+    async fn send(&self, request: RequestPacket) -> TransportResult<ResponsePacket> {
+        loop {
+            let now = Instant::now();
+            let mut last_error = None;
+            let mut earliest_slot: Option<Duration> = None;
+            for endpoint in self.candidates(now) {
+                if let Err(wait) = endpoint.try_acquire(now) {
+                    earliest_slot = Some(earliest_slot.map_or(wait, |slot| slot.min(wait)));
+                    continue;
+                }
+                match endpoint.transport.clone().call(request.clone()).await {
+                    Ok(response) => {
+                        endpoint.record_success();
+                        return Ok(response);
+                    }
+                    Err(err) => {
+                        debug!("EVM RPC request to {} failed: {err}", endpoint.label);
+                        endpoint.record_failure(Instant::now());
+                        last_error = Some(err);
+                    }
+                }
+            }
+
+            match (last_error, earliest_slot) {
+                (Some(err), _) => return Err(err),
+                // Every endpoint is at its rate limit, wait for the first to free up a slot.
+                (None, Some(wait)) => tokio::time::sleep(wait).await,
+                (None, None) => {
+                    return Err(TransportErrorKind::custom_str(
+                        "No EVM RPC endpoint is configured",
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// A transport failing over between the endpoints of a shared [`RpcPool`].
+#[derive(Clone)]
+pub(crate) struct FailoverTransport {
+    pool: Arc<RpcPool>,
+}
+
+impl FailoverTransport {
+    pub(crate) fn new(pool: Arc<RpcPool>) -> Self {
+        Self { pool }
+    }
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let pool = Arc::clone(&self.pool);
+        Box::pin(async move { pool.send(request).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::rpc::json_rpc::{Id, Request};
+    use alloy::transports::mock::{Asserter, MockTransport};
+
+    fn endpoint(
+        label: &str,
+        priority: u32,
+        max_requests_per_sec: Option<u32>,
+        asserter: &Asserter,
+    ) -> Endpoint {
+        Endpoint::new(
+            label.to_string(),
+            priority,
+            max_requests_per_sec,
+            MockTransport::new(asserter.clone()).into_box_transport(),
+        )
+    }
+
+    fn request() -> RequestPacket {
+        let request: Request<()> = Request::new("eth_blockNumber", Id::Number(1), ());
+        RequestPacket::Single(request.serialize().expect("request to serialize"))
+    }
+
+    fn result(response: ResponsePacket) -> u64 {
+        match response {
+            ResponsePacket::Single(response) => response
+                .deser_success::<u64>()
+                .ok()
+                .and_then(|response| response.payload.as_success().copied())
+                .expect("a successful response"),
+            ResponsePacket::Batch(_) => panic!("a single response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_lower_priority_and_sets_the_failed_endpoint_aside() {
+        // An empty asserter fails every request at the transport level.
+        let failing = Asserter::new();
+        let backup = Asserter::new();
+        backup.push_success(&1u64);
+        backup.push_success(&2u64);
+        let pool = RpcPool::from_endpoints(vec![
+            endpoint("backup", 1, None, &backup),
+            endpoint("primary", 0, None, &failing),
+        ]);
+
+        assert_eq!(result(pool.send(request()).await.expect("failover")), 1);
+        assert!(!pool.endpoints[1].is_healthy(Instant::now()));
+
+        // The failed endpoint is only tried after the healthy ones.
+        let order: Vec<_> = pool
+            .candidates(Instant::now())
+            .iter()
+            .map(|endpoint| endpoint.label.as_str())
+            .collect();
+        assert_eq!(order, vec!["backup", "primary"]);
+        assert_eq!(result(pool.send(request()).await.expect("backup")), 2);
+
+        // Once every endpoint fails, the last error is returned.
+        assert!(pool.send(request()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rotates_endpoints_of_the_same_priority_within_their_rate_limits() {
+        let first = Asserter::new();
+        let second = Asserter::new();
+        for i in 0..4u64 {
+            first.push_success(&i);
+            second.push_success(&(i + 100));
+        }
+        let pool = RpcPool::from_endpoints(vec![
+            endpoint("first", 0, Some(1), &first),
+            endpoint("second", 0, Some(1), &second),
+        ]);
+
+        let mut results = vec![];
+        for _ in 0..3 {
+            results.push(result(pool.send(request()).await.expect("response")));
+        }
+        // Both endpoints took a request, and the third one waited for a free slot.
+        assert!(results.contains(&0) && results.contains(&100));
+        assert_eq!(first.read_q().len() + second.read_q().len(), 5);
+    }
+}
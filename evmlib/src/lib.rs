@@ -26,8 +26,11 @@ extern crate tracing;
 pub mod common;
 pub mod contract;
 pub mod cryptography;
+pub mod evm_config;
 #[cfg(feature = "external-signer")]
 pub mod external_signer;
+mod failover;
+pub mod nonce_manager;
 pub mod quoting_metrics;
 mod retry;
 pub mod testnet;
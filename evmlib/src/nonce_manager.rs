@@ -0,0 +1,105 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Local assignment of transaction nonces, so a wallet can have several transactions in flight.
+//!
+//! Without it, the nonce of a transaction is read from the chain as it is sent, so a wallet must
+//! wait for each transaction to confirm before sending the next. The manager hands out increasing
+//! nonces instead, reading the first one from the pending transaction count of the chain. It is
+//! read from the chain again after a nonce was skipped, e.g. a transaction that was given up on,
+//! so later transactions do not wait behind the gap forever.
+
+use crate::common::Address;
+use alloy::network::Network;
+use alloy::providers::Provider;
+use alloy::transports::{RpcError, TransportErrorKind};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Assigns the nonces of the transactions of an address.
+///
+/// Clones share their state, so every transaction of the address must go through them.
+#[derive(Clone, Debug)]
+pub struct NonceManager {
+    address: Address,
+    /// The nonce to assign next, unknown until read from the chain.
+    next: Arc<Mutex<Option<u64>>>,
+}
+
+impl NonceManager {
+    /// Creates a manager for the transactions of the given address.
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            next: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the address the nonces are assigned for.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Assigns the next nonce.
+    pub async fn next_nonce<P: Provider<N>, N: Network>(
+        &self,
+        provider: &P,
+    ) -> Result<u64, RpcError<TransportErrorKind>> {
+        let mut next = self.next.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => {
+                let nonce = provider
+                    .get_transaction_count(self.address)
+                    .pending()
+                    .await?;
+                debug!(
+                    "Read the next nonce of {} from the chain: {nonce}",
+                    self.address
+                );
+                nonce
+            }
+        };
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    /// Forgets the assigned nonces, so the next one is read from the chain again.
+    pub async fn resync(&self) {
+        *self.next.lock().await = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet::Testnet;
+    use crate::utils::http_provider;
+    use crate::wallet::Wallet;
+
+    #[tokio::test]
+    async fn assigns_increasing_nonces_and_resyncs_from_the_chain() {
+        let testnet = Testnet::new().await;
+        let network = testnet.to_network();
+        let wallet =
+            Wallet::new_from_private_key(network.clone(), &testnet.default_wallet_private_key())
+                .unwrap();
+        let provider = http_provider(network.rpc_url().clone());
+        let nonce_manager = NonceManager::new(wallet.address());
+
+        let first = nonce_manager.next_nonce(&provider).await.unwrap();
+        assert_eq!(
+            nonce_manager.next_nonce(&provider).await.unwrap(),
+            first + 1
+        );
+
+        // Neither nonce was used, so the chain hands out the first again.
+        nonce_manager.resync().await;
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), first);
+    }
+}
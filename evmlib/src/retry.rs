@@ -1,7 +1,8 @@
 use crate::TX_TIMEOUT;
 use crate::common::{Address, Calldata, TxHash};
+use crate::nonce_manager::NonceManager;
 use crate::transaction_config::{MaxFeePerGas, TransactionConfig};
-use alloy::network::{Network, TransactionBuilder};
+use alloy::network::{Network, ReceiptResponse, TransactionBuilder};
 use alloy::providers::{PendingTransactionBuilder, Provider};
 use std::time::Duration;
 
@@ -9,6 +10,9 @@ pub(crate) const MAX_RETRIES: u8 = 3;
 const DEFAULT_RETRY_INTERVAL_MS: u64 = 4000;
 const BROADCAST_TRANSACTION_TIMEOUT_MS: u64 = 5000;
 const WATCH_TIMEOUT_MS: u64 = 1000;
+const BROADCAST_TIMEOUT_REASON: &str = "timeout";
+/// Fee increase of each replacement of a transaction, nodes accepting replacements from 10% up.
+const REPLACEMENT_FEE_BUMP_PERCENT: u128 = 25;
 
 #[derive(thiserror::Error, Debug)]
 pub enum TransactionError {
@@ -20,6 +24,8 @@ pub enum TransactionError {
     TransactionFailedToSend(String),
     #[error("Transaction failed to confirm in time: {0}")]
     TransactionFailedToConfirm(String, Option<u64>), // Includes the nonce
+    #[error("Transaction was mined but reverted: {0:?}")]
+    TransactionReverted(TxHash),
 }
 
 /// Execute an async closure that returns a result. Retry on failure.
@@ -60,28 +66,52 @@ where
 }
 
 /// Generic function to send a transaction with retries.
+///
+/// With a nonce manager, the transaction is assigned its nonce up front, and retries replace it at
+/// a higher fee, e.g. when it is underpriced or dropped. Any of the replaced transactions being
+/// mined then completes the send.
 pub(crate) async fn send_transaction_with_retries<P, N>(
     provider: &P,
     calldata: Calldata,
     to: Address,
     tx_identifier: &str,
     transaction_config: &TransactionConfig,
+    nonce_manager: Option<&NonceManager>,
 ) -> Result<TxHash, TransactionError>
 where
     P: Provider<N>,
     N: Network,
 {
-    let mut previous_nonce: Option<u64> = None;
+    let mut previous_nonce: Option<u64> = match nonce_manager {
+        Some(nonce_manager) => Some(next_managed_nonce(provider, nonce_manager).await?),
+        None => None,
+    };
+    // Transactions sent with the managed nonce, any of which may be mined.
+    let mut sent_tx_hashes: Vec<TxHash> = vec![];
+    // Whether a broadcast timed out, in which case the transaction may have been sent anyway.
+    let mut broadcast_timed_out = false;
     let mut retries: u8 = 0;
 
     loop {
+        if nonce_manager.is_some()
+            && let Some(mined) = mined_transaction(provider, &sent_tx_hashes).await
+        {
+            debug!("{tx_identifier} transaction was mined: {mined:?}");
+            break mined;
+        }
+
+        // A managed transaction is replaced on retries, which takes a higher fee.
+        let fee_bump = if nonce_manager.is_some() { retries } else { 0 };
+
         match send_transaction(
             provider,
             calldata.clone(),
             to,
             previous_nonce,
+            fee_bump,
             tx_identifier,
             transaction_config,
+            &mut sent_tx_hashes,
         )
         .await
         {
@@ -91,6 +121,10 @@ where
                     error!(
                         "Transaction {tx_identifier} failed after {retries} retries. Giving up. Error: {err:?}"
                     );
+                    if let Some(nonce_manager) = nonce_manager {
+                        // The nonce may be left unused, so later transactions would wait on it.
+                        nonce_manager.resync().await;
+                    }
                     break Err(err);
                 }
 
@@ -103,11 +137,30 @@ where
                     }
                     TransactionError::TransactionFailedToSend(reason) => {
                         warn!("Transaction failed to send: {reason}");
+                        broadcast_timed_out |= reason == BROADCAST_TIMEOUT_REASON;
+                        if let Some(nonce_manager) = nonce_manager
+                            && is_nonce_too_low(&reason)
+                            && sent_tx_hashes.is_empty()
+                        {
+                            if broadcast_timed_out {
+                                // The nonce may have been used by this very transaction.
+                                nonce_manager.resync().await;
+                                break Err(TransactionError::TransactionFailedToSend(reason));
+                            }
+                            // Another transaction of the wallet took the nonce.
+                            nonce_manager.resync().await;
+                            previous_nonce =
+                                Some(next_managed_nonce(provider, nonce_manager).await?);
+                        }
                     }
                     TransactionError::TransactionFailedToConfirm(reason, nonce) => {
                         warn!("Transaction failed to confirm: {reason} (nonce: {nonce:?})");
                         previous_nonce = nonce;
                     }
+                    // The nonce was used, and sending it again would revert again.
+                    TransactionError::TransactionReverted(tx_hash) => {
+                        break Err(TransactionError::TransactionReverted(tx_hash));
+                    }
                 }
 
                 retries += 1;
@@ -129,13 +182,56 @@ where
     }
 }
 
+async fn next_managed_nonce<P, N>(
+    provider: &P,
+    nonce_manager: &NonceManager,
+) -> Result<u64, TransactionError>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    nonce_manager
+        .next_nonce(provider)
+        .await
+        .map_err(|err| TransactionError::TransactionFailedToSend(err.to_string()))
+}
+
+/// Returns the first of the transactions that was mined, if any, or an error if it reverted.
+async fn mined_transaction<P, N>(
+    provider: &P,
+    tx_hashes: &[TxHash],
+) -> Option<Result<TxHash, TransactionError>>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    for tx_hash in tx_hashes {
+        if let Ok(Some(receipt)) = provider.get_transaction_receipt(*tx_hash).await {
+            if !receipt.status() {
+                error!("Transaction with hash {tx_hash:?} was mined but reverted");
+                return Some(Err(TransactionError::TransactionReverted(*tx_hash)));
+            }
+            return Some(Ok(*tx_hash));
+        }
+    }
+    None
+}
+
+fn is_nonce_too_low(reason: &str) -> bool {
+    let reason = reason.to_lowercase();
+    reason.contains("nonce too low") || reason.contains("nonce has already been used")
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn send_transaction<P, N>(
     provider: &P,
     calldata: Calldata,
     to: Address,
     mut nonce: Option<u64>,
+    fee_bump: u8,
     tx_identifier: &str,
     transaction_config: &TransactionConfig,
+    sent_tx_hashes: &mut Vec<TxHash>,
 ) -> Result<TxHash, TransactionError>
 where
     P: Provider<N>,
@@ -150,7 +246,23 @@ where
         .with_to(to)
         .with_input(calldata.clone());
 
-    if let Some(max_fee_per_gas) = max_fee_per_gas {
+    if fee_bump > 0 {
+        // A replacement is only accepted at a higher max fee and priority fee than the original.
+        let fees = provider
+            .estimate_eip1559_fees()
+            .await
+            .map_err(|err| TransactionError::CouldNotGetGasPrice(err.to_string()))?;
+        let bump = |fee: u128| {
+            fee.saturating_mul(100 + REPLACEMENT_FEE_BUMP_PERCENT * u128::from(fee_bump)) / 100
+        };
+        let mut max_fee = bump(max_fee_per_gas.unwrap_or(fees.max_fee_per_gas));
+        if let Some(fee_cap) = fee_cap(transaction_config) {
+            max_fee = max_fee.min(fee_cap);
+        }
+        transaction_request.set_max_fee_per_gas(max_fee);
+        transaction_request
+            .set_max_priority_fee_per_gas(bump(fees.max_priority_fee_per_gas).min(max_fee));
+    } else if let Some(max_fee_per_gas) = max_fee_per_gas {
         transaction_request.set_max_fee_per_gas(max_fee_per_gas);
     }
 
@@ -172,7 +284,7 @@ where
         Ok(Err(err)) => return Err(TransactionError::TransactionFailedToSend(err.to_string())),
         Err(_) => {
             return Err(TransactionError::TransactionFailedToSend(
-                "timeout".to_string(),
+                BROADCAST_TIMEOUT_REASON.to_string(),
             ));
         }
     };
@@ -181,6 +293,7 @@ where
         "{tx_identifier} transaction is pending with tx_hash: {:?}",
         pending_tx_builder.tx_hash()
     );
+    sent_tx_hashes.push(*pending_tx_builder.tx_hash());
 
     let watch_result = retry(
         || async {
@@ -209,6 +322,15 @@ where
     }
 }
 
+/// Returns the highest max fee per gas the configuration allows, if any.
+fn fee_cap(transaction_config: &TransactionConfig) -> Option<u128> {
+    match transaction_config.max_fee_per_gas {
+        MaxFeePerGas::LimitedAuto(limit) => Some(limit),
+        MaxFeePerGas::Custom(wei) => Some(wei),
+        MaxFeePerGas::Auto | MaxFeePerGas::Unlimited => None,
+    }
+}
+
 async fn get_max_fee_per_gas<P: Provider<N>, N: Network>(
     provider: &P,
     transaction_config: &TransactionConfig,
@@ -9,6 +9,7 @@
 #![allow(dead_code)]
 
 use crate::common::{Address, Hash};
+use crate::evm_config::rpc_client;
 use crate::{CustomNetwork, Network};
 use alloy::network::Ethereum;
 use alloy::providers::fillers::{
@@ -19,8 +20,8 @@ use alloy::providers::{Identity, ProviderBuilder, RootProvider};
 use alloy::transports::http::reqwest;
 use std::env;
 
-const MAINNET_ID: u8 = 1;
-const ALPHANET_ID: u8 = 2;
+pub(crate) const MAINNET_ID: u8 = 1;
+pub(crate) const ALPHANET_ID: u8 = 2;
 
 /// environment variable to connect to a custom EVM network
 pub const RPC_URL: &str = "RPC_URL";
@@ -52,6 +53,13 @@ use std::sync::OnceLock;
 
 static EVM_NETWORK: OnceLock<Network> = OnceLock::new();
 
+/// Selects the EVM network for this process, unless another one was already selected.
+/// Returns whether the network is the selected one.
+pub(crate) fn set_evm_network(network: Network) -> bool {
+    let selected = EVM_NETWORK.get_or_init(|| network.clone());
+    *selected == network
+}
+
 /// Initialize the EVM Network.
 ///
 /// Try to obtain it first from environment variables. If that fails and `local` is true,
@@ -197,5 +205,5 @@ pub fn http_provider(
 > {
     ProviderBuilder::new()
         .with_simple_nonce_management()
-        .connect_http(rpc_url)
+        .connect_client(rpc_client(rpc_url))
 }
@@ -6,11 +6,13 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use crate::common::{Address, Amount, QuoteHash, QuotePayment, TxHash, U256};
+use crate::common::{Address, Amount, Calldata, QuoteHash, QuotePayment, TxHash, U256};
 use crate::contract::network_token::NetworkToken;
 use crate::contract::payment_vault::MAX_TRANSFERS_PER_TRANSACTION;
 use crate::contract::payment_vault::handler::PaymentVaultHandler;
 use crate::contract::{network_token, payment_vault};
+use crate::evm_config::rpc_client;
+use crate::nonce_manager::NonceManager;
 use crate::transaction_config::TransactionConfig;
 use crate::utils::http_provider;
 use crate::{Network, TX_TIMEOUT};
@@ -21,7 +23,7 @@ use alloy::providers::fillers::{
     SimpleNonceManager, WalletFiller,
 };
 use alloy::providers::{Identity, Provider, ProviderBuilder, RootProvider};
-use alloy::rpc::types::TransactionRequest;
+use alloy::rpc::types::{BlockNumberOrTag, TransactionRequest};
 use alloy::signers::local::{LocalSigner, PrivateKeySigner};
 use alloy::transports::http::reqwest;
 use alloy::transports::{RpcError, TransportErrorKind};
@@ -48,6 +50,7 @@ pub struct Wallet {
     network: Network,
     transaction_config: TransactionConfig,
     lock: Arc<tokio::sync::Mutex<()>>,
+    nonce_manager: Option<NonceManager>,
 }
 
 impl Wallet {
@@ -58,6 +61,7 @@ impl Wallet {
             network,
             transaction_config: Default::default(),
             lock: Arc::new(tokio::sync::Mutex::new(())),
+            nonce_manager: None,
         }
     }
 
@@ -98,14 +102,11 @@ impl Wallet {
         to: Address,
         amount: U256,
     ) -> Result<TxHash, network_token::Error> {
-        transfer_tokens(
-            self.wallet.clone(),
-            &self.network,
-            to,
-            amount,
-            &self.transaction_config,
-        )
-        .await
+        let provider =
+            http_provider_with_wallet(self.network.rpc_url().clone(), self.wallet.clone());
+        self.network_token(provider)
+            .transfer(to, amount, &self.transaction_config)
+            .await
     }
 
     /// Transfer a raw amount of gas tokens to another address.
@@ -114,7 +115,14 @@ impl Wallet {
         to: Address,
         amount: U256,
     ) -> Result<TxHash, network_token::Error> {
-        transfer_gas_tokens(self.wallet.clone(), &self.network, to, amount).await
+        transfer_gas_tokens_with_nonce_manager(
+            self.wallet.clone(),
+            &self.network,
+            to,
+            amount,
+            self.nonce_manager.as_ref(),
+        )
+        .await
     }
 
     /// See how many tokens of the owner may be spent by the spender.
@@ -128,14 +136,11 @@ impl Wallet {
         spender: Address,
         amount: U256,
     ) -> Result<TxHash, network_token::Error> {
-        approve_to_spend_tokens(
-            self.wallet.clone(),
-            &self.network,
-            spender,
-            amount,
-            &self.transaction_config,
-        )
-        .await
+        let provider =
+            http_provider_with_wallet(self.network.rpc_url().clone(), self.wallet.clone());
+        self.network_token(provider)
+            .approve(spender, amount, &self.transaction_config)
+            .await
     }
 
     /// Function for batch payments of quotes. It accepts an iterator of QuotePayment and returns
@@ -144,15 +149,25 @@ impl Wallet {
         &self,
         quote_payments: I,
     ) -> Result<BTreeMap<QuoteHash, TxHash>, PayForQuotesError> {
-        pay_for_quotes(
+        pay_for_quotes_with_nonce_manager(
             self.wallet.clone(),
             &self.network,
             quote_payments,
             &self.transaction_config,
+            self.nonce_manager.as_ref(),
         )
         .await
     }
 
+    /// Estimates the transactions and gas `pay_for_quotes` would take to pay for the given
+    /// quotes, without sending anything.
+    pub async fn estimate_pay_for_quotes<I: IntoIterator<Item = QuotePayment>>(
+        &self,
+        quote_payments: I,
+    ) -> Result<PaymentFeeEstimate, Error> {
+        estimate_pay_for_quotes(self.address(), &self.network, quote_payments).await
+    }
+
     /// Build a provider using this wallet.
     pub fn to_provider(&self) -> ProviderWithWallet {
         http_provider_with_wallet(self.network.rpc_url().clone(), self.wallet.clone())
@@ -160,6 +175,8 @@ impl Wallet {
 
     /// Lock the wallet to prevent concurrent use.
     /// Drop the guard to unlock the wallet.
+    ///
+    /// A wallet with nonce management enabled does not need to be locked to pay concurrently.
     pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, ()> {
         self.lock.lock().await
     }
@@ -174,6 +191,29 @@ impl Wallet {
     pub fn set_transaction_config(&mut self, config: TransactionConfig) {
         self.transaction_config = config;
     }
+
+    /// Assigns the nonces of the transactions of this wallet and its clones locally, so payments
+    /// no longer wait on each other's confirmations. Every transaction of the wallet's address
+    /// must then be sent through them.
+    pub fn enable_nonce_management(&mut self) {
+        self.nonce_manager = Some(NonceManager::new(self.address()));
+    }
+
+    /// Returns the nonce manager of the wallet, if nonce management is enabled.
+    pub fn nonce_manager(&self) -> Option<&NonceManager> {
+        self.nonce_manager.as_ref()
+    }
+
+    fn network_token(
+        &self,
+        provider: ProviderWithWallet,
+    ) -> NetworkToken<ProviderWithWallet, Ethereum> {
+        let network_token = NetworkToken::new(*self.network.payment_token_address(), provider);
+        match &self.nonce_manager {
+            Some(nonce_manager) => network_token.with_nonce_manager(nonce_manager.clone()),
+            None => network_token,
+        }
+    }
 }
 
 /// Generate an EthereumWallet with a random private key.
@@ -212,7 +252,7 @@ fn http_provider_with_wallet(rpc_url: reqwest::Url, wallet: EthereumWallet) -> P
     ProviderBuilder::new()
         .with_simple_nonce_management()
         .wallet(wallet)
-        .connect_http(rpc_url)
+        .connect_client(rpc_client(rpc_url))
 }
 
 /// Returns the address of this wallet.
@@ -292,26 +332,51 @@ pub async fn transfer_gas_tokens(
     network: &Network,
     receiver: Address,
     amount: U256,
+) -> Result<TxHash, network_token::Error> {
+    transfer_gas_tokens_with_nonce_manager(wallet, network, receiver, amount, None).await
+}
+
+async fn transfer_gas_tokens_with_nonce_manager(
+    wallet: EthereumWallet,
+    network: &Network,
+    receiver: Address,
+    amount: U256,
+    nonce_manager: Option<&NonceManager>,
 ) -> Result<TxHash, network_token::Error> {
     debug!("Transferring {amount} gas tokens to {receiver}");
     let provider = http_provider_with_wallet(network.rpc_url().clone(), wallet);
-    let tx = TransactionRequest::default()
+    let mut tx = TransactionRequest::default()
         .with_to(receiver)
         .with_value(amount);
+    if let Some(nonce_manager) = nonce_manager {
+        tx.set_nonce(nonce_manager.next_nonce(&provider).await?);
+    }
 
-    let pending_tx_builder = provider
-        .send_transaction(tx)
-        .await
-        .inspect_err(|err| {
+    let pending_tx_builder = match provider.send_transaction(tx).await {
+        Ok(pending_tx_builder) => pending_tx_builder.with_timeout(Some(TX_TIMEOUT)),
+        Err(err) => {
             error!("Error to send_transaction during transfer_gas_tokens: {err}");
-        })?
-        .with_timeout(Some(TX_TIMEOUT));
+            if let Some(nonce_manager) = nonce_manager {
+                // The nonce may be left unused, so later transactions would wait on it.
+                nonce_manager.resync().await;
+            }
+            return Err(err.into());
+        }
+    };
     let pending_tx_hash = *pending_tx_builder.tx_hash();
     debug!("The transfer of gas tokens is pending with tx_hash: {pending_tx_hash}");
 
-    let tx_hash = pending_tx_builder.watch().await.inspect_err(|err| {
-        error!("Error watching transfer_gas_tokens tx with hash {pending_tx_hash}: {err}")
-    })?;
+    let tx_hash = match pending_tx_builder.watch().await {
+        Ok(tx_hash) => tx_hash,
+        Err(err) => {
+            error!("Error watching transfer_gas_tokens tx with hash {pending_tx_hash}: {err}");
+            if let Some(nonce_manager) = nonce_manager {
+                // The transaction may have been dropped, leaving its nonce unused.
+                nonce_manager.resync().await;
+            }
+            return Err(err.into());
+        }
+    };
     debug!("Transfer of gas tokens with tx_hash: {tx_hash} is successful");
 
     Ok(tx_hash)
@@ -328,6 +393,18 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
     network: &Network,
     payments: T,
     transaction_config: &TransactionConfig,
+) -> Result<BTreeMap<QuoteHash, TxHash>, PayForQuotesError> {
+    pay_for_quotes_with_nonce_manager(wallet, network, payments, transaction_config, None).await
+}
+
+/// Pays for quotes as [`pay_for_quotes`] does. With a nonce manager, the batch transactions are
+/// all sent before waiting for their confirmations.
+async fn pay_for_quotes_with_nonce_manager<T: IntoIterator<Item = QuotePayment>>(
+    wallet: EthereumWallet,
+    network: &Network,
+    payments: T,
+    transaction_config: &TransactionConfig,
+    nonce_manager: Option<&NonceManager>,
 ) -> Result<BTreeMap<QuoteHash, TxHash>, PayForQuotesError> {
     let payments: Vec<_> = payments.into_iter().collect();
     info!("Paying for quotes of len: {}", payments.len());
@@ -356,22 +433,27 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
     .await
     .map_err(|err| PayForQuotesError(Error::from(err), Default::default()))?;
 
+    let provider = http_provider_with_wallet(network.rpc_url().clone(), wallet);
+
     // TODO: Get rid of approvals altogether, by using permits or whatever..
     if allowance < total_amount_to_be_paid {
         // Approve the contract to spend all the client's tokens.
-        approve_to_spend_tokens(
-            wallet.clone(),
-            network,
-            *network.data_payments_address(),
-            U256::MAX,
-            transaction_config,
-        )
-        .await
-        .map_err(|err| PayForQuotesError(Error::from(err), Default::default()))?;
+        let mut network_token =
+            NetworkToken::new(*network.payment_token_address(), provider.clone());
+        if let Some(nonce_manager) = nonce_manager {
+            network_token = network_token.with_nonce_manager(nonce_manager.clone());
+        }
+        network_token
+            .approve(
+                *network.data_payments_address(),
+                U256::MAX,
+                transaction_config,
+            )
+            .await
+            .map_err(|err| PayForQuotesError(Error::from(err), Default::default()))?;
     }
 
-    let provider = http_provider_with_wallet(network.rpc_url().clone(), wallet);
-    let data_payments = PaymentVaultHandler::new(*network.data_payments_address(), provider);
+    let mut data_payments = PaymentVaultHandler::new(*network.data_payments_address(), provider);
 
     // remove payments with 0 amount as they don't need to be paid for
     let payment_for_batch: Vec<QuotePayment> = payments
@@ -384,6 +466,45 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
 
     let mut tx_hashes_by_quote = BTreeMap::new();
 
+    if let Some(nonce_manager) = nonce_manager {
+        data_payments = data_payments.with_nonce_manager(nonce_manager.clone());
+        let data_payments = &data_payments;
+
+        // The nonces are assigned locally, so the batches need not wait on each other.
+        let results = futures::future::join_all(chunks.map(|batch| async move {
+            debug!(
+                "Paying for batch of quotes of len: {}, {batch:?}",
+                batch.len()
+            );
+            let result = data_payments
+                .pay_for_quotes(batch.to_vec(), transaction_config)
+                .await;
+            (batch, result)
+        }))
+        .await;
+
+        let mut first_error = None;
+        for (batch, result) in results {
+            match result {
+                Ok(tx_hash) => {
+                    info!("Paid for batch of quotes with final tx hash: {tx_hash}");
+                    for (quote_hash, _, _) in batch {
+                        tx_hashes_by_quote.insert(*quote_hash, tx_hash);
+                    }
+                }
+                Err(err) => {
+                    error!("Failed to pay for batch of quotes: {err}");
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        return match first_error {
+            Some(err) => Err(PayForQuotesError(Error::from(err), tx_hashes_by_quote)),
+            None => Ok(tx_hashes_by_quote),
+        };
+    }
+
     for batch in chunks {
         let batch: Vec<QuotePayment> = batch.to_vec();
 
@@ -407,9 +528,129 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
     Ok(tx_hashes_by_quote)
 }
 
+/// Gas units assumed for an approval that cannot be simulated.
+const APPROVE_GAS_FALLBACK: u64 = 60_000;
+/// Gas units assumed for a batch payment that cannot be simulated, e.g. because it awaits the
+/// approval: a base cost plus a cost per transfer.
+const PAY_FOR_QUOTES_GAS_FALLBACK: u64 = 50_000;
+const PAY_FOR_QUOTES_GAS_PER_TRANSFER_FALLBACK: u64 = 40_000;
+
+/// The transactions and gas paying for quotes is estimated to take.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentFeeEstimate {
+    /// Whether the data payments contract must first be approved to spend the payer's tokens.
+    pub needs_approval: bool,
+    /// Number of transactions, the approval included.
+    pub transaction_count: usize,
+    /// Gas units the transactions are estimated to burn.
+    pub gas_units: u64,
+    /// Fee per gas unit in wei, the base fee of the latest block.
+    pub fee_per_gas: u128,
+    /// Whether every transaction was simulated. Transactions that could not be, e.g. payments
+    /// awaiting the approval, are estimated from typical gas usage instead.
+    pub simulated: bool,
+}
+
+impl PaymentFeeEstimate {
+    /// Returns the estimated fee in wei, paid in gas tokens.
+    pub fn fee(&self) -> U256 {
+        U256::from(self.gas_units) * U256::from(self.fee_per_gas)
+    }
+}
+
+/// Estimates the transactions and gas `pay_for_quotes` would take for `payer` to pay for the given
+/// quotes, by simulating them. Nothing is sent.
+pub async fn estimate_pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
+    payer: Address,
+    network: &Network,
+    payments: T,
+) -> Result<PaymentFeeEstimate, Error> {
+    // remove payments with 0 amount as they don't need to be paid for
+    let payments: Vec<QuotePayment> = payments
+        .into_iter()
+        .filter(|(_, _, amount)| *amount > Amount::ZERO)
+        .collect();
+    let total_amount_to_be_paid: Amount = payments.iter().map(|(_, _, amount)| amount).sum();
+
+    let provider = http_provider(network.rpc_url().clone());
+    let fee_per_gas = match provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await?
+        .and_then(|block| block.header.base_fee_per_gas)
+    {
+        Some(base_fee) => u128::from(base_fee),
+        None => provider.get_gas_price().await?,
+    };
+
+    let mut estimate = PaymentFeeEstimate {
+        needs_approval: false,
+        transaction_count: 0,
+        gas_units: 0,
+        fee_per_gas,
+        simulated: true,
+    };
+    if payments.is_empty() {
+        return Ok(estimate);
+    }
+
+    let estimate_gas = |(calldata, to): (Calldata, Address)| {
+        let provider = &provider;
+        async move {
+            let tx = TransactionRequest::default()
+                .with_from(payer)
+                .with_to(to)
+                .with_input(calldata);
+            provider.estimate_gas(tx).await.inspect_err(|err| {
+                debug!("Could not simulate a transaction to {to}: {err}");
+            })
+        }
+    };
+
+    let allowance = token_allowance(network, payer, *network.data_payments_address()).await?;
+    if allowance < total_amount_to_be_paid {
+        let network_token = NetworkToken::new(*network.payment_token_address(), &provider);
+        let approve = network_token.approve_calldata(*network.data_payments_address(), U256::MAX);
+        estimate.needs_approval = true;
+        estimate.transaction_count += 1;
+        estimate.gas_units += match estimate_gas(approve).await {
+            Ok(gas) => gas,
+            Err(_) => {
+                estimate.simulated = false;
+                APPROVE_GAS_FALLBACK
+            }
+        };
+    }
+
+    let data_payments = PaymentVaultHandler::new(*network.data_payments_address(), &provider);
+    for batch in payments.chunks(MAX_TRANSFERS_PER_TRANSACTION) {
+        estimate.transaction_count += 1;
+        // Payments revert until the approval is in, so they are not simulated before.
+        let simulated = if estimate.needs_approval {
+            None
+        } else {
+            let pay_for_quotes = data_payments.pay_for_quotes_calldata(batch.to_vec())?;
+            estimate_gas(pay_for_quotes).await.ok()
+        };
+        estimate.gas_units += match simulated {
+            Some(gas) => gas,
+            None => {
+                estimate.simulated = false;
+                PAY_FOR_QUOTES_GAS_FALLBACK
+                    + PAY_FOR_QUOTES_GAS_PER_TRANSFER_FALLBACK * batch.len() as u64
+            }
+        };
+    }
+
+    debug!(
+        "Estimated paying for {} quotes: {estimate:?}",
+        payments.len()
+    );
+    Ok(estimate)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::common::Amount;
+    use crate::common::{Address, Amount, QuoteHash, U256};
     use crate::testnet::Testnet;
     use crate::wallet::{Wallet, from_private_key};
     use alloy::network::{Ethereum, EthereumWallet, NetworkWallet};
@@ -451,4 +692,45 @@ mod tests {
 
         assert_eq!(final_balance, transfer_amount);
     }
+
+    #[tokio::test]
+    async fn test_estimate_pay_for_quotes() {
+        let testnet = Testnet::new().await;
+        let network = testnet.to_network();
+        let wallet =
+            Wallet::new_from_private_key(network.clone(), &testnet.default_wallet_private_key())
+                .unwrap();
+        let payments: Vec<_> = (0..3u8)
+            .map(|i| {
+                (
+                    QuoteHash::repeat_byte(i),
+                    Address::repeat_byte(i + 1),
+                    Amount::from(i),
+                )
+            })
+            .collect();
+
+        // The free quote is not paid for, and the payment awaits the approval.
+        let estimate = wallet
+            .estimate_pay_for_quotes(payments.clone())
+            .await
+            .unwrap();
+        assert!(estimate.needs_approval);
+        assert_eq!(estimate.transaction_count, 2);
+        assert!(!estimate.simulated);
+
+        let _ = wallet
+            .approve_to_spend_tokens(*network.data_payments_address(), U256::MAX)
+            .await
+            .unwrap();
+        let estimate = wallet.estimate_pay_for_quotes(payments).await.unwrap();
+        assert!(!estimate.needs_approval);
+        assert_eq!(estimate.transaction_count, 1);
+        assert!(estimate.simulated);
+        assert!(estimate.gas_units > 0);
+        assert_eq!(
+            estimate.fee(),
+            U256::from(estimate.gas_units) * U256::from(estimate.fee_per_gas)
+        );
+    }
 }
@@ -135,3 +135,59 @@ async fn test_pay_for_quotes_and_data_payment_verification() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_concurrent_pay_for_quotes_with_nonce_management() {
+    const PAYMENTS_PER_UPLOAD: usize = MAX_TRANSFERS_PER_TRANSACTION * 2 + 1;
+
+    let (_anvil, network, genesis_wallet) = local_testnet().await;
+    let mut wallet = funded_wallet(&network, genesis_wallet).await;
+    wallet.enable_nonce_management();
+
+    let first_upload: Vec<_> = (0..PAYMENTS_PER_UPLOAD)
+        .map(|_| random_quote_payment())
+        .collect();
+    let second_upload: Vec<_> = (0..PAYMENTS_PER_UPLOAD)
+        .map(|_| random_quote_payment())
+        .collect();
+
+    // Both uploads pay at once from clones of the wallet, sharing its nonces.
+    let other_wallet = wallet.clone();
+    let (first, second) = tokio::join!(
+        wallet.pay_for_quotes(first_upload.clone()),
+        other_wallet.pay_for_quotes(second_upload.clone()),
+    );
+    let first = first.expect("first upload paid");
+    let second = second.expect("second upload paid");
+
+    let unique_tx_hashes: HashSet<TxHash> =
+        first.values().chain(second.values()).cloned().collect();
+    assert_eq!(
+        unique_tx_hashes.len(),
+        2 * PAYMENTS_PER_UPLOAD.div_ceil(MAX_TRANSFERS_PER_TRANSACTION)
+    );
+
+    for (quote_hash, reward_addr, _) in first_upload.iter().chain(second_upload.iter()) {
+        let result = verify_data_payment(
+            &network,
+            vec![],
+            vec![(
+                *quote_hash,
+                QuotingMetrics {
+                    data_size: 0,
+                    data_type: 0,
+                    close_records_stored: 0,
+                    records_per_type: vec![],
+                    max_records: 0,
+                    received_payment_count: 0,
+                    live_time: 0,
+                    network_density: None,
+                    network_size: None,
+                },
+                *reward_addr,
+            )],
+        )
+        .await;
+        assert!(result.is_ok(), "Verification failed: {:?}", result.err());
+    }
+}
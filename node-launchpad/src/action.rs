@@ -8,6 +8,7 @@
 
 use crate::upnp::UpnpSupport;
 use crate::{
+    config::ThrottleSettings,
     connection_mode::ConnectionMode,
     mode::{InputMode, Scene},
     node_stats::NodeStats,
@@ -28,6 +29,8 @@ pub enum Action {
     StoreStorageDrive(PathBuf, String),
     StoreConnectionMode(ConnectionMode),
     StorePortRange(u32, u32),
+    StoreBandwidthLimits(Option<u64>, Option<u64>),
+    StoreThrottleSettings(ThrottleSettings),
     StoreRewardsAddress(String),
     StoreNodesToStart(usize),
 
@@ -122,6 +125,10 @@ pub enum StatusActions {
     TriggerRewardsAddress,
     TriggerNodeLogs,
     TriggerRemoveNode,
+    ShowNodeLogs {
+        service_name: String,
+        log_dir_path: PathBuf,
+    },
 
     PreviousTableItem,
     NextTableItem,
@@ -135,12 +142,16 @@ pub enum OptionsActions {
     TriggerChangeDrive,
     TriggerChangeConnectionMode,
     TriggerChangePortRange,
+    TriggerChangeBandwidthLimits,
+    TriggerChangeQuietHours,
     TriggerRewardsAddress,
     TriggerUpdateNodes,
     TriggerResetNodes,
     TriggerAccessLogs,
     UpdateConnectionMode(ConnectionMode),
     UpdatePortRange(u32, u32),
+    UpdateBandwidthLimits(Option<u64>, Option<u64>),
+    UpdateThrottleSettings(ThrottleSettings),
     UpdateRewardsAddress(String),
     UpdateStorageDrive(PathBuf, String),
 }
@@ -15,10 +15,11 @@ use crate::{
         help::Help,
         options::Options,
         popup::{
-            change_drive::ChangeDrivePopup, connection_mode::ChangeConnectionModePopUp,
-            manage_nodes::ManageNodes, port_range::PortRangePopUp, remove_node::RemoveNodePopUp,
-            reset_nodes::ResetNodesPopup, rewards_address::RewardsAddress,
-            upgrade_nodes::UpgradeNodesPopUp,
+            bandwidth_limits::BandwidthLimitsPopUp, change_drive::ChangeDrivePopup,
+            connection_mode::ChangeConnectionModePopUp, manage_nodes::ManageNodes,
+            node_logs::NodeLogsPopUp, port_range::PortRangePopUp, quiet_hours::QuietHoursPopUp,
+            remove_node::RemoveNodePopUp, reset_nodes::ResetNodesPopup,
+            rewards_address::RewardsAddress, upgrade_nodes::UpgradeNodesPopUp,
         },
         status::{Status, StatusConfig},
     },
@@ -58,6 +59,7 @@ impl App {
         antnode_path: Option<PathBuf>,
         app_data_path: Option<PathBuf>,
         network_id: Option<u8>,
+        evm_config: Option<PathBuf>,
     ) -> Result<Self> {
         // Configurations
         let app_data = AppData::load(app_data_path)?;
@@ -100,12 +102,16 @@ impl App {
             rewards_address: app_data.discord_username.clone(),
             init_peers_config,
             network_id,
+            evm_config,
             antnode_path,
             data_dir_path,
             connection_mode,
             upnp_support,
             port_from: Some(port_from),
             port_to: Some(port_to),
+            max_upload_rate: app_data.max_upload_rate,
+            max_download_rate: app_data.max_download_rate,
+            throttle_settings: app_data.throttle_settings.clone(),
             storage_mountpoint: storage_mountpoint.clone(),
         };
 
@@ -117,6 +123,9 @@ impl App {
             connection_mode,
             Some(port_from),
             Some(port_to),
+            app_data.max_upload_rate,
+            app_data.max_download_rate,
+            app_data.throttle_settings.clone(),
         )
         .await?;
         let help = Help::new().await?;
@@ -128,9 +137,13 @@ impl App {
             ChangeDrivePopup::new(storage_mountpoint.clone(), app_data.nodes_to_start)?;
         let change_connection_mode = ChangeConnectionModePopUp::new(connection_mode)?;
         let port_range = PortRangePopUp::new(connection_mode, port_from, port_to);
+        let bandwidth_limits =
+            BandwidthLimitsPopUp::new(app_data.max_upload_rate, app_data.max_download_rate);
+        let quiet_hours = QuietHoursPopUp::new(app_data.throttle_settings.clone());
         let rewards_address = RewardsAddress::new(app_data.discord_username.clone());
         let upgrade_nodes = UpgradeNodesPopUp::new();
         let remove_node = RemoveNodePopUp::default();
+        let node_logs = NodeLogsPopUp::default();
         let upgrade_launchpad_popup = UpgradeLaunchpadPopup::default();
 
         let components: Vec<Box<dyn Component>> = vec![
@@ -142,11 +155,14 @@ impl App {
             Box::new(change_drive),
             Box::new(change_connection_mode),
             Box::new(port_range),
+            Box::new(bandwidth_limits),
+            Box::new(quiet_hours),
             Box::new(rewards_address),
             Box::new(reset_nodes),
             Box::new(manage_nodes),
             Box::new(upgrade_nodes),
             Box::new(remove_node),
+            Box::new(node_logs),
             Box::new(upgrade_launchpad_popup),
         ];
 
@@ -160,6 +176,9 @@ impl App {
                 connection_mode: Some(connection_mode),
                 port_from: Some(port_from),
                 port_to: Some(port_to),
+                max_upload_rate: app_data.max_upload_rate,
+                max_download_rate: app_data.max_download_rate,
+                throttle_settings: app_data.throttle_settings,
             },
             tick_rate,
             frame_rate,
@@ -310,6 +329,19 @@ impl App {
                         self.app_data.port_to = Some(*to);
                         self.app_data.save(None)?;
                     }
+                    Action::StoreBandwidthLimits(ref upload, ref download) => {
+                        debug!("Storing bandwidth limits: {upload:?}, {download:?}");
+                        self.app_data.max_upload_rate = *upload;
+                        self.app_data.max_download_rate = *download;
+                        self.app_data.save(None)?;
+                    }
+                    Action::StoreThrottleSettings(ref throttle_settings) => {
+                        debug!("Storing throttle settings: {throttle_settings:?}");
+                        self.app_data
+                            .throttle_settings
+                            .clone_from(throttle_settings);
+                        self.app_data.save(None)?;
+                    }
                     Action::StoreRewardsAddress(ref rewards_address) => {
                         debug!("Storing rewards address: {rewards_address:?}");
                         self.app_data.discord_username.clone_from(rewards_address);
@@ -385,8 +417,16 @@ mod tests {
         let mut output = Cursor::new(Vec::new());
 
         // Create and run the App, capturing its output
-        let app_result =
-            App::new(60.0, 60.0, init_peers_config, None, Some(config_path), None).await;
+        let app_result = App::new(
+            60.0,
+            60.0,
+            init_peers_config,
+            None,
+            Some(config_path),
+            None,
+            None,
+        )
+        .await;
 
         match app_result {
             Ok(app) => {
@@ -453,6 +493,7 @@ mod tests {
             None,
             Some(test_app_data_path),
             None,
+            None,
         )
         .await;
 
@@ -515,6 +556,7 @@ mod tests {
             None,
             Some(non_existent_config_path),
             None,
+            None,
         )
         .await;
 
@@ -577,8 +619,16 @@ mod tests {
         let init_peers_config = InitialPeersConfig::default();
 
         // Create and run the App, capturing its output
-        let app_result =
-            App::new(60.0, 60.0, init_peers_config, None, Some(config_path), None).await;
+        let app_result = App::new(
+            60.0,
+            60.0,
+            init_peers_config,
+            None,
+            Some(config_path),
+            None,
+            None,
+        )
+        .await;
 
         // Could be that the mountpoint doesn't exists
         // or that the user doesn't have permissions to access it
@@ -625,6 +675,7 @@ mod tests {
             None,
             Some(test_app_data_path),
             None,
+            None,
         )
         .await;
 
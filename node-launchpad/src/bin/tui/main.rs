@@ -46,6 +46,13 @@ pub struct Cli {
     #[clap(long, verbatim_doc_comment)]
     network_id: Option<u8>,
 
+    /// Provide the path of an EVM network config file for the nodes to use.
+    ///
+    /// The file lists several RPC endpoints for the nodes to fail over between. Its network must
+    /// be the one of the network ID.
+    #[clap(long)]
+    evm_config: Option<PathBuf>,
+
     /// Frame rate, i.e. number of frames per second
     #[arg(short, long, value_name = "FLOAT", default_value_t = 60.0)]
     frame_rate: f64,
@@ -129,6 +136,7 @@ fn main() -> Result<()> {
             args.antnode_path,
             args.path,
             args.network_id,
+            args.evm_config,
         )
         .await?;
         app.run().await?;
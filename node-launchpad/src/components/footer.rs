@@ -81,7 +81,7 @@ impl StatefulWidget for Footer {
             Span::styled("Start/Stop Node", start_stop_text_style),
             Span::styled(" ", Style::default()),
             Span::styled("[L] ", open_logs_command_style),
-            Span::styled("Open Logs", open_logs_text_style),
+            Span::styled("View Logs", open_logs_text_style),
         ];
 
         let stop_all = vec![
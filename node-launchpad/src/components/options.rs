@@ -21,6 +21,7 @@ use super::{Component, header::SelectedMenuItem, utils::open_logs};
 use crate::{
     action::{Action, OptionsActions},
     components::header::Header,
+    config::ThrottleSettings,
     connection_mode::ConnectionMode,
     mode::{InputMode, Scene},
     style::{
@@ -37,11 +38,15 @@ pub struct Options {
     pub port_edit: bool,
     pub port_from: Option<u32>,
     pub port_to: Option<u32>,
+    pub max_upload_rate: Option<u64>,
+    pub max_download_rate: Option<u64>,
+    pub throttle_settings: ThrottleSettings,
     pub active: bool,
     pub action_tx: Option<UnboundedSender<Action>>,
 }
 
 impl Options {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         storage_mountpoint: PathBuf,
         storage_drive: String,
@@ -49,6 +54,9 @@ impl Options {
         connection_mode: ConnectionMode,
         port_from: Option<u32>,
         port_to: Option<u32>,
+        max_upload_rate: Option<u64>,
+        max_download_rate: Option<u64>,
+        throttle_settings: ThrottleSettings,
     ) -> Result<Self> {
         Ok(Self {
             storage_mountpoint,
@@ -58,6 +66,9 @@ impl Options {
             port_edit: false,
             port_from,
             port_to,
+            max_upload_rate,
+            max_download_rate,
+            throttle_settings,
             active: false,
             action_tx: None,
         })
@@ -75,7 +86,7 @@ impl Component for Options {
             .constraints(
                 [
                     Constraint::Length(1),
-                    Constraint::Length(5),
+                    Constraint::Length(7),
                     Constraint::Length(3),
                     Constraint::Length(3),
                     Constraint::Length(4),
@@ -92,6 +103,10 @@ impl Component for Options {
         // Storage Drive
         let port_legend = " Edit Port Range ";
         let port_key = " [Ctrl+P] ";
+        let bandwidth_legend = " Edit Limits ";
+        let bandwidth_key = " [Ctrl+T] ";
+        let quiet_hours_legend = " Edit Hours ";
+        let quiet_hours_key = " [Ctrl+Q] ";
         let block1 = Block::default()
             .title(" Device Options ")
             .title_style(Style::default().bold().fg(GHOST_WHITE))
@@ -183,6 +198,71 @@ impl Component for Options {
                         .alignment(Alignment::Right),
                     ),
                 ]),
+                Row::new(vec![
+                    Cell::from(
+                        Line::from(vec![Span::styled(
+                            " Bandwidth Limit: ",
+                            Style::default().fg(LIGHT_PERIWINKLE),
+                        )])
+                        .alignment(Alignment::Left),
+                    ),
+                    Cell::from(
+                        Line::from(vec![if self.max_upload_rate.is_none()
+                            && self.max_download_rate.is_none()
+                        {
+                            Span::styled(" Unlimited ", Style::default().fg(COOL_GREY))
+                        } else {
+                            Span::styled(
+                                format!(
+                                    " Up {} / Down {} ",
+                                    format_rate(self.max_upload_rate),
+                                    format_rate(self.max_download_rate)
+                                ),
+                                Style::default().fg(VIVID_SKY_BLUE),
+                            )
+                        }])
+                        .alignment(Alignment::Left),
+                    ),
+                    Cell::from(
+                        Line::from(vec![
+                            Span::styled(bandwidth_legend, Style::default().fg(VERY_LIGHT_AZURE)),
+                            Span::styled(bandwidth_key, Style::default().fg(GHOST_WHITE)),
+                        ])
+                        .alignment(Alignment::Right),
+                    ),
+                ]),
+                Row::new(vec![
+                    Cell::from(
+                        Line::from(vec![Span::styled(
+                            " Quiet Hours: ",
+                            Style::default().fg(LIGHT_PERIWINKLE),
+                        )])
+                        .alignment(Alignment::Left),
+                    ),
+                    Cell::from(
+                        Line::from(vec![match &self.throttle_settings.quiet_hours {
+                            _ if self.throttle_settings.is_disabled() => {
+                                Span::styled(" Off ", Style::default().fg(COOL_GREY))
+                            }
+                            Some(quiet_hours) => Span::styled(
+                                format!(" {quiet_hours} "),
+                                Style::default().fg(VIVID_SKY_BLUE),
+                            ),
+                            None => Span::styled(
+                                " Only when busy ",
+                                Style::default().fg(VIVID_SKY_BLUE),
+                            ),
+                        }])
+                        .alignment(Alignment::Left),
+                    ),
+                    Cell::from(
+                        Line::from(vec![
+                            Span::styled(quiet_hours_legend, Style::default().fg(VERY_LIGHT_AZURE)),
+                            Span::styled(quiet_hours_key, Style::default().fg(GHOST_WHITE)),
+                        ])
+                        .alignment(Alignment::Right),
+                    ),
+                ]),
             ],
             &[
                 Constraint::Length(18),
@@ -379,6 +459,8 @@ impl Component for Options {
                 | Scene::ChangeDrivePopUp
                 | Scene::ChangeConnectionModePopUp
                 | Scene::ChangePortsPopUp { .. }
+                | Scene::ChangeBandwidthLimitsPopUp
+                | Scene::ChangeQuietHoursPopUp
                 | Scene::OptionsRewardsAddressPopUp
                 | Scene::ResetNodesPopUp
                 | Scene::UpgradeNodesPopUp => {
@@ -411,6 +493,19 @@ impl Component for Options {
                     self.port_from = Some(from);
                     self.port_to = Some(to);
                 }
+                OptionsActions::TriggerChangeBandwidthLimits => {
+                    return Ok(Some(Action::SwitchScene(Scene::ChangeBandwidthLimitsPopUp)));
+                }
+                OptionsActions::UpdateBandwidthLimits(upload, download) => {
+                    self.max_upload_rate = upload;
+                    self.max_download_rate = download;
+                }
+                OptionsActions::TriggerChangeQuietHours => {
+                    return Ok(Some(Action::SwitchScene(Scene::ChangeQuietHoursPopUp)));
+                }
+                OptionsActions::UpdateThrottleSettings(throttle_settings) => {
+                    self.throttle_settings = throttle_settings;
+                }
                 OptionsActions::TriggerRewardsAddress => {
                     return Ok(Some(Action::SwitchScene(Scene::OptionsRewardsAddressPopUp)));
                 }
@@ -433,3 +528,11 @@ impl Component for Options {
         Ok(None)
     }
 }
+
+/// Formats a rate limit in KiB/s for display.
+fn format_rate(rate: Option<u64>) -> String {
+    match rate {
+        Some(rate) => format!("{rate} KiB/s"),
+        None => "unlimited".to_string(),
+    }
+}
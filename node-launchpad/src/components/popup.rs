@@ -6,10 +6,13 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+pub mod bandwidth_limits;
 pub mod change_drive;
 pub mod connection_mode;
 pub mod manage_nodes;
+pub mod node_logs;
 pub mod port_range;
+pub mod quiet_hours;
 pub mod remove_node;
 pub mod reset_nodes;
 pub mod rewards_address;
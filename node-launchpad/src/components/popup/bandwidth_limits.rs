@@ -0,0 +1,440 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::rc::Rc;
+
+use super::super::Component;
+use super::super::utils::centered_rect_fixed;
+use crate::{
+    action::{Action, OptionsActions},
+    mode::{InputMode, Scene},
+    style::{EUCALYPTUS, GHOST_WHITE, INDIGO, LIGHT_PERIWINKLE, RED, VIVID_SKY_BLUE, clear_area},
+};
+use color_eyre::Result;
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tui_input::{Input, backend::crossterm::EventHandler};
+
+const INPUT_SIZE: u16 = 9;
+const INPUT_AREA: u16 = INPUT_SIZE + 2; // +2 for the left and right padding
+
+#[derive(Default)]
+enum BandwidthLimitsState {
+    #[default]
+    Selection,
+    ConfirmChange,
+}
+
+#[derive(Default, PartialEq)]
+enum Field {
+    #[default]
+    Upload,
+    Download,
+}
+
+pub struct BandwidthLimitsPopUp {
+    active: bool,
+    state: BandwidthLimitsState,
+    focus: Field,
+    upload: Input,
+    download: Input,
+    upload_old_value: String,
+    download_old_value: String,
+    can_save: bool,
+}
+
+/// Parses the value of an input field. An empty field means no limit.
+fn parse_limit(value: &str) -> Result<Option<u64>, ()> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<u64>() {
+        Ok(limit) if limit > 0 => Ok(Some(limit)),
+        _ => Err(()),
+    }
+}
+
+fn limit_to_string(limit: Option<u64>) -> String {
+    limit.map(|limit| limit.to_string()).unwrap_or_default()
+}
+
+impl BandwidthLimitsPopUp {
+    pub fn new(max_upload_rate: Option<u64>, max_download_rate: Option<u64>) -> Self {
+        Self {
+            active: false,
+            state: BandwidthLimitsState::Selection,
+            focus: Field::Upload,
+            upload: Input::default().with_value(limit_to_string(max_upload_rate)),
+            download: Input::default().with_value(limit_to_string(max_download_rate)),
+            upload_old_value: Default::default(),
+            download_old_value: Default::default(),
+            can_save: false,
+        }
+    }
+
+    pub fn validate(&mut self) {
+        self.can_save =
+            parse_limit(self.upload.value()).is_ok() && parse_limit(self.download.value()).is_ok();
+    }
+
+    fn limits(&self) -> (Option<u64>, Option<u64>) {
+        (
+            parse_limit(self.upload.value()).unwrap_or_default(),
+            parse_limit(self.download.value()).unwrap_or_default(),
+        )
+    }
+
+    fn focused_input(&mut self) -> &mut Input {
+        match self.focus {
+            Field::Upload => &mut self.upload,
+            Field::Download => &mut self.download,
+        }
+    }
+
+    fn input_line(&self, label: &str, input: &Input, focused: bool) -> Line<'_> {
+        let spaces = " ".repeat((INPUT_AREA - 1) as usize - input.value().len());
+        let valid = parse_limit(input.value()).is_ok();
+        let mut style = Style::default()
+            .fg(if valid { VIVID_SKY_BLUE } else { RED })
+            .bg(INDIGO);
+        if focused {
+            style = style.underlined();
+        }
+        Line::from(vec![
+            Span::styled(format!("{label:>10} "), Style::default().fg(GHOST_WHITE)),
+            Span::styled(format!("{}{} ", spaces, input.value()), style),
+            Span::styled(" KiB/s", Style::default().fg(LIGHT_PERIWINKLE)),
+        ])
+        .alignment(Alignment::Center)
+    }
+
+    // -- Draw functions --
+
+    // Draws the limits selection screen
+    fn draw_selection_state(
+        &mut self,
+        f: &mut crate::tui::Frame<'_>,
+        layer_zero: Rect,
+        layer_one: Rc<[Rect]>,
+    ) -> Paragraph<'_> {
+        // layer zero
+        let pop_up_border = Paragraph::new("").block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Bandwidth Limits ")
+                .bold()
+                .title_style(Style::new().fg(VIVID_SKY_BLUE))
+                .padding(Padding::uniform(2))
+                .border_style(Style::new().fg(VIVID_SKY_BLUE)),
+        );
+        clear_area(f, layer_zero);
+
+        // split into 5 parts, for the prompt, inputs, text, dash and buttons
+        let layer_two = Layout::new(
+            Direction::Vertical,
+            [
+                // for the prompt text
+                Constraint::Length(2),
+                // for the inputs
+                Constraint::Length(3),
+                // for the text
+                Constraint::Length(3),
+                // gap
+                Constraint::Length(3),
+                // for the buttons
+                Constraint::Length(1),
+            ],
+        )
+        .split(layer_one[1]);
+
+        let prompt = Paragraph::new("Limit the bandwidth of each node")
+            .bold()
+            .alignment(Alignment::Center);
+        f.render_widget(prompt.fg(GHOST_WHITE), layer_two[0]);
+
+        let inputs = Paragraph::new(vec![
+            self.input_line("Upload", &self.upload, self.focus == Field::Upload),
+            Line::raw(""),
+            self.input_line("Download", &self.download, self.focus == Field::Download),
+        ]);
+        f.render_widget(inputs, layer_two[1]);
+
+        let text = Paragraph::new(vec![
+            Line::from(Span::styled(
+                "Leave a field empty for no limit.",
+                Style::default().fg(LIGHT_PERIWINKLE),
+            )),
+            Line::from(Span::styled(
+                "Switch between the fields with [Tab].",
+                Style::default().fg(LIGHT_PERIWINKLE),
+            )),
+        ])
+        .block(block::Block::default().padding(Padding::horizontal(2)))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+        f.render_widget(text.fg(GHOST_WHITE), layer_two[2]);
+
+        let dash = Block::new()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::new().fg(GHOST_WHITE));
+        f.render_widget(dash, layer_two[3]);
+
+        let buttons_layer =
+            Layout::horizontal(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(layer_two[4]);
+
+        let button_no = Line::from(vec![Span::styled(
+            "  Cancel [Esc]",
+            Style::default().fg(LIGHT_PERIWINKLE),
+        )]);
+        let button_yes_style = if self.can_save {
+            Style::default().fg(EUCALYPTUS)
+        } else {
+            Style::default().fg(LIGHT_PERIWINKLE)
+        };
+        f.render_widget(button_no, buttons_layer[0]);
+
+        let button_yes = Line::from(vec![
+            Span::styled("Save Limits ", button_yes_style),
+            Span::styled("[Enter]", Style::default().fg(GHOST_WHITE)),
+        ]);
+        f.render_widget(button_yes, buttons_layer[1]);
+
+        pop_up_border
+    }
+
+    // Draws Confirmation screen
+    fn draw_confirm_and_reset(
+        &mut self,
+        f: &mut crate::tui::Frame<'_>,
+        layer_zero: Rect,
+        layer_one: Rc<[Rect]>,
+    ) -> Paragraph<'_> {
+        // layer zero
+        let pop_up_border = Paragraph::new("").block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Confirm & Reset ")
+                .bold()
+                .title_style(Style::new().fg(VIVID_SKY_BLUE))
+                .padding(Padding::uniform(2))
+                .border_style(Style::new().fg(VIVID_SKY_BLUE)),
+        );
+        clear_area(f, layer_zero);
+
+        // split into 3 parts, paragraph, dash, buttons
+        let layer_two = Layout::new(
+            Direction::Vertical,
+            [
+                // for the text
+                Constraint::Length(8),
+                // gap
+                Constraint::Length(3),
+                // for the buttons
+                Constraint::Length(1),
+            ],
+        )
+        .split(layer_one[1]);
+
+        let paragraph_text = Paragraph::new(vec![
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(vec![
+                Span::styled(
+                    "Changing bandwidth limits will ",
+                    Style::default().fg(LIGHT_PERIWINKLE),
+                ),
+                Span::styled("reset all nodes.", Style::default().fg(GHOST_WHITE)),
+            ]),
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(vec![
+                Span::styled("You’ll need to ", Style::default().fg(LIGHT_PERIWINKLE)),
+                Span::styled("Add", Style::default().fg(GHOST_WHITE)),
+                Span::styled(" and ", Style::default().fg(LIGHT_PERIWINKLE)),
+                Span::styled("Start", Style::default().fg(GHOST_WHITE)),
+                Span::styled(
+                    " them again afterwards. Are you sure you want to continue?",
+                    Style::default().fg(LIGHT_PERIWINKLE),
+                ),
+            ]),
+        ])
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true })
+        .block(block::Block::default().padding(Padding::horizontal(2)));
+
+        f.render_widget(paragraph_text, layer_two[0]);
+
+        let dash = Block::new()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::new().fg(GHOST_WHITE));
+        f.render_widget(dash, layer_two[1]);
+
+        let buttons_layer =
+            Layout::horizontal(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(layer_two[2]);
+
+        let button_no = Line::from(vec![Span::styled(
+            "  Cancel [Esc]",
+            Style::default().fg(LIGHT_PERIWINKLE),
+        )]);
+        f.render_widget(button_no, buttons_layer[0]);
+
+        let button_yes = Line::from(vec![
+            Span::styled("Yes, Change Limits ", Style::default().fg(EUCALYPTUS)),
+            Span::styled("[Enter]", Style::default().fg(GHOST_WHITE)),
+        ]);
+        f.render_widget(button_yes, buttons_layer[1]);
+
+        pop_up_border
+    }
+}
+
+impl Component for BandwidthLimitsPopUp {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Vec<Action>> {
+        if !self.active {
+            return Ok(vec![]);
+        }
+        // while in entry mode, keybinds are not captured, so gotta exit entry mode from here
+        let send_back: Vec<Action> = match &self.state {
+            BandwidthLimitsState::Selection => match key.code {
+                KeyCode::Enter => {
+                    if !self.can_save {
+                        debug!("Got Enter, but the limits are not valid, ignoring.");
+                        return Ok(vec![]);
+                    }
+                    if self.upload.value() == self.upload_old_value
+                        && self.download.value() == self.download_old_value
+                    {
+                        debug!("Got Enter, but the limits are unchanged, switching to Options");
+                        return Ok(vec![Action::SwitchScene(Scene::Options)]);
+                    }
+                    self.state = BandwidthLimitsState::ConfirmChange;
+                    vec![]
+                }
+                KeyCode::Esc => {
+                    debug!("Got Esc, restoring the old values and switching to actual screen");
+                    self.upload = self
+                        .upload
+                        .clone()
+                        .with_value(self.upload_old_value.clone());
+                    self.download = self
+                        .download
+                        .clone()
+                        .with_value(self.download_old_value.clone());
+                    vec![Action::SwitchScene(Scene::Options)]
+                }
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                    self.focus = match self.focus {
+                        Field::Upload => Field::Download,
+                        Field::Download => Field::Upload,
+                    };
+                    vec![]
+                }
+                KeyCode::Char(c) if !c.is_numeric() => vec![],
+                KeyCode::Backspace => {
+                    self.focused_input().handle_event(&Event::Key(key));
+                    self.validate();
+                    vec![]
+                }
+                _ => {
+                    // if max limit reached, we should not allow any more inputs.
+                    if self.focused_input().value().len() < INPUT_SIZE as usize {
+                        self.focused_input().handle_event(&Event::Key(key));
+                    }
+                    self.validate();
+                    vec![]
+                }
+            },
+            BandwidthLimitsState::ConfirmChange => match key.code {
+                KeyCode::Enter => {
+                    debug!("Got Enter, saving the bandwidth limits and switching to Status Screen");
+                    self.state = BandwidthLimitsState::Selection;
+                    let (upload, download) = self.limits();
+                    vec![
+                        Action::StoreBandwidthLimits(upload, download),
+                        Action::OptionsActions(OptionsActions::UpdateBandwidthLimits(
+                            upload, download,
+                        )),
+                        Action::SwitchScene(Scene::Status),
+                    ]
+                }
+                KeyCode::Esc => {
+                    self.state = BandwidthLimitsState::Selection;
+                    self.upload = self
+                        .upload
+                        .clone()
+                        .with_value(self.upload_old_value.clone());
+                    self.download = self
+                        .download
+                        .clone()
+                        .with_value(self.download_old_value.clone());
+                    vec![Action::SwitchScene(Scene::Options)]
+                }
+                _ => vec![],
+            },
+        };
+        Ok(send_back)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        let send_back = match action {
+            Action::SwitchScene(scene) => match scene {
+                Scene::ChangeBandwidthLimitsPopUp => {
+                    self.active = true;
+                    self.focus = Field::Upload;
+                    self.upload_old_value = self.upload.value().to_string();
+                    self.download_old_value = self.download.value().to_string();
+                    self.validate();
+                    // Set to InputMode::Entry as we want to handle everything within our handle_key_events
+                    // so by default if this scene is active, we capture inputs.
+                    Some(Action::SwitchInputMode(InputMode::Entry))
+                }
+                _ => {
+                    self.active = false;
+                    None
+                }
+            },
+            _ => None,
+        };
+        Ok(send_back)
+    }
+
+    fn draw(&mut self, f: &mut crate::tui::Frame<'_>, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let layer_zero = centered_rect_fixed(52, 15, area);
+
+        let layer_one = Layout::new(
+            Direction::Vertical,
+            [
+                // for the pop_up_border
+                Constraint::Length(2),
+                // for the input field
+                Constraint::Min(1),
+                // for the pop_up_border
+                Constraint::Length(1),
+            ],
+        )
+        .split(layer_zero);
+
+        let pop_up_border: Paragraph = match self.state {
+            BandwidthLimitsState::Selection => self.draw_selection_state(f, layer_zero, layer_one),
+            BandwidthLimitsState::ConfirmChange => {
+                self.draw_confirm_and_reset(f, layer_zero, layer_one)
+            }
+        };
+        // We render now so the borders are on top of the other widgets
+        f.render_widget(pop_up_border, layer_zero);
+
+        Ok(())
+    }
+}
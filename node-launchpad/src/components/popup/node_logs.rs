@@ -0,0 +1,336 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use super::super::Component;
+use super::super::utils::{centered_rect, open_logs};
+use crate::{
+    action::{Action, StatusActions},
+    mode::{InputMode, Scene},
+    style::{
+        COOL_GREY, EUCALYPTUS, GHOST_WHITE, LIGHT_PERIWINKLE, RED, VIVID_SKY_BLUE, clear_area,
+    },
+};
+use ant_node_manager::logs::{LogEntry, LogFilter, NodeLogReader};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::Level;
+
+/// The number of entries kept in the viewer.
+const MAX_ENTRIES: usize = 1000;
+const PAGE_SIZE: usize = 10;
+/// How often new log entries are read while the logs are shown.
+const READ_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The entries read in the background, or why the logs could not be read.
+type LogUpdate = std::result::Result<Vec<LogEntry>, String>;
+
+/// Shows the most recent log entries of a node and follows them as they are written.
+#[derive(Default)]
+pub struct NodeLogsPopUp {
+    /// Whether the component is active right now, capturing keystrokes + draw things.
+    active: bool,
+    service_name: String,
+    /// The entries read by the background task following the logs, which stops once this is
+    /// dropped.
+    updates: Option<UnboundedReceiver<LogUpdate>>,
+    entries: VecDeque<LogEntry>,
+    /// How many lines the view is scrolled up from the most recent entry. At zero, new entries
+    /// are followed.
+    scroll: usize,
+    error: Option<String>,
+}
+
+impl NodeLogsPopUp {
+    fn open(&mut self, service_name: String, log_dir_path: PathBuf) {
+        debug!("Opening the logs of {service_name} at {log_dir_path:?}");
+        self.service_name = service_name.clone();
+        self.entries.clear();
+        self.scroll = 0;
+        self.error = None;
+
+        // The logs, archives included, are read off the UI thread.
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.updates = Some(rx);
+        tokio::spawn(follow_logs(service_name, log_dir_path, tx));
+    }
+
+    fn read_new_entries(&mut self) {
+        let Some(updates) = self.updates.as_mut() else {
+            return;
+        };
+        while let Ok(update) = updates.try_recv() {
+            match update {
+                Ok(entries) => {
+                    if self.scroll > 0 {
+                        // Keep the same lines in view while scrolled up.
+                        self.scroll += entries
+                            .iter()
+                            .map(|entry| entry.message.lines().count().max(1))
+                            .sum::<usize>();
+                    }
+                    self.entries.extend(entries);
+                    while self.entries.len() > MAX_ENTRIES {
+                        let _ = self.entries.pop_front();
+                    }
+                }
+                Err(err) => self.error = Some(err),
+            }
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for entry in self.entries.iter() {
+            let time = entry
+                .timestamp
+                .map(|timestamp| timestamp.format("%H:%M:%S").to_string())
+                .unwrap_or_else(|| " ".repeat(8));
+            let (level, level_style) = match entry.level {
+                Some(Level::ERROR) => ("ERROR", Style::default().fg(RED)),
+                Some(Level::WARN) => ("WARN ", Style::default().fg(VIVID_SKY_BLUE)),
+                Some(Level::INFO) => ("INFO ", Style::default().fg(EUCALYPTUS)),
+                Some(Level::DEBUG) => ("DEBUG", Style::default().fg(COOL_GREY)),
+                Some(Level::TRACE) => ("TRACE", Style::default().fg(COOL_GREY)),
+                None => ("     ", Style::default()),
+            };
+
+            for (index, message) in entry.message.lines().enumerate() {
+                let prefix = if index == 0 {
+                    vec![
+                        Span::styled(format!("{time} "), Style::default().fg(COOL_GREY)),
+                        Span::styled(format!("{level} "), level_style),
+                    ]
+                } else {
+                    vec![Span::raw(" ".repeat(15))]
+                };
+                let mut spans = prefix;
+                spans.push(Span::styled(
+                    message.to_string(),
+                    Style::default().fg(LIGHT_PERIWINKLE),
+                ));
+                lines.push(Line::from(spans));
+            }
+        }
+        lines
+    }
+}
+
+/// Reads the most recent entries of the logs, then the new ones as they are written, until the
+/// receiver is dropped.
+async fn follow_logs(service_name: String, log_dir_path: PathBuf, tx: UnboundedSender<LogUpdate>) {
+    let opened = {
+        let service_name = service_name.clone();
+        tokio::task::spawn_blocking(move || open_reader(&service_name, &log_dir_path)).await
+    };
+    let mut reader = match opened {
+        Ok(Ok((reader, history))) => {
+            if tx.send(Ok(history)).is_err() {
+                return;
+            }
+            reader
+        }
+        Ok(Err(err)) => {
+            error!("Failed to read the logs of {service_name}: {err:?}");
+            let _ = tx.send(Err(format!("Failed to read the logs: {err}")));
+            return;
+        }
+        Err(err) => {
+            error!("Failed to read the logs of {service_name}: {err:?}");
+            return;
+        }
+    };
+
+    loop {
+        tokio::time::sleep(READ_INTERVAL).await;
+        if tx.is_closed() {
+            debug!("Stopped following the logs of {service_name}");
+            return;
+        }
+        let read = tokio::task::spawn_blocking(move || {
+            let result = reader.read_new(&LogFilter::default());
+            (reader, result)
+        })
+        .await;
+        let result = match read {
+            Ok((returned_reader, result)) => {
+                reader = returned_reader;
+                result
+            }
+            Err(err) => {
+                error!("Failed to read new logs of {service_name}: {err:?}");
+                return;
+            }
+        };
+        match result {
+            Ok(entries) if entries.is_empty() => {}
+            Ok(entries) => {
+                if tx.send(Ok(entries)).is_err() {
+                    return;
+                }
+            }
+            Err(err) => {
+                error!("Failed to read new logs of {service_name}: {err:?}");
+            }
+        }
+    }
+}
+
+fn open_reader(service_name: &str, log_dir_path: &Path) -> Result<(NodeLogReader, Vec<LogEntry>)> {
+    let reader = NodeLogReader::new(service_name, log_dir_path)?;
+    let history = reader.read_history(&LogFilter::default(), Some(MAX_ENTRIES))?;
+    Ok((reader, history))
+}
+
+impl Component for NodeLogsPopUp {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Vec<Action>> {
+        if !self.active {
+            return Ok(vec![]);
+        }
+        let send_back = match key.code {
+            KeyCode::Esc => {
+                debug!("Got Esc, closing the node logs.");
+                self.updates = None;
+                self.entries.clear();
+                vec![Action::SwitchScene(Scene::Status)]
+            }
+            KeyCode::Up => {
+                self.scroll += 1;
+                vec![]
+            }
+            KeyCode::Down => {
+                self.scroll = self.scroll.saturating_sub(1);
+                vec![]
+            }
+            KeyCode::PageUp => {
+                self.scroll += PAGE_SIZE;
+                vec![]
+            }
+            KeyCode::PageDown => {
+                self.scroll = self.scroll.saturating_sub(PAGE_SIZE);
+                vec![]
+            }
+            KeyCode::End => {
+                self.scroll = 0;
+                vec![]
+            }
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                open_logs(Some(self.service_name.clone()))?;
+                vec![]
+            }
+            _ => vec![],
+        };
+        Ok(send_back)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        let send_back = match action {
+            Action::StatusActions(StatusActions::ShowNodeLogs {
+                service_name,
+                log_dir_path,
+            }) => {
+                self.open(service_name, log_dir_path);
+                Some(Action::SwitchScene(Scene::NodeLogsPopUp))
+            }
+            Action::Tick if self.active => {
+                self.read_new_entries();
+                None
+            }
+            Action::SwitchScene(scene) => match scene {
+                Scene::NodeLogsPopUp => {
+                    self.active = true;
+                    Some(Action::SwitchInputMode(InputMode::Entry))
+                }
+                _ => {
+                    self.active = false;
+                    None
+                }
+            },
+            _ => None,
+        };
+        Ok(send_back)
+    }
+
+    fn draw(&mut self, f: &mut crate::tui::Frame<'_>, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let layer_zero = centered_rect(90, 80, area);
+        clear_area(f, layer_zero);
+
+        let pop_up_border = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Logs of {} ", self.service_name))
+            .bold()
+            .title_style(Style::new().fg(VIVID_SKY_BLUE))
+            .padding(Padding::horizontal(1))
+            .border_style(Style::new().fg(VIVID_SKY_BLUE));
+        let inner = pop_up_border.inner(layer_zero);
+        f.render_widget(pop_up_border, layer_zero);
+
+        let layer_one = Layout::new(
+            Direction::Vertical,
+            [
+                // for the log lines
+                Constraint::Min(1),
+                // for the key hints
+                Constraint::Length(1),
+            ],
+        )
+        .split(inner);
+
+        let lines = if let Some(error) = &self.error {
+            vec![Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(RED),
+            ))]
+        } else if self.entries.is_empty() {
+            vec![Line::from(Span::styled(
+                "No log entries yet.",
+                Style::default().fg(LIGHT_PERIWINKLE),
+            ))]
+        } else {
+            self.lines()
+        };
+
+        // Show the most recent lines that fit, moved up by the scroll.
+        let height = layer_one[0].height as usize;
+        let max_scroll = lines.len().saturating_sub(height);
+        self.scroll = self.scroll.min(max_scroll);
+        let offset = max_scroll - self.scroll;
+        let logs = Paragraph::new(lines).scroll((offset as u16, 0));
+        f.render_widget(logs, layer_one[0]);
+
+        let following = if self.scroll == 0 {
+            Span::styled("Following  ", Style::default().fg(EUCALYPTUS))
+        } else {
+            Span::styled("Paused  ", Style::default().fg(COOL_GREY))
+        };
+        let hints = Line::from(vec![
+            following,
+            Span::styled("[↑↓/PgUp/PgDn] ", Style::default().fg(GHOST_WHITE)),
+            Span::styled("Scroll  ", Style::default().fg(LIGHT_PERIWINKLE)),
+            Span::styled("[End] ", Style::default().fg(GHOST_WHITE)),
+            Span::styled("Follow  ", Style::default().fg(LIGHT_PERIWINKLE)),
+            Span::styled("[O] ", Style::default().fg(GHOST_WHITE)),
+            Span::styled("Open Folder  ", Style::default().fg(LIGHT_PERIWINKLE)),
+            Span::styled("[Esc] ", Style::default().fg(GHOST_WHITE)),
+            Span::styled("Close", Style::default().fg(LIGHT_PERIWINKLE)),
+        ]);
+        f.render_widget(Paragraph::new(hints), layer_one[1]);
+
+        Ok(())
+    }
+}
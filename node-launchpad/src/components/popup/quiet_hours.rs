@@ -0,0 +1,530 @@
+// Copyright 2025 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::rc::Rc;
+
+use super::super::Component;
+use super::super::utils::centered_rect_fixed;
+use crate::{
+    action::{Action, OptionsActions},
+    config::ThrottleSettings,
+    mode::{InputMode, Scene},
+    style::{EUCALYPTUS, GHOST_WHITE, INDIGO, LIGHT_PERIWINKLE, RED, VIVID_SKY_BLUE, clear_area},
+};
+use ant_node_manager::add_services::config::parse_quiet_hours;
+use color_eyre::Result;
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use tui_input::{Input, backend::crossterm::EventHandler};
+
+const HOURS_INPUT_SIZE: usize = 23;
+const NUMBER_INPUT_SIZE: usize = 9;
+
+#[derive(Default)]
+enum QuietHoursState {
+    #[default]
+    Selection,
+    ConfirmChange,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+enum Field {
+    #[default]
+    Hours,
+    Upload,
+    Download,
+    Cpu,
+    Memory,
+}
+
+impl Field {
+    const ALL: [Field; 5] = [
+        Field::Hours,
+        Field::Upload,
+        Field::Download,
+        Field::Cpu,
+        Field::Memory,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Field::Hours => "Hours",
+            Field::Upload => "Upload",
+            Field::Download => "Download",
+            Field::Cpu => "CPU above",
+            Field::Memory => "Memory above",
+        }
+    }
+
+    fn unit(self) -> &'static str {
+        match self {
+            Field::Hours => "",
+            Field::Upload | Field::Download => " KiB/s",
+            Field::Cpu | Field::Memory => " %",
+        }
+    }
+
+    fn max_len(self) -> usize {
+        match self {
+            Field::Hours => HOURS_INPUT_SIZE,
+            _ => NUMBER_INPUT_SIZE,
+        }
+    }
+
+    fn accepts(self, c: char) -> bool {
+        match self {
+            Field::Hours => c.is_ascii_digit() || matches!(c, ':' | '-' | ','),
+            _ => c.is_ascii_digit(),
+        }
+    }
+
+    /// Returns true if the value can be saved. An empty field means the setting is off.
+    fn is_valid(self, value: &str) -> bool {
+        if value.is_empty() {
+            return true;
+        }
+        match self {
+            Field::Hours => parse_quiet_hours(value).is_ok(),
+            Field::Upload | Field::Download => value.parse::<u64>().is_ok_and(|rate| rate > 0),
+            Field::Cpu | Field::Memory => value
+                .parse::<u8>()
+                .is_ok_and(|percent| (1..=100).contains(&percent)),
+        }
+    }
+}
+
+pub struct QuietHoursPopUp {
+    active: bool,
+    state: QuietHoursState,
+    focus: Field,
+    inputs: [Input; 5],
+    old_values: [String; 5],
+    can_save: bool,
+}
+
+fn to_string<T: ToString>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .map(|value| value.to_string())
+        .unwrap_or_default()
+}
+
+impl QuietHoursPopUp {
+    pub fn new(settings: ThrottleSettings) -> Self {
+        let values = [
+            to_string(&settings.quiet_hours),
+            to_string(&settings.throttled_upload_rate),
+            to_string(&settings.throttled_download_rate),
+            to_string(&settings.throttle_cpu_usage),
+            to_string(&settings.throttle_memory_usage),
+        ];
+        Self {
+            active: false,
+            state: QuietHoursState::Selection,
+            focus: Field::Hours,
+            inputs: values.map(|value| Input::default().with_value(value)),
+            old_values: Default::default(),
+            can_save: false,
+        }
+    }
+
+    pub fn validate(&mut self) {
+        self.can_save = Field::ALL
+            .iter()
+            .all(|field| field.is_valid(self.inputs[field.index()].value()));
+    }
+
+    fn value(&self, field: Field) -> &str {
+        self.inputs[field.index()].value()
+    }
+
+    fn settings(&self) -> ThrottleSettings {
+        let non_empty = |field| Some(self.value(field)).filter(|value| !value.is_empty());
+        ThrottleSettings {
+            quiet_hours: non_empty(Field::Hours).and_then(|hours| parse_quiet_hours(hours).ok()),
+            throttled_upload_rate: non_empty(Field::Upload).and_then(|rate| rate.parse().ok()),
+            throttled_download_rate: non_empty(Field::Download).and_then(|rate| rate.parse().ok()),
+            throttle_cpu_usage: non_empty(Field::Cpu).and_then(|percent| percent.parse().ok()),
+            throttle_memory_usage: non_empty(Field::Memory)
+                .and_then(|percent| percent.parse().ok()),
+        }
+    }
+
+    fn is_unchanged(&self) -> bool {
+        Field::ALL
+            .iter()
+            .all(|field| self.value(*field) == self.old_values[field.index()])
+    }
+
+    fn restore_old_values(&mut self) {
+        for field in Field::ALL {
+            let old_value = self.old_values[field.index()].clone();
+            let input = &mut self.inputs[field.index()];
+            *input = input.clone().with_value(old_value);
+        }
+    }
+
+    fn input_line(&self, field: Field) -> Line<'_> {
+        let value = self.value(field);
+        let spaces = " ".repeat(field.max_len().saturating_sub(value.len()));
+        let mut style = Style::default()
+            .fg(if field.is_valid(value) {
+                VIVID_SKY_BLUE
+            } else {
+                RED
+            })
+            .bg(INDIGO);
+        if self.focus == field {
+            style = style.underlined();
+        }
+        Line::from(vec![
+            Span::styled(
+                format!("{:>12} ", field.label()),
+                Style::default().fg(GHOST_WHITE),
+            ),
+            Span::styled(format!("{spaces}{value} "), style),
+            Span::styled(
+                format!("{:<6}", field.unit()),
+                Style::default().fg(LIGHT_PERIWINKLE),
+            ),
+        ])
+        .alignment(Alignment::Center)
+    }
+
+    // -- Draw functions --
+
+    // Draws the settings selection screen
+    fn draw_selection_state(
+        &mut self,
+        f: &mut crate::tui::Frame<'_>,
+        layer_zero: Rect,
+        layer_one: Rc<[Rect]>,
+    ) -> Paragraph<'_> {
+        // layer zero
+        let pop_up_border = Paragraph::new("").block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Quiet Hours ")
+                .bold()
+                .title_style(Style::new().fg(VIVID_SKY_BLUE))
+                .padding(Padding::uniform(2))
+                .border_style(Style::new().fg(VIVID_SKY_BLUE)),
+        );
+        clear_area(f, layer_zero);
+
+        // split into 5 parts, for the prompt, inputs, text, dash and buttons
+        let layer_two = Layout::new(
+            Direction::Vertical,
+            [
+                // for the prompt text
+                Constraint::Length(2),
+                // for the inputs
+                Constraint::Length(9),
+                // for the text
+                Constraint::Length(3),
+                // gap
+                Constraint::Length(2),
+                // for the buttons
+                Constraint::Length(1),
+            ],
+        )
+        .split(layer_one[1]);
+
+        let prompt = Paragraph::new("When should the nodes hold back?")
+            .bold()
+            .alignment(Alignment::Center);
+        f.render_widget(prompt.fg(GHOST_WHITE), layer_two[0]);
+
+        let section = |title: &'static str| {
+            Line::from(Span::styled(title, Style::default().fg(LIGHT_PERIWINKLE)))
+                .alignment(Alignment::Center)
+        };
+        let inputs = Paragraph::new(vec![
+            self.input_line(Field::Hours),
+            Line::raw(""),
+            section("Bandwidth while quiet"),
+            self.input_line(Field::Upload),
+            self.input_line(Field::Download),
+            Line::raw(""),
+            section("Also hold back while the system is busy"),
+            self.input_line(Field::Cpu),
+            self.input_line(Field::Memory),
+        ]);
+        f.render_widget(inputs, layer_two[1]);
+
+        let text = Paragraph::new(vec![
+            Line::from(Span::styled(
+                "e.g. 09:00-17:00,22:00-07:00. Empty fields are off.",
+                Style::default().fg(LIGHT_PERIWINKLE),
+            )),
+            Line::from(Span::styled(
+                "Switch between the fields with [Tab].",
+                Style::default().fg(LIGHT_PERIWINKLE),
+            )),
+        ])
+        .block(block::Block::default().padding(Padding::horizontal(2)))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+        f.render_widget(text.fg(GHOST_WHITE), layer_two[2]);
+
+        let dash = Block::new()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::new().fg(GHOST_WHITE));
+        f.render_widget(dash, layer_two[3]);
+
+        let buttons_layer =
+            Layout::horizontal(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(layer_two[4]);
+
+        let button_no = Line::from(vec![Span::styled(
+            "  Cancel [Esc]",
+            Style::default().fg(LIGHT_PERIWINKLE),
+        )]);
+        let button_yes_style = if self.can_save {
+            Style::default().fg(EUCALYPTUS)
+        } else {
+            Style::default().fg(LIGHT_PERIWINKLE)
+        };
+        f.render_widget(button_no, buttons_layer[0]);
+
+        let button_yes = Line::from(vec![
+            Span::styled("Save Settings ", button_yes_style),
+            Span::styled("[Enter]", Style::default().fg(GHOST_WHITE)),
+        ]);
+        f.render_widget(button_yes, buttons_layer[1]);
+
+        pop_up_border
+    }
+
+    // Draws Confirmation screen
+    fn draw_confirm_and_reset(
+        &mut self,
+        f: &mut crate::tui::Frame<'_>,
+        layer_zero: Rect,
+        layer_one: Rc<[Rect]>,
+    ) -> Paragraph<'_> {
+        // layer zero
+        let pop_up_border = Paragraph::new("").block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Confirm & Reset ")
+                .bold()
+                .title_style(Style::new().fg(VIVID_SKY_BLUE))
+                .padding(Padding::uniform(2))
+                .border_style(Style::new().fg(VIVID_SKY_BLUE)),
+        );
+        clear_area(f, layer_zero);
+
+        // split into 3 parts, paragraph, dash, buttons
+        let layer_two = Layout::new(
+            Direction::Vertical,
+            [
+                // for the text
+                Constraint::Length(14),
+                // gap
+                Constraint::Length(3),
+                // for the buttons
+                Constraint::Length(1),
+            ],
+        )
+        .split(layer_one[1]);
+
+        let paragraph_text = Paragraph::new(vec![
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(vec![
+                Span::styled(
+                    "Changing quiet hours will ",
+                    Style::default().fg(LIGHT_PERIWINKLE),
+                ),
+                Span::styled("reset all nodes.", Style::default().fg(GHOST_WHITE)),
+            ]),
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(Span::styled("\n\n", Style::default())),
+            Line::from(vec![
+                Span::styled("You’ll need to ", Style::default().fg(LIGHT_PERIWINKLE)),
+                Span::styled("Add", Style::default().fg(GHOST_WHITE)),
+                Span::styled(" and ", Style::default().fg(LIGHT_PERIWINKLE)),
+                Span::styled("Start", Style::default().fg(GHOST_WHITE)),
+                Span::styled(
+                    " them again afterwards. Are you sure you want to continue?",
+                    Style::default().fg(LIGHT_PERIWINKLE),
+                ),
+            ]),
+        ])
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true })
+        .block(block::Block::default().padding(Padding::horizontal(2)));
+
+        f.render_widget(paragraph_text, layer_two[0]);
+
+        let dash = Block::new()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::new().fg(GHOST_WHITE));
+        f.render_widget(dash, layer_two[1]);
+
+        let buttons_layer =
+            Layout::horizontal(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(layer_two[2]);
+
+        let button_no = Line::from(vec![Span::styled(
+            "  Cancel [Esc]",
+            Style::default().fg(LIGHT_PERIWINKLE),
+        )]);
+        f.render_widget(button_no, buttons_layer[0]);
+
+        let button_yes = Line::from(vec![
+            Span::styled("Yes, Change Settings ", Style::default().fg(EUCALYPTUS)),
+            Span::styled("[Enter]", Style::default().fg(GHOST_WHITE)),
+        ]);
+        f.render_widget(button_yes, buttons_layer[1]);
+
+        pop_up_border
+    }
+}
+
+impl Component for QuietHoursPopUp {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Vec<Action>> {
+        if !self.active {
+            return Ok(vec![]);
+        }
+        // while in entry mode, keybinds are not captured, so gotta exit entry mode from here
+        let send_back: Vec<Action> = match &self.state {
+            QuietHoursState::Selection => match key.code {
+                KeyCode::Enter => {
+                    if !self.can_save {
+                        debug!("Got Enter, but the settings are not valid, ignoring.");
+                        return Ok(vec![]);
+                    }
+                    if self.is_unchanged() {
+                        debug!("Got Enter, but the settings are unchanged, switching to Options");
+                        return Ok(vec![Action::SwitchScene(Scene::Options)]);
+                    }
+                    self.state = QuietHoursState::ConfirmChange;
+                    vec![]
+                }
+                KeyCode::Esc => {
+                    debug!("Got Esc, restoring the old values and switching to actual screen");
+                    self.restore_old_values();
+                    vec![Action::SwitchScene(Scene::Options)]
+                }
+                KeyCode::Tab | KeyCode::Down => {
+                    self.focus = self.focus.next();
+                    vec![]
+                }
+                KeyCode::BackTab | KeyCode::Up => {
+                    self.focus = self.focus.previous();
+                    vec![]
+                }
+                KeyCode::Char(c) if !self.focus.accepts(c) => vec![],
+                KeyCode::Backspace => {
+                    self.inputs[self.focus.index()].handle_event(&Event::Key(key));
+                    self.validate();
+                    vec![]
+                }
+                _ => {
+                    // if max limit reached, we should not allow any more inputs.
+                    let input = &mut self.inputs[self.focus.index()];
+                    if input.value().len() < self.focus.max_len() {
+                        input.handle_event(&Event::Key(key));
+                    }
+                    self.validate();
+                    vec![]
+                }
+            },
+            QuietHoursState::ConfirmChange => match key.code {
+                KeyCode::Enter => {
+                    debug!("Got Enter, saving the quiet hours and switching to Status Screen");
+                    self.state = QuietHoursState::Selection;
+                    let settings = self.settings();
+                    vec![
+                        Action::StoreThrottleSettings(settings.clone()),
+                        Action::OptionsActions(OptionsActions::UpdateThrottleSettings(settings)),
+                        Action::SwitchScene(Scene::Status),
+                    ]
+                }
+                KeyCode::Esc => {
+                    self.state = QuietHoursState::Selection;
+                    self.restore_old_values();
+                    vec![Action::SwitchScene(Scene::Options)]
+                }
+                _ => vec![],
+            },
+        };
+        Ok(send_back)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        let send_back = match action {
+            Action::SwitchScene(scene) => match scene {
+                Scene::ChangeQuietHoursPopUp => {
+                    self.active = true;
+                    self.focus = Field::Hours;
+                    self.old_values = self
+                        .inputs
+                        .each_ref()
+                        .map(|input| input.value().to_string());
+                    self.validate();
+                    // Set to InputMode::Entry as we want to handle everything within our handle_key_events
+                    // so by default if this scene is active, we capture inputs.
+                    Some(Action::SwitchInputMode(InputMode::Entry))
+                }
+                _ => {
+                    self.active = false;
+                    None
+                }
+            },
+            _ => None,
+        };
+        Ok(send_back)
+    }
+
+    fn draw(&mut self, f: &mut crate::tui::Frame<'_>, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let layer_zero = centered_rect_fixed(56, 22, area);
+
+        let layer_one = Layout::new(
+            Direction::Vertical,
+            [
+                // for the pop_up_border
+                Constraint::Length(2),
+                // for the input field
+                Constraint::Min(1),
+                // for the pop_up_border
+                Constraint::Length(1),
+            ],
+        )
+        .split(layer_zero);
+
+        let pop_up_border: Paragraph = match self.state {
+            QuietHoursState::Selection => self.draw_selection_state(f, layer_zero, layer_one),
+            QuietHoursState::ConfirmChange => self.draw_confirm_and_reset(f, layer_zero, layer_one),
+        };
+        // We render now so the borders are on top of the other widgets
+        f.render_widget(pop_up_border, layer_zero);
+
+        Ok(())
+    }
+}
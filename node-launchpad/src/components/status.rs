@@ -14,8 +14,7 @@ use super::{Component, Frame, footer::Footer, header::Header, popup::manage_node
 use crate::action::OptionsActions;
 use crate::components::popup::manage_nodes::MAX_NODE_COUNT;
 use crate::components::popup::port_range::PORT_ALLOCATION;
-use crate::components::utils::open_logs;
-use crate::config::get_launchpad_nodes_data_dir_path;
+use crate::config::{ThrottleSettings, get_launchpad_nodes_data_dir_path};
 use crate::connection_mode::{ConnectionMode, NodeConnectionMode};
 use crate::error::ErrorPopup;
 use crate::node_mgmt::{
@@ -90,6 +89,8 @@ pub struct Status<'a> {
     items: Option<StatefulTable<NodeItem<'a>>>,
     /// To pass into node services.
     network_id: Option<u8>,
+    /// To pass into node services.
+    evm_config: Option<PathBuf>,
     // Node Management
     node_management: NodeManagement,
     // Amount of nodes
@@ -110,6 +111,12 @@ pub struct Status<'a> {
     port_from: Option<u32>,
     // Port to
     port_to: Option<u32>,
+    // Upload limit of each node, in KiB/s
+    max_upload_rate: Option<u64>,
+    // Download limit of each node, in KiB/s
+    max_download_rate: Option<u64>,
+    // When and how the nodes throttle themselves
+    throttle_settings: ThrottleSettings,
     storage_mountpoint: PathBuf,
     available_disk_space_gb: usize,
     error_popup: Option<ErrorPopup>,
@@ -122,9 +129,13 @@ pub struct StatusConfig {
     pub upnp_support: UpnpSupport,
     pub data_dir_path: PathBuf,
     pub network_id: Option<u8>,
+    pub evm_config: Option<PathBuf>,
     pub init_peers_config: InitialPeersConfig,
     pub port_from: Option<u32>,
     pub port_to: Option<u32>,
+    pub max_upload_rate: Option<u64>,
+    pub max_download_rate: Option<u64>,
+    pub throttle_settings: ThrottleSettings,
     pub storage_mountpoint: PathBuf,
     pub rewards_address: String,
 }
@@ -141,6 +152,7 @@ impl Status<'_> {
             error_while_running_nat_detection: 0,
             nat_detection_in_progress: false,
             network_id: config.network_id,
+            evm_config: config.evm_config,
             node_stats: NodeStats::default(),
             node_stats_last_update: Instant::now(),
             node_services: Default::default(),
@@ -154,6 +166,9 @@ impl Status<'_> {
             upnp_support: config.upnp_support,
             port_from: config.port_from,
             port_to: config.port_to,
+            max_upload_rate: config.max_upload_rate,
+            max_download_rate: config.max_download_rate,
+            throttle_settings: config.throttle_settings,
             error_popup: None,
             storage_mountpoint: config.storage_mountpoint.clone(),
             available_disk_space_gb: (get_available_space_b(&config.storage_mountpoint)? / GB)
@@ -456,6 +471,7 @@ impl Component for Status<'_> {
                 Scene::Status
                 | Scene::StatusRewardsAddressPopUp
                 | Scene::RemoveNodePopUp
+                | Scene::NodeLogsPopUp
                 | Scene::UpgradeLaunchpadPopUp => {
                     self.active = true;
                     // make sure we're in navigation mode
@@ -523,6 +539,27 @@ impl Component for Status<'_> {
                         action_sender,
                     })?;
             }
+            Action::StoreBandwidthLimits(upload, download) => {
+                self.max_upload_rate = upload;
+                self.max_download_rate = download;
+                info!("Resetting antnode services because the Bandwidth Limits were changed.");
+                let action_sender = self.get_actions_sender()?;
+                self.node_management
+                    .send_task(NodeManagementTask::ResetNodes {
+                        start_nodes_after_reset: false,
+                        action_sender,
+                    })?;
+            }
+            Action::StoreThrottleSettings(throttle_settings) => {
+                self.throttle_settings = throttle_settings;
+                info!("Resetting antnode services because the Quiet Hours were changed.");
+                let action_sender = self.get_actions_sender()?;
+                self.node_management
+                    .send_task(NodeManagementTask::ResetNodes {
+                        start_nodes_after_reset: false,
+                        action_sender,
+                    })?;
+            }
             Action::SetUpnpSupport(ref upnp_support) => {
                 debug!("Setting UPnP support: {upnp_support:?}");
                 self.upnp_support = upnp_support.clone();
@@ -888,9 +925,13 @@ impl Component for Status<'_> {
                         connection_mode: self.connection_mode,
                         count: self.nodes_to_start as u16,
                         data_dir_path: Some(self.data_dir_path.clone()),
+                        evm_config: self.evm_config.clone(),
                         network_id: self.network_id,
                         owner: self.rewards_address.clone(),
                         init_peers_config: self.init_peers_config.clone(),
+                        max_download_rate: self.max_download_rate,
+                        max_upload_rate: self.max_upload_rate,
+                        throttle_settings: self.throttle_settings.clone(),
                         port_range: Some(port_range),
                         rewards_address: self.rewards_address.clone(),
                         run_nat_detection: self.should_we_run_nat_detection(),
@@ -991,9 +1032,13 @@ impl Component for Status<'_> {
                         connection_mode: self.connection_mode,
                         count: 1,
                         data_dir_path: Some(self.data_dir_path.clone()),
+                        evm_config: self.evm_config.clone(),
                         network_id: self.network_id,
                         owner: self.rewards_address.clone(),
                         init_peers_config: self.init_peers_config.clone(),
+                        max_download_rate: self.max_download_rate,
+                        max_upload_rate: self.max_upload_rate,
+                        throttle_settings: self.throttle_settings.clone(),
                         port_range: Some(port_range),
                         rewards_address: self.rewards_address.clone(),
                         run_nat_detection: self.should_we_run_nat_detection(),
@@ -1063,12 +1108,23 @@ impl Component for Status<'_> {
                 StatusActions::TriggerNodeLogs => {
                     if let Some(node) = self.items.as_ref().and_then(|items| items.selected_item())
                     {
-                        debug!("Got action to open node logs {:?}", node.name);
-                        open_logs(Some(node.name.clone()))?;
+                        debug!("Got action to show node logs {:?}", node.name);
+                        if let Some(node_service) = self
+                            .node_services
+                            .iter()
+                            .find(|node_service| node_service.service_name == node.name)
+                        {
+                            return Ok(Some(Action::StatusActions(StatusActions::ShowNodeLogs {
+                                service_name: node.name.clone(),
+                                log_dir_path: node_service.log_dir_path.clone(),
+                            })));
+                        }
                     } else {
                         debug!("Got action to open node logs but no node was selected.");
                     }
                 }
+                // Handled by the node logs pop-up.
+                StatusActions::ShowNodeLogs { .. } => {}
             },
             Action::OptionsActions(OptionsActions::UpdateNodes) => {
                 debug!("Got action to Update Nodes");
@@ -1248,8 +1304,9 @@ impl Component for Status<'_> {
         let total_attos_earned_and_wallet_row = Row::new(vec![
             Cell::new("Attos Earned".to_string()).fg(VIVID_SKY_BLUE),
             Cell::new(format!(
-                "{:?}",
-                self.node_stats.total_rewards_wallet_balance
+                "{:?} ({:?} today)",
+                self.node_stats.total_rewards_wallet_balance,
+                self.node_stats.total_rewards_earned_today
             ))
             .fg(VIVID_SKY_BLUE)
             .bold(),
@@ -124,6 +124,37 @@ pub struct AppData {
     pub connection_mode: Option<ConnectionMode>,
     pub port_from: Option<u32>,
     pub port_to: Option<u32>,
+    /// Upload limit of each node, in KiB/s.
+    pub max_upload_rate: Option<u64>,
+    /// Download limit of each node, in KiB/s.
+    pub max_download_rate: Option<u64>,
+    /// When the nodes throttle themselves.
+    #[serde(default)]
+    pub throttle_settings: ThrottleSettings,
+}
+
+/// When the nodes throttle themselves, and how much bandwidth they use meanwhile.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ThrottleSettings {
+    /// Daily windows of local time, e.g. "22:00-07:00,12:00-13:00".
+    pub quiet_hours: Option<String>,
+    /// Upload limit of each node while throttled, in KiB/s.
+    pub throttled_upload_rate: Option<u64>,
+    /// Download limit of each node while throttled, in KiB/s.
+    pub throttled_download_rate: Option<u64>,
+    /// System-wide CPU usage, in percent, above which the nodes are throttled.
+    pub throttle_cpu_usage: Option<u8>,
+    /// System-wide memory usage, in percent, above which the nodes are throttled.
+    pub throttle_memory_usage: Option<u8>,
+}
+
+impl ThrottleSettings {
+    /// Returns true if the nodes never get throttled.
+    pub fn is_disabled(&self) -> bool {
+        self.quiet_hours.is_none()
+            && self.throttle_cpu_usage.is_none()
+            && self.throttle_memory_usage.is_none()
+    }
 }
 
 impl Default for AppData {
@@ -136,6 +167,9 @@ impl Default for AppData {
             connection_mode: None,
             port_from: None,
             port_to: None,
+            max_upload_rate: None,
+            max_download_rate: None,
+            throttle_settings: ThrottleSettings::default(),
         }
     }
 }
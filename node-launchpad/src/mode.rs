@@ -21,6 +21,8 @@ pub enum Scene {
     ChangePortsPopUp {
         connection_mode_old_value: Option<ConnectionMode>,
     },
+    ChangeBandwidthLimitsPopUp,
+    ChangeQuietHoursPopUp,
     StatusRewardsAddressPopUp,
     OptionsRewardsAddressPopUp,
     ManageNodesPopUp {
@@ -30,6 +32,7 @@ pub enum Scene {
     UpgradeNodesPopUp,
     UpgradeLaunchpadPopUp,
     RemoveNodePopUp,
+    NodeLogsPopUp,
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
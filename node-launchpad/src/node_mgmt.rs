@@ -7,15 +7,19 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::action::{Action, StatusActions};
+use crate::config::ThrottleSettings;
 use crate::connection_mode::ConnectionMode;
 use ant_bootstrap::InitialPeersConfig;
 use ant_evm::{EvmNetwork, RewardsAddress};
 use ant_node_manager::{VerbosityLevel, add_services::config::PortRange};
 use ant_releases::{self, AntReleaseRepoActions, ReleaseType};
-use ant_service_management::NodeRegistryManager;
+use ant_service_management::{
+    NodeRegistryManager, ResourceLimits,
+    control::{ServiceController, SharedServiceControl},
+};
 use color_eyre::Result;
 use color_eyre::eyre::eyre;
-use std::{path::PathBuf, str::FromStr};
+use std::{path::PathBuf, str::FromStr, sync::Arc};
 use tokio::runtime::Builder;
 use tokio::sync::mpsc::{self, UnboundedSender};
 use tokio::task::LocalSet;
@@ -148,6 +152,7 @@ async fn stop_nodes(
     if let Err(err) = ant_node_manager::cmd::node::stop(
         None,
         node_registry.clone(),
+        service_control(),
         vec![],
         services.clone(),
         VerbosityLevel::Minimal,
@@ -184,9 +189,13 @@ pub struct MaintainNodesArgs {
     pub connection_mode: ConnectionMode,
     pub count: u16,
     pub data_dir_path: Option<PathBuf>,
+    pub evm_config: Option<PathBuf>,
     pub network_id: Option<u8>,
     pub owner: String,
     pub init_peers_config: InitialPeersConfig,
+    pub max_download_rate: Option<u64>,
+    pub max_upload_rate: Option<u64>,
+    pub throttle_settings: ThrottleSettings,
     pub port_range: Option<PortRange>,
     pub rewards_address: String,
     pub run_nat_detection: bool,
@@ -241,9 +250,13 @@ async fn reset_nodes(
     node_registry: NodeRegistryManager,
     start_nodes_after_reset: bool,
 ) {
-    if let Err(err) =
-        ant_node_manager::cmd::node::reset(true, node_registry.clone(), VerbosityLevel::Minimal)
-            .await
+    if let Err(err) = ant_node_manager::cmd::node::reset(
+        true,
+        node_registry.clone(),
+        service_control(),
+        VerbosityLevel::Minimal,
+    )
+    .await
     {
         error!("Error while resetting services {err:?}");
         send_action(
@@ -285,6 +298,7 @@ async fn upgrade_nodes(args: UpgradeNodesArgs, node_registry: NodeRegistryManage
     if let Err(err) = ant_node_manager::cmd::node::stop(
         None,
         node_registry.clone(),
+        service_control(),
         vec![],
         args.service_names.clone(),
         VerbosityLevel::Minimal,
@@ -306,9 +320,13 @@ async fn upgrade_nodes(args: UpgradeNodesArgs, node_registry: NodeRegistryManage
         args.custom_bin_path,
         args.force,
         Some(FIXED_INTERVAL),
+        None, // health_check
         node_registry.clone(),
+        service_control(),
         args.peer_ids,
         args.provided_env_variables,
+        ResourceLimits::default(),
+        false,
         args.service_names,
         args.url,
         args.version,
@@ -344,6 +362,7 @@ async fn remove_nodes(
     if let Err(err) = ant_node_manager::cmd::node::stop(
         None,
         node_registry.clone(),
+        service_control(),
         vec![],
         services.clone(),
         VerbosityLevel::Minimal,
@@ -364,6 +383,7 @@ async fn remove_nodes(
         false,
         vec![],
         node_registry.clone(),
+        service_control(),
         services.clone(),
         VerbosityLevel::Minimal,
     )
@@ -427,30 +447,44 @@ async fn add_node(args: MaintainNodesArgs, node_registry: NodeRegistryManager) {
         config.auto_set_nat_flags,
         Some(config.count),
         config.data_dir_path,
-        true,       // enable_metrics_server,
-        None,       // env_variables,
-        None,       // evm_network
-        None,       // log_dir_path,
-        None,       // log_format,
-        None,       // max_archived_log_files,
-        None,       // max_log_files,
+        true, // enable_metrics_server,
+        None, // env_variables,
+        config.evm_config,
+        None, // evm_network
+        None, // log_dir_path,
+        None, // log_format,
+        None, // max_archived_log_files,
+        config.max_download_rate,
+        None, // max_log_files,
+        None, // max_peer_download_rate,
+        None, // max_peer_upload_rate,
+        config.max_upload_rate,
         None,       // metrics_port,
         None,       // network_id
         None,       // node_ip,
         port_range, // node_port
         node_registry.clone(),
+        service_control(),
         config.init_peers_config.clone(),
+        config.throttle_settings.quiet_hours.clone(),
         config.relay, // relay,
+        ResourceLimits::default(),
         RewardsAddress::from_str(config.rewards_address.as_str()).unwrap(),
         None,                        // rpc_address,
         None,                        // rpc_port,
         config.antnode_path.clone(), // src_path,
         !config.upnp,
+        None, // tcp_port,
+        config.throttle_settings.throttle_cpu_usage,
+        config.throttle_settings.throttle_memory_usage,
+        config.throttle_settings.throttled_download_rate,
+        config.throttle_settings.throttled_upload_rate,
         None, // url,
         None, // user,
         None, // version,
         VerbosityLevel::Minimal,
         false, // write_older_cache_files
+        None,  // ws_port
     )
     .await
     {
@@ -489,6 +523,7 @@ async fn start_nodes(
         CONNECTION_TIMEOUT_START,
         None,
         node_registry.clone(),
+        service_control(),
         vec![],
         services.clone(),
         VerbosityLevel::Minimal,
@@ -520,6 +555,11 @@ async fn start_nodes(
 
 // --- Helper functions ---
 
+/// The launchpad manages the nodes with the service manager of the OS.
+fn service_control() -> SharedServiceControl {
+    Arc::new(ServiceController {})
+}
+
 fn send_action(action_sender: UnboundedSender<Action>, action: Action) {
     if let Err(err) = action_sender.send(action) {
         error!("Error while sending action: {err:?}");
@@ -532,6 +572,10 @@ struct NodeConfig {
     count: u16,
     custom_ports: Option<PortRange>,
     data_dir_path: Option<PathBuf>,
+    evm_config: Option<PathBuf>,
+    max_download_rate: Option<u64>,
+    max_upload_rate: Option<u64>,
+    throttle_settings: ThrottleSettings,
     relay: bool,
     network_id: Option<u8>,
     owner: Option<String>,
@@ -597,7 +641,11 @@ fn prepare_node_config(args: &MaintainNodesArgs) -> NodeConfig {
         antnode_path: args.antnode_path.clone(),
         auto_set_nat_flags: args.connection_mode == ConnectionMode::Automatic,
         data_dir_path: args.data_dir_path.clone(),
+        evm_config: args.evm_config.clone(),
         count: args.count,
+        max_download_rate: args.max_download_rate,
+        max_upload_rate: args.max_upload_rate,
+        throttle_settings: args.throttle_settings.clone(),
         custom_ports: if args.connection_mode == ConnectionMode::CustomPorts {
             args.port_range.clone()
         } else {
@@ -670,18 +718,26 @@ async fn scale_down_nodes(config: &NodeConfig, count: u16, node_registry: NodeRe
         config.data_dir_path.clone(),
         true,
         None,
+        config.evm_config.clone(),
         Some(EvmNetwork::default()),
         None,
         None,
         None,
+        config.max_download_rate,
+        None,
         None,
         None,
+        config.max_upload_rate,
+        None,
         config.network_id,
         None,
         None, // We don't care about the port, as we are scaling down
         node_registry,
+        service_control(),
         config.init_peers_config.clone(),
+        config.throttle_settings.quiet_hours.clone(),
         config.relay,
+        ResourceLimits::default(),
         RewardsAddress::from_str(config.rewards_address.as_str()).unwrap(),
         None,
         None,
@@ -692,6 +748,10 @@ async fn scale_down_nodes(config: &NodeConfig, count: u16, node_registry: NodeRe
         None,
         VerbosityLevel::Minimal,
         None,
+        config.throttle_settings.throttle_cpu_usage,
+        config.throttle_settings.throttle_memory_usage,
+        config.throttle_settings.throttled_download_rate,
+        config.throttle_settings.throttled_upload_rate,
         false,
     )
     .await
@@ -746,18 +806,26 @@ async fn add_nodes(
             config.data_dir_path.clone(),
             true,
             None,
+            config.evm_config.clone(),
             Some(EvmNetwork::default()),
             None,
             None,
             None,
+            config.max_download_rate,
+            None,
+            None,
             None,
+            config.max_upload_rate,
             None,
             config.network_id,
             None,
             port_range,
             node_registry.clone(),
+            service_control(),
             config.init_peers_config.clone(),
+            config.throttle_settings.quiet_hours.clone(),
             config.relay,
+            ResourceLimits::default(),
             RewardsAddress::from_str(config.rewards_address.as_str()).unwrap(),
             None,
             None,
@@ -768,6 +836,10 @@ async fn add_nodes(
             None,
             VerbosityLevel::Minimal,
             None,
+            config.throttle_settings.throttle_cpu_usage,
+            config.throttle_settings.throttle_memory_usage,
+            config.throttle_settings.throttled_download_rate,
+            config.throttle_settings.throttled_upload_rate,
             false,
         )
         .await
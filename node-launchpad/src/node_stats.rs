@@ -6,11 +6,18 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use ant_service_management::{NodeServiceData, ServiceStatus};
+use ant_service_management::{
+    NodeServiceData, ServiceStatus,
+    rpc::{RpcActions, RpcClient},
+};
 use color_eyre::Result;
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, time::Instant};
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    time::{Instant, SystemTime},
+};
 use tokio::sync::mpsc::UnboundedSender;
 
 use super::components::status::NODE_STAT_UPDATE_INTERVAL;
@@ -22,6 +29,7 @@ pub struct IndividualNodeStats {
     pub service_name: String,
     pub forwarded_rewards: usize,
     pub rewards_wallet_balance: usize,
+    pub rewards_earned_today: usize,
     pub memory_usage_mb: usize,
     pub bandwidth_inbound: usize,
     pub bandwidth_outbound: usize,
@@ -36,6 +44,7 @@ pub struct IndividualNodeStats {
 pub struct NodeStats {
     pub total_forwarded_rewards: usize,
     pub total_rewards_wallet_balance: usize,
+    pub total_rewards_earned_today: usize,
     pub total_memory_usage_mb: usize,
     pub individual_stats: Vec<IndividualNodeStats>,
 }
@@ -44,6 +53,7 @@ impl NodeStats {
     fn merge(&mut self, other: &IndividualNodeStats) {
         self.total_forwarded_rewards += other.forwarded_rewards;
         self.total_rewards_wallet_balance += other.rewards_wallet_balance;
+        self.total_rewards_earned_today += other.rewards_earned_today;
         self.total_memory_usage_mb += other.memory_usage_mb;
         self.individual_stats.push(other.clone()); // Store individual stats
     }
@@ -75,6 +85,7 @@ impl NodeStats {
                         Some((
                             node.service_name.clone(),
                             metrics_port,
+                            node.rpc_socket_addr,
                             node.data_dir_path.clone(),
                         ))
                     } else {
@@ -115,19 +126,21 @@ impl NodeStats {
     ///
     /// # Parameters
     ///
-    /// * `node_details`: A vector of tuples, each containing the service name, metrics port, and data directory path of a node.
+    /// * `node_details`: A vector of tuples, each containing the service name, metrics port, RPC address and data directory path of a node.
     /// * `action_sender`: An unbounded sender of `Action` instances used to send the aggregated node statistics.
     async fn fetch_all_node_stats_inner(
-        node_details: Vec<(String, u16, PathBuf)>,
+        node_details: Vec<(String, u16, SocketAddr, PathBuf)>,
         action_sender: UnboundedSender<Action>,
     ) {
         let mut stream = futures::stream::iter(node_details)
-            .map(|(service_name, metrics_port, data_dir)| async move {
-                (
-                    Self::fetch_stat_per_node(metrics_port, data_dir).await,
-                    service_name,
-                )
-            })
+            .map(
+                |(service_name, metrics_port, rpc_socket_addr, data_dir)| async move {
+                    (
+                        Self::fetch_stat_per_node(metrics_port, rpc_socket_addr, data_dir).await,
+                        service_name,
+                    )
+                },
+            )
             .buffer_unordered(5);
 
         let mut all_node_stats = NodeStats::default();
@@ -139,6 +152,7 @@ impl NodeStats {
                         service_name: service_name.clone(),
                         forwarded_rewards: stats.forwarded_rewards,
                         rewards_wallet_balance: stats.rewards_wallet_balance,
+                        rewards_earned_today: stats.rewards_earned_today,
                         memory_usage_mb: stats.memory_usage_mb,
                         bandwidth_inbound: stats.bandwidth_inbound,
                         bandwidth_outbound: stats.bandwidth_outbound,
@@ -165,6 +179,7 @@ impl NodeStats {
 
     async fn fetch_stat_per_node(
         metrics_port: u16,
+        rpc_socket_addr: SocketAddr,
         _data_dir: PathBuf,
     ) -> Result<IndividualNodeStats> {
         let now = Instant::now();
@@ -267,6 +282,25 @@ impl NodeStats {
                 }
             }
         }
+
+        // The metrics only carry the lifetime earnings, the per-day figure comes from the
+        // node's earnings ledger.
+        let mut rpc_client = RpcClient::from_socket_addr(rpc_socket_addr);
+        rpc_client.set_max_attempts(1);
+        let today = start_of_today();
+        match rpc_client.earnings(today).await {
+            Ok(earnings) => {
+                stats.rewards_earned_today = earnings
+                    .total_since(today)
+                    .as_atto()
+                    .try_into()
+                    .unwrap_or(usize::MAX);
+            }
+            Err(err) => {
+                debug!("Failed to fetch earnings from {rpc_socket_addr:?}: {err:?}");
+            }
+        }
+
         trace!(
             "Fetched stats from metrics_port {metrics_port:?} in {:?}",
             now.elapsed()
@@ -274,3 +308,14 @@ impl NodeStats {
         Ok(stats)
     }
 }
+
+/// Returns the start of the current day, in local time.
+fn start_of_today() -> SystemTime {
+    chrono::Local::now()
+        .date_naive()
+        .and_time(chrono::NaiveTime::MIN)
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(SystemTime::from)
+        .unwrap_or_else(SystemTime::now)
+}